@@ -263,6 +263,46 @@ pub fn main(
 ///
 /// For types that need custom write barrier behavior, implement `Trace` manually.
 ///
+/// # Skipping Fields
+///
+/// A field marked `#[rudo_gc(skip)]` is not traced, and its type need not
+/// implement `Trace`; a type parameter used only in skipped fields gets no
+/// `Trace` bound. Use it for buffers and markers that cannot hold a `Gc`.
+///
+/// Skipping a field that does reach a `Gc` hides that object from the
+/// collector, which can then free it while the field still points to it.
+/// Only skip fields you know are GC-free.
+///
+/// ```rust
+/// use std::marker::PhantomData;
+///
+/// use rudo_gc::{Gc, Trace};
+///
+/// struct NotTrace;
+///
+/// #[derive(Trace)]
+/// struct Frame<M> {
+///     next: Option<Gc<Frame<M>>>,
+///     #[rudo_gc(skip)]
+///     pixels: Vec<u8>,
+///     #[rudo_gc(skip)]
+///     marker: PhantomData<M>,
+/// }
+///
+/// let frame = Gc::new(Frame::<NotTrace> {
+///     next: None,
+///     pixels: vec![0; 64],
+///     marker: PhantomData,
+/// });
+/// ```
+///
+/// # Trait Objects
+///
+/// Fields holding `Gc<dyn Trait>` are rejected with a compile error: `Gc<T>`
+/// requires a sized `T`, and `Trace` takes a generic visitor, so a trait
+/// built on it cannot be made into a trait object. Use an enum over the
+/// concrete types instead.
+///
 /// # Example
 ///
 /// ```rust
@@ -298,8 +338,17 @@ pub fn derive_trace(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         }
     }
 
+    if let Some(err) = reject_dyn_gc_fields(&input.data) {
+        return err.into_compile_error().into();
+    }
+
+    let traced_types = match traced_field_types(&input.data) {
+        Ok(types) => types,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
     let name = &input.ident;
-    let generics = add_trait_bounds(&rudo_gc, input.generics);
+    let generics = add_trait_bounds(&rudo_gc, input.generics, traced_types.as_deref());
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let trace_body = generate_trace_body(&rudo_gc, name, &input.data);
 
@@ -315,9 +364,124 @@ pub fn derive_trace(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     generated.into()
 }
 
-fn add_trait_bounds(rudo_gc: &Path, mut generics: Generics) -> Generics {
+/// Report the first `Gc<dyn Trait>` in a field type, which cannot be traced.
+fn reject_dyn_gc_fields(data: &Data) -> Option<syn::Error> {
+    let fields: Vec<&syn::Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    fields.into_iter().find_map(|field| {
+        find_dyn_gc(&field.ty).map(|ty| {
+            syn::Error::new_spanned(
+                ty,
+                "`Gc<dyn Trait>` is not supported: `Gc` requires a sized value type \
+                 and `Trace` cannot be used as a trait object; use an enum over the \
+                 concrete types instead",
+            )
+        })
+    })
+}
+
+/// Find a `Gc<dyn Trait>` anywhere inside `ty`.
+fn find_dyn_gc(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.iter().find_map(|seg| {
+            let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+                return None;
+            };
+            args.args.iter().find_map(|arg| {
+                let syn::GenericArgument::Type(inner) = arg else {
+                    return None;
+                };
+                let is_dyn = matches!(strip_parens(inner), syn::Type::TraitObject(_));
+                if seg.ident == "Gc" && is_dyn {
+                    Some(ty)
+                } else {
+                    find_dyn_gc(inner)
+                }
+            })
+        }),
+        syn::Type::Array(array) => find_dyn_gc(&array.elem),
+        syn::Type::Slice(slice) => find_dyn_gc(&slice.elem),
+        syn::Type::Reference(reference) => find_dyn_gc(&reference.elem),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().find_map(find_dyn_gc),
+        syn::Type::Paren(paren) => find_dyn_gc(&paren.elem),
+        syn::Type::Group(group) => find_dyn_gc(&group.elem),
+        _ => None,
+    }
+}
+
+fn strip_parens(ty: &syn::Type) -> &syn::Type {
+    match ty {
+        syn::Type::Paren(paren) => strip_parens(&paren.elem),
+        syn::Type::Group(group) => strip_parens(&group.elem),
+        _ => ty,
+    }
+}
+
+/// Whether `field` carries `#[rudo_gc(skip)]`.
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("rudo_gc") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported field attribute"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Types of the fields the derived `Trace` visits, or `None` for a union.
+///
+/// Also reports malformed `#[rudo_gc(...)]` field attributes.
+fn traced_field_types(data: &Data) -> syn::Result<Option<Vec<&syn::Type>>> {
+    let fields: Vec<&syn::Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => return Ok(None),
+    };
+    let mut types = Vec::new();
+    for field in fields {
+        if !is_skipped(field)? {
+            types.push(&field.ty);
+        }
+    }
+    Ok(Some(types))
+}
+
+/// Whether `ident` appears anywhere in `tokens`.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        proc_macro2::TokenTree::Ident(i) => i == *ident,
+        proc_macro2::TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Bound each type parameter by `Trace + 'static`.
+///
+/// With `traced_types`, parameters that only appear in skipped fields get
+/// `'static` but no `Trace` bound.
+fn add_trait_bounds(
+    rudo_gc: &Path,
+    mut generics: Generics,
+    traced_types: Option<&[&syn::Type]>,
+) -> Generics {
     for param in &mut generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
+            let traced = traced_types.is_none_or(|types| {
+                types
+                    .iter()
+                    .any(|ty| mentions(quote!(#ty), &type_param.ident))
+            });
             let has_trace = type_param.bounds.iter().any(|b| {
                 if let syn::TypeParamBound::Trait(t) = b {
                     t.path.segments.last().is_some_and(|s| s.ident == "Trace")
@@ -333,7 +497,7 @@ fn add_trait_bounds(rudo_gc: &Path, mut generics: Generics) -> Generics {
                 }
             });
 
-            if !has_trace {
+            if !has_trace && traced {
                 type_param.bounds.push(parse_quote!(#rudo_gc::Trace));
             }
             if !has_static {
@@ -356,10 +520,15 @@ fn generate_trace_body(rudo_gc: &Path, name: &Ident, data: &Data) -> TokenStream
     }
 }
 
+/// [`is_skipped`] for attributes already validated by [`traced_field_types`].
+fn skipped(field: &syn::Field) -> bool {
+    is_skipped(field).unwrap_or(false)
+}
+
 fn generate_struct_trace(rudo_gc: &Path, fields: &Fields) -> TokenStream {
     match fields {
         Fields::Named(f) => {
-            let trace_calls = f.named.iter().map(|field| {
+            let trace_calls = f.named.iter().filter(|f| !skipped(f)).map(|field| {
                 let name = &field.ident;
                 quote_spanned! {field.span() =>
                     #rudo_gc::Trace::trace(&self.#name, visitor);
@@ -368,12 +537,17 @@ fn generate_struct_trace(rudo_gc: &Path, fields: &Fields) -> TokenStream {
             quote! { #(#trace_calls)* }
         }
         Fields::Unnamed(f) => {
-            let trace_calls = f.unnamed.iter().enumerate().map(|(i, field)| {
-                let index = Index::from(i);
-                quote_spanned! {field.span() =>
-                    #rudo_gc::Trace::trace(&self.#index, visitor);
-                }
-            });
+            let trace_calls = f
+                .unnamed
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| !skipped(f))
+                .map(|(i, field)| {
+                    let index = Index::from(i);
+                    quote_spanned! {field.span() =>
+                        #rudo_gc::Trace::trace(&self.#index, visitor);
+                    }
+                });
             quote! { #(#trace_calls)* }
         }
         Fields::Unit => quote! {},
@@ -388,31 +562,52 @@ fn generate_enum_trace(rudo_gc: &Path, name: &Ident, data: &syn::DataEnum) -> To
                 let field_names: Vec<_> = f
                     .named
                     .iter()
+                    .filter(|f| !skipped(f))
                     .enumerate()
                     .map(|(i, _)| format_ident!("field{}", i))
                     .collect();
-                let field_idents: Vec<_> =
-                    f.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+                let field_idents: Vec<_> = f
+                    .named
+                    .iter()
+                    .filter(|f| !skipped(f))
+                    .map(|f| f.ident.as_ref().unwrap())
+                    .collect();
                 let trace_calls = field_names.iter().map(|field| {
                     quote! { #rudo_gc::Trace::trace(#field, visitor); }
                 });
 
                 quote! {
-                    #name::#var_name { #(#field_idents: #field_names),* } => {
+                    #name::#var_name { #(#field_idents: #field_names,)* .. } => {
                         #(#trace_calls)*
                     }
                 }
             }
             Fields::Unnamed(f) => {
-                let field_names: Vec<_> = (0..f.unnamed.len())
-                    .map(|i| format_ident!("field{}", i))
+                let patterns: Vec<_> = f
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        if skipped(field) {
+                            quote! { _ }
+                        } else {
+                            let name = format_ident!("field{}", i);
+                            quote! { #name }
+                        }
+                    })
                     .collect();
-                let trace_calls = field_names.iter().map(|field| {
-                    quote! { #rudo_gc::Trace::trace(#field, visitor); }
-                });
+                let trace_calls = f
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, f)| !skipped(f))
+                    .map(|(i, _)| {
+                        let field = format_ident!("field{}", i);
+                        quote! { #rudo_gc::Trace::trace(#field, visitor); }
+                    });
 
                 quote! {
-                    #name::#var_name(#(#field_names),*) => {
+                    #name::#var_name(#(#patterns),*) => {
                         #(#trace_calls)*
                     }
                 }
@@ -432,6 +627,154 @@ fn generate_enum_trace(rudo_gc: &Path, name: &Ident, data: &syn::DataEnum) -> To
     }
 }
 
+/// Derive macro for the `DeepEq` trait.
+///
+/// Generates a field-by-field structural comparison that forwards the shared
+/// `DeepEqContext`, so `Gc` edges are followed and cycles terminate. Enum
+/// values compare equal only when both sides are the same variant.
+///
+/// # Example
+///
+/// ```rust
+/// use rudo_gc::{DeepEq, Gc, Trace};
+///
+/// #[derive(Trace, DeepEq)]
+/// struct Pair {
+///     left: Gc<i32>,
+///     right: Option<Gc<Pair>>,
+/// }
+/// ```
+#[proc_macro_derive(DeepEq, attributes(rudo_gc))]
+pub fn derive_deep_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let mut rudo_gc: Path = parse_quote!(::rudo_gc);
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("rudo_gc") {
+            continue;
+        }
+
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                rudo_gc = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        });
+
+        if let Err(err) = result {
+            return err.into_compile_error().into();
+        }
+    }
+
+    let name = &input.ident;
+    let mut generics = input.generics;
+    for param in &mut generics.params {
+        if let GenericParam::Type(ref mut type_param) = *param {
+            type_param.bounds.push(parse_quote!(#rudo_gc::DeepEq));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => generate_struct_deep_eq(&rudo_gc, &data.fields),
+        Data::Enum(data) => generate_enum_deep_eq(&rudo_gc, name, data),
+        Data::Union(u) => {
+            quote_spanned! {
+                u.union_token.span => compile_error!("`DeepEq` must be manually implemented for unions");
+            }
+        }
+    };
+
+    let generated = quote! {
+        impl #impl_generics #rudo_gc::DeepEq for #name #ty_generics #where_clause {
+            fn deep_eq(&self, other: &Self, ctx: &mut #rudo_gc::DeepEqContext) -> bool {
+                #body
+            }
+        }
+    };
+
+    generated.into()
+}
+
+fn generate_struct_deep_eq(rudo_gc: &Path, fields: &Fields) -> TokenStream {
+    let comparisons: Vec<TokenStream> = match fields {
+        Fields::Named(f) => f
+            .named
+            .iter()
+            .map(|field| {
+                let name = &field.ident;
+                quote_spanned! {field.span() =>
+                    #rudo_gc::DeepEq::deep_eq(&self.#name, &other.#name, ctx)
+                }
+            })
+            .collect(),
+        Fields::Unnamed(f) => f
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let index = Index::from(i);
+                quote_spanned! {field.span() =>
+                    #rudo_gc::DeepEq::deep_eq(&self.#index, &other.#index, ctx)
+                }
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+    quote! { true #(&& #comparisons)* }
+}
+
+fn generate_enum_deep_eq(rudo_gc: &Path, name: &Ident, data: &syn::DataEnum) -> TokenStream {
+    let match_arms = data.variants.iter().map(|variant| {
+        let var_name = &variant.ident;
+        match &variant.fields {
+            Fields::Named(f) => {
+                let field_idents: Vec<_> =
+                    f.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+                let lhs: Vec<_> = (0..field_idents.len())
+                    .map(|i| format_ident!("lhs{}", i))
+                    .collect();
+                let rhs: Vec<_> = (0..field_idents.len())
+                    .map(|i| format_ident!("rhs{}", i))
+                    .collect();
+                quote! {
+                    (
+                        #name::#var_name { #(#field_idents: #lhs),* },
+                        #name::#var_name { #(#field_idents: #rhs),* },
+                    ) => true #(&& #rudo_gc::DeepEq::deep_eq(#lhs, #rhs, ctx))*,
+                }
+            }
+            Fields::Unnamed(f) => {
+                let lhs: Vec<_> = (0..f.unnamed.len())
+                    .map(|i| format_ident!("lhs{}", i))
+                    .collect();
+                let rhs: Vec<_> = (0..f.unnamed.len())
+                    .map(|i| format_ident!("rhs{}", i))
+                    .collect();
+                quote! {
+                    (#name::#var_name(#(#lhs),*), #name::#var_name(#(#rhs),*)) =>
+                        true #(&& #rudo_gc::DeepEq::deep_eq(#lhs, #rhs, ctx))*,
+                }
+            }
+            Fields::Unit => {
+                quote! {
+                    (#name::#var_name, #name::#var_name) => true,
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[allow(unreachable_patterns)]
+        match (self, other) {
+            #(#match_arms)*
+            _ => false,
+        }
+    }
+}
+
 /// Derive macro for `GcCell` compatibility.
 ///
 /// This macro automatically implements `GcCapture` for types containing `Gc<T>` fields,
@@ -462,9 +805,14 @@ fn generate_enum_trace(rudo_gc: &Path, name: &Ident, data: &syn::DataEnum) -> To
 ///
 /// For types without `Gc<T>` fields, no impl is generated (no SATB barrier needed).
 ///
+/// For enums, the generated body is a `match self` that captures the
+/// Gc-containing fields of the active variant; unit variants capture nothing.
+///
+/// `Weak<T>` and `WeakGc<T>` fields are never captured: a weak reference
+/// does not keep its target alive, so overwriting it needs no SATB record.
+///
 /// # Limitations
 ///
-/// - Enums: Not supported (use manual implementation)
 /// - Generic types: Not supported (use manual implementation)
 /// - Recursive types: Not supported (use manual implementation)
 ///
@@ -518,56 +866,74 @@ pub fn derive_gc_cell(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     let gc_fields = match &input.data {
         Data::Struct(struct_data) => analyze_struct_fields(&struct_data.fields),
-        _ => Vec::new(),
+        Data::Enum(enum_data) => enum_data
+            .variants
+            .iter()
+            .flat_map(|variant| analyze_struct_fields(&variant.fields))
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+
+    let (fields, gc_capture_body): (Vec<&syn::Field>, _) = match &input.data {
+        Data::Struct(struct_data) => (
+            struct_data.fields.iter().collect(),
+            generate_gc_capture_body(&rudo_gc, &gc_fields),
+        ),
+        Data::Enum(enum_data) => (
+            enum_data
+                .variants
+                .iter()
+                .flat_map(|variant| &variant.fields)
+                .collect(),
+            generate_enum_gc_capture_body(&rudo_gc, name, enum_data),
+        ),
+        Data::Union(_) => {
+            return syn::Error::new_spanned(name, "GcCell derive does not support unions.")
+                .into_compile_error()
+                .into();
+        }
     };
 
     let generics = add_gc_capture_bounds(&rudo_gc, input.generics, &gc_fields);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    match &input.data {
-        Data::Struct(_struct_data) => {
-            if gc_fields.is_empty() {
-                let gc_capture_body = generate_empty_gc_capture_body(&rudo_gc);
-                let expanded = quote! {
-                    impl #impl_generics #rudo_gc::cell::GcCapture
-                        for #name #ty_generics #where_clause {
-                        #gc_capture_body
-                    }
+    if gc_fields.is_empty() {
+        let gc_capture_body = generate_empty_gc_capture_body(&rudo_gc);
+        let no_gc_pointers_impl = generate_no_gc_pointers_impl(&rudo_gc, name, &generics, &fields);
+        let expanded = quote! {
+            impl #impl_generics #rudo_gc::cell::GcCapture
+                for #name #ty_generics #where_clause {
+                const NO_GC_POINTERS: bool = {
+                    use #rudo_gc::cell::NoGcPointersFallback as _;
+                    #rudo_gc::cell::NoGcPointersProbe::<Self>::NO_GC_POINTERS
                 };
-                return expanded.into();
+
+                #gc_capture_body
             }
 
-            let gc_capture_body = generate_gc_capture_body(&rudo_gc, &gc_fields);
+            #no_gc_pointers_impl
+        };
+        return expanded.into();
+    }
 
-            let expanded = quote! {
-                impl #impl_generics #rudo_gc::cell::GcCapture
-                    for #name #ty_generics #where_clause {
-                    #[inline]
-                    fn capture_gc_ptrs(&self) -> &[std::ptr::NonNull<#rudo_gc::GcBox<()>>] {
-                        &[]
-                    }
+    let expanded = quote! {
+        impl #impl_generics #rudo_gc::cell::GcCapture
+            for #name #ty_generics #where_clause {
+            #[inline]
+            fn capture_gc_ptrs(&self) -> &[std::ptr::NonNull<#rudo_gc::GcBox<()>>] {
+                &[]
+            }
 
-                    #[inline]
-                    fn capture_gc_ptrs_into(
-                        &self,
-                        ptrs: &mut Vec<std::ptr::NonNull<#rudo_gc::GcBox<()>>>
-                    ) {
-                        #gc_capture_body
-                    }
-                }
-            };
-            expanded.into()
+            #[inline]
+            fn capture_gc_ptrs_into(
+                &self,
+                ptrs: &mut Vec<std::ptr::NonNull<#rudo_gc::GcBox<()>>>
+            ) {
+                #gc_capture_body
+            }
         }
-        Data::Enum(_) => syn::Error::new_spanned(
-            name,
-            "GcCell derive does not support enums. Use manual implementation.",
-        )
-        .into_compile_error()
-        .into(),
-        Data::Union(_) => syn::Error::new_spanned(name, "GcCell derive does not support unions.")
-            .into_compile_error()
-            .into(),
-    }
+    };
+    expanded.into()
 }
 
 /// Analyzes struct fields and returns those that contain `Gc<T>`.
@@ -615,6 +981,14 @@ struct FieldInfo<'a> {
 fn field_contains_gc(ty: &syn::Type) -> bool {
     match ty {
         syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            // A weak reference does not keep its target alive, so overwriting
+            // one needs no SATB record, even as `Option<Weak<T>>`.
+            if let Some(last_seg) = path.segments.last() {
+                if last_seg.ident == "Weak" || last_seg.ident == "WeakGc" {
+                    return false;
+                }
+            }
+
             // Check if the path is `Gc` or `Gc<T>` (including fully qualified paths like ::Gc or module::Gc)
             if let Some(first_seg) = path.segments.first() {
                 if first_seg.ident == "Gc" {
@@ -692,6 +1066,63 @@ fn generate_gc_capture_body(rudo_gc: &syn::Path, fields: &[FieldInfo]) -> TokenS
     }
 }
 
+/// Generates a `match self` for `GcCapture::capture_gc_ptrs_into` on an enum.
+///
+/// Each arm binds only the Gc-containing fields of its variant; variants
+/// without any, including unit variants, get an empty arm.
+fn generate_enum_gc_capture_body(
+    rudo_gc: &syn::Path,
+    name: &syn::Ident,
+    data: &syn::DataEnum,
+) -> TokenStream {
+    let match_arms = data.variants.iter().map(|variant| {
+        let var_name = &variant.ident;
+        let gc_fields = analyze_struct_fields(&variant.fields);
+        let bindings: Vec<_> = gc_fields
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect();
+        let calls = bindings.iter().map(|binding| {
+            quote! { #rudo_gc::cell::GcCapture::capture_gc_ptrs_into(#binding, ptrs); }
+        });
+        match &variant.fields {
+            Fields::Named(_) => quote! {
+                #name::#var_name { #(#bindings,)* .. } => {
+                    #(#calls)*
+                }
+            },
+            Fields::Unnamed(f) => {
+                let patterns = (0..f.unnamed.len()).map(|i| {
+                    gc_fields
+                        .iter()
+                        .find(|field| field.index == Some(i))
+                        .map_or_else(
+                            || quote! { _ },
+                            |field| {
+                                let binding = &field.ident;
+                                quote! { #binding }
+                            },
+                        )
+                });
+                quote! {
+                    #name::#var_name(#(#patterns),*) => {
+                        #(#calls)*
+                    }
+                }
+            }
+            Fields::Unit => quote! {
+                #name::#var_name => {}
+            },
+        }
+    });
+
+    quote! {
+        match self {
+            #(#match_arms)*
+        }
+    }
+}
+
 /// Generates an empty `GcCapture` impl for types without Gc<T> fields.
 fn generate_empty_gc_capture_body(rudo_gc: &syn::Path) -> TokenStream {
     quote! {
@@ -707,6 +1138,39 @@ fn generate_empty_gc_capture_body(rudo_gc: &syn::Path) -> TokenStream {
     }
 }
 
+/// Generates a `NoGcPointers` impl that holds when every field's type
+/// implements `NoGcPointers`.
+///
+/// For enums, `fields` holds the fields of every variant. The field bounds
+/// are higher-ranked so that a bound on a concrete type that does not hold
+/// only makes the impl inapplicable instead of failing to compile. Recursive
+/// types get no impl, since proving the bound for them would never terminate.
+fn generate_no_gc_pointers_impl(
+    rudo_gc: &Path,
+    name: &syn::Ident,
+    generics: &Generics,
+    fields: &[&syn::Field],
+) -> TokenStream {
+    let self_ident = format_ident!("Self");
+    if fields.iter().any(|field| {
+        type_contains_param(&field.ty, name) || type_contains_param(&field.ty, &self_ident)
+    }) {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let existing = where_clause.into_iter().flat_map(|w| &w.predicates);
+    let field_types = fields.iter().map(|field| &field.ty);
+    quote! {
+        // SAFETY: no field can hold a `Gc`, as each implements `NoGcPointers`.
+        unsafe impl #impl_generics #rudo_gc::cell::NoGcPointers for #name #ty_generics
+        where
+            #(#existing,)*
+            #(for<'__rudo_gc> #field_types: #rudo_gc::cell::NoGcPointers,)*
+        {}
+    }
+}
+
 /// Checks if a type contains a specific type parameter.
 fn type_contains_param(ty: &syn::Type, target_param: &syn::Ident) -> bool {
     match ty {
@@ -782,3 +1246,162 @@ fn add_gc_capture_bounds(
     }
     generics
 }
+
+/// Derive macro for strongly typed `Gc` handles.
+///
+/// For a struct with a single `Gc<T>` field, such as `struct NodeId(Gc<Node>)`,
+/// this generates `Trace`, `GcCapture`, `Deref<Target = T>`, `Clone`, and an
+/// associated `ptr_eq`, all forwarding to the inner `Gc`, so the wrapper can
+/// be used wherever the `Gc` itself would be. Pairs naturally with
+/// `#[repr(transparent)]`, though it does not require it.
+///
+/// # Example
+///
+/// ```rust
+/// use rudo_gc::{Gc, GcTransparent, Trace};
+///
+/// #[derive(Trace)]
+/// struct Node {
+///     value: i32,
+/// }
+///
+/// #[derive(GcTransparent)]
+/// #[repr(transparent)]
+/// struct NodeId(Gc<Node>);
+///
+/// let id = NodeId(Gc::new(Node { value: 1 }));
+/// let copy = id.clone();
+/// assert_eq!(copy.value, 1);
+/// assert!(NodeId::ptr_eq(&id, &copy));
+/// ```
+#[proc_macro_derive(GcTransparent, attributes(rudo_gc))]
+pub fn derive_gc_transparent(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let mut rudo_gc: Path = parse_quote!(::rudo_gc);
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("rudo_gc") {
+            continue;
+        }
+
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                rudo_gc = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        });
+
+        if let Err(err) = result {
+            return err.into_compile_error().into();
+        }
+    }
+
+    let name = &input.ident;
+    let field = match transparent_gc_field(&input) {
+        Ok(field) => field,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    let inner = field.ident.as_ref().map_or_else(
+        || {
+            let index = Index::from(0);
+            quote!(#index)
+        },
+        |ident| quote!(#ident),
+    );
+    let construct = field.ident.as_ref().map_or_else(
+        || quote!(Self(::core::clone::Clone::clone(&self.#inner))),
+        |ident| quote!(Self { #ident: ::core::clone::Clone::clone(&self.#inner) }),
+    );
+    let field_ty = &field.ty;
+    let generics = add_trait_bounds(&rudo_gc, input.generics.clone(), None);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        unsafe impl #impl_generics #rudo_gc::Trace for #name #ty_generics #where_clause {
+            #[inline]
+            fn trace(&self, visitor: &mut impl #rudo_gc::Visitor) {
+                #rudo_gc::Trace::trace(&self.#inner, visitor);
+            }
+        }
+
+        impl #impl_generics #rudo_gc::cell::GcCapture for #name #ty_generics #where_clause {
+            #[inline]
+            fn capture_gc_ptrs(&self) -> &[::core::ptr::NonNull<#rudo_gc::GcBox<()>>] {
+                #rudo_gc::cell::GcCapture::capture_gc_ptrs(&self.#inner)
+            }
+
+            #[inline]
+            fn capture_gc_ptrs_into(
+                &self,
+                ptrs: &mut ::std::vec::Vec<::core::ptr::NonNull<#rudo_gc::GcBox<()>>>,
+            ) {
+                #rudo_gc::cell::GcCapture::capture_gc_ptrs_into(&self.#inner, ptrs);
+            }
+        }
+
+        impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+            type Target = <#field_ty as ::core::ops::Deref>::Target;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.#inner
+            }
+        }
+
+        impl #impl_generics ::core::clone::Clone for #name #ty_generics #where_clause {
+            #[inline]
+            fn clone(&self) -> Self {
+                #construct
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Whether both handles point to the same object.
+            #[inline]
+            #[must_use]
+            pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+                #rudo_gc::Gc::ptr_eq(&this.#inner, &other.#inner)
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// The single `Gc<T>` field of a `GcTransparent` struct.
+fn transparent_gc_field(input: &DeriveInput) -> syn::Result<&syn::Field> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "GcTransparent derive requires a struct with exactly one `Gc<T>` field",
+        ));
+    };
+    let mut fields = data.fields.iter();
+    let (Some(field), None) = (fields.next(), fields.next()) else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "GcTransparent derive requires a struct with exactly one `Gc<T>` field",
+        ));
+    };
+    let is_gc = matches!(
+        strip_parens(&field.ty),
+        syn::Type::Path(syn::TypePath { qself: None, path })
+            if path.segments.last().is_some_and(|s| s.ident == "Gc")
+    );
+    if !is_gc {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "GcTransparent field must be a `Gc<T>`",
+        ));
+    }
+    if let Some(ty) = find_dyn_gc(&field.ty) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`Gc<dyn Trait>` is not supported",
+        ));
+    }
+    Ok(field)
+}
@@ -0,0 +1,68 @@
+//! Benchmark: Allocation fast path throughput
+//!
+//! Tight `Gc::new` loops for a few size classes. Only the allocation loop is
+//! timed: automatic collection is disabled inside it and each batch is
+//! reclaimed by an untimed `collect_full`, so the numbers reflect the TLAB
+//! bump path and slow-path refills rather than collector work.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rudo_gc::{collect_full, set_gc_enabled, Gc, Trace};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const BATCH: usize = 1_000;
+
+#[derive(Trace)]
+struct Medium {
+    data: [u64; 8],
+}
+
+#[derive(Trace)]
+struct Big {
+    data: [u64; 100],
+}
+
+fn bench_alloc<T: Trace + 'static>(c: &mut Criterion, name: &str, make: fn(u64) -> T) {
+    let mut group = c.benchmark_group("alloc_fast_path");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function(BenchmarkId::new(name, BATCH), |b| {
+        b.iter_custom(|iters| {
+            let mut keep: Vec<Gc<T>> = Vec::with_capacity(BATCH);
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                set_gc_enabled(false);
+                let start = Instant::now();
+                for i in 0..BATCH as u64 {
+                    keep.push(Gc::new(make(black_box(i))));
+                }
+                total += start.elapsed();
+                black_box(&keep);
+                keep.clear();
+                set_gc_enabled(true);
+                collect_full();
+            }
+            total
+        });
+    });
+    group.finish();
+}
+
+fn bench_alloc_u64(c: &mut Criterion) {
+    bench_alloc(c, "u64", |i| i);
+}
+
+fn bench_alloc_medium(c: &mut Criterion) {
+    bench_alloc(c, "medium_64b", |i| Medium { data: [i; 8] });
+}
+
+fn bench_alloc_big(c: &mut Criterion) {
+    bench_alloc(c, "big_800b", |i| Big { data: [i; 100] });
+}
+
+criterion_group!(
+    benches,
+    bench_alloc_u64,
+    bench_alloc_medium,
+    bench_alloc_big
+);
+criterion_main!(benches);
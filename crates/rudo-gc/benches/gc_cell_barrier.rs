@@ -0,0 +1,58 @@
+//! Benchmark: `GcCell::borrow_mut` barrier cost
+//!
+//! Mutates cells held by an old object, where the generational barrier is
+//! live. A `GcCell` of a `NoGcPointers` type skips its barriers and should
+//! match a plain `RefCell`; a `GcCell` holding a `Gc` still pays for them.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rudo_gc::{collect_full, Gc, GcCell, Trace};
+use std::cell::RefCell;
+use std::hint::black_box;
+
+const BATCH: usize = 1_000;
+
+#[derive(Trace)]
+struct Cells {
+    plain: RefCell<usize>,
+    no_gc: GcCell<usize>,
+    with_gc: GcCell<Option<Gc<i32>>>,
+}
+
+fn bench_borrow_mut(c: &mut Criterion) {
+    let cells = Gc::new(Cells {
+        plain: RefCell::new(0),
+        no_gc: GcCell::new(0),
+        with_gc: GcCell::new(None),
+    });
+    let target = Gc::new(7);
+    // Promote both so writes go through the old-object barrier path.
+    collect_full();
+
+    let mut group = c.benchmark_group("gc_cell_borrow_mut");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function(BenchmarkId::new("ref_cell_usize", BATCH), |b| {
+        b.iter(|| {
+            for i in 0..BATCH {
+                *cells.plain.borrow_mut() = black_box(i);
+            }
+        });
+    });
+    group.bench_function(BenchmarkId::new("gc_cell_usize", BATCH), |b| {
+        b.iter(|| {
+            for i in 0..BATCH {
+                *cells.no_gc.borrow_mut() = black_box(i);
+            }
+        });
+    });
+    group.bench_function(BenchmarkId::new("gc_cell_gc", BATCH), |b| {
+        b.iter(|| {
+            for _ in 0..BATCH {
+                *cells.with_gc.borrow_mut() = Some(black_box(target.clone()));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_borrow_mut);
+criterion_main!(benches);
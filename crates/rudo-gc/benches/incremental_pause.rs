@@ -60,6 +60,7 @@ fn benchmark_incremental_pause_time(c: &mut Criterion) {
         increment_size: 1000,
         max_dirty_pages: 1000,
         remembered_buffer_len: 32,
+        satb_buffer_len: 32,
         slice_timeout_ms: 50,
     });
 
@@ -0,0 +1,42 @@
+//! Benchmark: Large object churn
+//!
+//! Allocates and immediately drops 8KB objects, which take the large-object
+//! path. Run it with and without the `large-object-malloc` feature to compare
+//! the global-allocator backend against a dedicated mapping per object.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rudo_gc::{Gc, Trace};
+use std::hint::black_box;
+
+const BATCH: usize = 1_000;
+
+const BACKEND: &str = if cfg!(feature = "large-object-malloc") {
+    "malloc"
+} else {
+    "mmap"
+};
+
+#[derive(Trace)]
+struct Block {
+    data: [u64; 1024],
+}
+
+fn bench_churn_8kb(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_object_churn_8kb");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function(BenchmarkId::new(BACKEND, BATCH), |b| {
+        b.iter(|| {
+            // Cycle-free, so each drop frees the object immediately.
+            for i in 0..BATCH as u64 {
+                let block = Gc::new(Block {
+                    data: [black_box(i); 1024],
+                });
+                black_box(&block);
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_churn_8kb);
+criterion_main!(benches);
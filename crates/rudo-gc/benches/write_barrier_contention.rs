@@ -0,0 +1,110 @@
+//! Benchmark: generational write barrier under multi-threaded mutation
+//!
+//! Every thread writes into old objects spread over a few hundred pages,
+//! then clears their dirty state like a minor collection would, so each
+//! round dirties every page afresh and goes through the barrier's slow
+//! path for it.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rudo_gc::heap::with_heap;
+use rudo_gc::{collect_full, safepoint, Gc, GcCell, Trace};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const HOLDERS: usize = 4_096;
+
+#[derive(Trace)]
+struct Holder {
+    slot: GcCell<Option<Gc<u64>>>,
+    pad: [u64; 24],
+}
+
+/// Forget which pages are dirty, as a minor collection does after scanning
+/// them.
+fn clear_dirty_pages() {
+    with_heap(|heap| {
+        heap.take_dirty_pages_snapshot();
+        for page in heap.dirty_pages_iter() {
+            // SAFETY: dirty-listed pages belong to the heap and stay mapped.
+            unsafe {
+                (*page.as_ptr()).clear_all_dirty();
+                (*page.as_ptr()).clear_dirty_listed();
+            }
+        }
+        heap.clear_dirty_pages_snapshot();
+    });
+}
+
+/// Wait for every thread to reach `target`, letting collections run.
+fn rendezvous(arrived: &AtomicUsize, target: usize) {
+    arrived.fetch_add(1, Ordering::AcqRel);
+    while arrived.load(Ordering::Acquire) < target {
+        safepoint();
+        std::hint::spin_loop();
+    }
+}
+
+fn run(threads: usize, rounds: u64) -> Duration {
+    let arrived = Arc::new(AtomicUsize::new(0));
+    // Every thread must be running before any is joined.
+    #[allow(clippy::needless_collect)]
+    let handles: Vec<_> = (0..threads)
+        .map(|id| {
+            let arrived = arrived.clone();
+            std::thread::spawn(move || {
+                let target = Gc::new(7u64);
+                // Only the stack is scanned for roots, so the holders hang
+                // off a `Gc` rather than a plain `Vec`.
+                let holders: Gc<Vec<Gc<Holder>>> = Gc::new(
+                    (0..HOLDERS)
+                        .map(|_| {
+                            Gc::new(Holder {
+                                slot: GcCell::new(None),
+                                pad: [0; 24],
+                            })
+                        })
+                        .collect(),
+                );
+                rendezvous(&arrived, threads);
+                // One collection promotes every thread's holders.
+                if id == 0 {
+                    collect_full();
+                }
+                rendezvous(&arrived, 2 * threads);
+
+                let start = Instant::now();
+                for _ in 0..rounds {
+                    for holder in holders.iter() {
+                        *holder.slot.borrow_mut() = Some(black_box(target.clone()));
+                    }
+                    clear_dirty_pages();
+                }
+                let elapsed = start.elapsed();
+                rendezvous(&arrived, 3 * threads);
+                elapsed
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .max()
+        .unwrap()
+}
+
+fn bench_write_barrier_contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_barrier_contention");
+    group.sample_size(10);
+    for threads in [1, 2, 4, 8] {
+        group.throughput(Throughput::Elements((threads * HOLDERS) as u64));
+        group.bench_function(BenchmarkId::from_parameter(threads), |b| {
+            b.iter_custom(|rounds| run(threads, rounds));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_write_barrier_contention);
+criterion_main!(benches);
@@ -0,0 +1,167 @@
+//! Sampling allocation-site profiler.
+//!
+//! While profiling is on, every `sample_rate`-th `Gc` allocation on the
+//! thread captures a backtrace and adds it to a per-thread profile; the
+//! allocations in between only decrement a counter in `LocalHeap`. Samples
+//! with the same call stack are aggregated, and
+//! [`AllocProfile::to_folded`] renders them in the collapsed-stack format
+//! flame graph tools read.
+
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A finished allocation profile, returned by [`stop_alloc_profiling`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllocProfile {
+    /// One in this many allocations was sampled; 0 if profiling never ran.
+    pub sample_rate: u32,
+    /// Samples aggregated by call stack, most frequent first.
+    pub samples: Vec<AllocSample>,
+}
+
+/// Sampled allocations sharing one call stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocSample {
+    /// Function names from the outermost caller down to the one that
+    /// allocated. Frames inside `rudo_gc` and the standard library above the
+    /// allocating function are omitted.
+    pub frames: Vec<String>,
+    /// Number of sampled allocations made from this stack.
+    pub count: usize,
+    /// Total size in bytes of the sampled allocations, headers included.
+    pub bytes: usize,
+}
+
+impl AllocProfile {
+    /// Number of allocations sampled.
+    #[must_use]
+    pub fn total_samples(&self) -> usize {
+        self.samples.iter().map(|sample| sample.count).sum()
+    }
+
+    /// The profile in collapsed-stack format: one `outer;...;inner count`
+    /// line per sample, as consumed by `inferno` and `flamegraph.pl`.
+    #[must_use]
+    pub fn to_folded(&self) -> String {
+        let mut folded = String::new();
+        for sample in &self.samples {
+            let _ = writeln!(folded, "{} {}", sample.frames.join(";"), sample.count);
+        }
+        folded
+    }
+}
+
+struct Profiler {
+    sample_rate: u32,
+    /// `(count, bytes)` per call stack.
+    stacks: HashMap<Vec<String>, (usize, usize)>,
+}
+
+thread_local! {
+    static PROFILER: RefCell<Option<Profiler>> = const { RefCell::new(None) };
+}
+
+/// Start sampling roughly one in `sample_rate` allocations on this thread.
+///
+/// Restarting discards the samples collected so far. Overhead is one
+/// backtrace capture per sample, so a rate in the thousands is suitable for
+/// production use.
+///
+/// # Panics
+///
+/// Panics if `sample_rate` is 0.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{start_alloc_profiling, stop_alloc_profiling, Gc};
+///
+/// start_alloc_profiling(10);
+/// let values: Vec<Gc<u64>> = (0..100).map(Gc::new).collect();
+/// let profile = stop_alloc_profiling();
+/// assert_eq!(profile.total_samples(), 10);
+/// # drop(values);
+/// ```
+pub fn start_alloc_profiling(sample_rate: u32) {
+    assert!(sample_rate > 0, "sample_rate must be at least 1");
+    PROFILER.with(|profiler| {
+        *profiler.borrow_mut() = Some(Profiler {
+            sample_rate,
+            stacks: HashMap::new(),
+        });
+    });
+    crate::heap::with_heap(|heap| heap.set_alloc_sample_countdown(sample_rate));
+}
+
+/// Stop profiling on this thread and return what was sampled.
+///
+/// Returns an empty profile if profiling was not running.
+#[must_use]
+pub fn stop_alloc_profiling() -> AllocProfile {
+    crate::heap::with_heap(|heap| heap.set_alloc_sample_countdown(0));
+    let Some(profiler) = PROFILER.with(|profiler| profiler.borrow_mut().take()) else {
+        return AllocProfile::default();
+    };
+    let mut samples: Vec<AllocSample> = profiler
+        .stacks
+        .into_iter()
+        .map(|(frames, (count, bytes))| AllocSample {
+            frames,
+            count,
+            bytes,
+        })
+        .collect();
+    samples.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.frames.cmp(&b.frames)));
+    AllocProfile {
+        sample_rate: profiler.sample_rate,
+        samples,
+    }
+}
+
+/// Record a sampled allocation of `size` bytes made from the current stack.
+///
+/// Returns the number of allocations until the next sample, or 0 if
+/// profiling has stopped.
+#[cold]
+#[inline(never)]
+pub fn record_sample(size: usize) -> u32 {
+    PROFILER.with(|profiler| {
+        let mut profiler = profiler.borrow_mut();
+        let Some(profiler) = profiler.as_mut() else {
+            return 0;
+        };
+        let entry = profiler.stacks.entry(call_stack()).or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 += size;
+        profiler.sample_rate
+    })
+}
+
+/// Function names on the current stack, outermost first, without the frames
+/// of the allocation itself or of the thread runtime.
+fn call_stack() -> Vec<String> {
+    let backtrace = Backtrace::force_capture().to_string();
+    let mut frames: Vec<String> = backtrace
+        .lines()
+        .filter_map(|line| {
+            let (index, name) = line.trim_start().split_once(": ")?;
+            index
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| name.to_owned())
+        })
+        .skip_while(|name| is_internal_frame(name))
+        .take_while(|name| !name.contains("__rust_begin_short_backtrace"))
+        .collect();
+    frames.reverse();
+    frames
+}
+
+fn is_internal_frame(name: &str) -> bool {
+    let name = name.trim_start_matches('<');
+    ["rudo_gc::", "std::", "core::", "alloc::"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
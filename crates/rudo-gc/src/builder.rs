@@ -0,0 +1,199 @@
+//! Field-by-field construction of a value directly in its `Gc` slot.
+//!
+//! `Gc::new` takes a finished value, which means building it on the stack
+//! and moving it into the heap. A [`GcBuilder`] instead allocates the slot
+//! first and lets the caller write each field in place, the `Gc` analog of
+//! initializing a struct through `MaybeUninit`. The builder remembers which
+//! fields were written, so abandoning it, including by a panic part way
+//! through, drops exactly those fields and frees the slot.
+
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use crate::gc::NoGcGuard;
+use crate::heap::with_heap;
+use crate::ptr::GcBox;
+use crate::trace::Trace;
+use crate::Gc;
+
+/// Builds a `Gc<T>` by writing its fields one at a time into the allocated
+/// slot.
+///
+/// Write each field with [`init_field`](Self::init_field), then call
+/// [`finish`](Self::finish) to get the `Gc`. If the builder is dropped
+/// before `finish`, for example because computing a field panicked, the
+/// fields written so far are dropped in reverse order and the slot is
+/// returned to the heap.
+///
+/// Collections are paused, as by a [`NoGcGuard`], for as long as the
+/// builder lives, so no collection ever sees the half-built value. Keep the
+/// construction short.
+///
+/// # Example
+///
+/// ```
+/// use std::ptr::addr_of_mut;
+///
+/// use rudo_gc::{Gc, GcBuilder, Trace};
+///
+/// #[derive(Trace)]
+/// struct Pair {
+///     name: String,
+///     next: Option<Gc<Pair>>,
+/// }
+///
+/// let mut builder = GcBuilder::<Pair>::new();
+/// // SAFETY: each projection names a distinct field of `Pair`, and every
+/// // field is written before `finish`.
+/// let pair = unsafe {
+///     builder.init_field(|p| addr_of_mut!((*p).name), "head".to_string());
+///     builder.init_field(|p| addr_of_mut!((*p).next), None);
+///     builder.finish()
+/// };
+/// assert_eq!(pair.name, "head");
+/// ```
+pub struct GcBuilder<T: Trace + 'static> {
+    /// The allocated `GcBox`, until `finish` hands it to a `Gc`.
+    ptr: Option<NonNull<u8>>,
+    /// Offset, size and drop glue of every field written so far.
+    initialized: Vec<(usize, usize, unsafe fn(*mut u8))>,
+    _no_gc: NoGcGuard,
+    /// The slot lives on this thread's heap, so the builder is `!Send`.
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T: Trace + 'static> GcBuilder<T> {
+    /// Allocate a slot for a `T` and pause collection until the builder is
+    /// finished or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a zero-sized type.
+    #[must_use]
+    pub fn new() -> Self {
+        assert!(
+            std::mem::size_of::<T>() != 0,
+            "GcBuilder does not support zero-sized types"
+        );
+
+        // Pause before allocating: the allocation itself may collect.
+        let no_gc = NoGcGuard::new();
+        let ptr = GcBox::<T>::allocate();
+        // SAFETY: `ptr` is a fresh `GcBox<T>` slot. No-op drop and trace
+        // functions keep it inert until `finish` writes the real header.
+        unsafe { GcBox::<()>::init_header_at(ptr.as_ptr().cast()) };
+
+        Self {
+            ptr: Some(ptr),
+            initialized: Vec::new(),
+            _no_gc: no_gc,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pointer to the uninitialized value in the slot.
+    #[must_use]
+    pub const fn as_mut_ptr(&self) -> *mut T {
+        value_ptr::<T>(self.ptr.expect("GcBuilder already finished"))
+    }
+
+    /// Write `value` to the field that `field` projects from the value
+    /// pointer, typically `|p| addr_of_mut!((*p).name)`.
+    ///
+    /// The field is dropped with the rest of the value once the builder is
+    /// finished, or on its own if the builder is dropped first.
+    ///
+    /// # Safety
+    ///
+    /// `field` must return a pointer to a field of the `T` it is given,
+    /// without reading through it, and fields must not overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field lies outside the value or was already written.
+    pub unsafe fn init_field<F>(
+        &mut self,
+        field: impl FnOnce(*mut T) -> *mut F,
+        value: F,
+    ) -> &mut Self {
+        unsafe fn drop_field<F>(ptr: *mut u8) {
+            // SAFETY: the builder only calls this on a written field of type F.
+            unsafe { std::ptr::drop_in_place(ptr.cast::<F>()) };
+        }
+
+        let base = self.as_mut_ptr();
+        let field_ptr = field(base);
+        let offset = (field_ptr as usize).wrapping_sub(base as usize);
+        let size = std::mem::size_of::<F>();
+        assert!(
+            offset <= std::mem::size_of::<T>() && size <= std::mem::size_of::<T>() - offset,
+            "GcBuilder::init_field: field lies outside the value"
+        );
+        assert!(
+            self.initialized
+                .iter()
+                .all(|&(start, len, _)| offset + size <= start || start + len <= offset),
+            "GcBuilder::init_field: field written twice"
+        );
+
+        // SAFETY: the caller guarantees `field_ptr` is an unwritten field of
+        // the value, which the assertions above keep within the slot.
+        unsafe { field_ptr.write(value) };
+        self.initialized.push((offset, size, drop_field::<F>));
+        self
+    }
+
+    /// Finish construction and return the `Gc`.
+    ///
+    /// Collection resumes, running any collection deferred while building.
+    ///
+    /// # Safety
+    ///
+    /// Every field of the value must have been written with
+    /// [`init_field`](Self::init_field).
+    #[must_use]
+    pub unsafe fn finish(mut self) -> Gc<T> {
+        let ptr = self.ptr.take().expect("GcBuilder already finished");
+        self.initialized.clear();
+        // SAFETY: the caller guarantees the value is fully initialized.
+        let gc = unsafe { Gc::init_header_in_place(ptr) };
+        crate::gc::notify_allocated();
+        gc
+    }
+}
+
+impl<T: Trace + 'static> Default for GcBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace + 'static> Drop for GcBuilder<T> {
+    fn drop(&mut self) {
+        let Some(ptr) = self.ptr.take() else {
+            return;
+        };
+        let base = value_ptr::<T>(ptr).cast::<u8>();
+        for (offset, _, drop_field) in self.initialized.drain(..).rev() {
+            // SAFETY: the field at `offset` was written and is dropped once.
+            unsafe { drop_field(base.add(offset)) };
+        }
+        // SAFETY: the slot came from this thread's heap and its header marks
+        // the value as needing no drop, so only the memory is reclaimed.
+        with_heap(|heap| unsafe { heap.dealloc(ptr) });
+    }
+}
+
+/// Pointer to the value in the `GcBox<T>` allocated at `ptr`.
+const fn value_ptr<T: Trace + 'static>(ptr: NonNull<u8>) -> *mut T {
+    // SAFETY: the value lies within the allocated `GcBox<T>`.
+    unsafe { ptr.as_ptr().add(GcBox::<T>::value_offset()).cast() }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for GcBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcBuilder")
+            .field("initialized_fields", &self.initialized.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -12,7 +12,7 @@ use parking_lot::Mutex;
 use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::ptr::NonNull;
 use std::rc::Rc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
 use std::sync::{Arc as StdArc, Mutex as StdMutex, RwLock};
 
 /// A memory location with interior mutability that triggers a write barrier.
@@ -106,6 +106,74 @@ pub struct GcCell<T: ?Sized> {
     inner: RefCell<T>,
 }
 
+thread_local! {
+    /// Live [`GcRef`] guards per cell address, for [`GcCell::borrow_state`].
+    ///
+    /// Kept out of line so that cells nobody inspects pay nothing for it.
+    /// A cell cannot move while it is borrowed, and `GcRef` is not `Send`,
+    /// so the address identifies the cell on this thread.
+    static COUNTED_BORROWS: RefCell<HashMap<usize, usize>> = RefCell::new(HashMap::new());
+}
+
+/// The current borrow state of a [`GcCell`], as reported by
+/// [`GcCell::borrow_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorrowState {
+    /// No borrows are active; `borrow_mut` would succeed.
+    Unborrowed,
+    /// The value is immutably borrowed. Counts the live [`GcRef`] guards
+    /// from [`GcCell::borrow_counted`]; plain [`GcCell::borrow`] guards are
+    /// not counted.
+    Shared(usize),
+    /// The value is mutably borrowed.
+    Exclusive,
+}
+
+/// An immutable borrow from [`GcCell::borrow_counted`].
+///
+/// Behaves like [`Ref`], and additionally counts towards the cell's
+/// [`BorrowState::Shared`] while it is alive.
+pub struct GcRef<'a, T: ?Sized> {
+    inner: Ref<'a, T>,
+    key: usize,
+}
+
+impl<T: ?Sized> std::ops::Deref for GcRef<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: ?Sized> Drop for GcRef<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        COUNTED_BORROWS.with(|counts| {
+            let mut counts = counts.borrow_mut();
+            if let Some(count) = counts.get_mut(&self.key) {
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(&self.key);
+                }
+            }
+        });
+    }
+}
+
+impl<T: std::fmt::Debug + ?Sized> std::fmt::Debug for GcRef<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: std::fmt::Display + ?Sized> std::fmt::Display for GcRef<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
 impl<T> GcCell<T> {
     /// Creates a new `GcCell` containing `value`.
     pub const fn new(value: T) -> Self {
@@ -135,6 +203,80 @@ impl<T: ?Sized> GcCell<T> {
         self.inner.borrow()
     }
 
+    /// Immutably borrows the wrapped value, counting the borrow in
+    /// [`GcCell::borrow_state`].
+    ///
+    /// Use it in place of [`GcCell::borrow`] on the paths under suspicion
+    /// when hunting a conflicting borrow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    #[inline]
+    pub fn borrow_counted(&self) -> GcRef<'_, T> {
+        let inner = self.borrow();
+        let key = self.counted_borrows_key();
+        COUNTED_BORROWS.with(|counts| *counts.borrow_mut().entry(key).or_insert(0) += 1);
+        GcRef { inner, key }
+    }
+
+    fn counted_borrows_key(&self) -> usize {
+        std::ptr::from_ref(self).cast::<()>() as usize
+    }
+
+    /// Returns the current borrow state of the cell.
+    ///
+    /// Useful for tracking down the code path that holds a conflicting borrow
+    /// when `borrow_mut` panics with "already borrowed". The shared count only
+    /// covers borrows taken through [`GcCell::borrow_counted`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::cell::BorrowState;
+    /// use rudo_gc::GcCell;
+    ///
+    /// let cell = GcCell::new(1);
+    /// let a = cell.borrow_counted();
+    /// let b = cell.borrow_counted();
+    /// assert_eq!(cell.borrow_state(), BorrowState::Shared(2));
+    /// drop((a, b));
+    /// assert_eq!(cell.borrow_state(), BorrowState::Unborrowed);
+    /// ```
+    #[must_use]
+    pub fn borrow_state(&self) -> BorrowState {
+        if self.inner.try_borrow_mut().is_ok() {
+            BorrowState::Unborrowed
+        } else if self.inner.try_borrow().is_err() {
+            BorrowState::Exclusive
+        } else {
+            let key = self.counted_borrows_key();
+            BorrowState::Shared(
+                COUNTED_BORROWS.with(|counts| counts.borrow().get(&key).copied().unwrap_or(0)),
+            )
+        }
+    }
+
+    /// Reads the wrapped value for tracing, bypassing the borrow flag.
+    ///
+    /// The collector may trace a cell while a mutator holds a `borrow_mut`
+    /// guard on it, at a stop-the-world safepoint or between incremental
+    /// marking slices. Going through `borrow` would panic there. Reading the
+    /// value directly is sound for marking because the mutator does not run
+    /// while the collector reads, and any `Gc` it overwrites after the
+    /// snapshot was recorded by the SATB barrier in `borrow_mut`.
+    ///
+    /// # Safety
+    ///
+    /// No `&mut T` obtained from this cell may be used while the returned
+    /// reference is alive.
+    #[inline]
+    pub(crate) unsafe fn trace_borrow(&self) -> &T {
+        // SAFETY: RefCell::as_ptr() is always valid; the caller guarantees
+        // no outstanding `&mut T` is used concurrently.
+        unsafe { &*self.inner.as_ptr() }
+    }
+
     /// Mutably borrows the wrapped value with automatic SATB barrier.
     ///
     /// This method performs generational and incremental write barriers,
@@ -144,6 +286,9 @@ impl<T: ?Sized> GcCell<T> {
     ///
     /// Use this as the primary mutation method for `GcCell<T>`.
     ///
+    /// For `T:` [`NoGcPointers`] there is nothing for the barriers to record,
+    /// and they are compiled out.
+    ///
     /// # Type Bounds
     ///
     /// - `T: GcCapture` - Required for SATB barrier. Add `#[derive(GcCell)]` to your type.
@@ -158,6 +303,10 @@ impl<T: ?Sized> GcCell<T> {
     {
         self.validate_thread_affinity("borrow_mut");
 
+        if T::NO_GC_POINTERS {
+            return self.inner.borrow_mut();
+        }
+
         let ptr = std::ptr::from_ref(self).cast::<u8>();
 
         // Cache barrier states once to avoid TOCTOU between check and use.
@@ -190,9 +339,17 @@ impl<T: ?Sized> GcCell<T> {
 
         // FIX bug302: Check is_generational_barrier_active() before triggering barrier, matching GcThreadSafeCell.
         if generational_active || incremental_active {
-            crate::heap::gc_cell_validate_and_barrier(ptr, "borrow_mut", incremental_active);
+            crate::heap::gc_cell_validate_and_barrier(
+                ptr,
+                "borrow_mut",
+                incremental_active,
+                Self::field_scan(),
+            );
         }
 
+        #[cfg(feature = "gc-mutation-log")]
+        crate::mutation_log::record(self);
+
         let result = self.inner.borrow_mut();
 
         if incremental_active {
@@ -215,6 +372,31 @@ impl<T: ?Sized> GcCell<T> {
         result
     }
 
+    /// How minor GC scans this cell on its own, when it is a field of a large
+    /// object. `None` for unsized `T`, whose cells can't be found from a
+    /// thin pointer.
+    #[inline]
+    fn field_scan() -> Option<crate::heap::FieldScanFn>
+    where
+        T: GcCapture,
+    {
+        (std::mem::size_of::<*const T>() == std::mem::size_of::<*const u8>())
+            .then_some(scan_dirty_cell::<T> as crate::heap::FieldScanFn)
+    }
+
+    /// Captures the wrapped value's `Gc` pointers into `ptrs`, unless the
+    /// cell is mutably borrowed. Returns whether it captured.
+    #[cfg(feature = "gc-mutation-log")]
+    pub(crate) fn capture_unborrowed(&self, ptrs: &mut Vec<NonNull<GcBox<()>>>) -> bool
+    where
+        T: GcCapture,
+    {
+        self.inner.try_borrow().is_ok_and(|value| {
+            value.capture_gc_ptrs_into(ptrs);
+            true
+        })
+    }
+
     /// Mutably borrows the wrapped value with SATB barrier.
     ///
     /// This method is equivalent to `borrow_mut()`. It captures old GC pointer
@@ -259,7 +441,7 @@ impl<T: ?Sized> GcCell<T> {
         let generational_active = crate::gc::incremental::is_generational_barrier_active();
         if generational_active {
             let ptr = std::ptr::from_ref(self).cast::<u8>();
-            crate::heap::gc_cell_validate_and_barrier(ptr, "borrow_mut_gen_only", false);
+            crate::heap::gc_cell_validate_and_barrier(ptr, "borrow_mut_gen_only", false, None);
         }
 
         self.inner.borrow_mut()
@@ -328,6 +510,28 @@ unsafe fn record_page_in_remembered_buffer(page: NonNull<PageHeader>) -> bool {
     })
 }
 
+/// Mark the `Gc`s held by the `GcCell<T>` at `cell` for minor GC.
+///
+/// # Safety
+///
+/// `cell` must point to a live `GcCell<T>`, and `T` must be sized (see
+/// `GcCell::field_scan`).
+unsafe fn scan_dirty_cell<T: GcCapture + ?Sized>(
+    cell: *const u8,
+    visitor: &mut crate::trace::GcVisitor,
+) {
+    // SAFETY: `T` is sized, so `*const GcCell<T>` is a thin pointer of the
+    // same size as `cell`.
+    let cell = unsafe { std::mem::transmute_copy::<*const u8, *const GcCell<T>>(&cell) };
+    let mut ptrs = Vec::new();
+    // SAFETY: The collector runs with the mutator stopped.
+    unsafe { (*cell).trace_borrow() }.capture_gc_ptrs_into(&mut ptrs);
+    for ptr in ptrs {
+        // SAFETY: `ptr` was captured from a live `Gc`.
+        unsafe { crate::gc::mark_object_minor(ptr, visitor) };
+    }
+}
+
 /// Trait for types that can participate in SATB barrier.
 /// Implement this to enable automatic old-value capture during write barriers.
 ///
@@ -376,6 +580,13 @@ unsafe fn record_page_in_remembered_buffer(page: NonNull<PageHeader>) -> bool {
 /// }
 /// ```
 pub trait GcCapture {
+    /// Whether values of this type can never hold a `Gc`.
+    ///
+    /// When `true`, [`GcCell::borrow_mut`] skips its write barriers. Only
+    /// types implementing [`NoGcPointers`] may set it; `#[derive(GcCell)]`
+    /// sets it together with that impl.
+    const NO_GC_POINTERS: bool = false;
+
     /// Returns a slice of all `GcBox` pointers contained in this type.
     ///
     /// For single `Gc<T>`: returns slice of length 0 or 1
@@ -436,6 +647,8 @@ impl<T: GcCapture + ?Sized> GcCapture for &T {
 }
 
 impl<T: GcCapture + 'static> GcCapture for Option<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -469,6 +682,8 @@ where
 }
 
 impl<T: GcCapture + 'static> GcCapture for Vec<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -483,6 +698,8 @@ impl<T: GcCapture + 'static> GcCapture for Vec<T> {
 }
 
 impl<T: GcCapture + 'static> GcCapture for std::collections::VecDeque<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -525,6 +742,8 @@ impl<T: GcCapture + 'static> GcCapture for std::collections::BinaryHeap<T> {
 }
 
 impl<T: GcCapture + 'static, const N: usize> GcCapture for [T; N] {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -605,6 +824,8 @@ impl<T: GcCapture + 'static> GcCapture for BTreeSet<T> {
 }
 
 impl<T: GcCapture + 'static> GcCapture for Box<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -641,6 +862,8 @@ impl<T: GcCapture + 'static> GcCapture for Rc<T> {
 }
 
 impl<T: GcCapture + ?Sized> GcCapture for GcCell<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -663,6 +886,8 @@ impl<T: GcCapture + ?Sized> GcCapture for GcCell<T> {
 }
 
 impl<T: GcCapture + Copy + 'static> GcCapture for Cell<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -676,6 +901,8 @@ impl<T: GcCapture + Copy + 'static> GcCapture for Cell<T> {
 }
 
 impl<T: GcCapture + 'static> GcCapture for RefCell<T> {
+    const NO_GC_POINTERS: bool = T::NO_GC_POINTERS;
+
     #[inline]
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
@@ -797,120 +1024,223 @@ macro_rules! impl_gc_capture {
 
 // Primitives: no-op GcCapture so GcRwLock/GcMutex guards can require T: GcCapture on drop.
 impl GcCapture for i8 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for i16 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for i32 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for i64 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for i128 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for u8 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for u16 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for u32 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for u64 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for u128 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for usize {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for isize {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
+#[allow(clippy::use_self)]
 impl GcCapture for bool {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for f32 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for f64 {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for char {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for () {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for str {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 impl GcCapture for String {
+    const NO_GC_POINTERS: bool = true;
+
     fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
         &[]
     }
     fn capture_gc_ptrs_into(&self, _: &mut Vec<NonNull<GcBox<()>>>) {}
 }
 
+/// Marker for types whose values can never hold a `Gc`.
+///
+/// Mutating such a value cannot create an old-to-young edge or overwrite a
+/// pointer incremental marking still has to see, so `GcCell<T>` skips its
+/// write barriers for it and costs about as much as a `RefCell<T>`.
+///
+/// Implemented for primitives, `str`, `String` and `Option`, `Vec`,
+/// `VecDeque`, `Box`, arrays and cells of such types. `#[derive(GcCell)]`
+/// implements it for a struct whose fields all implement it. The barriers
+/// are skipped where [`GcCapture::NO_GC_POINTERS`] is `true`, which the
+/// derive can only prove for structs without type parameters.
+///
+/// # Safety
+///
+/// No value of the type may contain a `Gc`, directly or through any field.
+pub unsafe trait NoGcPointers {}
+
+// SAFETY: none of these can hold a `Gc`.
+unsafe impl NoGcPointers for i8 {}
+unsafe impl NoGcPointers for i16 {}
+unsafe impl NoGcPointers for i32 {}
+unsafe impl NoGcPointers for i64 {}
+unsafe impl NoGcPointers for i128 {}
+unsafe impl NoGcPointers for u8 {}
+unsafe impl NoGcPointers for u16 {}
+unsafe impl NoGcPointers for u32 {}
+unsafe impl NoGcPointers for u64 {}
+unsafe impl NoGcPointers for u128 {}
+unsafe impl NoGcPointers for usize {}
+unsafe impl NoGcPointers for isize {}
+unsafe impl NoGcPointers for bool {}
+unsafe impl NoGcPointers for f32 {}
+unsafe impl NoGcPointers for f64 {}
+unsafe impl NoGcPointers for char {}
+unsafe impl NoGcPointers for () {}
+unsafe impl NoGcPointers for str {}
+unsafe impl NoGcPointers for String {}
+// SAFETY: these hold nothing but `T`s, which hold no `Gc`.
+unsafe impl<T: NoGcPointers> NoGcPointers for Option<T> {}
+unsafe impl<T: NoGcPointers> NoGcPointers for Vec<T> {}
+unsafe impl<T: NoGcPointers> NoGcPointers for std::collections::VecDeque<T> {}
+unsafe impl<T: NoGcPointers, const N: usize> NoGcPointers for [T; N] {}
+unsafe impl<T: NoGcPointers> NoGcPointers for Box<T> {}
+unsafe impl<T: NoGcPointers + ?Sized> NoGcPointers for GcCell<T> {}
+unsafe impl<T: NoGcPointers> NoGcPointers for Cell<T> {}
+unsafe impl<T: NoGcPointers> NoGcPointers for RefCell<T> {}
+
+/// Resolves `NO_GC_POINTERS` to whether `T: NoGcPointers` for
+/// `#[derive(GcCell)]`, falling back to [`NoGcPointersFallback`] when the
+/// bound does not hold.
+#[doc(hidden)]
+pub struct NoGcPointersProbe<T: ?Sized>(std::marker::PhantomData<T>);
+
+impl<T: NoGcPointers + ?Sized> NoGcPointersProbe<T> {
+    pub const NO_GC_POINTERS: bool = true;
+}
+
+#[doc(hidden)]
+pub trait NoGcPointersFallback {
+    const NO_GC_POINTERS: bool = false;
+}
+
+impl<T: ?Sized> NoGcPointersFallback for NoGcPointersProbe<T> {}
+
 // SAFETY: GcCell is Trace if T is Trace.
 // It just traces the inner value.
 //
@@ -921,15 +1251,10 @@ unsafe impl<T: Trace + ?Sized> Trace for GcCell<T> {
     #[inline]
     fn trace(&self, visitor: &mut impl crate::trace::Visitor) {
         // SAFETY:
-        // 1. GC happens during Stop-The-World (STW), all mutator threads are paused
-        // 2. There may be active RefMut on the stack, but there won't be concurrent writes
-        //    during GC scanning
-        // 3. We only read fields for marking, we don't modify RefCell's internal state
-        // 4. RefCell::as_ptr() is safe and doesn't panic
-        let ptr = self.inner.as_ptr();
-        unsafe {
-            (*ptr).trace(visitor);
-        }
+        // 1. Tracing happens at a safepoint or inside a marking slice, so the
+        //    mutator is paused even if it holds a RefMut on its stack
+        // 2. We only read fields for marking, we don't modify RefCell's internal state
+        unsafe { self.trace_borrow() }.trace(visitor);
     }
 }
 
@@ -958,6 +1283,264 @@ impl<T: std::fmt::Display + ?Sized> std::fmt::Display for GcCell<T> {
     }
 }
 
+/// Serializes the current value of the cell.
+///
+/// Returns a serializer error instead of panicking if the cell is currently
+/// mutably borrowed.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize + ?Sized> serde::Serialize for GcCell<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.validate_thread_affinity("serialize");
+        let value = self
+            .inner
+            .try_borrow()
+            .map_err(|_| serde::ser::Error::custom("GcCell is already mutably borrowed"))?;
+        value.serialize(serializer)
+    }
+}
+
+/// Deserializes a value and wraps it in a fresh cell.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for GcCell<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// A cell holding a [`Weak`](crate::Weak) reference that can be refilled.
+///
+/// This codifies the weak-cache pattern: keep a weak handle to an object,
+/// hand out the existing object while it is alive, and recreate it once it
+/// has been collected. Updates go through a [`GcCell`], so storing the new
+/// weak reference runs the usual write barriers.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, GcWeakCell};
+///
+/// let cache: GcWeakCell<String> = GcWeakCell::new();
+/// let a = cache.get_or_insert_with(|| Gc::new("config".to_string()));
+/// let b = cache.get_or_insert_with(|| unreachable!());
+/// assert!(Gc::ptr_eq(&a, &b));
+/// ```
+pub struct GcWeakCell<T: Trace + 'static> {
+    weak: GcCell<crate::Weak<T>>,
+}
+
+impl<T: Trace + 'static> GcWeakCell<T> {
+    /// Creates an empty `GcWeakCell`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            weak: GcCell::new(crate::Weak::default()),
+        }
+    }
+
+    /// Creates a `GcWeakCell` holding a weak reference to `value`.
+    #[must_use]
+    pub fn from_gc(value: &crate::Gc<T>) -> Self {
+        Self {
+            weak: GcCell::new(crate::Gc::downgrade(value)),
+        }
+    }
+
+    /// Upgrades the stored weak reference.
+    ///
+    /// Returns `None` if the cell is empty or the object has been collected.
+    #[must_use]
+    pub fn get(&self) -> Option<crate::Gc<T>> {
+        self.weak.borrow().upgrade()
+    }
+
+    /// Stores a weak reference to `value`, replacing the previous one.
+    pub fn set(&self, value: &crate::Gc<T>) {
+        *self.weak.borrow_mut() = crate::Gc::downgrade(value);
+    }
+
+    /// Returns the stored object if it is still alive, otherwise calls `f`,
+    /// stores a weak reference to its result and returns it.
+    ///
+    /// No borrow of the cell is held while `f` runs, so `f` may use the cell.
+    pub fn get_or_insert_with(&self, f: impl FnOnce() -> crate::Gc<T>) -> crate::Gc<T> {
+        if let Some(existing) = self.get() {
+            return existing;
+        }
+        let value = f();
+        self.set(&value);
+        value
+    }
+}
+
+impl<T: Trace + 'static> Default for GcWeakCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for GcWeakCell<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcWeakCell")
+            .field("alive", &self.weak.borrow().is_alive())
+            .finish()
+    }
+}
+
+unsafe impl<T: Trace + 'static> Trace for GcWeakCell<T> {
+    #[inline]
+    fn trace(&self, visitor: &mut impl crate::trace::Visitor) {
+        self.weak.trace(visitor);
+    }
+}
+
+impl<T: Trace + 'static> GcCapture for GcWeakCell<T> {
+    #[inline]
+    fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
+        &[]
+    }
+
+    #[inline]
+    fn capture_gc_ptrs_into(&self, ptrs: &mut Vec<NonNull<GcBox<()>>>) {
+        self.weak.capture_gc_ptrs_into(ptrs);
+    }
+}
+
+/// A list of [`Weak`](crate::Weak) references that drops its dead entries.
+///
+/// This codifies the observer-list pattern: subscribers are held weakly, so
+/// the list never keeps them alive, and entries whose object is gone are
+/// removed rather than compacted by hand. Removal happens in
+/// [`iter`](Self::iter), in [`compact`](Self::compact), and automatically on
+/// the first use of the list after a collection has run.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, GcWeakVec};
+///
+/// let subscribers: GcWeakVec<String> = GcWeakVec::new();
+/// let a = Gc::new("a".to_string());
+/// subscribers.push(&a);
+/// subscribers.push(&Gc::new("b".to_string()));
+///
+/// let live: Vec<_> = subscribers.iter().collect();
+/// assert_eq!(live.len(), 1);
+/// assert_eq!(subscribers.len(), 1);
+/// ```
+pub struct GcWeakVec<T: Trace + 'static> {
+    weaks: GcCell<Vec<crate::Weak<T>>>,
+    /// Collection count when the list was last compacted.
+    compacted_at: Cell<usize>,
+}
+
+impl<T: Trace + 'static> GcWeakVec<T> {
+    /// Creates an empty `GcWeakVec`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            weaks: GcCell::new(Vec::new()),
+            compacted_at: Cell::new(crate::global_metrics().total_collections()),
+        }
+    }
+
+    /// Adds a weak reference to `value`.
+    pub fn push(&self, value: &crate::Gc<T>) {
+        self.compact_if_collected();
+        self.weaks.borrow_mut().push(crate::Gc::downgrade(value));
+    }
+
+    /// Number of entries, compacting first if a collection ran since the
+    /// last compaction.
+    ///
+    /// Objects can also die when their last `Gc` is dropped, so entries
+    /// counted here may already be dead; [`compact`](Self::compact) first
+    /// for an exact count.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.compact_if_collected();
+        self.weaks.borrow().len()
+    }
+
+    /// Whether the list has no entries. See [`len`](Self::len).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry whose object is gone, returning how many were
+    /// removed.
+    pub fn compact(&self) -> usize {
+        self.compacted_at
+            .set(crate::global_metrics().total_collections());
+        let mut weaks = self.weaks.borrow_mut();
+        let before = weaks.len();
+        weaks.retain(crate::Weak::is_alive);
+        before - weaks.len()
+    }
+
+    /// Upgrades the live entries, removing the dead ones.
+    ///
+    /// The returned iterator owns strong references, so the list may be
+    /// modified while iterating.
+    pub fn iter(&self) -> std::vec::IntoIter<crate::Gc<T>> {
+        self.compacted_at
+            .set(crate::global_metrics().total_collections());
+        let mut live = Vec::new();
+        self.weaks
+            .borrow_mut()
+            .retain(|weak| weak.upgrade().map(|gc| live.push(gc)).is_some());
+        live.into_iter()
+    }
+
+    fn compact_if_collected(&self) {
+        if self.compacted_at.get() != crate::global_metrics().total_collections() {
+            self.compact();
+        }
+    }
+}
+
+impl<T: Trace + 'static> Default for GcWeakVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for GcWeakVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcWeakVec")
+            .field("len", &self.weaks.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Trace + 'static> IntoIterator for &GcWeakVec<T> {
+    type Item = crate::Gc<T>;
+    type IntoIter = std::vec::IntoIter<crate::Gc<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Trace + 'static> Trace for GcWeakVec<T> {
+    #[inline]
+    fn trace(&self, visitor: &mut impl crate::trace::Visitor) {
+        self.weaks.trace(visitor);
+    }
+}
+
+impl<T: Trace + 'static> GcCapture for GcWeakVec<T> {
+    #[inline]
+    fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
+        &[]
+    }
+
+    #[inline]
+    fn capture_gc_ptrs_into(&self, ptrs: &mut Vec<NonNull<GcBox<()>>>) {
+        self.weaks.capture_gc_ptrs_into(ptrs);
+    }
+}
+
 /// A thread-safe interior mutability type for GC-managed data.
 ///
 /// `GcThreadSafeCell<T>` is like `GcCell<T>` but uses a `Mutex` to allow
@@ -996,6 +1579,12 @@ impl<T: std::fmt::Display + ?Sized> std::fmt::Display for GcCell<T> {
 ///
 /// For maximum performance when mutations are always on the same thread, use `GcCell`.
 pub struct GcThreadSafeCell<T: ?Sized> {
+    /// Bumped each time the lock is taken, for [`GcThreadSafeCell::read_optimistic`].
+    ///
+    /// 32 bits keep the cell small: next to the one-byte lock, a `u64`
+    /// counter would double a small cell's size. A reader is only fooled if
+    /// the counter wraps exactly back while it copies one small value.
+    version: AtomicU32,
     inner: Mutex<T>,
 }
 
@@ -1003,6 +1592,7 @@ impl<T> GcThreadSafeCell<T> {
     /// Creates a new `GcThreadSafeCell` containing `value`.
     pub const fn new(value: T) -> Self {
         Self {
+            version: AtomicU32::new(0),
             inner: Mutex::new(value),
         }
     }
@@ -1011,6 +1601,60 @@ impl<T> GcThreadSafeCell<T> {
     pub fn into_inner(self) -> T {
         self.inner.into_inner()
     }
+
+    /// Reads the wrapped value without taking the lock, seqlock-style.
+    ///
+    /// The value is copied out byte by byte with atomic loads, and the copy
+    /// is thrown away and retried if the lock was taken in the meantime. It
+    /// only becomes a `T` once that check passes, so `f` never sees a value
+    /// a writer was halfway through. Readers never block writers; they spin
+    /// while a writer holds the lock. Suited to small, read-mostly values.
+    ///
+    /// `T: Copy` allows the bitwise copy and rules out `Gc` pointers, whose
+    /// lifetime would otherwise have to be coordinated with the SATB barrier;
+    /// read cells holding them with [`borrow`](Self::borrow).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::GcThreadSafeCell;
+    ///
+    /// let cell = GcThreadSafeCell::new((1, 1));
+    /// *cell.borrow_mut_gen_only() = (2, 2);
+    /// assert_eq!(cell.read_optimistic(|&(a, b)| a + b), 4);
+    /// ```
+    pub fn read_optimistic<R>(&self, f: impl Fn(&T) -> R) -> R
+    where
+        T: Copy,
+    {
+        loop {
+            let before = self.version.load(Ordering::Acquire);
+            // A writer that locked before `before` was read hasn't bumped
+            // the version for its writes; wait for it to finish.
+            if self.inner.is_locked() {
+                std::hint::spin_loop();
+                continue;
+            }
+            // Synchronize with the last unlock, which `is_locked` read.
+            std::sync::atomic::fence(Ordering::Acquire);
+            let mut copy = std::mem::MaybeUninit::<T>::uninit();
+            let src = self.inner.data_ptr().cast::<AtomicU8>();
+            let dst = copy.as_mut_ptr().cast::<u8>();
+            for i in 0..std::mem::size_of::<T>() {
+                // SAFETY: both pointers cover `size_of::<T>()` bytes of live
+                // memory, and `AtomicU8` has the size and alignment of `u8`.
+                unsafe { dst.add(i).write((*src.add(i)).load(Ordering::Relaxed)) };
+            }
+            // Pairs with the release fence in `lock`: if the copy saw any
+            // write, the version load below sees that writer's bump.
+            std::sync::atomic::fence(Ordering::Acquire);
+            if self.version.load(Ordering::Relaxed) == before {
+                // SAFETY: no writer held or took the lock during the copy,
+                // so it holds every byte of a valid `T`.
+                return f(&unsafe { copy.assume_init() });
+            }
+        }
+    }
 }
 
 impl<T: ?Sized> GcThreadSafeCell<T> {
@@ -1019,7 +1663,18 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
     /// The borrow lasts until the returned `MutexGuard` exits scope.
     #[inline]
     pub fn borrow(&self) -> parking_lot::MutexGuard<'_, T> {
-        self.inner.lock()
+        self.lock()
+    }
+
+    /// Takes the lock, first telling optimistic readers that the value may
+    /// change: every guard handed out allows writes.
+    #[inline]
+    fn lock(&self) -> parking_lot::MutexGuard<'_, T> {
+        let guard = self.inner.lock();
+        self.version.fetch_add(1, Ordering::Release);
+        // Order the bump before any write made through the guard.
+        std::sync::atomic::fence(Ordering::Release);
+        guard
     }
 
     /// Mutably borrows the wrapped value with generational write barrier.
@@ -1063,7 +1718,7 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
     where
         T: Trace + GcCapture,
     {
-        let guard = self.inner.lock();
+        let guard = self.lock();
 
         // Cache barrier states once to avoid TOCTOU between SATB capture
         // and trigger_write_barrier (bug116, bug153)
@@ -1151,7 +1806,7 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
     where
         T: GcCapture,
     {
-        let guard = self.inner.lock();
+        let guard = self.lock();
 
         // FIX bug174: Capture old GC pointers for SATB when incremental marking is active.
         // This was previously missing - borrow_mut_simple would skip SATB capture even when
@@ -1229,7 +1884,7 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
     pub fn borrow_mut_gen_only(&self) -> parking_lot::MutexGuard<'_, T> {
         let incremental_active = false;
         let generational_active = crate::gc::incremental::is_generational_barrier_active();
-        let guard = self.inner.lock();
+        let guard = self.lock();
         self.trigger_write_barrier_with_incremental(incremental_active, generational_active);
         guard
     }
@@ -1264,7 +1919,7 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
 
         // SAFETY: This fence synchronizes with the GC thread to ensure
         // that all prior writes are visible before we check page metadata.
-        std::sync::atomic::fence(Ordering::AcqRel);
+        crate::gc::gc_fence(Ordering::AcqRel);
 
         unsafe {
             if state.fallback_requested() {
@@ -1272,6 +1927,8 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
             }
 
             let ptr_addr = ptr as usize;
+            // Young objects need the barrier too while marking runs.
+            let marking = crate::gc::incremental::is_write_barrier_active();
             crate::heap::with_heap(|heap| {
                 if ptr_addr < crate::heap::heap_start() || ptr_addr >= crate::heap::heap_end() {
                     return;
@@ -1280,66 +1937,67 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
                 let page_addr = ptr_addr & page_mask();
 
                 // Tail pages of multi-page large objects have no PageHeader; ptr_to_page_header
-                // would yield garbage. Check large_object_map first (see find_gc_box_from_ptr).
-                let header = if let Some(&(head_addr, size, h_size)) =
-                    heap.large_object_map.get(&page_addr)
-                {
-                    if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
-                        return;
-                    }
-                    let h_ptr = head_addr as *mut PageHeader;
-                    if (*h_ptr).magic != MAGIC_GC_PAGE {
-                        return;
-                    }
-                    // Skip if slot was swept; avoids corrupting remembered set with reused slot.
-                    if !(*h_ptr).is_allocated(0) {
-                        return;
-                    }
-                    let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
-                    let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h_ptr).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
-                        return;
-                    }
-                    if !(*h_ptr).is_allocated(0) {
-                        return;
-                    }
-                    NonNull::new_unchecked(h_ptr)
-                } else {
-                    let h = ptr_to_page_header(ptr);
-                    if (*h.as_ptr()).magic != MAGIC_GC_PAGE {
-                        return;
-                    }
-                    let block_size = (*h.as_ptr()).block_size as usize;
-                    let header_size = (*h.as_ptr()).header_size as usize;
-                    let header_page_addr = h.as_ptr() as usize;
+                // would yield garbage. Check the large object map first (see find_gc_box_from_ptr).
+                let header =
+                    if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
+                        if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
+                            return;
+                        }
+                        let h_ptr = head_addr as *mut PageHeader;
+                        if (*h_ptr).magic != MAGIC_GC_PAGE {
+                            return;
+                        }
+                        // Skip if slot was swept; avoids corrupting remembered set with reused slot.
+                        if !(*h_ptr).is_allocated(0) {
+                            return;
+                        }
+                        let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
+                        let has_gen_old = (*gc_box_addr).has_gen_old_flag();
+                        let young = (*h_ptr).generation.load(Ordering::Acquire) == 0;
+                        if young && !has_gen_old && !marking {
+                            return;
+                        }
+                        if !(*h_ptr).is_allocated(0) {
+                            return;
+                        }
+                        NonNull::new_unchecked(h_ptr)
+                    } else {
+                        let h = ptr_to_page_header(ptr);
+                        if (*h.as_ptr()).magic != MAGIC_GC_PAGE {
+                            return;
+                        }
+                        let block_size = (*h.as_ptr()).block_size as usize;
+                        let header_size = (*h.as_ptr()).header_size as usize;
+                        let header_page_addr = h.as_ptr() as usize;
 
-                    if ptr_addr < header_page_addr + header_size {
-                        return;
-                    }
+                        if ptr_addr < header_page_addr + header_size {
+                            return;
+                        }
 
-                    let offset = ptr_addr - (header_page_addr + header_size);
-                    let index = offset / block_size;
-                    let obj_count = (*h.as_ptr()).obj_count as usize;
-                    if index >= obj_count {
-                        return;
-                    }
+                        let offset = ptr_addr - (header_page_addr + header_size);
+                        let index = offset / block_size;
+                        let obj_count = (*h.as_ptr()).obj_count as usize;
+                        if index >= obj_count {
+                            return;
+                        }
 
-                    // Skip if slot was swept; avoids corrupting remembered set with reused slot.
-                    if !(*h.as_ptr()).is_allocated(index) {
-                        return;
-                    }
-                    let gc_box_addr =
-                        (header_page_addr + header_size + index * block_size) as *const GcBox<()>;
-                    let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h.as_ptr()).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
-                        return;
-                    }
-                    // Second is_allocated check - prevents TOCTOU race (bug376)
-                    if !(*h.as_ptr()).is_allocated(index) {
-                        return;
-                    }
-                    h
-                };
+                        // Skip if slot was swept; avoids corrupting remembered set with reused slot.
+                        if !(*h.as_ptr()).is_allocated(index) {
+                            return;
+                        }
+                        let gc_box_addr = (header_page_addr + header_size + index * block_size)
+                            as *const GcBox<()>;
+                        let has_gen_old = (*gc_box_addr).has_gen_old_flag();
+                        let young = (*h.as_ptr()).generation.load(Ordering::Acquire) == 0;
+                        if young && !has_gen_old && !marking {
+                            return;
+                        }
+                        // Second is_allocated check - prevents TOCTOU race (bug376)
+                        if !(*h.as_ptr()).is_allocated(index) {
+                            return;
+                        }
+                        h
+                    };
 
                 heap.record_in_remembered_buffer(header);
             });
@@ -1356,36 +2014,32 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
         unsafe {
             crate::heap::with_heap(|heap| {
                 let page_addr = (ptr as usize) & crate::heap::page_mask();
-                let is_large = heap.large_object_map.contains_key(&page_addr);
-
-                if is_large {
-                    if let Some(&(head_addr, size, h_size)) = heap.large_object_map.get(&page_addr)
-                    {
-                        let ptr_addr = ptr as usize;
-                        if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
-                            return;
-                        }
-                        let header = head_addr as *mut crate::heap::PageHeader;
-                        if (*header).magic != MAGIC_GC_PAGE {
-                            return;
-                        }
-                        // Skip if slot was swept; read has_gen_old_flag only after is_allocated (bug247).
-                        if !(*header).is_allocated(0) {
-                            return;
-                        }
-                        // GEN_OLD early-exit: skip if page young AND object has no gen_old_flag
-                        // (bug202, matches unified_write_barrier).
-                        let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
-                        let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                        if (*header).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
-                            return;
-                        }
-                        if !(*header).is_allocated(0) {
-                            return;
-                        }
-                        (*header).set_dirty(0);
-                        heap.add_to_dirty_pages(NonNull::new_unchecked(header));
+                if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
+                    let ptr_addr = ptr as usize;
+                    if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
+                        return;
+                    }
+                    let header = head_addr as *mut crate::heap::PageHeader;
+                    if (*header).magic != MAGIC_GC_PAGE {
+                        return;
+                    }
+                    // Skip if slot was swept; read has_gen_old_flag only after is_allocated (bug247).
+                    if !(*header).is_allocated(0) {
+                        return;
+                    }
+                    // GEN_OLD early-exit: skip if page young AND object has no gen_old_flag
+                    // (bug202, matches unified_write_barrier).
+                    let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
+                    let has_gen_old = (*gc_box_addr).has_gen_old_flag();
+                    if (*header).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                        return;
+                    }
+                    if !(*header).is_allocated(0) {
+                        return;
                     }
+                    heap.record_dirty_field(NonNull::new_unchecked(header), None);
+                    (*header).set_dirty(0);
+                    heap.add_to_dirty_pages(NonNull::new_unchecked(header));
                 } else {
                     let header = ptr_to_page_header(ptr);
                     if (*header.as_ptr()).magic != MAGIC_GC_PAGE {
@@ -1446,7 +2100,7 @@ impl<T: ?Sized> GcThreadSafeCell<T> {
 
                 let page_addr = addr & crate::heap::page_mask();
 
-                if heap.large_object_map.contains_key(&page_addr) {
+                if heap.large_object_entry(page_addr).is_some() {
                     return true;
                 }
 
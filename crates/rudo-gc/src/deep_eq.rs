@@ -0,0 +1,258 @@
+//! Structural deep equality for `Gc` object graphs.
+//!
+//! `PartialEq` on a cyclic graph recurses forever. [`DeepEq`] instead follows
+//! `Gc` edges while recording which pairs of allocations have already been
+//! matched, so cycles terminate and sharing is compared as part of the shape.
+
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::cell::GcCell;
+use crate::trace::Trace;
+use crate::Gc;
+
+/// Types that can be compared structurally across `Gc` edges.
+///
+/// Implementations compare their own data and forward to `deep_eq` on every
+/// field, passing the shared [`DeepEqContext`] along. Prefer
+/// `#[derive(DeepEq)]` over manual implementations.
+pub trait DeepEq {
+    /// Compare `self` with `other`, following `Gc` pointers through `ctx`.
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool;
+}
+
+/// Bookkeeping for a single [`deep_eq`] run.
+///
+/// Every `Gc` pair that is compared is recorded as a mapping in both
+/// directions. Revisiting a recorded pair succeeds immediately (this is what
+/// terminates cycles), while pairing an allocation with a *different* partner
+/// than before fails, so two graphs are only equal if their sharing matches.
+#[derive(Debug, Default)]
+pub struct DeepEqContext {
+    forward: HashMap<usize, usize>,
+    backward: HashMap<usize, usize>,
+}
+
+/// Result of recording a `Gc` pair in a [`DeepEqContext`].
+enum Pairing {
+    /// First time this pair is seen; contents still need comparing.
+    New,
+    /// Pair was already recorded; assume equal.
+    Seen,
+    /// One side is already paired with a different allocation.
+    Conflict,
+}
+
+impl DeepEqContext {
+    /// Create an empty context.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn pair(&mut self, a: usize, b: usize) -> Pairing {
+        match self.forward.entry(a) {
+            Entry::Occupied(e) => {
+                if *e.get() == b {
+                    Pairing::Seen
+                } else {
+                    Pairing::Conflict
+                }
+            }
+            Entry::Vacant(e) => {
+                if self.backward.contains_key(&b) {
+                    return Pairing::Conflict;
+                }
+                e.insert(b);
+                self.backward.insert(b, a);
+                Pairing::New
+            }
+        }
+    }
+}
+
+/// Compare two `Gc` graphs for structural equality.
+///
+/// Unlike `PartialEq`, this follows `Gc` edges and terminates on cycles. Two
+/// graphs compare equal when their values are equal and their pointer
+/// structure (including cycles and shared nodes) is isomorphic.
+///
+/// Comparison is recursive, so extremely deep acyclic chains are bounded by
+/// the thread's stack size.
+///
+/// # Examples
+///
+/// ```
+/// use rudo_gc::{deep_eq, DeepEq, Gc, GcCell, Trace};
+///
+/// #[derive(Trace, DeepEq)]
+/// struct Node {
+///     value: i32,
+///     next: GcCell<Option<Gc<Node>>>,
+/// }
+///
+/// let a = Gc::new(Node { value: 1, next: GcCell::new(None) });
+/// *a.next.borrow_mut() = Some(a.clone());
+/// let b = Gc::new(Node { value: 1, next: GcCell::new(None) });
+/// *b.next.borrow_mut() = Some(b.clone());
+///
+/// assert!(deep_eq(&a, &b));
+/// ```
+#[must_use]
+pub fn deep_eq<T: DeepEq + Trace + 'static>(a: &Gc<T>, b: &Gc<T>) -> bool {
+    a.deep_eq(b, &mut DeepEqContext::new())
+}
+
+impl<T: DeepEq + Trace + 'static> DeepEq for Gc<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        // Immediates read as null pointers; compare them by value, and never
+        // as equal to an object.
+        match (self.immediate(), other.immediate()) {
+            (None, None) => {}
+            (a, b) => return a == b,
+        }
+        let (a, b) = (self.raw_ptr() as usize, other.raw_ptr() as usize);
+        if a == 0 || b == 0 {
+            return a == b;
+        }
+        match ctx.pair(a, b) {
+            Pairing::Seen => true,
+            Pairing::Conflict => false,
+            Pairing::New => match (Self::try_deref(self), Self::try_deref(other)) {
+                (Some(x), Some(y)) => x.deep_eq(y, ctx),
+                (None, None) => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+macro_rules! impl_deep_eq_via_partial_eq {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl DeepEq for $t {
+                #[inline]
+                fn deep_eq(&self, other: &Self, _ctx: &mut DeepEqContext) -> bool {
+                    self == other
+                }
+            }
+        )*
+    };
+}
+
+impl_deep_eq_via_partial_eq! {
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64,
+    bool, char, (),
+    String, str,
+    std::time::Duration,
+    std::path::Path,
+    std::path::PathBuf,
+}
+
+impl<T: DeepEq + ?Sized> DeepEq for &T {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        T::deep_eq(self, other, ctx)
+    }
+}
+
+impl<T: DeepEq + ?Sized> DeepEq for Box<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        T::deep_eq(self, other, ctx)
+    }
+}
+
+impl<T: DeepEq> DeepEq for Option<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.deep_eq(b, ctx),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: DeepEq, E: DeepEq> DeepEq for Result<T, E> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        match (self, other) {
+            (Ok(a), Ok(b)) => a.deep_eq(b, ctx),
+            (Err(a), Err(b)) => a.deep_eq(b, ctx),
+            _ => false,
+        }
+    }
+}
+
+impl<T: DeepEq> DeepEq for [T] {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.deep_eq(b, ctx))
+    }
+}
+
+impl<T: DeepEq, const N: usize> DeepEq for [T; N] {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.as_slice().deep_eq(other.as_slice(), ctx)
+    }
+}
+
+impl<T: DeepEq> DeepEq for Vec<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.as_slice().deep_eq(other.as_slice(), ctx)
+    }
+}
+
+impl<T: DeepEq> DeepEq for VecDeque<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.deep_eq(b, ctx))
+    }
+}
+
+impl<K: Ord, V: DeepEq> DeepEq for BTreeMap<K, V> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|((ka, va), (kb, vb))| ka == kb && va.deep_eq(vb, ctx))
+    }
+}
+
+impl<T: DeepEq + Copy> DeepEq for Cell<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.get().deep_eq(&other.get(), ctx)
+    }
+}
+
+impl<T: DeepEq + ?Sized> DeepEq for RefCell<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.borrow().deep_eq(&other.borrow(), ctx)
+    }
+}
+
+impl<T: DeepEq + ?Sized> DeepEq for GcCell<T> {
+    fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+        self.borrow().deep_eq(&other.borrow(), ctx)
+    }
+}
+
+macro_rules! impl_deep_eq_for_tuples {
+    ($(($($name:ident $idx:tt),+)),* $(,)?) => {
+        $(
+            impl<$($name: DeepEq),+> DeepEq for ($($name,)+) {
+                fn deep_eq(&self, other: &Self, ctx: &mut DeepEqContext) -> bool {
+                    $(self.$idx.deep_eq(&other.$idx, ctx))&&+
+                }
+            }
+        )*
+    };
+}
+
+impl_deep_eq_for_tuples! {
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5),
+}
@@ -0,0 +1,91 @@
+//! Pinning GC objects for foreign code.
+//!
+//! The collector never moves objects, so a `Gc`'s address is stable for its
+//! whole life. What foreign code also needs is a guarantee that the object is
+//! not collected, and its page not unmapped, while it holds a raw pointer, even
+//! after every Rust-side `Gc` is gone. [`Gc::lock_for_ffi`](crate::Gc::lock_for_ffi)
+//! returns an [`FfiLock`] that provides both for as long as it lives.
+
+use std::ops::Deref;
+
+use crate::handles::GcHandle;
+use crate::trace::Trace;
+use crate::Gc;
+
+/// Keeps a GC object alive and its memory mapped while foreign code uses it.
+///
+/// Created by [`Gc::lock_for_ffi`](crate::Gc::lock_for_ffi). The lock registers
+/// the object as a root, holds a strong reference, and pins the page the object
+/// lives on so no sweep releases it. Unlike a [`Handle`](crate::Handle), it is
+/// not tied to a scope and stays valid until dropped.
+///
+/// `FfiLock` is `!Send`, so it is dropped on the thread that created it.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// use rudo_gc::Gc;
+///
+/// let buffer = Gc::new([const { Cell::new(0u8) }; 64]);
+/// let lock = buffer.lock_for_ffi();
+/// drop(buffer);
+///
+/// // Hand `bytes` to C; it stays valid until `lock` is dropped.
+/// let bytes = lock.as_ptr().cast::<u8>().cast_mut();
+/// rudo_gc::collect_full();
+/// unsafe { bytes.write(7) };
+/// assert_eq!(lock[0].get(), 7);
+/// ```
+pub struct FfiLock<T: Trace + 'static> {
+    root: GcHandle<T>,
+    ptr: *const T,
+    page: usize,
+}
+
+impl<T: Trace + 'static> FfiLock<T> {
+    pub(crate) fn new(gc: &Gc<T>) -> Self {
+        let ptr = gc.as_ptr();
+        let root = gc.cross_thread_handle();
+        // SAFETY: `root` keeps the object allocated, so its page header is live.
+        let page = unsafe { crate::heap::ptr_to_page_header(root.ptr.as_ptr() as *const u8) };
+        let page = page.as_ptr() as usize;
+        crate::heap::pin_page_for_ffi(page);
+        Self { root, ptr, page }
+    }
+
+    /// Returns the address of the locked value.
+    ///
+    /// The pointer stays valid until the lock is dropped. Foreign code may
+    /// only write through it into parts of `T` that allow shared mutation,
+    /// such as an `UnsafeCell`.
+    #[must_use]
+    pub const fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+}
+
+impl<T: Trace + 'static> Deref for FfiLock<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the root and strong reference keep the value alive.
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T: Trace + 'static> Drop for FfiLock<T> {
+    fn drop(&mut self) {
+        crate::heap::unpin_page_for_ffi(self.page);
+    }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for FfiLock<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FfiLock")
+            .field("ptr", &self.ptr)
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,366 @@
+//! A dedicated thread that drives incremental collections.
+//!
+//! [`enable_collector_thread`] starts a collector thread that counts `Gc`
+//! allocations across all threads and, past a threshold, collects every
+//! registered heap with the incremental marker. Between pauses the
+//! mutators run, and the SATB write barrier records the references they
+//! overwrite.
+//!
+//! This is not concurrent marking. The world stops for the root snapshot,
+//! for every marking slice, and for the final mark and sweep: `Trace`
+//! implementations read through `GcCell`s and collections that a running
+//! mutator may be reallocating, so tracing never overlaps with mutators.
+//! What the thread buys is short pauses, each sized by
+//! [`IncrementalConfig::increment_size`](crate::gc::incremental::IncrementalConfig),
+//! taken off the threads that allocate.
+//!
+//! Mutators cooperate by reaching safe points: the allocation slow path, or
+//! [`safepoint`](crate::safepoint) in loops. If a thread stays away for
+//! longer than `SAFEPOINT_TIMEOUT`, the collector gives up on that pause and
+//! tries again on its next tick.
+
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::heap::{thread_registry, ThreadControlBlock, GC_REQUESTED};
+
+/// How long a pause waits for the mutators to reach a safe point.
+const SAFEPOINT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Whether a collector thread is running.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the collector thread has a collection under way.
+static CYCLE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// `Gc` allocations on all threads since the collector thread last started
+/// a collection.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+static CYCLES: AtomicUsize = AtomicUsize::new(0);
+static PAUSES: AtomicUsize = AtomicUsize::new(0);
+static MAX_PAUSE_NS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_PAUSE_NS: AtomicU64 = AtomicU64::new(0);
+
+/// Settings for [`enable_collector_thread_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectorThreadConfig {
+    /// `Gc` allocations, counted over all threads, that start a collection.
+    pub allocation_threshold: usize,
+    /// How long the collector sleeps between checks for the threshold, and
+    /// between the marking slices of a collection.
+    pub poll_interval: Duration,
+}
+
+impl Default for CollectorThreadConfig {
+    fn default() -> Self {
+        Self {
+            allocation_threshold: 100_000,
+            poll_interval: Duration::from_millis(1),
+        }
+    }
+}
+
+/// What the collector thread has done since it was enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectorThreadStats {
+    /// Collections finished.
+    pub cycles: usize,
+    /// Stop-the-world pauses taken, counting snapshots, marking slices and
+    /// final marks.
+    pub pauses: usize,
+    /// The longest pause, from the moment every mutator had stopped.
+    pub max_pause: Duration,
+    /// All pauses added together.
+    pub total_pause: Duration,
+}
+
+/// A running collector thread.
+///
+/// Dropping it stops the collector thread, after it finishes any collection
+/// it has under way.
+#[derive(Debug)]
+pub struct CollectorThread {
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for CollectorThread {
+    fn drop(&mut self) {
+        ENABLED.store(false, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Start a collector thread with the default [`CollectorThreadConfig`].
+///
+/// While it runs, mutators no longer collect on their own allocation
+/// heuristics; the collector thread does, once enough `Gc`s have been
+/// allocated. [`collect`](crate::collect) returns at once while the
+/// thread has a collection under way, and
+/// [`collect_full`](crate::collect_full) waits for it to finish first.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::AlreadyExists`] if a collector thread is
+/// already running, or the error from spawning the thread.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{enable_collector_thread, safepoint, Gc};
+///
+/// let collector = enable_collector_thread().unwrap();
+/// for i in 0..1000 {
+///     let _ = Gc::new(i);
+///     safepoint();
+/// }
+/// drop(collector);
+/// ```
+pub fn enable_collector_thread() -> io::Result<CollectorThread> {
+    enable_collector_thread_with(CollectorThreadConfig::default())
+}
+
+/// Like [`enable_collector_thread`], with explicit settings.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::AlreadyExists`] if a collector thread is
+/// already running, or the error from spawning the thread.
+pub fn enable_collector_thread_with(config: CollectorThreadConfig) -> io::Result<CollectorThread> {
+    if ENABLED.swap(true, Ordering::SeqCst) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "a collector thread is already running",
+        ));
+    }
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    CYCLES.store(0, Ordering::Relaxed);
+    PAUSES.store(0, Ordering::Relaxed);
+    MAX_PAUSE_NS.store(0, Ordering::Relaxed);
+    TOTAL_PAUSE_NS.store(0, Ordering::Relaxed);
+
+    match std::thread::Builder::new()
+        .name("rudo-gc-collector".into())
+        .spawn(move || Collector::new(config).run())
+    {
+        Ok(thread) => Ok(CollectorThread {
+            thread: Some(thread),
+        }),
+        Err(err) => {
+            ENABLED.store(false, Ordering::SeqCst);
+            Err(err)
+        }
+    }
+}
+
+/// Returns true while a collector thread is running.
+#[must_use]
+pub fn is_collector_thread_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What the collector thread has done since it was last enabled.
+#[must_use]
+pub fn collector_thread_stats() -> CollectorThreadStats {
+    CollectorThreadStats {
+        cycles: CYCLES.load(Ordering::Relaxed),
+        pauses: PAUSES.load(Ordering::Relaxed),
+        max_pause: Duration::from_nanos(MAX_PAUSE_NS.load(Ordering::Relaxed)),
+        total_pause: Duration::from_nanos(TOTAL_PAUSE_NS.load(Ordering::Relaxed)),
+    }
+}
+
+/// Count a `Gc` allocation towards the collector thread's threshold.
+#[inline]
+pub fn note_allocation() {
+    if ENABLED.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Returns true while the collector thread has a collection under way.
+///
+/// Mutators must not collect, or run marking slices of their own, until it
+/// finishes.
+#[inline]
+pub fn is_cycle_active() -> bool {
+    CYCLE_ACTIVE.load(Ordering::Acquire)
+}
+
+/// Wait, stopping for the collector's pauses, until the collector thread's
+/// collection under way has finished.
+pub fn wait_for_cycle() {
+    while is_cycle_active() {
+        crate::heap::check_safepoint();
+        std::thread::yield_now();
+    }
+}
+
+/// State of the collector thread.
+struct Collector {
+    config: CollectorThreadConfig,
+    /// Pause time of the collection under way so far.
+    cycle_pauses: Duration,
+}
+
+impl Collector {
+    const fn new(config: CollectorThreadConfig) -> Self {
+        Self {
+            config,
+            cycle_pauses: Duration::ZERO,
+        }
+    }
+
+    fn run(mut self) {
+        // Register a heap so the thread can take part in handshakes, then
+        // stay out of other collections' way while idle.
+        crate::heap::with_heap(|_| ());
+        crate::heap::enter_inactive();
+
+        while ENABLED.load(Ordering::SeqCst) {
+            std::thread::park_timeout(self.config.poll_interval);
+            if is_cycle_active() {
+                self.step();
+            } else if self.should_start() {
+                self.start();
+            }
+        }
+
+        // Mutators leave collecting to us while a cycle is active, so don't
+        // walk away from one.
+        while is_cycle_active() {
+            if !self.step() {
+                std::thread::sleep(self.config.poll_interval);
+            }
+        }
+    }
+
+    fn should_start(&self) -> bool {
+        ALLOCATIONS.load(Ordering::Relaxed) >= self.config.allocation_threshold
+            && super::gc::is_gc_enabled()
+            && !super::is_gc_paused()
+    }
+
+    /// Take the root snapshot. Without the incremental write barrier, the
+    /// whole collection runs in this pause.
+    fn start(&mut self) {
+        let mut finished = false;
+        let pause = pause(|tcbs| {
+            super::gc::start_collector_thread_cycle(tcbs);
+            ALLOCATIONS.store(0, Ordering::Relaxed);
+            CYCLE_ACTIVE.store(true, Ordering::Release);
+            if !crate::gc::incremental::write_barrier_needed() {
+                finish(tcbs, Duration::ZERO);
+                finished = true;
+            }
+        });
+        if let Some(pause) = pause {
+            self.cycle_pauses = if finished { Duration::ZERO } else { pause };
+        }
+        crate::metrics::notify_gc_observer();
+    }
+
+    /// Run a marking slice, then the final mark and sweep if marking is
+    /// done. Returns false if the mutators could not be stopped.
+    fn step(&mut self) -> bool {
+        let earlier = self.cycle_pauses;
+        let mut finished = false;
+        let pause = pause(|tcbs| {
+            if super::gc::collector_thread_mark_slice(tcbs) {
+                finish(tcbs, earlier);
+                finished = true;
+            }
+        });
+        let Some(pause) = pause else {
+            return false;
+        };
+        crate::metrics::notify_gc_observer();
+        self.cycle_pauses = if finished {
+            Duration::ZERO
+        } else {
+            earlier + pause
+        };
+        true
+    }
+}
+
+/// Final mark and sweep; ends the cycle.
+fn finish(tcbs: &[Arc<ThreadControlBlock>], earlier_pauses: Duration) {
+    super::gc::finish_collector_thread_cycle(tcbs, earlier_pauses);
+    CYCLE_ACTIVE.store(false, Ordering::Release);
+    CYCLES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Stop the world, run `f` with every registered thread's control block,
+/// and resume. Returns how long the world was stopped, or `None` if the
+/// mutators did not all reach a safe point in time.
+fn pause(f: impl FnOnce(&[Arc<ThreadControlBlock>])) -> Option<Duration> {
+    if super::is_gc_paused() {
+        return None;
+    }
+    crate::heap::leave_inactive();
+    let stopped = stop_mutators();
+    let elapsed = stopped.then(|| {
+        let start = Instant::now();
+        f(&crate::heap::get_all_thread_control_blocks());
+        let elapsed = start.elapsed();
+        crate::heap::resume_all_threads();
+        crate::heap::clear_gc_request();
+        thread_registry().lock().unwrap().set_gc_in_progress(false);
+        elapsed
+    });
+    crate::heap::enter_inactive();
+
+    if let Some(elapsed) = elapsed {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        PAUSES.fetch_add(1, Ordering::Relaxed);
+        MAX_PAUSE_NS.fetch_max(nanos, Ordering::Relaxed);
+        TOTAL_PAUSE_NS.fetch_add(nanos, Ordering::Relaxed);
+    }
+    elapsed
+}
+
+/// Ask every thread to stop at its next safe point and wait until the
+/// collector is the only one running.
+///
+/// Gives up after [`SAFEPOINT_TIMEOUT`], or as soon as a [`NoGcGuard`]
+/// is live, withdrawing the request, unless another thread's collection is
+/// in progress; that one parks its own threads and clears the request
+/// itself when done. A guarded thread skips its safe points, so waiting for
+/// it would only hold the parked threads up.
+///
+/// [`NoGcGuard`]: crate::NoGcGuard
+#[allow(clippy::significant_drop_tightening)]
+fn stop_mutators() -> bool {
+    let start = Instant::now();
+    loop {
+        {
+            let registry = thread_registry().lock().unwrap();
+            // A collection finishing on another thread clears the request,
+            // so keep renewing it.
+            GC_REQUESTED.store(true, Ordering::Release);
+            for tcb in &registry.threads {
+                tcb.gc_requested.store(true, Ordering::Release);
+            }
+            if registry.active_count.load(Ordering::Acquire) == 1 {
+                registry.set_gc_in_progress(true);
+                return true;
+            }
+            if (start.elapsed() > SAFEPOINT_TIMEOUT || super::is_gc_paused())
+                && !registry.is_gc_in_progress()
+            {
+                drop(registry);
+                crate::heap::resume_all_threads();
+                crate::heap::clear_gc_request();
+                return false;
+            }
+        }
+        std::thread::yield_now();
+    }
+}
@@ -7,14 +7,13 @@ use std::cell::Cell;
 use std::collections::HashSet;
 use std::ptr::NonNull;
 use std::sync::atomic::Ordering;
-use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::sync::PoisonError;
 
-use crate::gc::incremental::FallbackReason;
 use crate::gc::incremental::{
-    count_dirty_pages, execute_final_mark, execute_snapshot, mark_slice, IncrementalMarkState,
-    MarkPhase, MarkSliceResult, MarkStats,
+    count_dirty_pages, execute_final_mark, execute_snapshot, log_fallback_reason, mark_slice,
+    IncrementalMarkState, MarkPhase, MarkSliceResult, MarkStats,
 };
 use crate::gc::marker::{
     worker_mark_loop, worker_mark_loop_with_registry, GcWorkerRegistry, ParallelMarkConfig,
@@ -27,7 +26,7 @@ use crate::trace::{GcVisitor, Trace, Visitor, VisitorKind};
 #[cfg(feature = "tracing")]
 use crate::tracing::internal::{
     log_phase_end, log_phase_end_mark, log_phase_start, next_gc_id, trace_gc_collection,
-    trace_phase, GcId, GcPhase,
+    trace_phase, trace_step, GcId, GcPhase, GcStep,
 };
 
 // ============================================================================
@@ -72,6 +71,8 @@ pub struct CollectInfo {
     young_size: usize,
     /// Bytes in old generation.
     old_size: usize,
+    /// Young generation size above which a collection is due.
+    young_limit: usize,
 }
 
 impl CollectInfo {
@@ -104,6 +105,13 @@ impl CollectInfo {
     pub const fn old_size(&self) -> usize {
         self.old_size
     }
+
+    /// Young generation size above which the default condition collects,
+    /// as set by [`set_gc_policy`](crate::set_gc_policy).
+    #[must_use]
+    pub const fn young_limit(&self) -> usize {
+        self.young_limit
+    }
 }
 
 // ============================================================================
@@ -124,7 +132,7 @@ pub type CollectCondition = fn(&CollectInfo) -> bool;
 #[must_use]
 pub const fn default_collect_condition(info: &CollectInfo) -> bool {
     // Simple heuristic: Collect if we dropped more than existing, OR young gen is large
-    info.n_gcs_dropped > info.n_gcs_existing || info.young_size > 1024 * 1024 // 1MB young limit
+    info.n_gcs_dropped > info.n_gcs_existing || info.young_size > info.young_limit
 }
 
 // ============================================================================
@@ -136,6 +144,15 @@ thread_local! {
     static N_DROPS: Cell<usize> = const { Cell::new(0) };
     /// Number of Gc pointers currently existing.
     static N_EXISTING: Cell<usize> = const { Cell::new(0) };
+    /// Gc pointers created on this thread; never reset.
+    static N_CREATED_TOTAL: Cell<usize> = const { Cell::new(0) };
+    /// Gc drops counted on this thread; unlike `N_DROPS`, never reset.
+    static N_DROPS_TOTAL: Cell<usize> = const { Cell::new(0) };
+    /// `Gc` allocations since the last collection triggered by
+    /// [`set_collect_every_n_allocations`].
+    static N_ALLOCS: Cell<usize> = const { Cell::new(0) };
+    /// Allocation interval set by [`set_collect_every_n_allocations`]; 0 disables it.
+    static COLLECT_EVERY_N_ALLOCS: Cell<usize> = const { Cell::new(0) };
     /// The current collection condition.
     static COLLECT_CONDITION: Cell<CollectCondition> = const { Cell::new(default_collect_condition) };
     /// Whether a collection is currently in progress.
@@ -149,6 +166,22 @@ thread_local! {
 /// Global switch for enabling/disabling automatic GC collections across threads.
 static GC_ENABLED: AtomicBool = AtomicBool::new(true);
 
+/// Global switch for deferring `drop_fn` calls out of the sweep phase.
+static DEFER_FINALIZATION: AtomicBool = AtomicBool::new(false);
+
+/// Set by [`request_collect_deferred`]; cleared by the next full collection.
+static COLLECT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Number of live [`NoGcGuard`]s across all threads.
+static NO_GC_DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Set when a [`collect`] was skipped because a [`NoGcGuard`] was live.
+static COLLECT_DEFERRED_BY_GUARD: AtomicBool = AtomicBool::new(false);
+
+/// Empty pages a size class may keep after a major sweep before the rest are
+/// unmapped; `usize::MAX` turns automatic trimming off.
+static HEAP_TRIM_THRESHOLD: AtomicUsize = AtomicUsize::new(usize::MAX);
+
 /// Register a root for GC marking. This is useful for tests where Miri cannot find
 /// roots via conservative stack scanning.
 pub fn register_test_root(ptr: *const u8) {
@@ -180,14 +213,65 @@ where
 /// Notify that a Gc was created.
 pub fn notify_created_gc() {
     N_EXISTING.with(|n| n.set(n.get() + 1));
+    N_CREATED_TOTAL.with(|n| n.set(n.get() + 1));
 }
 
 /// Notify that a `Gc` was dropped, potentially triggering collection.
 pub fn notify_dropped_gc() {
     N_DROPS.with(|n| n.set(n.get() + 1));
+    N_DROPS_TOTAL.with(|n| n.set(n.get() + 1));
     maybe_collect();
 }
 
+/// Snapshot of the current thread's `Gc` lifecycle counters.
+///
+/// `created` and `dropped` only ever grow, so the difference between two
+/// snapshots (see [`AllocCounters::since`]) measures the code run between
+/// them, even if collections happened in between.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocCounters {
+    /// `Gc`s created by `Gc::new` or a successful weak upgrade.
+    pub created: usize,
+    /// `Gc` drops that left the object alive. Dropping the last reference
+    /// frees the object immediately and is not counted.
+    pub dropped: usize,
+    /// The live-`Gc` estimate the collection condition sees as
+    /// [`CollectInfo::n_gcs_existing`].
+    pub existing: usize,
+}
+
+impl AllocCounters {
+    /// Counter deltas from `earlier` to `self`.
+    ///
+    /// Returns `None` if any counter went backwards, which means the two
+    /// snapshots are not from the same thread in this order.
+    #[must_use]
+    pub const fn since(&self, earlier: &Self) -> Option<Self> {
+        match (
+            self.created.checked_sub(earlier.created),
+            self.dropped.checked_sub(earlier.dropped),
+            self.existing.checked_sub(earlier.existing),
+        ) {
+            (Some(created), Some(dropped), Some(existing)) => Some(Self {
+                created,
+                dropped,
+                existing,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Read the current thread's `Gc` lifecycle counters without collecting.
+#[must_use]
+pub fn alloc_counters() -> AllocCounters {
+    AllocCounters {
+        created: N_CREATED_TOTAL.with(Cell::get),
+        dropped: N_DROPS_TOTAL.with(Cell::get),
+        existing: N_EXISTING.with(Cell::get),
+    }
+}
+
 fn maybe_collect() {
     if !GC_ENABLED.load(AtomicOrdering::Relaxed) {
         return;
@@ -197,7 +281,30 @@ fn maybe_collect() {
         return;
     }
 
-    let stats = crate::heap::HEAP
+    if COLLECT_REQUESTED.load(AtomicOrdering::Relaxed) {
+        collect_full();
+        return;
+    }
+
+    // The collector thread owns the allocation heuristics.
+    if super::collector_thread::is_collector_thread_enabled() {
+        return;
+    }
+
+    let Some(info) = current_collect_info() else {
+        return;
+    };
+
+    let condition = COLLECT_CONDITION.with(Cell::get);
+    if condition(&info) {
+        collect();
+    }
+}
+
+/// Gather the current thread's heap statistics, or `None` while its heap is
+/// being torn down.
+fn current_collect_info() -> Option<CollectInfo> {
+    let (total, young, old) = crate::heap::HEAP
         .try_with(|heap| {
             (
                 unsafe { &*heap.tcb.heap.get() }.total_allocated(),
@@ -205,24 +312,37 @@ fn maybe_collect() {
                 unsafe { &*heap.tcb.heap.get() }.old_allocated(),
             )
         })
-        .ok();
-
-    let Some((total, young, old)) = stats else {
-        return;
-    };
+        .ok()?;
 
-    let info = CollectInfo {
+    Some(CollectInfo {
         n_gcs_dropped: N_DROPS.with(Cell::get),
         n_gcs_existing: N_EXISTING.with(Cell::get),
         heap_size: total,
         young_size: young,
         old_size: old,
-    };
+        young_limit: super::policy::young_limit(),
+    })
+}
 
-    let condition = COLLECT_CONDITION.with(Cell::get);
-    if condition(&info) {
+/// Notify that a `Gc` object was allocated, collecting if the interval set
+/// by [`set_collect_every_n_allocations`] has been reached.
+///
+/// Must not be called while the heap is borrowed or the new object is not
+/// yet initialized.
+pub fn notify_allocated() {
+    super::collector_thread::note_allocation();
+    let count = N_ALLOCS.with(|n| {
+        n.set(n.get() + 1);
+        n.get()
+    });
+    let every = COLLECT_EVERY_N_ALLOCS.with(Cell::get);
+    if every != 0 && count >= every {
+        N_ALLOCS.with(|n| n.set(0));
         collect();
     }
+    // The allocation itself may have finished an incremental or emergency
+    // collection, which could not call out while the heap was borrowed.
+    crate::metrics::notify_gc_observer();
 }
 
 /// Returns true if a garbage collection is currently in progress.
@@ -236,6 +356,45 @@ pub fn set_collect_condition(f: CollectCondition) {
     COLLECT_CONDITION.with(|c| c.set(f));
 }
 
+/// Run [`collect`] if `cond` approves of the current heap statistics.
+///
+/// Unlike [`set_collect_condition`], the condition is checked once, right
+/// now, and may capture state, such as the frame budget left or the heap
+/// size at a checkpoint of the caller's choosing. Does nothing while a
+/// collection is already in progress.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{collect_if, Gc};
+///
+/// let checkpoint = 1024;
+/// let _data = Gc::new([0u8; 64]);
+/// collect_if(|info| info.heap_size() > checkpoint);
+/// ```
+pub fn collect_if(cond: impl FnOnce(&CollectInfo) -> bool) {
+    if is_collecting() {
+        return;
+    }
+    if current_collect_info().is_some_and(|info| cond(&info)) {
+        collect();
+    }
+}
+
+/// Collect after every `n` `Gc` allocations on the current thread, whatever
+/// the collection condition says. `0`, the default, turns this off.
+///
+/// This gives reproducible benchmarks and stress tests a fixed collection
+/// cadence. Like the collection condition, the setting is per thread, and
+/// setting it restarts the count. Objects from [`Gc::alloc_from_bytes`]
+/// are not counted.
+///
+/// [`Gc::alloc_from_bytes`]: crate::Gc::alloc_from_bytes
+pub fn set_collect_every_n_allocations(n: usize) {
+    COLLECT_EVERY_N_ALLOCS.with(|every| every.set(n));
+    N_ALLOCS.with(|count| count.set(0));
+}
+
 /// Enable or disable automatic garbage collection globally.
 ///
 /// This affects all threads because collection is coordinated globally.
@@ -243,11 +402,154 @@ pub fn set_gc_enabled(enabled: bool) {
     GC_ENABLED.store(enabled, AtomicOrdering::Relaxed);
 }
 
+/// Returns false while [`set_gc_enabled`] has collection switched off.
+pub(super) fn is_gc_enabled() -> bool {
+    GC_ENABLED.load(AtomicOrdering::Relaxed)
+}
+
+/// RAII guard that keeps every collection from running while it is alive.
+///
+/// While any `NoGcGuard` exists, on any thread, [`collect`] and
+/// [`collect_full`] (including the ones triggered automatically) return
+/// without collecting and threads do not stop at safe points. The skipped
+/// collection runs when the last guard is dropped. Guards nest.
+///
+/// Reference counting is unaffected: dropping the last `Gc` to an object
+/// still frees it immediately.
+///
+/// The pause is process-wide, not per heap. The crate takes a guard itself
+/// while a thread drops a chain of objects, such as a long `Gc`-linked list,
+/// so a big drop on one thread also delays collection on the others.
+///
+/// See also [`gc_critical`].
+#[must_use = "collection resumes as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct NoGcGuard {
+    _private: (),
+}
+
+impl NoGcGuard {
+    /// Pause garbage collection until the returned guard is dropped.
+    ///
+    /// A stop requested before the guard is taken is honoured first, so the
+    /// threads already parked for it are not left waiting on this one.
+    pub fn new() -> Self {
+        crate::heap::check_safepoint();
+        NO_GC_DEPTH.fetch_add(1, AtomicOrdering::SeqCst);
+        Self { _private: () }
+    }
+}
+
+impl Default for NoGcGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for NoGcGuard {
+    fn drop(&mut self) {
+        if NO_GC_DEPTH.fetch_sub(1, AtomicOrdering::SeqCst) != 1 {
+            return;
+        }
+        crate::heap::check_safepoint();
+        if COLLECT_REQUESTED.load(AtomicOrdering::Relaxed) {
+            collect_full();
+        } else if COLLECT_DEFERRED_BY_GUARD.swap(false, AtomicOrdering::Relaxed) {
+            collect();
+        }
+    }
+}
+
+/// Run `f` with garbage collection paused, then run any collection it
+/// deferred.
+///
+/// This is useful for holding raw pointers into `Gc` objects across calls
+/// that might otherwise collect them, such as untrusted callbacks.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{collect, gc_critical, Gc};
+///
+/// let value = Gc::new(7);
+/// let sum = gc_critical(|| {
+///     let raw = Gc::as_ptr(&value);
+///     collect(); // deferred until `gc_critical` returns
+///     unsafe { *raw + 1 }
+/// });
+/// assert_eq!(sum, 8);
+/// ```
+pub fn gc_critical<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = NoGcGuard::new();
+    f()
+}
+
+/// Returns true while a [`NoGcGuard`] is keeping collections from running.
+#[must_use]
+pub fn is_gc_paused() -> bool {
+    NO_GC_DEPTH.load(AtomicOrdering::SeqCst) != 0
+}
+
+/// Enable or disable deferred finalization globally.
+///
+/// When enabled, the sweep phase no longer runs `Drop` for unreachable
+/// objects. Each one is instead queued on its owning thread's heap and
+/// finalized later on that thread by [`safepoint`], [`crate::yield_now`] or
+/// [`run_deferred_finalizers`], which keeps expensive `Drop` impls out of the
+/// stop-the-world pause.
+///
+/// A queued object keeps its slot until its finalizer has run; the memory is
+/// reclaimed by the next sweep. Finalizers still pending when the next
+/// collection sweeps that heap are run at that point, before anything else
+/// is reclaimed. A thread's pending finalizers run when it exits. Pages
+/// orphaned by exited threads have no thread to defer to and are still
+/// finalized during the pause.
+pub fn set_deferred_finalization(enabled: bool) {
+    DEFER_FINALIZATION.store(enabled, AtomicOrdering::Relaxed);
+}
+
+/// Ask for a full collection at the next opportunity.
+///
+/// This only sets a flag, so it is safe to call from any thread, including
+/// threads that never touch the GC heap (such as the listener started by
+/// [`crate::register_memory_pressure_handler`]). The collection runs on the
+/// next mutator thread that drops a `Gc` or calls [`safepoint`].
+pub fn request_collect_deferred() {
+    COLLECT_REQUESTED.store(true, AtomicOrdering::Relaxed);
+}
+
+/// Returns true if a collection requested by [`request_collect_deferred`]
+/// has not run yet.
+#[must_use]
+pub fn is_collect_requested() -> bool {
+    COLLECT_REQUESTED.load(AtomicOrdering::Relaxed)
+}
+
+/// Returns true if deferred finalization is enabled.
+#[must_use]
+pub fn is_deferred_finalization_enabled() -> bool {
+    DEFER_FINALIZATION.load(AtomicOrdering::Relaxed)
+}
+
+/// Run the finalizers that sweeps have deferred on the current thread's heap.
+///
+/// Returns the number of finalizers run. This is called automatically by
+/// [`safepoint`] and [`crate::yield_now`].
+#[allow(clippy::must_use_candidate)]
+pub fn run_deferred_finalizers() -> usize {
+    crate::heap::try_with_heap(std::ptr::from_mut)
+        .map_or(0, |heap| unsafe { drain_deferred_finalizers(heap) })
+}
+
 /// Manually check for a pending GC request and block until it's processed.
 ///
 /// This function should be called in long-running loops that don't perform
 /// allocations, to ensure threads can respond to GC requests in a timely manner.
 ///
+/// It also runs any finalizers that deferred finalization has queued for this
+/// thread (see [`set_deferred_finalization`]), and any collection asked for
+/// with [`request_collect_deferred`].
+///
 /// # Example
 ///
 /// ```
@@ -263,49 +565,47 @@ pub fn set_gc_enabled(enabled: bool) {
 /// ```
 pub fn safepoint() {
     crate::heap::check_safepoint();
+    if COLLECT_REQUESTED.load(AtomicOrdering::Relaxed) {
+        collect_full();
+    }
+    run_deferred_finalizers();
 }
 
 // ============================================================================
 // Mark-Sweep Collection
 // ============================================================================
 
-const MAJOR_THRESHOLD: usize = 10 * 1024 * 1024; // 10MB
-
-#[inline]
-fn log_fallback_reason(reason: FallbackReason) {
-    match reason {
-        FallbackReason::None => {
-            eprintln!("[GC] Incremental marking fallback: unknown reason (atomic not set)");
-        }
-        FallbackReason::DirtyPagesExceeded => {
-            eprintln!("[GC] Incremental marking fallback: dirty pages exceeded threshold");
-        }
-        FallbackReason::SliceTimeout => {
-            eprintln!("[GC] Incremental marking fallback: slice timeout exceeded");
-        }
-        FallbackReason::WorklistUnbounded => {
-            eprintln!("[GC] Incremental marking fallback: worklist grew unbounded");
-        }
-        FallbackReason::SatbBufferOverflow => {
-            eprintln!("[GC] Incremental marking fallback: SATB buffer overflowed");
-        }
-    }
-}
-
 /// Perform a garbage collection.
 ///
 /// Decides between Minor and Major collection based on heuristics.
 /// Implements cooperative rendezvous for multi-threaded safety.
+///
+/// Returns without collecting while a collector-thread collection (see
+/// [`crate::enable_collector_thread`]) is under way; that collection covers
+/// this one.
 pub fn collect() {
     if !GC_ENABLED.load(AtomicOrdering::Relaxed) {
         return;
     }
 
+    if is_gc_paused() {
+        COLLECT_DEFERRED_BY_GUARD.store(true, AtomicOrdering::Relaxed);
+        return;
+    }
+
     // Reentrancy guard
     if IN_COLLECT.with(Cell::get) {
         return;
     }
 
+    if super::collector_thread::is_cycle_active() {
+        crate::heap::check_safepoint();
+        return;
+    }
+
+    #[cfg(debug_assertions)]
+    crate::heap::debug_assert_heaps_registered();
+
     let is_collector = crate::heap::request_gc_handshake();
 
     if is_collector {
@@ -315,6 +615,7 @@ pub fn collect() {
         // to prevent race condition where threads enter rendezvous after wake-up
         perform_single_threaded_collect_with_wake();
     }
+    crate::metrics::notify_gc_observer();
 }
 
 /// Perform collection as the collector thread.
@@ -375,7 +676,7 @@ fn perform_multi_threaded_collect() {
         })
         .collect();
 
-    if total_size > MAJOR_THRESHOLD {
+    if total_size > super::policy::major_threshold() {
         // CRITICAL FIX: For major GC, we must clear ALL marks first, then mark ALL
         // reachable objects, then sweep ALL heaps. The old approach processed each
         // heap independently, which caused marks on other heaps (set during
@@ -385,7 +686,7 @@ fn perform_multi_threaded_collect() {
 
         // Phase 1: Clear all marks on ALL heaps
         #[cfg(feature = "tracing")]
-        let _clear_span = trace_phase(GcPhase::Clear);
+        let clear_span = trace_phase(GcPhase::Clear);
         #[cfg(feature = "tracing")]
         log_phase_start(GcPhase::Clear, before_bytes);
 
@@ -399,10 +700,12 @@ fn perform_multi_threaded_collect() {
 
         #[cfg(feature = "tracing")]
         log_phase_end(GcPhase::Clear, 0);
+        #[cfg(feature = "tracing")]
+        drop(clear_span);
 
         // Phase 2: Mark all reachable objects (tracing across all heaps)
         #[cfg(feature = "tracing")]
-        let _mark_span = trace_phase(GcPhase::Mark);
+        let mark_span = trace_phase(GcPhase::Mark);
         #[cfg(feature = "tracing")]
         log_phase_start(GcPhase::Mark, before_bytes);
 
@@ -411,10 +714,8 @@ fn perform_multi_threaded_collect() {
         super::sync::GC_MARK_IN_PROGRESS.store(true, std::sync::atomic::Ordering::Release);
         for tcb in &tcbs {
             unsafe {
-                total_objects_marked = total_objects_marked.saturating_add(mark_major_roots_multi(
-                    &mut *tcb.heap.get(),
-                    &all_stack_roots,
-                ));
+                total_objects_marked = total_objects_marked
+                    .saturating_add(mark_major_roots_multi(&*tcb.heap.get(), &all_stack_roots));
             }
         }
         super::sync::GC_MARK_IN_PROGRESS.store(false, std::sync::atomic::Ordering::Release);
@@ -422,16 +723,18 @@ fn perform_multi_threaded_collect() {
 
         #[cfg(feature = "tracing")]
         log_phase_end_mark(GcPhase::Mark, total_objects_marked);
+        #[cfg(feature = "tracing")]
+        drop(mark_span);
 
         // SAFETY: This fence ensures all mark bitmap writes from the marking phase
         // are visible before any sweeping thread clears marks. Without this fence,
         // a thread could start sweeping and clear marks that haven't yet propagated
         // from a slow marking thread, causing live objects to be swept.
-        std::sync::atomic::fence(std::sync::atomic::Ordering::AcqRel);
+        crate::gc::gc_fence(Ordering::AcqRel);
 
         // Phase 3: Sweep ALL heaps
         #[cfg(feature = "tracing")]
-        let _sweep_span = trace_phase(GcPhase::Sweep);
+        let sweep_span = trace_phase(GcPhase::Sweep);
         #[cfg(feature = "tracing")]
         log_phase_start(GcPhase::Sweep, before_bytes);
 
@@ -441,6 +744,9 @@ fn perform_multi_threaded_collect() {
                 #[cfg(feature = "lazy-sweep")]
                 {
                     let heap = unsafe { &mut *tcb.heap.get() };
+                    // Leftover deferred finalizers must run before their
+                    // slots are counted as dead for lazy sweeping.
+                    drain_deferred_finalizers(heap);
                     let pages: Vec<_> = heap.all_pages().collect();
                     for page_ptr in pages {
                         let header = page_ptr.as_ptr();
@@ -456,6 +762,14 @@ fn perform_multi_threaded_collect() {
                                     allocated_count += 1;
                                     if !(*header).is_marked(i) {
                                         dead_count += 1;
+                                        #[cfg(feature = "type-tracking")]
+                                        {
+                                            let header_size = PageHeader::header_size(block_size);
+                                            let obj_ptr = header
+                                                .cast::<u8>()
+                                                .add(header_size + (i * block_size));
+                                            crate::metrics::note_reclaimed(obj_ptr.cast());
+                                        }
                                     } else {
                                         (*header).clear_mark(i);
                                     }
@@ -487,6 +801,8 @@ fn perform_multi_threaded_collect() {
                         }
                     }
                     promote_all_pages(&*tcb.heap.get());
+                    trim_after_major_sweep(&mut *tcb.heap.get());
+                    (*tcb.heap.get()).shrink_buffers();
                 }
                 #[cfg(not(feature = "lazy-sweep"))]
                 {
@@ -494,6 +810,8 @@ fn perform_multi_threaded_collect() {
                     let reclaimed_large = sweep_large_objects(&mut *tcb.heap.get(), false);
                     objects_reclaimed += reclaimed + reclaimed_large;
                     promote_all_pages(&*tcb.heap.get());
+                    trim_after_major_sweep(&mut *tcb.heap.get());
+                    (*tcb.heap.get()).shrink_buffers();
                 }
             }
         }
@@ -508,6 +826,8 @@ fn perform_multi_threaded_collect() {
                 crate::heap::HEAP.with(|h| unsafe { &*h.tcb.heap.get() }.total_allocated()),
             ),
         );
+        #[cfg(feature = "tracing")]
+        drop(sweep_span);
     } else {
         // Minor GC doesn't have cross-heap issues since it only scans young objects
         // and uses remembered sets for inter-generational references
@@ -520,8 +840,9 @@ fn perform_multi_threaded_collect() {
         // For minor collections, record total time as sweep duration (no clear phase)
         sweep_duration = minor_start.elapsed();
     }
+    crate::handles::reap_dead_weak_handles();
 
-    let collection_type = if total_size > MAJOR_THRESHOLD {
+    let collection_type = if total_size > super::policy::major_threshold() {
         crate::metrics::CollectionType::Major
     } else {
         crate::metrics::CollectionType::Minor
@@ -569,6 +890,7 @@ fn perform_multi_threaded_collect() {
         slices_executed: incremental_stats.2,
         fallback_occurred: incremental_stats.3,
         fallback_reason: incremental_stats.4,
+        bytes_returned: 0,
     });
 
     crate::heap::resume_all_threads();
@@ -589,6 +911,8 @@ fn perform_multi_threaded_collect() {
 ///
 /// This will collect all unreachable objects in both Young and Old generations.
 /// Implements cooperative rendezvous for multi-threaded safety.
+///
+/// If a collector-thread collection is under way, waits for it to finish first.
 pub fn collect_full() {
     if !GC_ENABLED.load(AtomicOrdering::Relaxed) {
         return;
@@ -601,6 +925,22 @@ pub fn collect_full() {
         return;
     }
 
+    if is_gc_paused() {
+        COLLECT_REQUESTED.store(true, AtomicOrdering::Relaxed);
+        return;
+    }
+
+    // A collector-thread collection marks from an older snapshot; let it finish
+    // rather than clearing its marks.
+    super::collector_thread::wait_for_cycle();
+
+    // Any full collection satisfies a pending deferred request.
+    COLLECT_REQUESTED.store(false, AtomicOrdering::Relaxed);
+    COLLECT_DEFERRED_BY_GUARD.store(false, AtomicOrdering::Relaxed);
+
+    #[cfg(debug_assertions)]
+    crate::heap::debug_assert_heaps_registered();
+
     let is_collector = crate::heap::request_gc_handshake();
 
     if is_collector {
@@ -612,6 +952,283 @@ pub fn collect_full() {
         wake_waiting_threads();
         perform_single_threaded_collect_full();
     }
+    crate::metrics::notify_gc_observer();
+}
+
+/// Perform a stop-the-world major collection of `heap` alone.
+///
+/// Used by [`GcHeap`](crate::GcHeap), whose heap is not registered with any
+/// thread. Marking covers every heap, from the roots of a full collection, so
+/// objects in `heap` that are reachable only through other heaps or other
+/// threads survive; only `heap` is swept. The sweep is finished before
+/// returning, since the heap has no allocator of its own to finish a lazy
+/// sweep. Does nothing if collection is disabled, paused, already running on
+/// this thread, or an incremental collection is in progress.
+pub fn collect_heap(heap: &mut LocalHeap) {
+    if collect_heap_major(heap).is_some() {
+        crate::metrics::notify_gc_observer();
+    }
+}
+
+/// Emergency major collection for an allocation on `heap`, the current
+/// thread's heap, that could not map more pages.
+///
+/// The allocation already holds `heap`, so it is collected in place, like
+/// [`collect_heap`], rather than through [`collect_full`], which would
+/// borrow it a second time. Other heaps are marked but not swept. Skipped in
+/// the same cases as `collect_heap`.
+pub fn collect_for_oom(heap: &mut LocalHeap) {
+    let start = std::time::Instant::now();
+    let before_bytes = heap.total_allocated();
+    let Some(objects_reclaimed) = collect_heap_major(heap) else {
+        return;
+    };
+    let after_bytes = heap.total_allocated();
+    crate::metrics::record_metrics(crate::metrics::GcMetrics {
+        duration: start.elapsed(),
+        bytes_reclaimed: before_bytes.saturating_sub(after_bytes),
+        bytes_surviving: after_bytes,
+        objects_reclaimed,
+        collection_type: crate::metrics::CollectionType::Major,
+        ..crate::metrics::GcMetrics::new()
+    });
+    crate::metrics::notify_gc_observer();
+}
+
+/// Shared by [`collect_heap`] and [`collect_for_oom`]. Returns the objects
+/// reclaimed, or `None` if the collection was skipped.
+fn collect_heap_major(heap: &mut LocalHeap) -> Option<usize> {
+    if !GC_ENABLED.load(AtomicOrdering::Relaxed)
+        || is_gc_paused()
+        || IN_COLLECT.with(Cell::get)
+        || crate::gc::incremental::is_incremental_marking_active()
+    {
+        return None;
+    }
+    super::collector_thread::wait_for_cycle();
+
+    Some(with_full_marks(Some(heap), |_, heap| {
+        let Some(heap) = heap else { return 0 };
+        let reclaimed = sweep_segment_pages(heap, false) + sweep_large_objects(heap, false);
+        promote_all_pages(heap);
+        trim_after_major_sweep(heap);
+        heap.shrink_buffers();
+        #[cfg(feature = "lazy-sweep")]
+        sweep_pending(heap, usize::MAX);
+        reclaimed
+    }))
+}
+
+/// Mark from the roots of a full collection, then let `sweep` reclaim what
+/// it chooses.
+///
+/// Other threads are stopped as [`collect_full`] stops them, and every
+/// registered heap, plus `extra`, is marked from every thread's stack,
+/// handles and cross-thread roots. If the other threads cannot be stopped,
+/// only this thread's heap is marked, as in `collect_full`'s fallback.
+/// Pending lazy sweeps are finished first, since they read the previous
+/// marks.
+///
+/// `extra` is either a heap no thread owns or the current thread's heap,
+/// borrowed by the caller. It is handed to `sweep` and is left out of the
+/// registered heaps `sweep` gets, so that it is never borrowed twice. Those
+/// keep their dirty bits for the next minor collection, and their marks are
+/// cleared again once `sweep` returns, as their own sweep would leave them.
+fn with_full_marks<R>(
+    mut extra: Option<&mut LocalHeap>,
+    sweep: impl FnOnce(&[Arc<crate::heap::ThreadControlBlock>], Option<&mut LocalHeap>) -> R,
+) -> R {
+    IN_COLLECT.with(|in_collect| in_collect.set(true));
+
+    let is_collector = crate::heap::request_gc_handshake();
+    let tcbs = if is_collector {
+        crate::heap::thread_registry()
+            .lock()
+            .unwrap()
+            .set_gc_in_progress(true);
+        crate::heap::get_all_thread_control_blocks()
+    } else {
+        crate::heap::GC_REQUESTED.store(false, Ordering::Release);
+        wake_waiting_threads();
+        crate::heap::current_thread_control_block()
+            .into_iter()
+            .collect()
+    };
+    let stack_roots: Vec<(*const u8, Arc<crate::heap::ThreadControlBlock>)> = tcbs
+        .iter()
+        .flat_map(|tcb| {
+            let roots = crate::heap::take_stack_roots(tcb);
+            roots.into_iter().map(move |ptr| (ptr, tcb.clone()))
+        })
+        .collect();
+
+    // The caller's heap is only reached through `extra`.
+    let tcbs: Vec<_> = tcbs
+        .into_iter()
+        .filter(|tcb| {
+            extra
+                .as_deref()
+                .is_none_or(|heap| !std::ptr::eq(tcb.heap.get().cast_const(), heap))
+        })
+        .collect();
+
+    for tcb in &tcbs {
+        // SAFETY: the owning threads are stopped.
+        let heap = unsafe { &mut *tcb.heap.get() };
+        #[cfg(feature = "lazy-sweep")]
+        let _ = sweep_pending(heap, usize::MAX);
+        // Finalizers from an earlier sweep run first, as in a full sweep.
+        unsafe { drain_deferred_finalizers(heap) };
+    }
+    clear_marks_of_registered_heaps(&tcbs, is_collector);
+    if let Some(heap) = extra.as_deref_mut() {
+        #[cfg(feature = "lazy-sweep")]
+        let _ = sweep_pending(heap, usize::MAX);
+        clear_all_marks_and_dirty(heap);
+    }
+
+    super::sync::GC_MARK_IN_PROGRESS.store(true, Ordering::Release);
+    for tcb in &tcbs {
+        // SAFETY: the owning threads are stopped.
+        let _ = mark_major_roots_multi(unsafe { &*tcb.heap.get() }, &stack_roots);
+    }
+    if let Some(heap) = extra.as_deref() {
+        let _ = mark_major_roots_multi(heap, &stack_roots);
+    }
+    super::sync::GC_MARK_IN_PROGRESS.store(false, Ordering::Release);
+    crate::gc::gc_fence(Ordering::AcqRel);
+
+    let result = sweep(&tcbs, extra.take());
+    clear_marks_of_registered_heaps(&tcbs, is_collector);
+    crate::handles::reap_dead_weak_handles();
+
+    if is_collector {
+        crate::heap::resume_all_threads();
+        crate::heap::clear_gc_request();
+        crate::heap::thread_registry()
+            .lock()
+            .unwrap()
+            .set_gc_in_progress(false);
+    }
+    IN_COLLECT.with(|in_collect| in_collect.set(false));
+    result
+}
+
+/// Clear the marks, but not the dirty bits, on every page of `tcbs`' heaps
+/// and, with the other threads stopped, on orphan pages.
+fn clear_marks_of_registered_heaps(
+    tcbs: &[Arc<crate::heap::ThreadControlBlock>],
+    include_orphans: bool,
+) {
+    for tcb in tcbs {
+        // SAFETY: the owning threads are stopped.
+        for page_ptr in unsafe { &*tcb.heap.get() }.all_pages() {
+            // SAFETY: Page pointers in the heap are always valid
+            unsafe { (*page_ptr.as_ptr()).clear_all_marks() };
+        }
+    }
+    if include_orphans {
+        let manager = crate::heap::segment_manager().lock().unwrap();
+        for orphan in manager.orphan_by_addr.values() {
+            // SAFETY: orphan pages stay mapped until swept.
+            unsafe { (*(orphan.addr as *mut PageHeader)).clear_all_marks() };
+        }
+    }
+}
+
+/// Collect garbage among large objects only.
+///
+/// Stops the other threads and marks from the roots of a full collection, so
+/// it finds exactly the large objects that are unreachable, but sweeps only
+/// large-object pages. Small-object pages are left as they are, garbage
+/// included, until the next regular collection. Use it to hand big buffers
+/// back to the OS under memory pressure without paying to sweep every small
+/// page.
+///
+/// Does nothing if collection is disabled, paused, already running on this
+/// thread, or an incremental collection is in progress.
+pub fn collect_large_objects() {
+    if !GC_ENABLED.load(AtomicOrdering::Relaxed)
+        || is_gc_paused()
+        || IN_COLLECT.with(Cell::get)
+        || crate::gc::incremental::is_incremental_marking_active()
+    {
+        return;
+    }
+    super::collector_thread::wait_for_cycle();
+
+    with_full_marks(None, |tcbs, _| {
+        for tcb in tcbs {
+            // SAFETY: the owning threads are stopped.
+            sweep_large_objects(unsafe { &mut *tcb.heap.get() }, false);
+        }
+    });
+}
+
+/// Return this thread's empty small-object pages to the OS.
+///
+/// Sweeping frees objects but keeps their pages mapped for reuse, so after
+/// a transient allocation spike the process keeps its peak footprint. This
+/// unmaps every page with no allocated object left, except the ones the
+/// thread is currently allocating from, finishing a pending lazy sweep
+/// first so its garbage does not keep pages alive. Run a collection before
+/// it to free the garbage itself. Returns the bytes given back, which the
+/// next collection's [`GcMetrics::bytes_returned`] also reports.
+///
+/// Does nothing and returns 0 if a collection is running on this thread or
+/// an incremental collection is in progress. See
+/// [`set_heap_trim_threshold`] to trim after each major collection instead.
+///
+/// [`GcMetrics::bytes_returned`]: crate::GcMetrics::bytes_returned
+///
+/// # Examples
+///
+/// ```
+/// use rudo_gc::Gc;
+///
+/// let spike: Vec<_> = (0..10_000).map(Gc::new).collect();
+/// drop(spike);
+/// rudo_gc::collect_full();
+/// let returned = rudo_gc::trim_heap();
+/// println!("returned {returned} bytes to the OS");
+/// ```
+pub fn trim_heap() -> usize {
+    if IN_COLLECT.with(Cell::get) || crate::gc::incremental::is_incremental_marking_active() {
+        return 0;
+    }
+
+    crate::heap::with_heap(|heap| {
+        // Dead objects awaiting a lazy sweep still hold their slots.
+        #[cfg(feature = "lazy-sweep")]
+        let _ = sweep_pending(heap, usize::MAX);
+        heap.release_empty_pages(0)
+    })
+}
+
+/// Trim heaps automatically after each major collection.
+///
+/// A size class left with more than `threshold` empty pages by the sweep
+/// has the excess unmapped, as by [`trim_heap`]. `None`, the default,
+/// never trims. Pages that only a lazy sweep would empty are counted by a later
+/// collection, once allocation has swept them.
+pub fn set_heap_trim_threshold(threshold: Option<usize>) {
+    HEAP_TRIM_THRESHOLD.store(threshold.unwrap_or(usize::MAX), AtomicOrdering::Relaxed);
+}
+
+/// The threshold set by [`set_heap_trim_threshold`], if any.
+#[must_use]
+pub fn heap_trim_threshold() -> Option<usize> {
+    match HEAP_TRIM_THRESHOLD.load(AtomicOrdering::Relaxed) {
+        usize::MAX => None,
+        threshold => Some(threshold),
+    }
+}
+
+/// Apply [`set_heap_trim_threshold`] to a heap its major sweep just finished.
+fn trim_after_major_sweep(heap: &mut LocalHeap) {
+    if let Some(threshold) = heap_trim_threshold() {
+        heap.release_empty_pages(threshold);
+    }
 }
 
 /// Wake up any threads waiting at a safe point and clear `gc_requested` for ALL threads.
@@ -642,7 +1259,7 @@ fn wake_waiting_threads() {
         tcb.gc_requested.store(false, Ordering::Release);
 
         if tcb.state.load(Ordering::Acquire) == crate::heap::THREAD_STATE_SAFEPOINT {
-            tcb.park_cond.notify_all();
+            tcb.unpark();
             tcb.state
                 .store(crate::heap::THREAD_STATE_EXECUTING, Ordering::Release);
             woken_count += 1;
@@ -667,7 +1284,7 @@ fn perform_single_threaded_collect_with_wake() {
     let result = crate::heap::with_heap(|heap| {
         let total_size = heap.total_allocated();
 
-        if total_size > MAJOR_THRESHOLD {
+        if total_size > super::policy::major_threshold() {
             collect_major(heap)
         } else {
             collect_minor(heap)
@@ -689,7 +1306,7 @@ fn perform_single_threaded_collect_with_wake() {
             tcb.gc_requested.store(false, Ordering::SeqCst);
 
             if tcb.state.load(Ordering::Acquire) == crate::heap::THREAD_STATE_SAFEPOINT {
-                tcb.park_cond.notify_all();
+                tcb.unpark();
                 tcb.state
                     .store(crate::heap::THREAD_STATE_EXECUTING, Ordering::Release);
                 woken_count += 1;
@@ -760,6 +1377,7 @@ fn perform_single_threaded_collect_with_wake() {
         slices_executed: incremental_stats.2,
         fallback_occurred: incremental_stats.3,
         fallback_reason: incremental_stats.4,
+        bytes_returned: 0,
     };
     crate::metrics::record_metrics(metrics);
 
@@ -837,6 +1455,7 @@ fn perform_single_threaded_collect_full() {
         slices_executed: incremental_stats.2,
         fallback_occurred: incremental_stats.3,
         fallback_reason: incremental_stats.4,
+        bytes_returned: 0,
     });
 
     IN_COLLECT.with(|in_collect| in_collect.set(false));
@@ -874,7 +1493,7 @@ fn perform_multi_threaded_collect_full() {
     let mut total_objects_marked: usize = 0;
 
     #[cfg(feature = "tracing")]
-    let _clear_span = trace_phase(GcPhase::Clear);
+    let clear_span = trace_phase(GcPhase::Clear);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Clear, before_bytes);
 
@@ -924,9 +1543,11 @@ fn perform_multi_threaded_collect_full() {
 
     #[cfg(feature = "tracing")]
     log_phase_end(GcPhase::Clear, 0);
+    #[cfg(feature = "tracing")]
+    drop(clear_span);
 
     #[cfg(feature = "tracing")]
-    let _mark_span = trace_phase(GcPhase::Mark);
+    let mark_span = trace_phase(GcPhase::Mark);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Mark, before_bytes);
 
@@ -934,10 +1555,8 @@ fn perform_multi_threaded_collect_full() {
     super::sync::GC_MARK_IN_PROGRESS.store(true, std::sync::atomic::Ordering::Release);
     for tcb in &tcbs {
         unsafe {
-            total_objects_marked = total_objects_marked.saturating_add(mark_major_roots_multi(
-                &mut *tcb.heap.get(),
-                &all_stack_roots,
-            ));
+            total_objects_marked = total_objects_marked
+                .saturating_add(mark_major_roots_multi(&*tcb.heap.get(), &all_stack_roots));
         }
     }
     super::sync::GC_MARK_IN_PROGRESS.store(false, std::sync::atomic::Ordering::Release);
@@ -945,14 +1564,16 @@ fn perform_multi_threaded_collect_full() {
 
     #[cfg(feature = "tracing")]
     log_phase_end_mark(GcPhase::Mark, total_objects_marked);
+    #[cfg(feature = "tracing")]
+    drop(mark_span);
 
     // SAFETY: Fence ensures all mark bitmap writes are visible before sweeping.
     // This is the same race condition as perform_multi_threaded_collect.
-    std::sync::atomic::fence(std::sync::atomic::Ordering::AcqRel);
+    crate::gc::gc_fence(Ordering::AcqRel);
 
     // Phase 3: Sweep ALL heaps
     #[cfg(feature = "tracing")]
-    let _sweep_span = trace_phase(GcPhase::Sweep);
+    let sweep_span = trace_phase(GcPhase::Sweep);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Sweep, before_bytes);
 
@@ -963,17 +1584,22 @@ fn perform_multi_threaded_collect_full() {
             let reclaimed_large = sweep_large_objects(&mut *tcb.heap.get(), false);
             objects_reclaimed += reclaimed + reclaimed_large;
             promote_all_pages(&*tcb.heap.get());
+            trim_after_major_sweep(&mut *tcb.heap.get());
+            (*tcb.heap.get()).shrink_buffers();
         }
     }
 
     // Sweep orphan pages from terminated threads
     crate::heap::sweep_orphan_pages();
+    crate::handles::reap_dead_weak_handles();
     sweep_duration = sweep_start.elapsed();
 
     #[cfg(feature = "tracing")]
     let after_bytes = crate::heap::HEAP.with(|h| unsafe { &*h.tcb.heap.get() }.total_allocated());
     #[cfg(feature = "tracing")]
     log_phase_end(GcPhase::Sweep, before_bytes.saturating_sub(after_bytes));
+    #[cfg(feature = "tracing")]
+    drop(sweep_span);
 
     let duration = start.elapsed();
     let after_bytes = crate::heap::HEAP.with(|h| unsafe { &*h.tcb.heap.get() }.total_allocated());
@@ -1016,6 +1642,7 @@ fn perform_multi_threaded_collect_full() {
         slices_executed: incremental_stats.2,
         fallback_occurred: incremental_stats.3,
         fallback_reason: incremental_stats.4,
+        bytes_returned: 0,
     });
 
     crate::heap::resume_all_threads();
@@ -1039,6 +1666,7 @@ fn collect_minor_multi(
     let reclaimed = sweep_segment_pages(heap, true);
     let reclaimed_large = sweep_large_objects(heap, true);
     promote_young_pages(heap);
+    heap.shrink_buffers();
     reclaimed + reclaimed_large
 }
 
@@ -1056,19 +1684,26 @@ fn collect_major_multi(
     let reclaimed = sweep_segment_pages(heap, false);
     let reclaimed_large = sweep_large_objects(heap, false);
     promote_all_pages(heap);
+    trim_after_major_sweep(heap);
+    heap.shrink_buffers();
     reclaimed + reclaimed_large
 }
 
 /// Scan a single dirty page for minor GC (incremental path): mark refs and clear dirty state.
 #[inline]
 #[allow(unsafe_op_in_unsafe_fn)]
-unsafe fn scan_dirty_page_minor(page_ptr: NonNull<PageHeader>, visitor: &mut GcVisitor) {
+unsafe fn scan_dirty_page_minor(
+    heap: &LocalHeap,
+    page_ptr: NonNull<PageHeader>,
+    visitor: &mut GcVisitor,
+) {
     let header = page_ptr.as_ptr();
     if (*header).is_large_object() {
         let obj_ptr = header.cast::<u8>().add((*header).header_size as usize);
         #[allow(clippy::cast_ptr_alignment)]
         let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
-        mark_and_trace_incremental(std::ptr::NonNull::new_unchecked(gc_box_ptr), visitor);
+        let fields = heap.take_dirty_fields(page_ptr);
+        scan_dirty_object(header, 0, gc_box_ptr, fields.as_deref(), visitor);
     } else {
         let obj_count = (*header).obj_count as usize;
         for i in 0..obj_count {
@@ -1078,7 +1713,7 @@ unsafe fn scan_dirty_page_minor(page_ptr: NonNull<PageHeader>, visitor: &mut GcV
                 let obj_ptr = header.cast::<u8>().add(header_size + (i * block_size));
                 #[allow(clippy::cast_ptr_alignment)]
                 let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
-                mark_and_trace_incremental(std::ptr::NonNull::new_unchecked(gc_box_ptr), visitor);
+                scan_dirty_object(header, i, gc_box_ptr, None, visitor);
             }
         }
     }
@@ -1086,18 +1721,60 @@ unsafe fn scan_dirty_page_minor(page_ptr: NonNull<PageHeader>, visitor: &mut GcV
     (*header).clear_dirty_listed();
 }
 
+/// Scan one dirty object for minor GC.
+///
+/// An old object is a remembered-set root: it is traced in place, without
+/// marking it, so the young objects it references survive. With `fields`,
+/// only those `GcCell`s are scanned. A young object is only dirty while
+/// incremental marking runs, and is marked and traced as usual.
+#[inline]
+#[allow(unsafe_op_in_unsafe_fn)]
+unsafe fn scan_dirty_object(
+    header: *mut PageHeader,
+    index: usize,
+    gc_box_ptr: *mut GcBox<()>,
+    fields: Option<&[crate::heap::DirtyField]>,
+    visitor: &mut GcVisitor,
+) {
+    if (*header).generation.load(Ordering::Acquire) == 0 {
+        mark_and_trace_incremental(NonNull::new_unchecked(gc_box_ptr), visitor);
+        return;
+    }
+    // Skip if slot was swept; read the flags only after is_allocated (bug247).
+    if !(*header).is_allocated(index)
+        || (*gc_box_ptr).has_dead_flag()
+        || (*gc_box_ptr).is_under_construction()
+    {
+        return;
+    }
+    let obj_ptr = gc_box_ptr.cast::<u8>();
+    match fields {
+        Some(fields) => {
+            for field in fields {
+                (field.scan)(obj_ptr.add(field.offset), visitor);
+            }
+        }
+        None => (GcBox::trace_fn_of(gc_box_ptr))(obj_ptr, visitor),
+    }
+}
+
 /// Scan a single dirty page for minor GC (`trace_fn` path): trace refs and clear dirty state.
-/// Uses `mark_and_trace_incremental` (like `scan_dirty_page_minor`) to ensure `is_allocated`
+/// Uses `scan_dirty_object` (like `scan_dirty_page_minor`) to ensure `is_allocated`
 /// is checked before dereferencing, avoiding UAF when lazy sweep reclaims slots concurrently.
 #[inline]
 #[allow(unsafe_op_in_unsafe_fn)]
-unsafe fn scan_dirty_page_minor_trace(page_ptr: NonNull<PageHeader>, visitor: &mut GcVisitor) {
+unsafe fn scan_dirty_page_minor_trace(
+    heap: &LocalHeap,
+    page_ptr: NonNull<PageHeader>,
+    visitor: &mut GcVisitor,
+) {
     let header = page_ptr.as_ptr();
     if (*header).is_large_object() {
         let obj_ptr = header.cast::<u8>().add((*header).header_size as usize);
         #[allow(clippy::cast_ptr_alignment)]
         let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
-        mark_and_trace_incremental(std::ptr::NonNull::new_unchecked(gc_box_ptr), visitor);
+        let fields = heap.take_dirty_fields(page_ptr);
+        scan_dirty_object(header, 0, gc_box_ptr, fields.as_deref(), visitor);
     } else {
         let obj_count = (*header).obj_count as usize;
         for i in 0..obj_count {
@@ -1107,7 +1784,7 @@ unsafe fn scan_dirty_page_minor_trace(page_ptr: NonNull<PageHeader>, visitor: &m
                 let obj_ptr = header.cast::<u8>().add(header_size + (i * block_size));
                 #[allow(clippy::cast_ptr_alignment)]
                 let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
-                mark_and_trace_incremental(std::ptr::NonNull::new_unchecked(gc_box_ptr), visitor);
+                scan_dirty_object(header, i, gc_box_ptr, None, visitor);
             }
         }
     }
@@ -1168,6 +1845,14 @@ fn mark_minor_roots_multi(
         });
     }
 
+    for ptr in all_cross_thread_roots() {
+        unsafe {
+            if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr.cast::<u8>()) {
+                mark_object_minor(gc_box, &mut visitor);
+            }
+        }
+    }
+
     #[cfg(feature = "tokio")]
     #[allow(clippy::explicit_iter_loop)]
     {
@@ -1186,12 +1871,12 @@ fn mark_minor_roots_multi(
 
     for page_ptr in heap.dirty_pages_iter() {
         unsafe {
-            scan_dirty_page_minor(page_ptr, &mut visitor);
+            scan_dirty_page_minor(heap, page_ptr, &mut visitor);
         }
     }
     for page_ptr in heap.drain_dirty_pages_overflow() {
         unsafe {
-            scan_dirty_page_minor(page_ptr, &mut visitor);
+            scan_dirty_page_minor(heap, page_ptr, &mut visitor);
         }
     }
     heap.clear_dirty_pages_snapshot();
@@ -1410,18 +2095,65 @@ fn mark_minor_roots_parallel(
     crate::gc::marker::clear_overflow_queue();
 }
 
+/// Every cross-thread root: the handle and thread-local roots of all
+/// registered threads, then the handle roots orphaned by exited threads.
+///
+/// Walks the whole registry, not just the threads that parked stack roots:
+/// the collecting thread parks none but may own roots too. Every kind of
+/// collection marks these, minor ones included, or a young object rooted
+/// only by a `GcHandle` or `GcThreadLocalRoot` would be swept.
+fn all_cross_thread_roots() -> Vec<*const GcBox<()>> {
+    let mut roots: Vec<*const GcBox<()>> = crate::heap::thread_registry().lock().map_or_else(
+        |_| Vec::new(),
+        |registry| {
+            let mut roots = Vec::new();
+            for tcb in &registry.threads {
+                tcb.iterate_cross_thread_roots(|ptr| roots.push(ptr));
+            }
+            roots
+        },
+    );
+    roots.extend(crate::heap::get_orphaned_cross_thread_roots());
+    roots
+}
+
 /// Mark roots from all threads' stacks for Major GC.
 /// Returns the number of objects marked.
 fn mark_major_roots_multi(
-    heap: &mut LocalHeap,
+    heap: &LocalHeap,
     stack_roots: &[(*const u8, std::sync::Arc<crate::heap::ThreadControlBlock>)],
 ) -> usize {
+    #[cfg(feature = "tracing")]
+    let _span = trace_step(GcStep::MarkHeap);
+    #[cfg(feature = "tracing")]
+    let scan_span = trace_step(GcStep::ScanRoots);
+
     let mut visitor = GcVisitor::new(VisitorKind::Major);
+    for_each_major_root_multi(heap, stack_roots, |gc_box| unsafe {
+        mark_object(gc_box, &mut visitor);
+    });
+    #[cfg(feature = "tracing")]
+    drop(scan_span);
 
+    #[cfg(feature = "tracing")]
+    let _trace_span = trace_step(GcStep::TraceGraph);
+    visitor.process_worklist();
+    visitor.objects_marked()
+}
+
+/// Call `f` with every root of a multi-threaded major collection that lies in
+/// `heap`: parked stack roots, the current thread's stack, test roots,
+/// handles, cross-thread roots and, with the `tokio` feature, the tokio root
+/// set.
+fn for_each_major_root_multi(
+    heap: &LocalHeap,
+    stack_roots: &[(*const u8, std::sync::Arc<crate::heap::ThreadControlBlock>)],
+    mut f: impl FnMut(NonNull<GcBox<()>>),
+) {
     for &(ptr, _) in stack_roots {
         unsafe {
             if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr) {
-                mark_object(gc_box, &mut visitor);
+                f(gc_box);
             }
         }
     }
@@ -1429,7 +2161,7 @@ fn mark_major_roots_multi(
     unsafe {
         crate::stack::spill_registers_and_scan(|ptr, _addr, _is_reg| {
             if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr as *const u8) {
-                mark_object(gc_box, &mut visitor);
+                f(gc_box);
             }
         });
     }
@@ -1438,7 +2170,7 @@ fn mark_major_roots_multi(
         for &ptr in roots.borrow().iter() {
             unsafe {
                 if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr) {
-                    mark_object(gc_box, &mut visitor);
+                    f(gc_box);
                 }
             }
         }
@@ -1447,26 +2179,15 @@ fn mark_major_roots_multi(
     for (_, tcb) in stack_roots {
         tcb.iterate_all_handles(|ptr| unsafe {
             if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr.cast::<u8>()) {
-                mark_object(gc_box, &mut visitor);
+                f(gc_box);
             }
         });
     }
 
-    #[allow(clippy::type_complexity)]
-    let mut cross_thread_roots: Vec<*const GcBox<()>> = stack_roots
-        .iter()
-        .flat_map(|(_, tcb)| {
-            let mut roots = Vec::new();
-            tcb.iterate_cross_thread_roots(|ptr| roots.push(ptr));
-            roots
-        })
-        .collect();
-    cross_thread_roots.extend(crate::heap::get_orphaned_cross_thread_roots());
-
-    for ptr in cross_thread_roots {
+    for ptr in all_cross_thread_roots() {
         unsafe {
             if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr.cast::<u8>()) {
-                mark_object(gc_box, &mut visitor);
+                f(gc_box);
             }
         }
     }
@@ -1478,14 +2199,11 @@ fn mark_major_roots_multi(
         for ptr in GcRootSet::global().snapshot(heap) {
             unsafe {
                 if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr as *const u8) {
-                    mark_object(gc_box, &mut visitor);
+                    f(gc_box);
                 }
             }
         }
     }
-
-    visitor.process_worklist();
-    visitor.objects_marked()
 }
 
 /// Mark roots using parallel marking with work stealing.
@@ -1633,7 +2351,7 @@ fn collect_minor(heap: &mut LocalHeap) -> CollectResult {
 
     // 1. MARK PHASE - timer.start() immediately before mark_minor_roots()
     #[cfg(feature = "tracing")]
-    let _mark_span = trace_phase(GcPhase::Mark);
+    let mark_span = trace_phase(GcPhase::Mark);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Mark, before_bytes);
 
@@ -1643,21 +2361,27 @@ fn collect_minor(heap: &mut LocalHeap) -> CollectResult {
 
     #[cfg(feature = "tracing")]
     log_phase_end_mark(GcPhase::Mark, objects_marked);
+    #[cfg(feature = "tracing")]
+    drop(mark_span);
 
     // 2. SWEEP PHASE - continues same timer, ends at end_sweep()
     #[cfg(feature = "tracing")]
-    let _sweep_span = trace_phase(GcPhase::Sweep);
+    let sweep_span = trace_phase(GcPhase::Sweep);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Sweep, before_bytes);
 
     let reclaimed = sweep_segment_pages(heap, true);
     let reclaimed_large = sweep_large_objects(heap, true);
+    crate::handles::reap_dead_weak_handles();
 
     #[cfg(feature = "tracing")]
     log_phase_end(GcPhase::Sweep, reclaimed + reclaimed_large);
+    #[cfg(feature = "tracing")]
+    drop(sweep_span);
 
     // 3. PROMOTION PHASE - NOT timed (post-collection cleanup)
     promote_young_pages(heap);
+    heap.shrink_buffers();
 
     // For minor collections: timer.mark captures mark phase, timer.sweep captures sweep phase
     // Since minor GC skips clear phase, mark+sweep are combined under timer.sweep
@@ -1671,8 +2395,15 @@ fn collect_minor(heap: &mut LocalHeap) -> CollectResult {
 }
 
 /// Promote Young Pages to Old Generation.
+///
+/// After the pages are flipped to old, every promoted object is re-traced once
+/// with a `VisitorKind::Remember` visitor. Promotion does not go through the
+/// write barrier, so any old-to-young edge it creates (e.g. a reference into
+/// another heap's young pages) is recorded here by dirtying the object and
+/// listing its page, letting the next minor GC scan it as a root.
 fn promote_young_pages(heap: &mut LocalHeap) {
     let mut promoted_bytes = 0;
+    let mut promoted_pages: Vec<NonNull<PageHeader>> = Vec::new();
 
     for page_ptr in heap.all_pages() {
         unsafe {
@@ -1717,11 +2448,20 @@ fn promote_young_pages(heap: &mut LocalHeap) {
                     }
 
                     promoted_bytes += survivors_count * block_size;
+                    promoted_pages.push(page_ptr);
                 }
             }
         }
     }
 
+    // Rescan only after all pages are promoted, so edges between objects
+    // promoted together are not mistaken for old-to-young references.
+    for page_ptr in promoted_pages {
+        unsafe {
+            remember_promoted_page(heap, page_ptr);
+        }
+    }
+
     // Update GlobalHeap stats
     // After Minor GC, all small young objects are either promoted or swept.
     // So young generation usage for small objects is effectively 0.
@@ -1729,6 +2469,47 @@ fn promote_young_pages(heap: &mut LocalHeap) {
     heap.update_allocated_bytes(0, old + promoted_bytes);
 }
 
+/// Trace every live object on a freshly promoted page and record the ones that
+/// still reference young objects in the remembered set (dirty bit + dirty list).
+#[allow(unsafe_op_in_unsafe_fn)]
+unsafe fn remember_promoted_page(heap: &LocalHeap, page_ptr: NonNull<PageHeader>) {
+    let header = page_ptr.as_ptr();
+    let block_size = (*header).block_size as usize;
+    let header_size = if (*header).is_large_object() {
+        (*header).header_size as usize
+    } else {
+        PageHeader::header_size(block_size)
+    };
+    let obj_count = (*header).obj_count as usize;
+    let mut visitor = GcVisitor::new(VisitorKind::Remember);
+    let mut any_dirty = false;
+
+    for i in 0..obj_count {
+        if !(*header).is_allocated(i) {
+            continue;
+        }
+        let obj_ptr = header.cast::<u8>().add(header_size + i * block_size);
+        #[allow(clippy::cast_ptr_alignment)]
+        let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
+        if (*gc_box_ptr).has_dead_flag() || (*gc_box_ptr).is_under_construction() {
+            continue;
+        }
+
+        visitor.young_refs = 0;
+        (GcBox::trace_fn_of(gc_box_ptr))(obj_ptr, &mut visitor);
+        if visitor.young_refs > 0 {
+            (*header).set_dirty(i);
+            any_dirty = true;
+        }
+    }
+
+    if any_dirty {
+        // The object's young references need a full trace.
+        heap.take_dirty_fields(page_ptr);
+        heap.add_to_dirty_pages(page_ptr);
+    }
+}
+
 /// Major Collection: Collect Entire Heap.
 ///
 /// # Design Note
@@ -1760,7 +2541,7 @@ fn collect_major_stw(heap: &mut LocalHeap) -> CollectResult {
     let before_bytes = crate::heap::HEAP.with(|h| unsafe { &*h.tcb.heap.get() }.total_allocated());
 
     #[cfg(feature = "tracing")]
-    let _clear_span = trace_phase(GcPhase::Clear);
+    let clear_span = trace_phase(GcPhase::Clear);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Clear, before_bytes);
     timer.start();
@@ -1768,9 +2549,11 @@ fn collect_major_stw(heap: &mut LocalHeap) -> CollectResult {
     timer.end_clear();
     #[cfg(feature = "tracing")]
     log_phase_end(GcPhase::Clear, 0);
+    #[cfg(feature = "tracing")]
+    drop(clear_span);
 
     #[cfg(feature = "tracing")]
-    let _mark_span = trace_phase(GcPhase::Mark);
+    let mark_span = trace_phase(GcPhase::Mark);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Mark, before_bytes);
     timer.start();
@@ -1779,23 +2562,30 @@ fn collect_major_stw(heap: &mut LocalHeap) -> CollectResult {
     timer.end_mark();
     #[cfg(feature = "tracing")]
     log_phase_end_mark(GcPhase::Mark, objects_marked);
+    #[cfg(feature = "tracing")]
+    drop(mark_span);
 
     #[cfg(feature = "tracing")]
-    let _sweep_span = trace_phase(GcPhase::Sweep);
+    let sweep_span = trace_phase(GcPhase::Sweep);
     #[cfg(feature = "tracing")]
     log_phase_start(GcPhase::Sweep, before_bytes);
     timer.start();
 
     let reclaimed = sweep_segment_pages(heap, false);
     let reclaimed_large = sweep_large_objects(heap, false);
+    crate::handles::reap_dead_weak_handles();
 
     promote_all_pages(heap);
+    trim_after_major_sweep(heap);
+    heap.shrink_buffers();
     timer.end_sweep();
 
     #[cfg(feature = "tracing")]
     let after_bytes = crate::heap::HEAP.with(|h| unsafe { &*h.tcb.heap.get() }.total_allocated());
     #[cfg(feature = "tracing")]
     log_phase_end(GcPhase::Sweep, before_bytes.saturating_sub(after_bytes));
+    #[cfg(feature = "tracing")]
+    drop(sweep_span);
 
     CollectResult {
         objects_reclaimed: reclaimed + reclaimed_large,
@@ -1837,29 +2627,264 @@ fn collect_major_incremental(heap: &mut LocalHeap) -> CollectResult {
     }
     timer.end_mark();
 
+    let objects_reclaimed = finish_incremental_major(heap, &mut timer);
+
+    CollectResult {
+        objects_reclaimed,
+        timer,
+        collection_type: crate::metrics::CollectionType::IncrementalMajor,
+    }
+}
+
+/// Final mark and sweep of an incremental major collection whose marking
+/// slices are done. Returns the number of objects reclaimed.
+fn finish_incremental_major(heap: &mut LocalHeap, timer: &mut crate::metrics::PhaseTimer) -> usize {
+    let state = IncrementalMarkState::global();
+
     let remaining = state.worklist_len();
     let dirty_pages = count_dirty_pages(heap);
     if remaining > 0 || dirty_pages > 0 {
+        // The final mark only greys what the barriers recorded; trace it too.
         let heaps_mut: &mut [&mut LocalHeap; 1] = &mut [heap];
-        execute_final_mark(heaps_mut);
+        loop {
+            execute_final_mark(heaps_mut);
+            if state.worklist_is_empty() {
+                break;
+            }
+            crate::gc::incremental::drain_worklist();
+        }
+    }
+
+    state.set_phase(MarkPhase::Sweeping);
+
+    timer.start();
+    let reclaimed = sweep_segment_pages(heap, false);
+    let reclaimed_large = sweep_large_objects(heap, false);
+    crate::handles::reap_dead_weak_handles();
+
+    promote_all_pages(heap);
+    trim_after_major_sweep(heap);
+    heap.shrink_buffers();
+    timer.end_sweep();
+
+    state.set_phase(MarkPhase::Idle);
+
+    reclaimed + reclaimed_large
+}
+
+/// Give an in-progress incremental collection a marking slice before `heap`
+/// maps a new page.
+///
+/// If the slice finishes marking, the collection's final mark and sweep run
+/// too, and `true` is returned so the allocation can retry the memory just
+/// freed before growing the heap. Does nothing unless incremental marking
+/// is in its `Marking` phase, or if collection is disabled, paused,
+/// already running on this thread, or left to the collector thread.
+pub fn advance_incremental_marking(heap: &mut LocalHeap) -> bool {
+    let state = IncrementalMarkState::global();
+    if state.phase() != MarkPhase::Marking
+        || !GC_ENABLED.load(AtomicOrdering::Relaxed)
+        || is_gc_paused()
+        || IN_COLLECT.with(Cell::get)
+        || super::collector_thread::is_cycle_active()
+    {
+        return false;
     }
 
-    state.set_phase(MarkPhase::Sweeping);
+    let start = std::time::Instant::now();
+    let before_bytes = heap.total_allocated();
+    let mut timer = crate::metrics::PhaseTimer::new();
+
+    let budget = state.config().increment_size;
+    timer.start();
+    let result = mark_slice(heap, budget);
+    timer.end_mark();
+    match result {
+        MarkSliceResult::Pending { .. } => return false,
+        MarkSliceResult::Complete { .. } => {}
+        MarkSliceResult::Fallback { reason } => {
+            log_fallback_reason(reason);
+            state.set_phase(MarkPhase::FinalMark);
+        }
+    }
+
+    complete_incremental_major(heap, start, before_bytes, &mut timer);
+    true
+}
+
+/// Run the final mark and sweep of an incremental collection whose marking
+/// is done, and record its metrics.
+fn complete_incremental_major(
+    heap: &mut LocalHeap,
+    start: std::time::Instant,
+    before_bytes: usize,
+    timer: &mut crate::metrics::PhaseTimer,
+) {
+    IN_COLLECT.with(|in_collect| in_collect.set(true));
+    let objects_reclaimed = finish_incremental_major(heap, timer);
+    IN_COLLECT.with(|in_collect| in_collect.set(false));
+
+    let after_bytes = heap.total_allocated();
+    let mark_stats = IncrementalMarkState::global().stats();
+    crate::metrics::record_metrics(crate::metrics::GcMetrics {
+        duration: start.elapsed(),
+        bytes_reclaimed: before_bytes.saturating_sub(after_bytes),
+        bytes_surviving: after_bytes,
+        objects_reclaimed,
+        collection_type: crate::metrics::CollectionType::IncrementalMajor,
+        mark_duration: timer.mark,
+        sweep_duration: timer.sweep,
+        objects_marked: mark_stats.objects_marked.load(Ordering::Relaxed),
+        dirty_pages_scanned: mark_stats.dirty_pages_scanned.load(Ordering::Relaxed),
+        slices_executed: mark_stats.slices_executed.load(Ordering::Relaxed),
+        fallback_occurred: mark_stats.fallback_occurred.load(Ordering::Relaxed),
+        fallback_reason: crate::metrics::FallbackReason::from_u32(
+            mark_stats.fallback_reason.load(Ordering::Relaxed),
+        ),
+        ..crate::metrics::GcMetrics::new()
+    });
+}
+
+/// What a call to [`collect_full_within`] got done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectWithinResult {
+    /// The collection finished and the heap was swept.
+    Complete,
+    /// The budget ran out while marking; the next call resumes it.
+    Pending,
+    /// Nothing was done, because collection is disabled, paused (for
+    /// example by a [`NoGcGuard`]), already running on this thread, or a
+    /// collector-thread collection is in progress. Calling again at once
+    /// will most likely be skipped too.
+    Skipped,
+}
 
-    timer.start();
-    let reclaimed = sweep_segment_pages(heap, false);
-    let reclaimed_large = sweep_large_objects(heap, false);
+/// Make progress on a full collection for at most about `max`, marking
+/// incrementally instead of in one stop-the-world pause.
+///
+/// Starts an incremental collection if none is in progress, then runs
+/// marking slices until marking completes or `max` runs out. Returns
+/// [`CollectWithinResult::Complete`] once marking completed and the heap
+/// was swept, and [`CollectWithinResult::Pending`] if the budget expired
+/// first: the mark state is kept, the write barriers stay on, and the next
+/// call resumes from where this one stopped. Call it repeatedly, for
+/// example once per request or frame, while it returns `Pending`;
+/// allocation may also finish the collection in between.
+///
+/// The budget bounds marking only. A slice overruns it by up to one
+/// [`IncrementalConfig::increment_size`] worth of work, and the final mark
+/// and sweep run to completion. If marking falls back, for example because
+/// the write barriers' buffers overflowed, the collection is redone with
+/// [`collect_full`].
+/// Marking is only paused if incremental marking is enabled, because the
+/// write barriers that keep a paused mark sound are off otherwise; with it
+/// disabled, or with more than one thread registered, whose stacks this
+/// thread cannot scan, this runs [`collect_full`] and returns `Complete`.
+///
+/// Returns [`CollectWithinResult::Skipped`] without doing anything if
+/// collection is disabled, paused, already running on this thread, or a
+/// collector-thread collection is in progress.
+///
+/// [`IncrementalConfig::increment_size`]: crate::gc::incremental::IncrementalConfig::increment_size
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use rudo_gc::gc::incremental::{IncrementalConfig, IncrementalMarkState};
+/// use rudo_gc::{collect_full_within, CollectWithinResult, Gc};
+///
+/// IncrementalMarkState::global().set_config(IncrementalConfig {
+///     enabled: true,
+///     ..IncrementalConfig::default()
+/// });
+/// let _data = Gc::new([0u64; 16]);
+/// while collect_full_within(Duration::from_micros(500)) == CollectWithinResult::Pending {
+///     // Serve a request, render a frame...
+/// }
+/// ```
+pub fn collect_full_within(max: std::time::Duration) -> CollectWithinResult {
+    let start = std::time::Instant::now();
+    if !GC_ENABLED.load(AtomicOrdering::Relaxed)
+        || is_gc_paused()
+        || IN_COLLECT.with(Cell::get)
+        || super::collector_thread::is_cycle_active()
+    {
+        return CollectWithinResult::Skipped;
+    }
+    let state = IncrementalMarkState::global();
+    if !state.is_enabled() || crate::heap::thread_registry().lock().unwrap().threads.len() > 1 {
+        collect_full();
+        return CollectWithinResult::Complete;
+    }
 
-    promote_all_pages(heap);
-    timer.end_sweep();
+    let finished = crate::heap::with_heap(|heap| {
+        let mut timer = crate::metrics::PhaseTimer::new();
+        if state.phase() == MarkPhase::Idle {
+            timer.start();
+            // A pending lazy sweep reads the last collection's marks.
+            #[cfg(feature = "lazy-sweep")]
+            let _ = sweep_pending(heap, usize::MAX);
+            clear_all_marks_and_dirty(heap);
+            // Marking would rescan these pages as if written since the
+            // snapshot; the sweep promotes every survivor anyway.
+            heap.take_dirty_pages_snapshot();
+            let dirty_pages: Vec<*const PageHeader> = heap
+                .dirty_pages_iter()
+                .map(|page| page.as_ptr().cast_const())
+                .collect();
+            clear_dirty_page_states(&dirty_pages);
+            heap.clear_dirty_pages_snapshot();
+            let heaps: [&LocalHeap; 1] = [&*heap];
+            execute_snapshot(&heaps);
+            timer.end_clear();
+        }
+        let before_bytes = heap.total_allocated();
 
-    state.set_phase(MarkPhase::Idle);
+        let budget = state.config().increment_size;
+        timer.start();
+        // Grey what the barriers recorded since the last call, so that a
+        // long run of calls doesn't overflow the SATB buffer.
+        let mut visitor = GcVisitor::new(VisitorKind::Major);
+        for gc_box in heap.flush_satb_buffer() {
+            // SAFETY: the barrier recorded an allocated object, and nothing
+            // is swept while marking.
+            unsafe { mark_object(gc_box, &mut visitor) };
+        }
+        while let Some((ptr, _enqueue_generation)) = visitor.worklist.pop() {
+            state.push_work(ptr);
+        }
+        loop {
+            // Time between calls is the mutator's, not this slice's.
+            state.start_slice();
+            match mark_slice(heap, budget) {
+                MarkSliceResult::Complete { .. } => break,
+                MarkSliceResult::Pending { .. } => {
+                    if start.elapsed() >= max {
+                        return Ok(CollectWithinResult::Pending);
+                    }
+                }
+                MarkSliceResult::Fallback { reason } => {
+                    // The barriers stop recording once a fallback is
+                    // requested, and the mutator may have run since.
+                    log_fallback_reason(reason);
+                    state.reset();
+                    return Err(reason);
+                }
+            }
+        }
+        timer.end_mark();
 
-    CollectResult {
-        objects_reclaimed: reclaimed + reclaimed_large,
-        timer,
-        collection_type: crate::metrics::CollectionType::IncrementalMajor,
-    }
+        complete_incremental_major(heap, start, before_bytes, &mut timer);
+        Ok(CollectWithinResult::Complete)
+    });
+    finished.unwrap_or_else(|reason| {
+        collect_full();
+        // The redo took a fresh snapshot; keep the fallback observable.
+        state.stats().record_fallback(reason);
+        CollectWithinResult::Complete
+    })
 }
 
 /// Clear all mark bits, dirty bits, and reset `dead_count` in the heap.
@@ -1873,6 +2898,7 @@ fn collect_major_incremental(heap: &mut LocalHeap) -> CollectResult {
 ///
 /// This separation ensures dirty page tracking works correctly across GC cycles.
 fn clear_all_marks_and_dirty(heap: &LocalHeap) {
+    heap.clear_dirty_fields();
     for page_ptr in heap.all_pages() {
         // SAFETY: Page pointers in the heap are always valid
         unsafe {
@@ -1885,6 +2911,151 @@ fn clear_all_marks_and_dirty(heap: &LocalHeap) {
     }
 }
 
+/// Begin a collector-thread collection of every registered heap.
+///
+/// Runs on the collector thread while every mutator is stopped. Pending
+/// lazy sweeps are finished first, since they read the previous marks; then
+/// marks are cleared and the roots pushed gray onto the incremental worklist.
+/// Tracing is left to [`collector_thread_mark_slice`].
+pub(super) fn start_collector_thread_cycle(tcbs: &[Arc<crate::heap::ThreadControlBlock>]) {
+    IN_COLLECT.with(|in_collect| in_collect.set(true));
+
+    let stack_roots: Vec<(*const u8, Arc<crate::heap::ThreadControlBlock>)> = tcbs
+        .iter()
+        .flat_map(|tcb| {
+            let roots = crate::heap::take_stack_roots(tcb);
+            roots.into_iter().map(move |ptr| (ptr, tcb.clone()))
+        })
+        .collect();
+
+    for tcb in tcbs {
+        // SAFETY: the owning threads are stopped.
+        let heap = unsafe { &mut *tcb.heap.get() };
+        #[cfg(feature = "lazy-sweep")]
+        let _ = sweep_pending(heap, usize::MAX);
+        clear_all_marks_and_dirty(heap);
+    }
+
+    let state = IncrementalMarkState::global();
+    state.set_phase(MarkPhase::Snapshot);
+    state.stats().reset();
+    state.reset_fallback();
+    state.reset_worklist();
+
+    let mut visitor = GcVisitor::new(VisitorKind::Major);
+    for tcb in tcbs {
+        // SAFETY: as above.
+        let heap = unsafe { &*tcb.heap.get() };
+        for_each_major_root_multi(heap, &stack_roots, |gc_box| unsafe {
+            crate::gc::incremental::mark_root_for_snapshot(gc_box, &mut visitor);
+        });
+    }
+    while let Some((ptr, _enqueue_generation)) = visitor.worklist.pop() {
+        state.push_work(ptr);
+    }
+
+    state.set_root_count(state.worklist_len());
+    #[cfg(feature = "tracing")]
+    state.set_gc_id(next_gc_id());
+    state.set_phase(MarkPhase::Marking);
+
+    IN_COLLECT.with(|in_collect| in_collect.set(false));
+}
+
+/// Run one marking slice of a collector-thread collection over every heap, with
+/// all mutators stopped.
+///
+/// Returns true once the collection is ready for
+/// [`finish_collector_thread_cycle`]: the worklist ran dry, or marking fell
+/// back and has to finish in a single pause.
+pub(super) fn collector_thread_mark_slice(tcbs: &[Arc<crate::heap::ThreadControlBlock>]) -> bool {
+    let state = IncrementalMarkState::global();
+    let budget = state.config().increment_size;
+    state.start_slice();
+    for tcb in tcbs {
+        // SAFETY: the owning threads are stopped.
+        let heap = unsafe { &mut *tcb.heap.get() };
+        if let MarkSliceResult::Fallback { reason } = mark_slice(heap, budget) {
+            log_fallback_reason(reason);
+            return true;
+        }
+    }
+    state.worklist_is_empty()
+}
+
+/// Final mark and sweep of a collector-thread collection, with all mutators
+/// stopped.
+///
+/// `earlier_pauses` is the time the collection has already spent in
+/// snapshot and marking pauses; it is added to this pause in the recorded
+/// metrics.
+pub(super) fn finish_collector_thread_cycle(
+    tcbs: &[Arc<crate::heap::ThreadControlBlock>],
+    earlier_pauses: std::time::Duration,
+) {
+    let start = std::time::Instant::now();
+    IN_COLLECT.with(|in_collect| in_collect.set(true));
+
+    let state = IncrementalMarkState::global();
+    // SAFETY: the owning threads are stopped.
+    let mut heaps: Vec<&mut LocalHeap> = tcbs
+        .iter()
+        .map(|tcb| unsafe { &mut *tcb.heap.get() })
+        .collect();
+    let before_bytes: usize = heaps.iter().map(|heap| heap.total_allocated()).sum();
+    let mut timer = crate::metrics::PhaseTimer::new();
+
+    timer.start();
+    // The final mark only greys what the barriers recorded; trace it too,
+    // whatever the slice budget or a fallback say.
+    loop {
+        execute_final_mark(&mut heaps);
+        if state.worklist_is_empty() {
+            break;
+        }
+        crate::gc::incremental::drain_worklist();
+    }
+    timer.end_mark();
+
+    state.set_phase(MarkPhase::Sweeping);
+    crate::gc::gc_fence(Ordering::AcqRel);
+
+    timer.start();
+    let mut objects_reclaimed = 0;
+    for heap in &mut heaps {
+        objects_reclaimed += sweep_segment_pages(heap, false) + sweep_large_objects(heap, false);
+        promote_all_pages(heap);
+        trim_after_major_sweep(heap);
+        heap.shrink_buffers();
+    }
+    crate::heap::sweep_orphan_pages();
+    crate::handles::reap_dead_weak_handles();
+    timer.end_sweep();
+
+    state.set_phase(MarkPhase::Idle);
+    IN_COLLECT.with(|in_collect| in_collect.set(false));
+
+    let after_bytes: usize = heaps.iter().map(|heap| heap.total_allocated()).sum();
+    let mark_stats = state.stats();
+    crate::metrics::record_metrics(crate::metrics::GcMetrics {
+        duration: earlier_pauses + start.elapsed(),
+        bytes_reclaimed: before_bytes.saturating_sub(after_bytes),
+        bytes_surviving: after_bytes,
+        objects_reclaimed,
+        collection_type: crate::metrics::CollectionType::IncrementalMajor,
+        mark_duration: timer.mark,
+        sweep_duration: timer.sweep,
+        objects_marked: mark_stats.objects_marked.load(Ordering::Relaxed),
+        dirty_pages_scanned: mark_stats.dirty_pages_scanned.load(Ordering::Relaxed),
+        slices_executed: mark_stats.slices_executed.load(Ordering::Relaxed),
+        fallback_occurred: mark_stats.fallback_occurred.load(Ordering::Relaxed),
+        fallback_reason: crate::metrics::FallbackReason::from_u32(
+            mark_stats.fallback_reason.load(Ordering::Relaxed),
+        ),
+        ..crate::metrics::GcMetrics::new()
+    });
+}
+
 #[inline]
 fn clear_dirty_page_states(headers: &[*const PageHeader]) {
     for &header_ptr in headers {
@@ -1931,26 +3102,7 @@ fn mark_minor_roots(heap: &mut LocalHeap) -> usize {
         });
     }
 
-    #[allow(clippy::type_complexity)]
-    let mut cross_thread_roots: Vec<*const GcBox<()>> = {
-        crate::heap::thread_registry().lock().map_or_else(
-            |_| Vec::new(),
-            |registry| {
-                registry
-                    .threads
-                    .iter()
-                    .flat_map(|tcb| {
-                        let mut roots = Vec::new();
-                        tcb.iterate_cross_thread_roots(|ptr| roots.push(ptr));
-                        roots
-                    })
-                    .collect()
-            },
-        )
-    };
-    cross_thread_roots.extend(crate::heap::get_orphaned_cross_thread_roots());
-
-    for ptr in cross_thread_roots {
+    for ptr in all_cross_thread_roots() {
         unsafe {
             if let Some(gc_box_ptr) = crate::heap::find_gc_box_from_ptr(heap, ptr.cast::<u8>()) {
                 mark_object_minor(gc_box_ptr, &mut visitor);
@@ -1975,12 +3127,12 @@ fn mark_minor_roots(heap: &mut LocalHeap) -> usize {
 
     for page_ptr in heap.dirty_pages_iter() {
         unsafe {
-            scan_dirty_page_minor_trace(page_ptr, &mut visitor);
+            scan_dirty_page_minor_trace(heap, page_ptr, &mut visitor);
         }
     }
     for page_ptr in heap.drain_dirty_pages_overflow() {
         unsafe {
-            scan_dirty_page_minor_trace(page_ptr, &mut visitor);
+            scan_dirty_page_minor_trace(heap, page_ptr, &mut visitor);
         }
     }
     heap.clear_dirty_pages_snapshot();
@@ -1991,6 +3143,9 @@ fn mark_minor_roots(heap: &mut LocalHeap) -> usize {
 /// Mark roots for Major GC (Stack).
 /// Returns the number of objects marked.
 fn mark_major_roots(heap: &LocalHeap) -> usize {
+    #[cfg(feature = "tracing")]
+    let scan_span = trace_step(GcStep::ScanRoots);
+
     let mut visitor = GcVisitor::new(VisitorKind::Major);
     unsafe {
         crate::stack::spill_registers_and_scan(|ptr, _addr, _is_reg| {
@@ -2009,26 +3164,7 @@ fn mark_major_roots(heap: &LocalHeap) -> usize {
         });
     }
 
-    #[allow(clippy::type_complexity)]
-    let mut cross_thread_roots: Vec<*const GcBox<()>> = {
-        crate::heap::thread_registry().lock().map_or_else(
-            |_| Vec::new(),
-            |registry| {
-                registry
-                    .threads
-                    .iter()
-                    .flat_map(|tcb| {
-                        let mut roots = Vec::new();
-                        tcb.iterate_cross_thread_roots(|ptr| roots.push(ptr));
-                        roots
-                    })
-                    .collect()
-            },
-        )
-    };
-    cross_thread_roots.extend(crate::heap::get_orphaned_cross_thread_roots());
-
-    for ptr in cross_thread_roots {
+    for ptr in all_cross_thread_roots() {
         unsafe {
             if let Some(gc_box) = crate::heap::find_gc_box_from_ptr(heap, ptr.cast::<u8>()) {
                 mark_object(gc_box, &mut visitor);
@@ -2047,7 +3183,11 @@ fn mark_major_roots(heap: &LocalHeap) -> usize {
             }
         }
     }
+    #[cfg(feature = "tracing")]
+    drop(scan_span);
 
+    #[cfg(feature = "tracing")]
+    let _trace_span = trace_step(GcStep::TraceGraph);
     visitor.process_worklist();
     visitor.objects_marked()
 }
@@ -2114,6 +3254,111 @@ pub unsafe fn mark_object_minor(ptr: NonNull<GcBox<()>>, visitor: &mut GcVisitor
     }
 }
 
+/// Record a reference to `ptr` if it points into the young generation.
+///
+/// Used by `VisitorKind::Remember` passes: nothing is marked or enqueued, the
+/// visitor only counts edges whose target is still young so the caller can put
+/// the referencing object into the remembered set.
+///
+/// # Safety
+///
+/// `ptr` must be a non-null pointer that may or may not refer to a `GcBox`.
+pub unsafe fn remember_young_ref(ptr: NonNull<GcBox<()>>, visitor: &mut GcVisitor) {
+    let ptr_addr = ptr.as_ptr() as *const u8;
+    unsafe {
+        let header = crate::heap::ptr_to_page_header(ptr_addr);
+        if (*header.as_ptr()).magic != crate::heap::MAGIC_GC_PAGE {
+            return;
+        }
+        let Some(idx) = crate::heap::ptr_to_object_index(ptr_addr) else {
+            return;
+        };
+        if !(*header.as_ptr()).is_allocated(idx) {
+            return;
+        }
+        if (*header.as_ptr()).generation.load(Ordering::Acquire) == 0
+            && !(*ptr.as_ptr()).has_gen_old_flag()
+        {
+            visitor.young_refs += 1;
+        }
+    }
+}
+
+/// Queue an unreachable object for deferred finalization.
+///
+/// Returns `false` if deferred finalization is disabled and the caller must
+/// finalize the object itself. Marking the object as dropping blocks
+/// `Weak::upgrade`, and because it is not flagged dead yet, the reclaim
+/// phase leaves its slot alone. An object that is already dropping is either
+/// queued from an earlier sweep or mid-drop, so it is skipped as well.
+unsafe fn try_defer_finalizer(
+    gc_box_ptr: *mut GcBox<()>,
+    deferred: &mut Vec<NonNull<GcBox<()>>>,
+) -> bool {
+    if !DEFER_FINALIZATION.load(AtomicOrdering::Relaxed) {
+        return false;
+    }
+    unsafe {
+        if (*gc_box_ptr).try_mark_dropping() {
+            deferred.push(NonNull::new_unchecked(gc_box_ptr));
+        }
+    }
+    true
+}
+
+/// Run every finalizer queued on `heap`, returning how many ran.
+///
+/// Entries are popped one at a time because a finalizer may allocate and
+/// trigger a collection, which drains the rest of the queue itself.
+///
+/// # Safety
+///
+/// `heap` must point to a live `LocalHeap` that is not being swept.
+unsafe fn drain_deferred_finalizers(heap: *mut LocalHeap) -> usize {
+    let mut ran = 0;
+    unsafe {
+        while let Some(ptr) = (*heap).deferred_finalizers.pop() {
+            let gc_box_ptr = ptr.as_ptr();
+            (GcBox::drop_fn_of(gc_box_ptr))(gc_box_ptr.cast::<u8>());
+            GcBox::retire_fns(gc_box_ptr);
+            (*gc_box_ptr).set_dead();
+            ran += 1;
+        }
+    }
+    ran
+}
+
+/// Run the finalizers still queued on an exiting thread's heap, so that
+/// they run on their own thread as [`set_deferred_finalization`] promises.
+///
+/// The thread-local heap is being destroyed, so collections the finalizers
+/// would trigger are suppressed.
+///
+/// # Safety
+///
+/// `heap` must point to the exiting thread's `LocalHeap`.
+pub unsafe fn drain_deferred_finalizers_at_exit(heap: *mut LocalHeap) {
+    if unsafe { (*heap).deferred_finalizers.is_empty() } {
+        return;
+    }
+    let was_collecting = IN_COLLECT.with(|in_collect| in_collect.replace(true));
+    unsafe { drain_deferred_finalizers(heap) };
+    IN_COLLECT.with(|in_collect| in_collect.set(was_collecting));
+}
+
+/// Order `heap`'s deferred finalizer queue so that draining it, which pops
+/// from the back, runs the highest
+/// [`Trace::trace_order`](crate::Trace::trace_order) first.
+fn sort_deferred_finalizers(heap: &mut LocalHeap) {
+    if heap.deferred_finalizers.is_empty() {
+        return;
+    }
+    let mut visitor = GcVisitor::new(VisitorKind::Order);
+    // SAFETY: queued objects stay allocated until their finalizer has run.
+    heap.deferred_finalizers
+        .sort_by_cached_key(|ptr| unsafe { GcBox::trace_order_of(ptr.as_ptr(), &mut visitor) });
+}
+
 /// Sweep pages in regular segments.
 ///
 /// Two-phase sweep to prevent Use-After-Free during Drop:
@@ -2123,8 +3368,15 @@ fn sweep_segment_pages(heap: &mut LocalHeap, only_young: bool) -> usize {
     #[cfg(feature = "tracing")]
     tracing::debug!(heap_bytes = heap.total_allocated(), "sweep_start");
 
-    sweep_phase1_finalize(heap, only_young);
+    // Finalizers left over from an earlier sweep run first, while everything
+    // they reference is still allocated. `sweep_large_objects` always follows
+    // this call, so it does not drain again.
+    unsafe { drain_deferred_finalizers(heap) };
+
+    let mut deferred = Vec::new();
+    sweep_phase1_finalize(heap, only_young, &mut deferred);
     let reclaimed = sweep_phase2_reclaim(heap, only_young);
+    heap.deferred_finalizers.append(&mut deferred);
 
     #[cfg(feature = "tracing")]
     tracing::debug!(objects_freed = reclaimed, "sweep_end");
@@ -2132,6 +3384,16 @@ fn sweep_segment_pages(heap: &mut LocalHeap, only_young: bool) -> usize {
     reclaimed
 }
 
+/// Whether `gc_box_ptr`'s value is being dropped further up the stack.
+///
+/// `drop_fn` flags the object dead before running the value's destructor, so
+/// a collection triggered from inside that destructor sees an unmarked, dead
+/// object. Its slot has to outlive the destructor, so sweeps leave it for a
+/// later cycle instead of finalizing or reclaiming it.
+unsafe fn is_mid_drop(gc_box_ptr: *const GcBox<()>) -> bool {
+    unsafe { (*gc_box_ptr).has_dead_flag() && (*gc_box_ptr).dropping_state() == 1 }
+}
+
 /// Phase 1: Execute Drop functions for all dead objects.
 ///
 /// This phase only calls `drop_fn` but does NOT reclaim memory yet.
@@ -2149,20 +3411,42 @@ fn sweep_segment_pages(heap: &mut LocalHeap, only_young: bool) -> usize {
 /// ```
 ///
 /// See `docs/reentrant-alloc-rules.md` for safety guidelines.
-fn sweep_phase1_finalize(heap: &LocalHeap, only_young: bool) {
+///
+/// With deferred finalization enabled, doomed objects are pushed onto
+/// `deferred` instead of being dropped here.
+///
+/// Doomed objects, including large ones, are finalized after the scan in
+/// [`Trace::trace_order`](crate::Trace::trace_order) order. Large objects
+/// are still reclaimed, or deferred, by `sweep_large_objects`.
+fn sweep_phase1_finalize(
+    heap: &LocalHeap,
+    only_young: bool,
+    deferred: &mut Vec<NonNull<GcBox<()>>>,
+) {
     // Snapshot pages to prevent iterator invalidation if drop_fn allocates memory
     // (which could trigger heap.pages.push() and invalidate the iterator)
     let pages_snapshot: Vec<_> = heap.all_pages().collect();
+    let mut doomed = Vec::new();
+    let mut order_visitor = GcVisitor::new(VisitorKind::Order);
 
     for page_ptr in pages_snapshot {
         unsafe {
             let header = page_ptr.as_ptr();
 
-            if (*header).is_large_object() {
+            if only_young && (*header).generation.load(Ordering::Acquire) > 0 {
                 continue;
             }
 
-            if only_young && (*header).generation.load(Ordering::Acquire) > 0 {
+            if (*header).is_large_object() {
+                if !(*header).is_marked(0) && !DEFER_FINALIZATION.load(AtomicOrdering::Relaxed) {
+                    let header_size = (*header).header_size as usize;
+                    #[allow(clippy::cast_ptr_alignment)]
+                    let gc_box_ptr = header.cast::<u8>().add(header_size).cast::<GcBox<()>>();
+                    if !(*gc_box_ptr).has_dead_flag() {
+                        let order = GcBox::trace_order_of(gc_box_ptr, &mut order_visitor);
+                        doomed.push((order, gc_box_ptr));
+                    }
+                }
                 continue;
             }
 
@@ -2201,28 +3485,51 @@ fn sweep_phase1_finalize(heap: &LocalHeap, only_young: bool) {
                     #[allow(clippy::cast_ptr_alignment)]
                     let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
 
-                    let (weak_count, dead_flag) = (*gc_box_ptr).weak_count_and_dead_flag();
+                    if is_mid_drop(gc_box_ptr) {
+                        continue;
+                    }
 
-                    if weak_count > 0 {
-                        // Has weak refs - drop value but keep allocation
-                        if !dead_flag {
-                            ((*gc_box_ptr).drop_fn)(obj_ptr);
-                            (*gc_box_ptr).drop_fn = GcBox::<()>::no_op_drop;
-                            (*gc_box_ptr).trace_fn = GcBox::<()>::no_op_trace;
-                            (*gc_box_ptr).set_dead();
-                        }
-                    } else {
-                        // No weak refs - will be fully reclaimed
-                        // Execute drop_fn now (phase 1)
-                        ((*gc_box_ptr).drop_fn)(obj_ptr);
-
-                        // CRITICAL FIX: Mark as dead so phase 2 knows to reclaim.
-                        // Without this, has_dead_flag() returns false in phase 2,
-                        // objects are never reclaimed, and the next GC cycle will
-                        // try to drop them again - use-after-free!
-                        (*gc_box_ptr).set_dead();
+                    let dead_flag = (*gc_box_ptr).has_dead_flag();
+
+                    #[cfg(feature = "type-tracking")]
+                    crate::metrics::note_reclaimed(gc_box_ptr);
+
+                    if !dead_flag && try_defer_finalizer(gc_box_ptr, deferred) {
+                        continue;
                     }
+
+                    let order = GcBox::trace_order_of(gc_box_ptr, &mut order_visitor);
+                    doomed.push((order, gc_box_ptr));
+                }
+            }
+        }
+    }
+
+    // Stable, so objects of equal order keep heap order.
+    doomed.sort_by_key(|&(order, _)| std::cmp::Reverse(order));
+    for (_, gc_box_ptr) in doomed {
+        unsafe {
+            let obj_ptr = gc_box_ptr.cast::<u8>();
+            // Read now: finalizers that ran earlier may have dropped the
+            // last weak reference or this object's value.
+            let (weak_count, dead_flag) = (*gc_box_ptr).weak_count_and_dead_flag();
+            if weak_count > 0 {
+                // Has weak refs - drop value but keep allocation
+                if !dead_flag {
+                    (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
+                    GcBox::retire_fns(gc_box_ptr);
+                    (*gc_box_ptr).set_dead();
                 }
+            } else {
+                // No weak refs - will be fully reclaimed
+                // Execute drop_fn now (phase 1)
+                (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
+
+                // CRITICAL FIX: Mark as dead so phase 2 knows to reclaim.
+                // Without this, has_dead_flag() returns false in phase 2,
+                // objects are never reclaimed, and the next GC cycle will
+                // try to drop them again - use-after-free!
+                (*gc_box_ptr).set_dead();
             }
         }
     }
@@ -2283,7 +3590,7 @@ fn sweep_phase2_reclaim(heap: &mut LocalHeap, only_young: bool) -> usize {
 
                     let (weak_count, dead_flag) = (*gc_box_ptr).weak_count_and_dead_flag();
 
-                    if weak_count == 0 && dead_flag {
+                    if weak_count == 0 && dead_flag && !is_mid_drop(gc_box_ptr) {
                         // No weak refs, already dropped and dead - reclaim
                         // CRITICAL FIX: Write free list head BEFORE clearing allocated bit
                         // to prevent new allocations from reusing this slot with corrupted metadata
@@ -2291,6 +3598,8 @@ fn sweep_phase2_reclaim(heap: &mut LocalHeap, only_young: bool) -> usize {
                         let obj_cast = obj_ptr.cast::<Option<u16>>();
                         obj_cast.write_unaligned(free_head);
                         free_head = Some(u16::try_from(i).unwrap());
+                        #[cfg(feature = "poison-freed")]
+                        crate::heap::poison_freed_slot(obj_ptr, block_size);
 
                         (*header).clear_allocated(i);
                         (*gc_box_ptr).clear_gen_old();
@@ -2490,6 +3799,7 @@ fn sweep_large_objects(heap: &mut LocalHeap, only_young: bool) -> usize {
     let target_pages = heap.large_object_pages();
 
     let mut to_deallocate: Vec<(NonNull<PageHeader>, usize, usize)> = Vec::new();
+    let mut deferred = Vec::new();
 
     for page_ptr in target_pages {
         unsafe {
@@ -2499,7 +3809,9 @@ fn sweep_large_objects(heap: &mut LocalHeap, only_young: bool) -> usize {
                 continue;
             }
 
-            if !(*header).is_marked(0) {
+            // An `FfiLock` roots its object, but its page must never be
+            // released under foreign code even if marking missed it.
+            if !(*header).is_marked(0) && !crate::heap::is_page_pinned_for_ffi(header as usize) {
                 let block_size = (*header).block_size as usize;
                 let header_size = (*header).header_size as usize;
                 let obj_ptr = header.cast::<u8>().add(header_size);
@@ -2508,11 +3820,17 @@ fn sweep_large_objects(heap: &mut LocalHeap, only_young: bool) -> usize {
 
                 let (weak_count, dead_flag) = (*gc_box_ptr).weak_count_and_dead_flag();
 
+                #[cfg(feature = "type-tracking")]
+                crate::metrics::note_reclaimed(gc_box_ptr);
+
+                if !dead_flag && try_defer_finalizer(gc_box_ptr, &mut deferred) {
+                    continue;
+                }
+
                 if weak_count > 0 {
                     if !dead_flag {
-                        ((*gc_box_ptr).drop_fn)(obj_ptr);
-                        (*gc_box_ptr).drop_fn = GcBox::<()>::no_op_drop;
-                        (*gc_box_ptr).trace_fn = GcBox::<()>::no_op_trace;
+                        (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
+                        GcBox::retire_fns(gc_box_ptr);
                         (*gc_box_ptr).set_dead();
                     }
                 } else {
@@ -2520,7 +3838,7 @@ fn sweep_large_objects(heap: &mut LocalHeap, only_young: bool) -> usize {
                     let pages_needed = total_size.div_ceil(crate::heap::page_size());
                     let alloc_size = pages_needed * crate::heap::page_size();
 
-                    ((*gc_box_ptr).drop_fn)(obj_ptr);
+                    (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
 
                     to_deallocate.push((page_ptr, alloc_size, pages_needed));
                 }
@@ -2528,6 +3846,11 @@ fn sweep_large_objects(heap: &mut LocalHeap, only_young: bool) -> usize {
         }
     }
 
+    heap.deferred_finalizers.append(&mut deferred);
+    // This pass runs after `sweep_segment_pages`, so the whole queue is this
+    // sweep's.
+    sort_deferred_finalizers(heap);
+
     let mut reclaimed = 0;
 
     // Batch collect pages to remove for O(N) instead of O(N²)
@@ -2540,24 +3863,9 @@ fn sweep_large_objects(heap: &mut LocalHeap, only_young: bool) -> usize {
         unsafe {
             let header_addr = page_ptr.as_ptr() as usize;
 
-            // Remove from both maps. If a panic occurs between operations,
-            // the state may be temporarily inconsistent, but the page will
-            // still be deallocated. In practice, panics during GC are catastrophic.
-            for p in 0..pages_needed {
-                let page_addr = header_addr + (p * crate::heap::page_size());
-                heap.large_object_map.remove(&page_addr);
-            }
-            {
-                let mut manager = crate::heap::segment_manager()
-                    .lock()
-                    .unwrap_or_else(PoisonError::into_inner);
-                for p in 0..pages_needed {
-                    let page_addr = header_addr + (p * crate::heap::page_size());
-                    manager.large_object_map.remove(&page_addr);
-                }
-            }
+            crate::heap::unregister_large_object(header_addr, pages_needed);
 
-            sys_alloc::Mmap::from_raw(page_ptr.as_ptr().cast::<u8>(), alloc_size);
+            crate::heap::release_large_pages(page_ptr.as_ptr().cast::<u8>(), alloc_size);
 
             reclaimed += 1;
         }
@@ -2610,18 +3918,22 @@ unsafe fn lazy_sweep_page(
             #[allow(clippy::cast_ptr_alignment)]
             let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
 
+            if is_mid_drop(gc_box_ptr) {
+                all_dead = false;
+                continue;
+            }
+
             let (weak_count, dead_flag) = (*gc_box_ptr).weak_count_and_dead_flag();
 
             if weak_count > 0 {
                 if !dead_flag {
-                    ((*gc_box_ptr).drop_fn)(obj_ptr);
-                    (*gc_box_ptr).drop_fn = GcBox::<()>::no_op_drop;
-                    (*gc_box_ptr).trace_fn = GcBox::<()>::no_op_trace;
+                    (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
+                    GcBox::retire_fns(gc_box_ptr);
                     (*gc_box_ptr).set_dead();
                 }
                 all_dead = false;
             } else {
-                ((*gc_box_ptr).drop_fn)(obj_ptr);
+                (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
                 (*gc_box_ptr).set_dead();
                 // Clear GEN_OLD_FLAG so reused slots don't inherit stale barrier state (bug135).
                 (*gc_box_ptr).clear_gen_old();
@@ -2695,6 +4007,8 @@ unsafe fn lazy_sweep_page(
                 }
 
                 if did_reclaim {
+                    #[cfg(feature = "poison-freed")]
+                    crate::heap::poison_freed_slot(obj_ptr, block_size);
                     (*header).clear_allocated(i);
                     reclaimed += 1;
                 }
@@ -2741,17 +4055,20 @@ unsafe fn lazy_sweep_page_all_dead(
             #[allow(clippy::cast_ptr_alignment)]
             let gc_box_ptr = obj_ptr.cast::<GcBox<()>>();
 
+            if is_mid_drop(gc_box_ptr) {
+                continue;
+            }
+
             let (weak_count, dead_flag) = (*gc_box_ptr).weak_count_and_dead_flag();
 
             if weak_count > 0 {
                 if !dead_flag {
-                    ((*gc_box_ptr).drop_fn)(obj_ptr);
-                    (*gc_box_ptr).drop_fn = GcBox::<()>::no_op_drop;
-                    (*gc_box_ptr).trace_fn = GcBox::<()>::no_op_trace;
+                    (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
+                    GcBox::retire_fns(gc_box_ptr);
                     (*gc_box_ptr).set_dead();
                 }
             } else {
-                ((*gc_box_ptr).drop_fn)(obj_ptr);
+                (GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
                 // Clear GEN_OLD_FLAG so reused slots don't inherit stale barrier state (bug135).
                 (*gc_box_ptr).clear_gen_old();
 
@@ -2823,6 +4140,8 @@ unsafe fn lazy_sweep_page_all_dead(
                 }
 
                 if did_reclaim {
+                    #[cfg(feature = "poison-freed")]
+                    crate::heap::poison_freed_slot(obj_ptr, block_size);
                     (*header).clear_allocated(i);
                     reclaimed += 1;
                 }
@@ -2870,7 +4189,7 @@ pub fn sweep_pending(heap: &mut LocalHeap, num_pages: usize) -> usize {
             if header.read().all_dead() {
                 lazy_sweep_page_all_dead(page_ptr, block_size, obj_count, header_size);
                 (*header).clear_all_dead();
-                std::sync::atomic::fence(Ordering::Release);
+                crate::gc::gc_fence(Ordering::Release);
                 (*header).clear_needs_sweep();
                 (*header).set_dead_count(0);
                 swept += 1;
@@ -2882,7 +4201,7 @@ pub fn sweep_pending(heap: &mut LocalHeap, num_pages: usize) -> usize {
                         (*header).set_all_dead();
                     }
                     if reclaimed == obj_count {
-                        std::sync::atomic::fence(Ordering::Release);
+                        crate::gc::gc_fence(Ordering::Release);
                         (*header).clear_needs_sweep();
                         (*header).set_dead_count(0);
                         (*header).clear_all_dead();
@@ -2898,7 +4217,7 @@ pub fn sweep_pending(heap: &mut LocalHeap, num_pages: usize) -> usize {
                     }
                     swept += 1;
                 } else if (*header).is_fully_marked() {
-                    std::sync::atomic::fence(Ordering::Release);
+                    crate::gc::gc_fence(Ordering::Release);
                     (*header).clear_needs_sweep();
                     (*header).set_dead_count(0);
                 }
@@ -2940,7 +4259,7 @@ pub unsafe fn sweep_specific_page(
         if header.read().all_dead() {
             lazy_sweep_page_all_dead(page_ptr, block_size, obj_count, header_size);
             (*header).clear_all_dead();
-            std::sync::atomic::fence(Ordering::Release);
+            crate::gc::gc_fence(Ordering::Release);
             (*header).clear_needs_sweep();
             (*header).set_dead_count(0);
             reclaimed = obj_count;
@@ -2952,7 +4271,7 @@ pub unsafe fn sweep_specific_page(
                     (*header).set_all_dead();
                 }
                 if reclaimed_count == obj_count {
-                    std::sync::atomic::fence(Ordering::Release);
+                    crate::gc::gc_fence(Ordering::Release);
                     (*header).clear_needs_sweep();
                     (*header).set_dead_count(0);
                     (*header).clear_all_dead();
@@ -2968,7 +4287,7 @@ pub unsafe fn sweep_specific_page(
                 }
                 reclaimed = reclaimed_count;
             } else if (*header).is_fully_marked() {
-                std::sync::atomic::fence(Ordering::Release);
+                crate::gc::gc_fence(Ordering::Release);
                 (*header).clear_needs_sweep();
                 (*header).set_dead_count(0);
             }
@@ -3015,6 +4334,9 @@ impl GcVisitor {
             kind,
             worklist: Vec::with_capacity(1024),
             objects_marked: 0,
+            young_refs: 0,
+            discovered: Vec::new(),
+            order: crate::trace::TraceOrder::DEFAULT,
         }
     }
 
@@ -3061,7 +4383,7 @@ impl GcVisitor {
                     continue;
                 }
 
-                ((*ptr.as_ptr()).trace_fn)(ptr.as_ptr().cast(), self);
+                (GcBox::trace_fn_of(ptr.as_ptr()))(ptr.as_ptr().cast(), self);
             }
         }
     }
@@ -3081,6 +4403,19 @@ impl Visitor for GcVisitor {
         if !raw.is_null() {
             let ptr = raw.cast::<crate::ptr::GcBox<()>>();
 
+            if self.kind == VisitorKind::Remember {
+                unsafe { remember_young_ref(std::ptr::NonNull::new_unchecked(ptr), self) };
+                return;
+            }
+
+            if self.kind == VisitorKind::Traverse {
+                self.discovered.push((
+                    unsafe { std::ptr::NonNull::new_unchecked(ptr) },
+                    std::any::TypeId::of::<T>(),
+                ));
+                return;
+            }
+
             unsafe {
                 let ptr_addr = ptr as *const u8;
                 let header = crate::heap::ptr_to_page_header(ptr_addr);
@@ -3141,6 +4476,7 @@ mod tests {
             heap_size: 1024,
             young_size: 512,
             old_size: 512,
+            young_limit: 1024,
         };
 
         assert_eq!(info.n_gcs_dropped_since_last_collect(), 5);
@@ -3148,6 +4484,7 @@ mod tests {
         assert_eq!(info.heap_size(), 1024);
         assert_eq!(info.young_size(), 512);
         assert_eq!(info.old_size(), 512);
+        assert_eq!(info.young_limit(), 1024);
     }
 
     #[test]
@@ -3159,6 +4496,7 @@ mod tests {
             heap_size: 1024,
             young_size: 0,
             old_size: 1024,
+            young_limit: 1024,
         };
         assert!(!default_collect_condition(&info));
 
@@ -3169,6 +4507,18 @@ mod tests {
             heap_size: 1024,
             young_size: 0,
             old_size: 1024,
+            young_limit: 1024,
+        };
+        assert!(default_collect_condition(&info));
+
+        // Should collect when the young generation outgrows its limit
+        let info = CollectInfo {
+            n_gcs_dropped: 5,
+            n_gcs_existing: 10,
+            heap_size: 2048,
+            young_size: 2048,
+            old_size: 0,
+            young_limit: 1024,
         };
         assert!(default_collect_condition(&info));
     }
@@ -3289,6 +4639,62 @@ mod tests {
         clear_test_roots();
     }
 
+    #[test]
+    fn test_promotion_remembers_old_to_young_refs() {
+        struct Holder {
+            child: crate::Gc<i32>,
+            _pad: [u64; 16],
+        }
+
+        unsafe impl crate::Trace for Holder {
+            fn trace(&self, visitor: &mut impl crate::trace::Visitor) {
+                visitor.visit(&self.child);
+            }
+        }
+
+        clear_test_roots();
+        let young = crate::Gc::new(7_i32);
+        let holder = crate::Gc::new(Holder {
+            child: young.clone(),
+            _pad: [0; 16],
+        });
+        register_test_root(crate::ptr::Gc::internal_ptr(&holder));
+
+        let raw = crate::Gc::internal_ptr(&holder);
+        unsafe {
+            let header = crate::heap::ptr_to_page_header(raw);
+            let idx = crate::heap::ptr_to_object_index(raw).unwrap();
+            let young_header = crate::heap::ptr_to_page_header(crate::Gc::internal_ptr(&young));
+            assert_ne!(
+                header, young_header,
+                "holder and child must live on different pages"
+            );
+            assert_eq!(
+                (*young_header.as_ptr()).generation.load(Ordering::Acquire),
+                0
+            );
+
+            // Promote only the holder's page: its child is now an old-to-young edge
+            // that no write barrier has seen.
+            (*header.as_ptr()).generation.store(1, Ordering::Release);
+            crate::heap::with_heap(|heap| remember_promoted_page(heap, header));
+
+            assert!(
+                (*header.as_ptr()).is_dirty(idx),
+                "promoted object referencing a young object must be dirtied"
+            );
+            assert!(
+                (*header.as_ptr()).is_dirty_listed(),
+                "page of a remembered object must be on the dirty list"
+            );
+        }
+
+        drop(young);
+        crate::heap::with_heap(collect_minor);
+        assert_eq!(*holder.child, 7);
+        clear_test_roots();
+    }
+
     #[test]
     fn test_metrics() {
         let x = crate::Gc::new(42i32);
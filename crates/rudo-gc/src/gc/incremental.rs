@@ -29,6 +29,7 @@ pub use crate::ptr::GcBox;
 pub const DEFAULT_INCREMENT_SIZE: usize = 1000;
 pub const DEFAULT_MAX_DIRTY_PAGES: usize = 1000;
 pub const DEFAULT_REMEMBERED_BUFFER_LEN: usize = 32;
+pub const DEFAULT_SATB_BUFFER_LEN: usize = 32;
 pub const DEFAULT_SLICE_TIMEOUT_MS: u64 = 50;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -65,6 +66,7 @@ pub enum FallbackReason {
     SliceTimeout = 2,
     WorklistUnbounded = 3,
     SatbBufferOverflow = 4,
+    NoGcGuard = 5,
 }
 
 impl FallbackReason {
@@ -75,6 +77,7 @@ impl FallbackReason {
             2 => Self::SliceTimeout,
             3 => Self::WorklistUnbounded,
             4 => Self::SatbBufferOverflow,
+            5 => Self::NoGcGuard,
             _ => Self::None,
         }
     }
@@ -85,6 +88,19 @@ impl FallbackReason {
     }
 }
 
+impl std::fmt::Display for FallbackReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "unknown reason",
+            Self::DirtyPagesExceeded => "dirty pages exceeded threshold",
+            Self::SliceTimeout => "slice timeout exceeded",
+            Self::WorklistUnbounded => "worklist grew unbounded",
+            Self::SatbBufferOverflow => "SATB buffer overflowed",
+            Self::NoGcGuard => "a NoGcGuard kept threads from stopping",
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarkSliceResult {
     Pending {
@@ -106,6 +122,10 @@ pub struct IncrementalConfig {
     pub increment_size: usize,
     pub max_dirty_pages: usize,
     pub remembered_buffer_len: usize,
+    /// Old values a thread's SATB buffer holds before it overflows and
+    /// marking falls back, for heaps created after the config is set.
+    /// Resize the current thread's with [`set_satb_buffer_capacity`].
+    pub satb_buffer_len: usize,
     pub slice_timeout_ms: u64,
 }
 
@@ -116,6 +136,7 @@ impl Default for IncrementalConfig {
             increment_size: DEFAULT_INCREMENT_SIZE,
             max_dirty_pages: DEFAULT_MAX_DIRTY_PAGES,
             remembered_buffer_len: DEFAULT_REMEMBERED_BUFFER_LEN,
+            satb_buffer_len: DEFAULT_SATB_BUFFER_LEN,
             slice_timeout_ms: DEFAULT_SLICE_TIMEOUT_MS,
         }
     }
@@ -479,6 +500,122 @@ pub fn is_write_barrier_active() -> bool {
     phase == MarkPhase::Marking
 }
 
+/// Estimate how much of the current incremental mark is done, from 0.0 to
+/// 1.0.
+///
+/// The estimate is objects marked over objects marked plus those still on
+/// the worklist. Objects tracing has yet to discover count on neither side,
+/// so it can move backwards as marking finds more work. Returns 0.0 while
+/// no collection is in progress and 1.0 once marking is done and the heap
+/// is being swept.
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn incremental_mark_progress() -> f32 {
+    let state = IncrementalMarkState::global();
+    match state.phase() {
+        MarkPhase::Idle => 0.0,
+        MarkPhase::Sweeping => 1.0,
+        MarkPhase::Snapshot | MarkPhase::Marking | MarkPhase::FinalMark => {
+            let marked = state.stats().objects_marked.load(Ordering::Relaxed);
+            let total = marked.saturating_add(state.worklist_len());
+            if total == 0 {
+                0.0
+            } else {
+                (marked as f64 / total as f64) as f32
+            }
+        }
+    }
+}
+
+/// Number of objects waiting on the incremental marking worklist.
+///
+/// A worklist that keeps growing between slices means the mutator is
+/// outrunning the collector; past ten times its high-water mark, marking
+/// falls back to a stop-the-world finish with
+/// [`FallbackReason::WorklistUnbounded`].
+#[must_use]
+pub fn incremental_worklist_len() -> usize {
+    IncrementalMarkState::global().worklist_len()
+}
+
+/// Why the current or most recent incremental collection fell back to a
+/// stop-the-world finish, if it did.
+///
+/// Set as soon as a fallback is requested and cleared when the next
+/// incremental collection takes its snapshot, so poll it after each
+/// collection, for example from a
+/// [`set_gc_observer`](crate::set_gc_observer) callback, to
+/// alert on collections that lost their latency bound.
+#[must_use]
+pub fn last_fallback_reason() -> Option<FallbackReason> {
+    let stats = IncrementalMarkState::global().stats();
+    stats
+        .fallback_occurred
+        .load(Ordering::Acquire)
+        .then(|| stats.fallback_reason())
+}
+
+/// Resize the current thread's SATB buffer to hold `capacity` old values
+/// before it overflows, at least one.
+///
+/// Write barriers record the value a pointer held before it is overwritten
+/// during incremental marking. A thread that overwrites more pointers
+/// between collector slices than its buffer holds makes marking fall back
+/// to a stop-the-world finish with [`FallbackReason::SatbBufferOverflow`];
+/// watch [`satb_overflow_count`] to size it. Values already buffered are
+/// kept. Threads created later use [`IncrementalConfig::satb_buffer_len`].
+pub fn set_satb_buffer_capacity(capacity: usize) {
+    crate::heap::with_heap(|heap| heap.set_satb_buffer_capacity(capacity));
+}
+
+static SATB_OVERFLOWS: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of times a SATB buffer has overflowed in this process.
+#[must_use]
+pub fn satb_overflow_count() -> usize {
+    SATB_OVERFLOWS.load(Ordering::Relaxed)
+}
+
+pub(crate) fn note_satb_overflow() {
+    SATB_OVERFLOWS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Callback registered with [`set_fallback_logger`].
+type FallbackLogger = std::sync::Arc<dyn Fn(FallbackReason) + Send + Sync>;
+
+static FALLBACK_LOGGER: Mutex<Option<FallbackLogger>> = Mutex::new(None);
+
+/// Call `f` whenever incremental marking falls back to a stop-the-world
+/// finish.
+///
+/// Fallbacks are silent otherwise. `f` runs on the collecting thread in the
+/// middle of a collection, so it must not allocate `Gc` values; logging the
+/// reason, which implements `Display`, is what it is for.
+///
+/// # Examples
+///
+/// ```
+/// rudo_gc::set_fallback_logger(|reason| {
+///     eprintln!("[GC] Incremental marking fallback: {reason}");
+/// });
+/// ```
+pub fn set_fallback_logger(f: impl Fn(FallbackReason) + Send + Sync + 'static) {
+    *FALLBACK_LOGGER.lock() = Some(std::sync::Arc::new(f));
+}
+
+/// Remove the logger set by [`set_fallback_logger`].
+pub fn clear_fallback_logger() {
+    *FALLBACK_LOGGER.lock() = None;
+}
+
+/// Pass a fallback the collector acted on to the logger, if one is set.
+pub(crate) fn log_fallback_reason(reason: FallbackReason) {
+    let logger = FALLBACK_LOGGER.lock().clone();
+    if let Some(logger) = logger {
+        logger(reason);
+    }
+}
+
 pub fn write_barrier_needed() -> bool {
     let state = IncrementalMarkState::global();
     state.is_enabled() && !state.fallback_requested() && is_write_barrier_active()
@@ -515,7 +652,7 @@ fn stop_all_mutators_for_snapshot() {
         let active = registry
             .active_count
             .load(std::sync::atomic::Ordering::Acquire);
-        std::sync::atomic::fence(std::sync::atomic::Ordering::Acquire);
+        crate::gc::gc_fence(Ordering::Acquire);
 
         if registry.threads.is_empty() {
             break;
@@ -525,6 +662,13 @@ fn stop_all_mutators_for_snapshot() {
             break;
         }
 
+        // A guarded thread skips its safe points; don't hold the others
+        // parked waiting for it.
+        if crate::gc::is_gc_paused() {
+            state.request_fallback(crate::gc::incremental::FallbackReason::NoGcGuard);
+            break;
+        }
+
         if start_time.elapsed() > timeout {
             eprintln!(
                 "[GC] WARNING: Timeout waiting for mutators to reach safepoint, \
@@ -543,7 +687,7 @@ fn resume_all_mutators() {
     for tcb in &registry.threads {
         tcb.gc_requested
             .store(false, std::sync::atomic::Ordering::Release);
-        tcb.park_cond.notify_all();
+        tcb.unpark();
     }
     drop(registry);
     crate::heap::GC_REQUESTED.store(false, std::sync::atomic::Ordering::Release);
@@ -551,7 +695,10 @@ fn resume_all_mutators() {
 
 #[inline]
 #[allow(unsafe_op_in_unsafe_fn)]
-unsafe fn mark_root_for_snapshot(ptr: NonNull<GcBox<()>>, visitor: &mut crate::trace::GcVisitor) {
+pub(crate) unsafe fn mark_root_for_snapshot(
+    ptr: NonNull<GcBox<()>>,
+    visitor: &mut crate::trace::GcVisitor,
+) {
     let ptr_addr = ptr.as_ptr() as *const u8;
     let header = crate::heap::ptr_to_page_header(ptr_addr);
 
@@ -575,12 +722,13 @@ unsafe fn mark_root_for_snapshot(ptr: NonNull<GcBox<()>>, visitor: &mut crate::t
 }
 
 pub fn execute_snapshot(heaps: &[&LocalHeap]) -> usize {
-    stop_all_mutators_for_snapshot();
-
     let state = IncrementalMarkState::global();
-    state.set_phase(MarkPhase::Snapshot);
+    // Reset before stopping: the stop may request a fallback itself.
     state.stats().reset();
     state.reset_fallback();
+    stop_all_mutators_for_snapshot();
+
+    state.set_phase(MarkPhase::Snapshot);
     state.reset_worklist();
 
     let mut visitor = crate::trace::GcVisitor::new(crate::trace::VisitorKind::Major);
@@ -627,7 +775,7 @@ pub fn execute_snapshot(heaps: &[&LocalHeap]) -> usize {
     state.set_gc_id(crate::tracing::internal::next_gc_id());
     state.set_phase(MarkPhase::Marking);
     debug_assert!(
-        write_barrier_needed(),
+        state.fallback_requested() || write_barrier_needed(),
         "Write barrier must be active before resuming mutators"
     );
     resume_all_mutators();
@@ -795,10 +943,6 @@ unsafe fn trace_and_mark_object(gc_box: NonNull<GcBox<()>>, state: &IncrementalM
     // generation will differ and we should skip this object.
     let marked_generation = (*gc_box.as_ptr()).generation();
 
-    let block_size = (*header.as_ptr()).block_size as usize;
-    let header_size = crate::heap::PageHeader::header_size(block_size);
-    let data_ptr = ptr.add(header_size);
-
     // Verify generation hasn't changed before calling trace_fn (bug426 fix).
     // If slot was reused, trace_fn would be called on wrong object data.
     if (*gc_box.as_ptr()).generation() != marked_generation {
@@ -815,7 +959,8 @@ unsafe fn trace_and_mark_object(gc_box: NonNull<GcBox<()>>, state: &IncrementalM
 
     let mut visitor = crate::trace::GcVisitor::new(crate::trace::VisitorKind::Major);
 
-    ((*gc_box.as_ptr()).trace_fn)(data_ptr, &mut visitor);
+    // `trace_fn` takes the `GcBox` itself, as in every other marker.
+    (GcBox::trace_fn_of(gc_box.as_ptr()))(ptr, &mut visitor);
 
     while let Some((child_ptr, _enqueue_generation)) = visitor.worklist.pop() {
         state.push_work(child_ptr);
@@ -887,6 +1032,20 @@ unsafe fn scan_page_for_marked_refs(
     refs_found
 }
 
+/// Trace everything on the worklist, ignoring slice budgets and fallback.
+///
+/// Only safe while every mutator is stopped. Returns the number of objects
+/// traced.
+pub(crate) fn drain_worklist() -> usize {
+    let state = IncrementalMarkState::global();
+    let mut traced = 0;
+    while let Some(ptr) = state.pop_work() {
+        unsafe { trace_and_mark_object(ptr, state) };
+        traced += 1;
+    }
+    traced
+}
+
 pub fn incremental_mark_slice(heap: &mut LocalHeap, budget: usize) -> MarkSliceResult {
     mark_slice(heap, budget)
 }
@@ -1093,7 +1252,9 @@ pub fn mark_new_object_black(ptr: *const u8) -> bool {
 
 /// Get the object index for a pointer and mark it black.
 ///
-/// Returns the index if successful, None otherwise.
+/// Returns the index if successful, None otherwise. During incremental
+/// marking, an object this call marks is also pushed onto the worklist, so
+/// the objects it references are still traced.
 ///
 /// Skips marking if the object has been swept (`!is_allocated`), preventing
 /// use-after-free when `GcThreadSafeRefMut::drop` runs concurrently with GC sweep.
@@ -1152,6 +1313,11 @@ pub unsafe fn mark_object_black(ptr: *const u8) -> Option<usize> {
                         // Slot was reused - mark belongs to new object
                         return None;
                     }
+                    // Queue it so its children are traced too; a marked
+                    // object is otherwise skipped by every later visit.
+                    if is_incremental_marking_active() {
+                        IncrementalMarkState::global().push_work(NonNull::from(gc_box));
+                    }
                     return Some(idx);
                 }
                 // Slot was swept between our check and try_mark.
@@ -977,7 +977,7 @@ pub fn worker_mark_loop(
                                 break; // Slot was reused - skip
                             }
                             marked += 1;
-                            ((*gc_box_ptr).trace_fn)(ptr_addr, &mut visitor);
+                            (GcBox::trace_fn_of(gc_box_ptr))(ptr_addr, &mut visitor);
                             break;
                         }
                         Err(()) => {}
@@ -1133,7 +1133,7 @@ pub fn worker_mark_loop_with_registry(
                                 break; // Slot was reused - skip
                             }
                             marked += 1;
-                            ((*gc_box_ptr).trace_fn)(ptr_addr, &mut visitor);
+                            (GcBox::trace_fn_of(gc_box_ptr))(ptr_addr, &mut visitor);
                             break;
                         }
                         Err(()) => {}
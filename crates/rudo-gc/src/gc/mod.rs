@@ -8,13 +8,17 @@
 //! - Lock ordering discipline for deadlock prevention
 //! - Mark phase optimizations (bitmap, ownership, push-based transfer)
 
+mod collector_thread;
 #[allow(clippy::module_inception)]
 mod gc;
 
 pub mod incremental;
 pub mod mark;
 pub mod marker;
+mod ordering;
+mod policy;
 pub mod sync;
+mod watchdog;
 pub mod worklist;
 
 #[cfg(feature = "debug-suspicious-sweep")]
@@ -30,11 +34,38 @@ pub use young_object_history::{
 #[cfg(feature = "tracing")]
 pub mod tracing;
 
+pub(crate) use ordering::{gc_fence, gc_ordering};
+
+pub(crate) use collector_thread::is_cycle_active as is_collector_thread_cycle_active;
+pub use collector_thread::{
+    collector_thread_stats, enable_collector_thread, enable_collector_thread_with,
+    is_collector_thread_enabled, CollectorThread, CollectorThreadConfig, CollectorThreadStats,
+};
+pub(crate) use policy::adapt_to_pause;
+pub use policy::{
+    current_gc_policy_config, pause_time_target, set_gc_policy, set_pause_time_target, GcPolicy,
+    GcPolicyConfig, DEFAULT_MAJOR_THRESHOLD, DEFAULT_YOUNG_LIMIT,
+};
+pub use watchdog::{
+    gc_thread_report, last_gc_watchdog_report, set_gc_watchdog_action, set_gc_watchdog_timeout,
+    GcWatchdogAction,
+};
+pub(crate) use watchdog::{watchdog_expired, watchdog_timeout};
+
 // Re-exports from gc
 pub use gc::{
-    clear_test_roots, collect, collect_full, default_collect_condition, is_collecting, mark_object,
-    mark_object_minor, notify_created_gc, notify_dropped_gc, register_test_root,
-    register_test_root_region, safepoint, set_collect_condition, set_gc_enabled, CollectInfo,
+    alloc_counters, clear_test_roots, collect, collect_full, collect_full_within, collect_if,
+    collect_large_objects, default_collect_condition, gc_critical, heap_trim_threshold,
+    is_collect_requested, is_collecting, is_deferred_finalization_enabled, is_gc_paused,
+    mark_object, mark_object_minor, notify_allocated, notify_created_gc, notify_dropped_gc,
+    register_test_root, register_test_root_region, remember_young_ref, request_collect_deferred,
+    run_deferred_finalizers, safepoint, set_collect_condition, set_collect_every_n_allocations,
+    set_deferred_finalization, set_gc_enabled, set_heap_trim_threshold, trim_heap, AllocCounters,
+    CollectInfo, CollectWithinResult, NoGcGuard,
+};
+
+pub(crate) use gc::{
+    advance_incremental_marking, collect_for_oom, collect_heap, drain_deferred_finalizers_at_exit,
 };
 
 #[cfg(any(test, feature = "test-util"))]
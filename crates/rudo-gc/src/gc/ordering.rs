@@ -0,0 +1,47 @@
+//! Memory orderings for the collector's cross-thread synchronization.
+//!
+//! The orderings that keep concurrent marking correct all go through
+//! [`gc_ordering`] and [`gc_fence`], so the `strict-ordering` feature can
+//! upgrade every one of them to `SeqCst` at once. That costs throughput but
+//! gives a single total order to reason about, which makes the collector
+//! easier to audit and to check under a thread sanitizer.
+//!
+//! The fences, and what each one orders:
+//!
+//! - **Write barriers** (`incremental_write_barrier`,
+//!   `unified_write_barrier` and `gc_cell_validate_and_barrier` in
+//!   `heap.rs`, and `GcThreadSafeCell`'s barrier): the mutator's store must be
+//!   visible before the page lands in the remembered buffer, or a marker
+//!   draining the buffer could rescan the page and miss the new edge.
+//! - **Mark to sweep** (`perform_multi_threaded_collect` and
+//!   `perform_multi_threaded_collect_full`): every mark bit set by any
+//!   marking thread must be visible before any heap is swept, or a slow
+//!   marker's bits could arrive after the sweeper freed the object.
+//! - **Lazy sweep** (`sweep_pending` and `sweep_specific_page`): the freed
+//!   slots and free list must be published before `needs_sweep` is
+//!   cleared, since allocation trusts a page without it.
+//! - **Snapshot handshake** (`stop_all_mutators_for_snapshot`): after
+//!   seeing the other mutators parked, the collector must also see every
+//!   write they made before parking, or the snapshot misses their edges.
+//!
+//! Mark bitmap updates use [`gc_ordering`] as well, since marking threads
+//! race on them.
+
+use std::sync::atomic::{fence, Ordering};
+
+/// `ordering`, or `SeqCst` with the `strict-ordering` feature.
+#[inline]
+#[must_use]
+pub const fn gc_ordering(ordering: Ordering) -> Ordering {
+    if cfg!(feature = "strict-ordering") {
+        Ordering::SeqCst
+    } else {
+        ordering
+    }
+}
+
+/// A fence with `ordering`, or `SeqCst` with the `strict-ordering` feature.
+#[inline]
+pub fn gc_fence(ordering: Ordering) {
+    fence(gc_ordering(ordering));
+}
@@ -0,0 +1,220 @@
+//! Collection scheduling presets.
+//!
+//! The heap-size thresholds that drive collection, incremental marking and
+//! the parallel marking worker cap can each be tuned separately. A
+//! [`GcPolicy`] bundles values for all of them that suit one goal, and
+//! [`set_gc_policy`] applies the bundle in one call. With a pause-time
+//! target set by [`set_pause_time_target`], the two heap-size thresholds
+//! are also adjusted after every collection.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use super::incremental::{
+    IncrementalConfig, IncrementalMarkState, DEFAULT_REMEMBERED_BUFFER_LEN, DEFAULT_SATB_BUFFER_LEN,
+};
+use super::marker::{available_parallelism, ParallelMarkConfig};
+
+/// Default heap size above which a collection is major rather than minor.
+pub const DEFAULT_MAJOR_THRESHOLD: usize = 10 * 1024 * 1024;
+
+/// Default young generation size above which the default collection
+/// condition asks for a collection.
+pub const DEFAULT_YOUNG_LIMIT: usize = 1024 * 1024;
+
+static MAJOR_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_MAJOR_THRESHOLD);
+static YOUNG_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_YOUNG_LIMIT);
+static MARK_WORKERS: AtomicUsize = AtomicUsize::new(0);
+/// Pause-time target in nanoseconds; `0` leaves the thresholds alone.
+static PAUSE_TIME_TARGET_NS: AtomicU64 = AtomicU64::new(0);
+
+/// Bounds the pause-time controller keeps the major threshold within.
+const ADAPTIVE_MAJOR_THRESHOLD: (usize, usize) = (1024 * 1024, 256 * 1024 * 1024);
+/// Bounds the pause-time controller keeps the young limit within.
+const ADAPTIVE_YOUNG_LIMIT: (usize, usize) = (64 * 1024, 32 * 1024 * 1024);
+
+/// What the collector should favour when scheduling collections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GcPolicy {
+    /// Few, large collections: high thresholds and stop-the-world marking.
+    /// Best for batch work where total run time matters more than pauses.
+    Throughput,
+    /// Frequent, short pauses: low thresholds and incremental marking in
+    /// small slices. Best for interactive work.
+    Latency,
+    /// The defaults the collector starts with.
+    #[default]
+    Balanced,
+}
+
+/// The knob values a [`GcPolicy`] sets.
+#[derive(Debug, Clone, Copy)]
+pub struct GcPolicyConfig {
+    /// Heap size in bytes above which a collection is major rather than minor.
+    pub major_threshold: usize,
+    /// Young generation size in bytes above which the default collection
+    /// condition asks for a collection.
+    pub young_limit: usize,
+    /// Incremental marking settings.
+    pub incremental: IncrementalConfig,
+    /// Upper bound on the threads used for parallel marking.
+    pub mark_workers: usize,
+}
+
+impl GcPolicy {
+    /// The knob values this policy sets.
+    #[must_use]
+    pub fn config(self) -> GcPolicyConfig {
+        match self {
+            Self::Throughput => GcPolicyConfig {
+                major_threshold: 64 * 1024 * 1024,
+                young_limit: 8 * 1024 * 1024,
+                incremental: IncrementalConfig {
+                    enabled: false,
+                    ..IncrementalConfig::default()
+                },
+                mark_workers: available_parallelism(),
+            },
+            Self::Latency => GcPolicyConfig {
+                major_threshold: 4 * 1024 * 1024,
+                young_limit: 256 * 1024,
+                incremental: IncrementalConfig {
+                    enabled: true,
+                    increment_size: 250,
+                    max_dirty_pages: 250,
+                    remembered_buffer_len: DEFAULT_REMEMBERED_BUFFER_LEN,
+                    satb_buffer_len: DEFAULT_SATB_BUFFER_LEN,
+                    slice_timeout_ms: 5,
+                },
+                mark_workers: available_parallelism(),
+            },
+            Self::Balanced => GcPolicyConfig {
+                major_threshold: DEFAULT_MAJOR_THRESHOLD,
+                young_limit: DEFAULT_YOUNG_LIMIT,
+                incremental: IncrementalConfig::default(),
+                mark_workers: ParallelMarkConfig::default().max_workers,
+            },
+        }
+    }
+}
+
+/// Configure collection scheduling for `policy`.
+///
+/// Sets the major collection threshold, the young generation limit used by
+/// [`default_collect_condition`](crate::default_collect_condition), the
+/// incremental marking configuration and the marking worker cap. The
+/// settings are global; a custom collection condition set with
+/// [`set_collect_condition`](crate::set_collect_condition) can read the young
+/// generation limit from [`CollectInfo::young_limit`](crate::CollectInfo::young_limit).
+///
+/// # Examples
+///
+/// ```
+/// use rudo_gc::{current_gc_policy_config, set_gc_policy, GcPolicy};
+///
+/// set_gc_policy(GcPolicy::Throughput);
+/// assert!(!current_gc_policy_config().incremental.enabled);
+/// set_gc_policy(GcPolicy::Balanced);
+/// ```
+pub fn set_gc_policy(policy: GcPolicy) {
+    let config = policy.config();
+    MAJOR_THRESHOLD.store(config.major_threshold, Ordering::Relaxed);
+    YOUNG_LIMIT.store(config.young_limit, Ordering::Relaxed);
+    MARK_WORKERS.store(config.mark_workers, Ordering::Relaxed);
+    IncrementalMarkState::global().set_config(config.incremental);
+}
+
+/// The knob values currently in effect.
+#[must_use]
+pub fn current_gc_policy_config() -> GcPolicyConfig {
+    GcPolicyConfig {
+        major_threshold: major_threshold(),
+        young_limit: young_limit(),
+        incremental: *IncrementalMarkState::global().config(),
+        mark_workers: mark_workers(),
+    }
+}
+
+/// Heap size above which a collection is major rather than minor.
+pub fn major_threshold() -> usize {
+    MAJOR_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Young generation size above which the default collection condition fires.
+pub fn young_limit() -> usize {
+    YOUNG_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Upper bound on the threads used for parallel marking.
+pub fn mark_workers() -> usize {
+    match MARK_WORKERS.load(Ordering::Relaxed) {
+        0 => ParallelMarkConfig::default().max_workers,
+        workers => workers,
+    }
+}
+
+/// Adapt the collection thresholds to keep pauses under `target`.
+///
+/// After each collection its pause is compared with `target`: the young
+/// generation limit for a minor collection, the major threshold for a
+/// major one. A longer pause lowers that threshold by a quarter, so the
+/// next collection has less heap to work through. A pause under half the
+/// target raises it by a quarter, letting the heap grow while collections
+/// are cheap. Both stay within fixed bounds.
+///
+/// `Duration::ZERO`, the default, turns the adjustment off and keeps the
+/// thresholds where they are. Like [`set_gc_policy`], the setting is
+/// global; setting a policy resets the thresholds the controller starts
+/// from.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use rudo_gc::{pause_time_target, set_pause_time_target};
+///
+/// set_pause_time_target(Duration::from_millis(5));
+/// assert_eq!(pause_time_target(), Duration::from_millis(5));
+/// set_pause_time_target(Duration::ZERO);
+/// ```
+pub fn set_pause_time_target(target: Duration) {
+    let nanos = u64::try_from(target.as_nanos()).unwrap_or(u64::MAX);
+    PAUSE_TIME_TARGET_NS.store(nanos, Ordering::Relaxed);
+}
+
+/// The pause-time target set by [`set_pause_time_target`], or
+/// `Duration::ZERO` when thresholds are not adapted.
+#[must_use]
+pub fn pause_time_target() -> Duration {
+    Duration::from_nanos(PAUSE_TIME_TARGET_NS.load(Ordering::Relaxed))
+}
+
+/// Feed a finished collection's pause back into the thresholds.
+pub fn adapt_to_pause(metrics: &crate::metrics::GcMetrics) {
+    use crate::metrics::CollectionType;
+
+    let target = pause_time_target();
+    if target.is_zero() {
+        return;
+    }
+    let (threshold, (min, max)) = match metrics.collection_type {
+        CollectionType::Minor => (&YOUNG_LIMIT, ADAPTIVE_YOUNG_LIMIT),
+        CollectionType::Major | CollectionType::IncrementalMajor => {
+            (&MAJOR_THRESHOLD, ADAPTIVE_MAJOR_THRESHOLD)
+        }
+        CollectionType::None => return,
+    };
+
+    let pause = metrics.duration;
+    let _ = threshold.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        let adapted = if pause > target {
+            current - current / 4
+        } else if pause < target / 2 {
+            current.saturating_add(current / 4)
+        } else {
+            return None;
+        };
+        Some(adapted.clamp(min, max))
+    });
+}
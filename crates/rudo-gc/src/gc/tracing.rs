@@ -19,6 +19,12 @@ pub fn log_incremental_slice(objects_marked: usize, dirty_pages: usize) {
     );
 }
 
+/// Log cross-thread SATB entries dropped because their slot was freed.
+#[cfg(feature = "tracing")]
+pub fn log_cross_thread_satb_skipped(skipped: usize) {
+    tracing::debug!(skipped = skipped, "cross_thread_satb_skipped");
+}
+
 /// Log a fallback to stop-the-world event.
 #[cfg(feature = "tracing")]
 pub fn log_fallback(reason: &str) {
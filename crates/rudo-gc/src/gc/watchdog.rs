@@ -0,0 +1,138 @@
+//! Watchdog for threads stuck at a safe point.
+//!
+//! A thread that reaches a safe point while a collection is requested parks
+//! until the collector resumes it. If the collector never does, because a
+//! request was left behind or the collector itself is stuck, the thread
+//! hangs silently. With a timeout set through [`set_gc_watchdog_timeout`],
+//! a thread parked for longer dumps the state of every registered thread and
+//! then aborts the process or forces all threads to resume, as chosen with
+//! [`set_gc_watchdog_action`].
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError, TryLockError};
+use std::time::Duration;
+
+use crate::heap::{
+    thread_registry, GC_REQUESTED, THREAD_STATE_EXECUTING, THREAD_STATE_INACTIVE,
+    THREAD_STATE_SAFEPOINT,
+};
+
+/// Watchdog timeout in nanoseconds; 0 disables the watchdog.
+static TIMEOUT_NS: AtomicU64 = AtomicU64::new(0);
+/// Whether the watchdog resumes threads instead of aborting.
+static RESUME: AtomicBool = AtomicBool::new(false);
+static LAST_REPORT: Mutex<Option<String>> = Mutex::new(None);
+
+/// What the watchdog does once it has reported a stuck thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GcWatchdogAction {
+    /// Abort the process, leaving the report on stderr. The default.
+    #[default]
+    Abort,
+    /// Withdraw the collection request and resume every parked thread.
+    ///
+    /// If a collector is in fact still running, resumed threads race with
+    /// it, so this is only for recovering from requests nobody will finish.
+    Resume,
+}
+
+/// Fire the watchdog when a thread stays parked at a safe point for longer
+/// than `timeout`. `Duration::ZERO`, the default, disables it.
+///
+/// Takes effect for threads that park after the call.
+pub fn set_gc_watchdog_timeout(timeout: Duration) {
+    let nanos = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+    TIMEOUT_NS.store(nanos, Ordering::Relaxed);
+}
+
+/// Choose what the watchdog does after reporting.
+pub fn set_gc_watchdog_action(action: GcWatchdogAction) {
+    RESUME.store(action == GcWatchdogAction::Resume, Ordering::Relaxed);
+}
+
+/// The report from the most recent time the watchdog fired, if it has.
+#[must_use]
+pub fn last_gc_watchdog_report() -> Option<String> {
+    LAST_REPORT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Describe the collection request and every registered thread's state.
+///
+/// Each thread is listed with its name, `THREAD_STATE_*` and whether its
+/// `gc_requested` flag is set. If the thread registry lock is held, which is
+/// itself a sign of where a hang is, the thread list is left out.
+#[must_use]
+#[allow(clippy::significant_drop_tightening)]
+pub fn gc_thread_report() -> String {
+    let mut report = String::new();
+    let _ = writeln!(
+        report,
+        "GC_REQUESTED={}",
+        GC_REQUESTED.load(Ordering::Acquire)
+    );
+    let registry = match thread_registry().try_lock() {
+        Ok(registry) => registry,
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) => {
+            report.push_str("thread registry lock is held; thread states unavailable\n");
+            return report;
+        }
+    };
+    let _ = writeln!(
+        report,
+        "gc_in_progress={} active_count={} threads={}",
+        registry.is_gc_in_progress(),
+        registry.active_count.load(Ordering::Acquire),
+        registry.threads.len()
+    );
+    for tcb in &registry.threads {
+        let state = match tcb.state.load(Ordering::Acquire) {
+            THREAD_STATE_EXECUTING => "EXECUTING",
+            THREAD_STATE_SAFEPOINT => "SAFEPOINT",
+            THREAD_STATE_INACTIVE => "INACTIVE",
+            _ => "UNKNOWN",
+        };
+        let _ = writeln!(
+            report,
+            "  {:?} {:?}: state={state} gc_requested={}",
+            tcb.thread.id(),
+            tcb.thread.name().unwrap_or("<unnamed>"),
+            tcb.gc_requested.load(Ordering::Acquire)
+        );
+    }
+    report
+}
+
+/// The watchdog timeout, or `None` while it is disabled.
+pub fn watchdog_timeout() -> Option<Duration> {
+    match TIMEOUT_NS.load(Ordering::Relaxed) {
+        0 => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
+
+/// Report that the current thread has been parked for `waited`, then take
+/// the configured action.
+///
+/// Must be called without holding the thread registry or park locks.
+#[cold]
+pub fn watchdog_expired(waited: Duration) {
+    let report = format!(
+        "rudo-gc watchdog: {:?} parked at a safe point for {waited:?} without the \
+         collection finishing\n{}",
+        std::thread::current().name().unwrap_or("<unnamed>"),
+        gc_thread_report()
+    );
+    eprint!("{report}");
+    *LAST_REPORT.lock().unwrap_or_else(PoisonError::into_inner) = Some(report);
+
+    if !RESUME.load(Ordering::Relaxed) {
+        std::process::abort();
+    }
+    crate::heap::resume_all_threads();
+    crate::heap::clear_gc_request();
+}
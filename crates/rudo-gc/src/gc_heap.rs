@@ -0,0 +1,206 @@
+//! Independently collected heaps.
+//!
+//! Every thread allocates `Gc` objects in its own `LocalHeap`, and every
+//! collection covers all of them. A [`GcHeap`] owns a separate `LocalHeap`
+//! that is not registered with any thread: thread collections never sweep
+//! it, and [`GcHeap::collect`] sweeps nothing else. This lets an embedder
+//! give each isolated context (a script instance, a tenant) its own heap and
+//! collect it on its own schedule.
+//!
+//! The collector only follows edges within one heap, so objects in different
+//! heaps must not reference each other. [`GcHeap::alloc_gc`] rejects values
+//! holding `Gc`s from elsewhere, and in debug builds [`GcHeap::collect`]
+//! checks the surviving objects as well.
+
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use crate::heap::LocalHeap;
+#[cfg(debug_assertions)]
+use crate::heap::PageHeader;
+use crate::ptr::GcBox;
+use crate::trace::{GcVisitor, Trace, VisitorKind};
+use crate::Gc;
+
+/// A heap whose objects are allocated and collected separately from the
+/// current thread's heap.
+///
+/// A `GcHeap` belongs to the thread that created it. `Gc` handles into it are
+/// ordinary `Gc`s: [`collect`](Self::collect) finds them wherever a full
+/// collection would, on any thread's stack or inside objects of other heaps.
+///
+/// Dropping a `GcHeap` collects it one last time. If objects are still alive
+/// then, its pages are leaked rather than freed, so remaining handles stay
+/// valid.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, GcHeap};
+///
+/// let isolate = GcHeap::new();
+/// let value = isolate.alloc_gc(42);
+/// assert!(isolate.contains(&value));
+/// assert!(!isolate.contains(&Gc::new(7)));
+///
+/// isolate.collect();
+/// assert_eq!(*value, 42);
+/// ```
+pub struct GcHeap {
+    heap: UnsafeCell<LocalHeap>,
+    /// Pages record their owning thread, so the heap must stay on it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl GcHeap {
+    /// Create an empty heap.
+    #[must_use]
+    pub fn new() -> Self {
+        let heap = LocalHeap::new();
+        // `collect` marks it along with the registered heaps, so it does not
+        // need a registered thread of its own.
+        #[cfg(debug_assertions)]
+        heap.exempt_owns_pages();
+        Self {
+            heap: UnsafeCell::new(heap),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Allocate `value` in this heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` holds a `Gc` allocated in another heap.
+    #[track_caller]
+    pub fn alloc_gc<T: Trace + 'static>(&self, value: T) -> Gc<T> {
+        let mut visitor = GcVisitor::new(VisitorKind::Traverse);
+        value.trace(&mut visitor);
+        // SAFETY: `GcHeap` is `!Sync` and no reference to the heap outlives
+        // the methods on `self`.
+        let heap = unsafe { &*self.heap.get() };
+        assert!(
+            visitor
+                .discovered
+                .iter()
+                .all(|&(target, _)| owns_object(heap, target)),
+            "GcHeap::alloc_gc: value references a Gc from another heap"
+        );
+
+        // Collections would see the thread's heap replaced by this one.
+        let _no_gc = crate::gc::NoGcGuard::new();
+        let _swap = SwapGuard::new(&self.heap);
+        Gc::new(value)
+    }
+
+    /// Collect this heap, leaving every other heap untouched.
+    ///
+    /// Marking stops the other threads and starts from the roots of a full
+    /// collection, so only this heap's unreachable objects are freed. Does
+    /// nothing while a [`NoGcGuard`](crate::NoGcGuard) is alive, collection is
+    /// disabled, or an incremental collection is in progress.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a surviving object references a `Gc` from
+    /// another heap.
+    pub fn collect(&self) {
+        // SAFETY: see `alloc_gc`.
+        let heap = unsafe { &mut *self.heap.get() };
+        crate::gc::collect_heap(heap);
+        #[cfg(debug_assertions)]
+        assert_no_foreign_edges(heap);
+    }
+
+    /// Whether `gc` was allocated in this heap.
+    #[must_use]
+    pub fn contains<T: Trace>(&self, gc: &Gc<T>) -> bool {
+        // SAFETY: see `alloc_gc`.
+        let heap = unsafe { &*self.heap.get() };
+        NonNull::new(gc.raw_ptr().cast::<GcBox<()>>()).is_some_and(|ptr| owns_object(heap, ptr))
+    }
+}
+
+impl Default for GcHeap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for GcHeap {
+    fn drop(&mut self) {
+        let heap = self.heap.get_mut();
+        crate::gc::collect_heap(heap);
+        if heap.has_allocated_objects() {
+            std::mem::forget(std::mem::take(heap));
+        }
+    }
+}
+
+impl std::fmt::Debug for GcHeap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcHeap").finish_non_exhaustive()
+    }
+}
+
+/// Swaps a `GcHeap`'s heap into the current thread for as long as it lives.
+struct SwapGuard<'a> {
+    heap: &'a UnsafeCell<LocalHeap>,
+}
+
+impl<'a> SwapGuard<'a> {
+    fn new(heap: &'a UnsafeCell<LocalHeap>) -> Self {
+        // SAFETY: see `GcHeap::alloc_gc`.
+        crate::heap::with_heap(|local| local.swap_contents(unsafe { &mut *heap.get() }));
+        Self { heap }
+    }
+}
+
+impl Drop for SwapGuard<'_> {
+    fn drop(&mut self) {
+        // SAFETY: see `GcHeap::alloc_gc`.
+        crate::heap::with_heap(|local| local.swap_contents(unsafe { &mut *self.heap.get() }));
+    }
+}
+
+/// Whether `ptr` is the address of an object allocated in `heap`.
+fn owns_object(heap: &LocalHeap, ptr: NonNull<GcBox<()>>) -> bool {
+    // SAFETY: `find_gc_box_from_ptr` accepts arbitrary addresses.
+    unsafe { crate::heap::find_gc_box_from_ptr(heap, ptr.as_ptr().cast::<u8>()) }
+        .is_some_and(|found| found == ptr)
+}
+
+/// Panic if a live object in `heap` references one outside it.
+#[cfg(debug_assertions)]
+fn assert_no_foreign_edges(heap: &LocalHeap) {
+    let mut visitor = GcVisitor::new(VisitorKind::Traverse);
+    for page_ptr in heap.all_pages() {
+        // SAFETY: Page pointers in the heap are always valid.
+        let header = unsafe { page_ptr.as_ref() };
+        let header_size = if header.is_large_object() {
+            header.header_size as usize
+        } else {
+            PageHeader::header_size(header.block_size as usize)
+        };
+        for i in (0..header.obj_count as usize).filter(|&i| header.is_allocated(i)) {
+            let obj_ptr = page_ptr.as_ptr() as usize + header_size + i * header.block_size as usize;
+            let gc_box = obj_ptr as *mut GcBox<()>;
+            // SAFETY: allocated slots hold a `GcBox`; dead ones are skipped
+            // before their value is traced.
+            unsafe {
+                if (*gc_box).has_dead_flag() {
+                    continue;
+                }
+                (GcBox::trace_fn_of(gc_box))(obj_ptr as *const u8, &mut visitor);
+            }
+        }
+    }
+    assert!(
+        visitor
+            .discovered
+            .iter()
+            .all(|&(target, _)| owns_object(heap, target)),
+        "GcHeap::collect: an object references a Gc from another heap"
+    );
+}
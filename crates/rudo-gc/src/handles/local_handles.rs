@@ -150,6 +150,13 @@ pub struct HandleScopeData {
     pub(crate) level: u32,
     #[cfg(debug_assertions)]
     pub(crate) sealed_level: u32,
+    /// Number of scopes dropped so far on this thread.
+    #[cfg(debug_assertions)]
+    pub(crate) generation: u64,
+    /// `generation` at the time the scope at each level (index `level - 1`)
+    /// was opened.
+    #[cfg(debug_assertions)]
+    opened_at: Vec<u64>,
 }
 
 impl HandleScopeData {
@@ -161,6 +168,10 @@ impl HandleScopeData {
             level: 0,
             #[cfg(debug_assertions)]
             sealed_level: 0,
+            #[cfg(debug_assertions)]
+            generation: 0,
+            #[cfg(debug_assertions)]
+            opened_at: Vec::new(),
         }
     }
 
@@ -186,6 +197,77 @@ impl HandleScopeData {
     pub const fn is_sealed(&self) -> bool {
         false
     }
+
+    /// Records that a scope was just opened at the current level.
+    #[cfg(debug_assertions)]
+    pub(crate) fn scope_opened(&mut self) {
+        self.opened_at.truncate(self.level as usize - 1);
+        self.opened_at.push(self.generation);
+    }
+
+    /// Records that the scope at the current level is being dropped, which
+    /// invalidates every handle created in it.
+    #[cfg(debug_assertions)]
+    pub(crate) fn scope_closed(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Returns `true` if a handle stamped at `level` with `generation` still
+    /// belongs to an open scope.
+    ///
+    /// A scope reopened at the same level after a drop was opened at a later
+    /// generation than any handle from its predecessor.
+    #[cfg(debug_assertions)]
+    pub(crate) fn is_handle_live(&self, level: u32, generation: u64) -> bool {
+        level <= self.level
+            && level
+                .checked_sub(1)
+                .and_then(|index| self.opened_at.get(index as usize))
+                .is_some_and(|&opened| opened <= generation)
+    }
+}
+
+/// Debug-build record of which scope a `Handle` was created in, checked on
+/// every access.
+#[cfg(debug_assertions)]
+#[derive(Clone, Copy, Debug)]
+pub struct HandleStamp {
+    handles: *const LocalHandles,
+    level: u32,
+    generation: u64,
+}
+
+#[cfg(debug_assertions)]
+impl HandleStamp {
+    /// Stamps a handle allocated in the scope at `level`.
+    pub(crate) fn new(handles: *const LocalHandles, level: u32) -> Self {
+        // SAFETY: `handles` is the current thread's handle storage.
+        let generation = unsafe { (*handles).scope_data.generation };
+        Self {
+            handles,
+            level,
+            generation,
+        }
+    }
+
+    /// Stamps a handle allocated in the current scope.
+    pub(crate) fn current(handles: *const LocalHandles) -> Self {
+        // SAFETY: `handles` is the current thread's handle storage.
+        let level = unsafe { (*handles).scope_data.level };
+        Self::new(handles, level)
+    }
+
+    /// Panics if the scope the handle was created in has been dropped.
+    #[track_caller]
+    pub(crate) fn check(&self) {
+        // SAFETY: handles are `!Send`, so this is still the creating
+        // thread's handle storage.
+        let scope_data = unsafe { &(*self.handles).scope_data };
+        assert!(
+            scope_data.is_handle_live(self.level, self.generation),
+            "handle used after its scope was dropped"
+        );
+    }
 }
 
 impl Default for HandleScopeData {
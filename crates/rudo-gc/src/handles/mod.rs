@@ -13,6 +13,8 @@
 //!
 //! - [`HandleScope`] - Basic scope for creating handles with compile-time lifetime binding
 //! - [`Handle`] - A GC reference bound to a specific scope's lifetime
+//! - [`GcIter`] - Iterates a rooted GC collection, yielding handles
+//! - [`GcChain`] - Walks a linked GC structure, yielding handles
 //! - [`EscapeableHandleScope`] - Allows handles to escape to an outer scope
 //! - [`MaybeHandle`] - Optional handle pattern for nullable GC references
 //! - [`SealedHandleScope`] - Debug-only scope that prevents handle creation
@@ -60,12 +62,17 @@
 mod r#async;
 mod cross_thread;
 mod local_handles;
+mod root;
+mod thread_local_root;
+mod weak_handle;
 
 #[cfg(test)]
 mod tests;
 
 pub(crate) use cross_thread::TcbRootRemoveGuard;
 pub use cross_thread::{GcHandle, WeakCrossThreadHandle};
+#[cfg(debug_assertions)]
+use local_handles::HandleStamp;
 pub use local_handles::{
     HandleBlock, HandleScopeData, HandleSlot, LocalHandles, HANDLE_BLOCK_SIZE,
 };
@@ -73,6 +80,10 @@ pub use r#async::{
     AsyncGcHandle, AsyncHandle, AsyncHandleGuard, AsyncHandleScope, AsyncScopeData,
     AsyncScopeEntry, GcScope,
 };
+pub use root::GcRoot;
+pub use thread_local_root::GcThreadLocalRoot;
+pub(crate) use weak_handle::reap_dead_weak_handles;
+pub use weak_handle::WeakHandle;
 
 use std::cell::Cell;
 use std::marker::PhantomData;
@@ -166,6 +177,8 @@ impl<'env> HandleScope<'env> {
             scope_data.level = prev_level
                 .checked_add(1)
                 .expect("HandleScope level overflow");
+            #[cfg(debug_assertions)]
+            scope_data.scope_opened();
             (prev_next, prev_limit, prev_level)
         };
 
@@ -222,10 +235,141 @@ impl<'env> HandleScope<'env> {
 
         Handle {
             slot,
+            #[cfg(debug_assertions)]
+            stamp: HandleStamp::current(local_handles),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a `Handle` to the target of a weak reference, if it is still
+    /// alive.
+    ///
+    /// The strong count is only raised while the handle slot is filled; once
+    /// the handle exists, the scope roots the target and the count is back
+    /// where it was. Useful when walking a graph whose back-edges are weak.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the target has been collected or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    /// use rudo_gc::handles::HandleScope;
+    ///
+    /// let gc = Gc::new(42);
+    /// let weak = Gc::downgrade(&gc);
+    /// let tcb = rudo_gc::heap::current_thread_control_block().unwrap();
+    /// let scope = HandleScope::new(&tcb);
+    ///
+    /// let handle = scope.try_handle_weak(&weak).unwrap();
+    /// assert_eq!(*handle, 42);
+    /// assert_eq!(Gc::ref_count(&gc).get(), 1);
+    /// ```
+    #[inline]
+    pub fn try_handle_weak<'scope, T: Trace + 'static>(
+        &'scope self,
+        weak: &crate::Weak<T>,
+    ) -> Option<Handle<'scope, T>> {
+        // Upgrading validates the target and pins it while the slot is set;
+        // the temporary `Gc` is dropped once the handle roots it.
+        let gc = weak.upgrade()?;
+        Some(self.handle(&gc))
+    }
+
+    /// Iterates over a GC-allocated collection, yielding a `Handle` per element.
+    ///
+    /// The collection itself is rooted by a handle in this scope, so its
+    /// elements stay reachable for the whole loop even if the body triggers a
+    /// collection. The `Gc` stays borrowed for as long as the iterator lives,
+    /// which keeps its reference count from dropping the collection early.
+    /// Each element is re-read from the collection on every step, so no
+    /// borrow of it is held while the loop body runs.
+    ///
+    /// Every yielded handle takes a slot in this scope's handle blocks rather
+    /// than cloning the `Gc`, so iteration does not allocate per element. The
+    /// slots are only released when the scope is dropped; for very long loops,
+    /// open a nested scope around the body if the handles are not kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    /// use rudo_gc::handles::HandleScope;
+    ///
+    /// let tcb = rudo_gc::heap::current_thread_control_block().unwrap();
+    /// let scope = HandleScope::new(&tcb);
+    /// let list = Gc::new(vec![Gc::new(1), Gc::new(2), Gc::new(3)]);
+    ///
+    /// let mut sum = 0;
+    /// for item in scope.iter(&list) {
+    ///     rudo_gc::collect_full();
+    ///     sum += *item;
+    /// }
+    /// assert_eq!(sum, 6);
+    /// ```
+    #[inline]
+    pub fn iter<'scope, C, T>(&'scope self, collection: &'scope Gc<C>) -> GcIter<'scope, 'env, C, T>
+    where
+        C: Trace + AsRef<[Gc<T>]> + 'static,
+        T: Trace + 'static,
+    {
+        GcIter {
+            scope: self,
+            collection: self.handle(collection),
+            index: 0,
             _marker: PhantomData,
         }
     }
 
+    /// Walks a linked structure, yielding a `Handle` per node.
+    ///
+    /// Starts at `start` and follows `next` until it returns `None`. The
+    /// current node is held through a handle, so the rest of the chain stays
+    /// reachable from it even if the loop body triggers a collection, and
+    /// every yielded handle stays valid for the rest of the scope.
+    ///
+    /// As with [`iter`](Self::iter), each node takes a slot that is only
+    /// released when the scope is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, Trace};
+    /// use rudo_gc::handles::HandleScope;
+    ///
+    /// #[derive(Trace)]
+    /// struct Node { value: i32, next: Option<Gc<Node>> }
+    ///
+    /// let tail = Gc::new(Node { value: 2, next: None });
+    /// let head = Gc::new(Node { value: 1, next: Some(tail) });
+    ///
+    /// let tcb = rudo_gc::heap::current_thread_control_block().unwrap();
+    /// let scope = HandleScope::new(&tcb);
+    /// let values: Vec<i32> = scope
+    ///     .iter_chain(&head, |node| node.next.as_ref())
+    ///     .map(|node| node.value)
+    ///     .collect();
+    /// assert_eq!(values, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn iter_chain<'scope, T, F>(
+        &'scope self,
+        start: &Gc<T>,
+        next: F,
+    ) -> GcChain<'scope, 'env, T, F>
+    where
+        T: Trace + 'static,
+        F: FnMut(&T) -> Option<&Gc<T>>,
+    {
+        GcChain {
+            scope: self,
+            next_node: Some(self.handle(start)),
+            next,
+        }
+    }
+
     /// Returns the current nesting level of this scope.
     ///
     /// The root scope has level 1, and each nested scope increments by 1.
@@ -248,6 +392,8 @@ impl Drop for HandleScope<'_> {
         unsafe {
             let handles = &mut *local_handles;
             let scope_data = handles.scope_data_mut();
+            #[cfg(debug_assertions)]
+            scope_data.scope_closed();
             scope_data.next = self.prev_next;
             scope_data.limit = self.prev_limit;
             scope_data.level = self.prev_level;
@@ -283,6 +429,8 @@ impl Drop for HandleScope<'_> {
 /// ```
 pub struct Handle<'scope, T: Trace + 'static> {
     slot: *const HandleSlot,
+    #[cfg(debug_assertions)]
+    stamp: HandleStamp,
     _marker: PhantomData<(&'scope (), *const T)>,
 }
 
@@ -296,10 +444,13 @@ impl<'scope, T: Trace + 'static> Handle<'scope, T> {
     /// # Safety
     ///
     /// The handle must be valid (the scope it belongs to must not have been dropped).
-    /// This is guaranteed by the lifetime system when used correctly.
+    /// This is guaranteed by the lifetime system when used correctly. Debug
+    /// builds panic if a handle that escaped it anyway is used.
     #[inline]
     #[track_caller]
     pub fn get(&self) -> &T {
+        #[cfg(debug_assertions)]
+        self.stamp.check();
         unsafe {
             let slot = &*self.slot;
             let gc_box_ptr = slot.as_ptr() as *const GcBox<T>;
@@ -495,6 +646,96 @@ impl<T: Trace + 'static + std::fmt::Display> std::fmt::Display for Handle<'_, T>
     }
 }
 
+/// An iterator over a rooted GC collection, created by [`HandleScope::iter`].
+///
+/// Yields a [`Handle`] for each element. The collection is held through a
+/// handle rather than a `Gc`, so it stays rooted for as long as the scope
+/// lives, and elements are looked up by index on each call to `next`.
+pub struct GcIter<'scope, 'env, C: Trace + 'static, T: Trace + 'static> {
+    scope: &'scope HandleScope<'env>,
+    collection: Handle<'scope, C>,
+    index: usize,
+    _marker: PhantomData<*const T>,
+}
+
+impl<'scope, C, T> Iterator for GcIter<'scope, '_, C, T>
+where
+    C: Trace + AsRef<[Gc<T>]> + 'static,
+    T: Trace + 'static,
+{
+    type Item = Handle<'scope, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let collection = self.collection;
+        let gc = collection.get().as_ref().get(self.index)?;
+        self.index += 1;
+        Some(self.scope.handle(gc))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self
+            .collection
+            .get()
+            .as_ref()
+            .len()
+            .saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<C, T> ExactSizeIterator for GcIter<'_, '_, C, T>
+where
+    C: Trace + AsRef<[Gc<T>]> + 'static,
+    T: Trace + 'static,
+{
+}
+
+impl<C: Trace + 'static, T: Trace + 'static> std::fmt::Debug for GcIter<'_, '_, C, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcIter")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator along a linked GC structure, created by
+/// [`HandleScope::iter_chain`].
+///
+/// The node to yield next is already held by a handle, so it is rooted
+/// between calls to `next`.
+pub struct GcChain<'scope, 'env, T: Trace + 'static, F> {
+    scope: &'scope HandleScope<'env>,
+    next_node: Option<Handle<'scope, T>>,
+    next: F,
+}
+
+impl<'scope, T, F> Iterator for GcChain<'scope, '_, T, F>
+where
+    T: Trace + 'static,
+    F: FnMut(&T) -> Option<&Gc<T>>,
+{
+    type Item = Handle<'scope, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next_node.take()?;
+        self.next_node = (self.next)(node.get()).map(|gc| self.scope.handle(gc));
+        Some(node)
+    }
+}
+
+impl<T: Trace + 'static, F> std::iter::FusedIterator for GcChain<'_, '_, T, F> where
+    F: FnMut(&T) -> Option<&Gc<T>>
+{
+}
+
+impl<T: Trace + 'static, F> std::fmt::Debug for GcChain<'_, '_, T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcChain")
+            .field("done", &self.next_node.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
 /// A handle scope that allows handles to escape to an outer scope.
 ///
 /// `EscapeableHandleScope` extends `HandleScope` with the ability to
@@ -503,8 +744,11 @@ impl<T: Trace + 'static + std::fmt::Display> std::fmt::Display for Handle<'_, T>
 ///
 /// # Single-Use Constraint
 ///
-/// The `escape()` method can only be called once per `EscapeableHandleScope`.
-/// Attempting to escape a second time will panic.
+/// Either `escape()` or `escape_many()` can be called once per
+/// `EscapeableHandleScope`. Attempting to escape a second time will panic.
+/// To escape several handles at once, create the scope with
+/// [`with_capacity`](Self::with_capacity) and use
+/// [`escape_many`](Self::escape_many).
 ///
 /// # Example
 ///
@@ -535,7 +779,7 @@ impl<T: Trace + 'static + std::fmt::Display> std::fmt::Display for Handle<'_, T>
 pub struct EscapeableHandleScope<'env> {
     inner: HandleScope<'env>,
     escaped: Cell<bool>,
-    escape_slot: *mut HandleSlot,
+    escape_slots: Vec<*mut HandleSlot>,
     #[cfg(debug_assertions)]
     #[allow(dead_code)]
     parent_level: u32,
@@ -552,10 +796,27 @@ impl<'env> EscapeableHandleScope<'env> {
     /// * `tcb` - The thread control block for the current thread
     #[inline]
     pub fn new(tcb: &'env ThreadControlBlock) -> Self {
+        Self::with_capacity(tcb, 1)
+    }
+
+    /// Creates a new `EscapeableHandleScope` that can escape up to
+    /// `capacity` handles through [`escape_many`](Self::escape_many).
+    ///
+    /// The escape slots must be pre-allocated in the parent scope because
+    /// handles allocated once this scope is active are released with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tcb` - The thread control block for the current thread
+    /// * `capacity` - The maximum number of handles that can be escaped
+    #[inline]
+    pub fn with_capacity(tcb: &'env ThreadControlBlock, capacity: usize) -> Self {
         let local_handles = tcb.local_handles_ptr();
 
-        // Pre-allocate escape slot in parent scope
-        let escape_slot = unsafe { (*local_handles).allocate() };
+        // Pre-allocate escape slots in parent scope
+        let escape_slots = (0..capacity)
+            .map(|_| unsafe { (*local_handles).allocate() })
+            .collect();
 
         #[cfg(debug_assertions)]
         let parent_level = unsafe { (*local_handles).scope_data().level };
@@ -565,7 +826,7 @@ impl<'env> EscapeableHandleScope<'env> {
         Self {
             inner,
             escaped: Cell::new(false),
-            escape_slot,
+            escape_slots,
             #[cfg(debug_assertions)]
             parent_level,
         }
@@ -585,6 +846,17 @@ impl<'env> EscapeableHandleScope<'env> {
         self.inner.handle(gc)
     }
 
+    /// Creates a handle within this scope to the target of a weak reference.
+    ///
+    /// See [`HandleScope::try_handle_weak`].
+    #[inline]
+    pub fn try_handle_weak<'scope, T: Trace + 'static>(
+        &'scope self,
+        weak: &crate::Weak<T>,
+    ) -> Option<Handle<'scope, T>> {
+        self.inner.try_handle_weak(weak)
+    }
+
     /// Escapes a handle to the parent scope.
     ///
     /// The handle returned is bound to the parent scope's lifetime
@@ -601,7 +873,8 @@ impl<'env> EscapeableHandleScope<'env> {
     ///
     /// # Panics
     ///
-    /// - Panics if `escape()` has already been called on this scope
+    /// - Panics if `escape()` or `escape_many()` has already been called on this scope
+    /// - Panics if the scope was created with a capacity of zero
     /// - Panics in debug mode if the parent scope level doesn't match
     ///
     /// # Example
@@ -657,18 +930,108 @@ impl<'env> EscapeableHandleScope<'env> {
             }
         }
 
+        let Some(&escape_slot) = self.escape_slots.first() else {
+            panic!("EscapeableHandleScope::escape() called on a scope with no escape slots");
+        };
+
         self.escaped.set(true);
 
         unsafe {
             let src_slot = &*handle.slot;
-            (*self.escape_slot).set(src_slot.as_ptr());
+            (*escape_slot).set(src_slot.as_ptr());
         }
 
         Handle {
-            slot: self.escape_slot,
+            slot: escape_slot,
+            #[cfg(debug_assertions)]
+            stamp: HandleStamp::new(self.inner.tcb.local_handles_ptr(), self.parent_level),
             _marker: PhantomData,
         }
     }
+
+    /// Escapes several handles to the parent scope at once.
+    ///
+    /// Each handle is copied into one of the escape slots pre-allocated by
+    /// [`with_capacity`](Self::with_capacity), in order. As with
+    /// [`escape`](Self::escape), the returned handles are bound to the
+    /// parent scope's lifetime.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The parent scope to escape to
+    /// * `handles` - The handles to escape
+    ///
+    /// # Returns
+    ///
+    /// Handles bound to the parent scope, in the same order as `handles`
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `escape()` or `escape_many()` has already been called on this scope
+    /// - Panics if `handles` is longer than the scope's capacity
+    /// - Panics in debug mode if the parent scope level doesn't match
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, Trace};
+    /// use rudo_gc::handles::{HandleScope, EscapeableHandleScope};
+    ///
+    /// let tcb = rudo_gc::heap::current_thread_control_block().unwrap();
+    /// let outer = HandleScope::new(&tcb);
+    /// let values: Vec<Gc<i32>> = (1..=3).map(Gc::new).collect();
+    ///
+    /// let escaped = {
+    ///     let escape_scope = EscapeableHandleScope::with_capacity(&tcb, values.len());
+    ///     let handles: Vec<_> = values.iter().map(|gc| escape_scope.handle(gc)).collect();
+    ///     escape_scope.escape_many(&outer, &handles)
+    /// };
+    ///
+    /// assert_eq!(escaped.iter().map(|h| **h).sum::<i32>(), 6);
+    /// ```
+    #[inline]
+    pub fn escape_many<'parent, T: Trace + 'static>(
+        &self,
+        _parent: &'parent HandleScope<'_>,
+        handles: &[Handle<'_, T>],
+    ) -> Vec<Handle<'parent, T>> {
+        if self.escaped.get() {
+            panic!("EscapeableHandleScope::escape_many() can only be called once");
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            if self.parent_level + 1 != self.inner.level() {
+                panic!("escape_many() called with incorrect parent scope");
+            }
+        }
+
+        assert!(
+            handles.len() <= self.escape_slots.len(),
+            "EscapeableHandleScope::escape_many() given {} handles but has capacity for {}",
+            handles.len(),
+            self.escape_slots.len()
+        );
+
+        self.escaped.set(true);
+
+        handles
+            .iter()
+            .zip(&self.escape_slots)
+            .map(|(handle, &escape_slot)| {
+                unsafe {
+                    let src_slot = &*handle.slot;
+                    (*escape_slot).set(src_slot.as_ptr());
+                }
+                Handle {
+                    slot: escape_slot,
+                    #[cfg(debug_assertions)]
+                    stamp: HandleStamp::new(self.inner.tcb.local_handles_ptr(), self.parent_level),
+                    _marker: PhantomData,
+                }
+            })
+            .collect()
+    }
 }
 
 /// An optional handle pattern for nullable GC references.
@@ -692,6 +1055,8 @@ impl<'env> EscapeableHandleScope<'env> {
 /// ```
 pub struct MaybeHandle<'scope, T: Trace + 'static> {
     slot: *const HandleSlot,
+    #[cfg(debug_assertions)]
+    stamp: Option<HandleStamp>,
     _marker: PhantomData<(&'scope (), *const T)>,
 }
 
@@ -705,6 +1070,8 @@ impl<'scope, T: Trace + 'static> MaybeHandle<'scope, T> {
     pub const fn empty() -> Self {
         Self {
             slot: std::ptr::null(),
+            #[cfg(debug_assertions)]
+            stamp: None,
             _marker: PhantomData,
         }
     }
@@ -722,6 +1089,8 @@ impl<'scope, T: Trace + 'static> MaybeHandle<'scope, T> {
     pub fn from_handle(handle: Handle<'scope, T>) -> Self {
         Self {
             slot: handle.slot,
+            #[cfg(debug_assertions)]
+            stamp: Some(handle.stamp),
             _marker: PhantomData,
         }
     }
@@ -748,6 +1117,8 @@ impl<'scope, T: Trace + 'static> MaybeHandle<'scope, T> {
         } else {
             Some(Handle {
                 slot: self.slot,
+                #[cfg(debug_assertions)]
+                stamp: self.stamp.expect("non-empty MaybeHandle has a stamp"),
                 _marker: PhantomData,
             })
         }
@@ -0,0 +1,134 @@
+//! Persistent roots that do not depend on where the `Gc` is stored.
+//!
+//! Conservative stack scanning only finds `Gc` pointers on the stack and in
+//! registers. A `Gc` kept in a heap-allocated structure, or handed through an
+//! FFI boundary, is invisible to it. [`GcRoot`] registers its `Gc` in the
+//! owning thread's control block, next to the cross-thread handle roots, so
+//! the collector marks it on every collection for as long as the root lives.
+//!
+//! This is the synchronous counterpart of the Tokio integration's
+//! `root_guard`.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Weak;
+
+use crate::heap::{HandleId, ThreadControlBlock};
+use crate::ptr::GcBox;
+use crate::trace::Trace;
+use crate::Gc;
+
+/// A `Gc` that the collector treats as a root until it is dropped.
+///
+/// The root belongs to the thread that created it and is neither `Send` nor
+/// `Sync`, even when the `Gc` it holds is. When the thread exits, its heap
+/// unregisters every root it still holds.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{collect_full, Gc, GcRoot, Trace};
+///
+/// #[derive(Trace)]
+/// struct Node { value: i32 }
+///
+/// // A boxed `Gc` is not on the stack, so only the root keeps it alive.
+/// let pinned = Box::new(GcRoot::new(Gc::new(Node { value: 7 })));
+/// collect_full();
+/// assert_eq!(pinned.value, 7);
+/// ```
+///
+/// A root cannot move to another thread:
+///
+/// ```compile_fail
+/// use rudo_gc::{Gc, GcRoot};
+///
+/// let root = GcRoot::new(Gc::new(1_u64));
+/// std::thread::spawn(move || drop(root));
+/// ```
+pub struct GcRoot<T: Trace + 'static> {
+    gc: Gc<T>,
+    id: HandleId,
+    tcb: Weak<ThreadControlBlock>,
+    /// The root entry lives in the creating thread's control block.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl<T: Trace + 'static> GcRoot<T> {
+    /// Roots `gc` for the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current thread has no GC heap.
+    #[must_use]
+    pub fn new(gc: Gc<T>) -> Self {
+        let tcb = crate::heap::current_thread_control_block()
+            .expect("GcRoot::new called outside of GC context");
+        let mut roots = tcb.cross_thread_roots.lock().unwrap();
+        let id = roots.allocate_id();
+        roots
+            .thread_local
+            .insert(id, gc.as_non_null().cast::<GcBox<()>>());
+        drop(roots);
+        Self {
+            gc,
+            id,
+            tcb: std::sync::Arc::downgrade(&tcb),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns a clone of the rooted `Gc`.
+    #[must_use]
+    pub fn get(&self) -> Gc<T> {
+        self.gc.clone()
+    }
+
+    /// Stops rooting the `Gc` and returns it.
+    #[must_use]
+    pub fn into_inner(self) -> Gc<T> {
+        self.unregister();
+        self.gc.clone()
+    }
+
+    fn unregister(&self) {
+        // The entry is already gone if the thread's heap has been torn down.
+        if let Some(tcb) = self.tcb.upgrade() {
+            tcb.cross_thread_roots
+                .lock()
+                .unwrap()
+                .thread_local
+                .remove(&self.id);
+        }
+    }
+}
+
+impl<T: Trace + 'static> Deref for GcRoot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.gc
+    }
+}
+
+impl<T: Trace + 'static> Clone for GcRoot<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.gc.clone())
+    }
+}
+
+impl<T: Trace + 'static> Drop for GcRoot<T> {
+    fn drop(&mut self) {
+        // A no-op after `into_inner`, which unregistered already.
+        self.unregister();
+    }
+}
+
+impl<T: Trace + 'static> fmt::Debug for GcRoot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcRoot")
+            .field("ptr", &self.gc.as_non_null())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,96 @@
+//! Persistent roots for `Gc` values kept in thread-local statics.
+//!
+//! A `thread_local!` value lives outside the stack, so conservative stack
+//! scanning never sees the `Gc` pointers inside it. [`GcThreadLocalRoot`]
+//! registers its `Gc` in the owning thread's control block, next to the
+//! cross-thread handle roots, so it is marked on every collection until it is
+//! replaced, taken, or the thread exits.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use super::root::GcRoot;
+use crate::trace::Trace;
+use crate::Gc;
+
+/// A `Gc` slot for thread-local caches that the collector treats as a root.
+///
+/// Values stored here stay alive without any other reference until they are
+/// replaced or taken, or until the thread exits (its heap unregisters every
+/// thread-local root on the way out).
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{collect_full, Gc, GcThreadLocalRoot, Trace};
+///
+/// #[derive(Trace)]
+/// struct Node { value: i32 }
+///
+/// thread_local! {
+///     static CACHE: GcThreadLocalRoot<Node> = const { GcThreadLocalRoot::new() };
+/// }
+///
+/// CACHE.with(|cache| cache.set(Gc::new(Node { value: 7 })));
+/// collect_full();
+/// assert_eq!(CACHE.with(|cache| cache.get().unwrap().value), 7);
+/// ```
+pub struct GcThreadLocalRoot<T: Trace + 'static> {
+    slot: RefCell<Option<GcRoot<T>>>,
+}
+
+impl<T: Trace + 'static> GcThreadLocalRoot<T> {
+    /// Creates an empty slot.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slot: RefCell::new(None),
+        }
+    }
+
+    /// Stores `gc`, rooting it for the current thread and releasing any
+    /// previously stored value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current thread has no GC heap.
+    pub fn set(&self, gc: Gc<T>) {
+        let old = self.slot.replace(Some(GcRoot::new(gc)));
+        drop(old);
+    }
+
+    /// Returns a clone of the stored `Gc`, if any.
+    pub fn get(&self) -> Option<Gc<T>> {
+        self.slot.borrow().as_ref().map(GcRoot::get)
+    }
+
+    /// Removes the stored `Gc` and stops rooting it.
+    pub fn take(&self) -> Option<Gc<T>> {
+        self.slot.take().map(GcRoot::into_inner)
+    }
+
+    /// Returns `true` if a value is stored.
+    pub fn is_set(&self) -> bool {
+        self.slot.borrow().is_some()
+    }
+}
+
+impl<T: Trace + 'static> Default for GcThreadLocalRoot<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace + 'static> Drop for GcThreadLocalRoot<T> {
+    fn drop(&mut self) {
+        drop(self.take());
+    }
+}
+
+impl<T: Trace + 'static> fmt::Debug for GcThreadLocalRoot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcThreadLocalRoot")
+            .field("is_set", &self.is_set())
+            .finish()
+    }
+}
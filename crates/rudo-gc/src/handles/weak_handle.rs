@@ -0,0 +1,212 @@
+//! Weak entries in the cross-thread root table.
+//!
+//! A [`WeakHandle`] registers its target in the origin thread's
+//! `CrossThreadRootTable` like a `GcHandle` does, but in the `weak` map,
+//! which marking never reads. The collector is free to reclaim the target;
+//! once a sweep has finalized it, the entry is reaped and every handle to it
+//! reports the object as gone without touching its memory again.
+
+use std::marker::PhantomData;
+use std::sync::{Arc, PoisonError, Weak};
+use std::thread::ThreadId;
+
+use crate::heap::{HandleId, ThreadControlBlock};
+use crate::ptr::{GcBox, GcBoxWeakRef};
+use crate::trace::Trace;
+use crate::Gc;
+
+/// A weak reference registered in its origin thread's root table.
+///
+/// It does not keep the target alive. The handle is `Send + Sync`, so it can
+/// sit in a cache shared between threads, but only the origin thread can
+/// upgrade it (`T` may be `!Send`). Dead targets are dropped from the table
+/// after the sweep that finalized them, so a cache full of stale handles
+/// does not keep their slots allocated.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, Trace, WeakHandle};
+///
+/// #[derive(Trace)]
+/// struct Data { value: i32 }
+///
+/// let gc = Gc::new(Data { value: 42 });
+/// let weak = WeakHandle::new(&gc);
+/// assert_eq!(weak.upgrade().unwrap().value, 42);
+/// ```
+pub struct WeakHandle<T: Trace + 'static> {
+    handle_id: HandleId,
+    origin_tcb: Weak<ThreadControlBlock>,
+    origin_thread: ThreadId,
+    _marker: PhantomData<*const T>,
+}
+
+// SAFETY: The handle only holds an ID and a `Weak` to the origin TCB; `T` is
+// only reached through `upgrade`, which is restricted to the origin thread.
+unsafe impl<T: Trace + 'static> Send for WeakHandle<T> {}
+// SAFETY: See `Send`.
+unsafe impl<T: Trace + 'static> Sync for WeakHandle<T> {}
+
+impl<T: Trace + 'static> WeakHandle<T> {
+    /// Registers a weak entry for `gc` in the current thread's root table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current thread has no GC heap, or if `gc` is dead or
+    /// being dropped.
+    #[must_use]
+    pub fn new(gc: &Gc<T>) -> Self {
+        let tcb = crate::heap::current_thread_control_block()
+            .expect("WeakHandle::new called outside of GC context");
+        let ptr = gc.as_non_null();
+        // SAFETY: `gc` keeps its `GcBox` allocated.
+        unsafe {
+            let gc_box = &*ptr.as_ptr();
+            assert!(
+                !gc_box.has_dead_flag() && gc_box.dropping_state() == 0,
+                "WeakHandle::new: cannot create handle for dead or dropping Gc"
+            );
+            // The table entry owns this weak count; it keeps the slot from
+            // being reused until the entry is removed.
+            gc_box.inc_weak();
+        }
+        let handle_id = Self::register(&tcb, ptr.cast::<GcBox<()>>());
+        Self {
+            handle_id,
+            origin_tcb: Arc::downgrade(&tcb),
+            origin_thread: std::thread::current().id(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn register(tcb: &ThreadControlBlock, ptr: std::ptr::NonNull<GcBox<()>>) -> HandleId {
+        let mut roots = tcb.cross_thread_roots.lock().unwrap();
+        let id = roots.allocate_id();
+        roots.weak.insert(id, ptr);
+        id
+    }
+
+    /// Returns the thread where this handle was created.
+    #[must_use]
+    pub fn origin_thread(&self) -> ThreadId {
+        self.origin_thread
+    }
+
+    /// Returns `true` if the target has not been finalized yet.
+    ///
+    /// Can be called from any thread. Even if this returns `true`, the
+    /// target may be collected before a subsequent `upgrade`.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        let Some(tcb) = self.origin_tcb.upgrade() else {
+            return false;
+        };
+        let roots = tcb.cross_thread_roots.lock().unwrap();
+        roots.weak.get(&self.handle_id).is_some_and(|ptr| {
+            // SAFETY: the entry's weak count keeps the `GcBox` allocated.
+            let gc_box = unsafe { &*ptr.as_ptr() };
+            !gc_box.has_dead_flag() && gc_box.dropping_state() == 0
+        })
+    }
+
+    /// Returns a strong reference if the target is still alive.
+    ///
+    /// Returns `None` once the target has been finalized, or if the origin
+    /// thread has exited.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a thread other than the origin thread while the
+    /// origin thread is still alive.
+    #[track_caller]
+    pub fn upgrade(&self) -> Option<Gc<T>> {
+        // TCB liveness before the ThreadId check, since ThreadIds are reused.
+        let tcb = self.origin_tcb.upgrade()?;
+        assert_eq!(
+            std::thread::current().id(),
+            self.origin_thread,
+            "WeakHandle::upgrade() must be called on the origin thread"
+        );
+        let roots = tcb.cross_thread_roots.lock().unwrap();
+        let ptr = *roots.weak.get(&self.handle_id)?;
+        // The entry's weak count keeps the slot from being reused. Like
+        // `Weak::upgrade`, refuse a target with no strong references: it may
+        // be unmarked and waiting for a lazy sweep.
+        let upgraded = GcBoxWeakRef::new(ptr.cast::<GcBox<T>>()).try_upgrade();
+        drop(roots);
+        upgraded
+    }
+}
+
+impl<T: Trace + 'static> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        // A clone of a reaped handle, or one whose thread has exited, is
+        // registered nowhere and never upgrades.
+        let handle_id = self
+            .origin_tcb
+            .upgrade()
+            .and_then(|tcb| {
+                let ptr = *tcb
+                    .cross_thread_roots
+                    .lock()
+                    .unwrap()
+                    .weak
+                    .get(&self.handle_id)?;
+                // SAFETY: the entry's weak count keeps the `GcBox` allocated.
+                unsafe { (*ptr.as_ptr()).inc_weak() };
+                Some(Self::register(&tcb, ptr))
+            })
+            .unwrap_or(HandleId::INVALID);
+        Self {
+            handle_id,
+            origin_tcb: Weak::clone(&self.origin_tcb),
+            origin_thread: self.origin_thread,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Trace + 'static> Drop for WeakHandle<T> {
+    fn drop(&mut self) {
+        // After the thread exits, its heap has released every entry already.
+        let Some(tcb) = self.origin_tcb.upgrade() else {
+            return;
+        };
+        let removed = tcb
+            .cross_thread_roots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .weak
+            .remove(&self.handle_id);
+        if let Some(ptr) = removed {
+            // SAFETY: the entry's weak count kept the `GcBox` allocated.
+            let _ = unsafe { GcBox::dec_weak_raw(ptr.as_ptr()) };
+        }
+    }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeakHandle")
+            .field("origin_thread", &self.origin_thread)
+            .field("handle_id", &self.handle_id)
+            .field("is_alive", &self.is_alive())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Drop the weak entries whose targets a sweep has finalized, releasing
+/// their weak counts so the slots can be reclaimed. Called by each
+/// collection once its sweep is done.
+pub fn reap_dead_weak_handles() {
+    let registry = crate::heap::thread_registry()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    for tcb in &registry.threads {
+        tcb.cross_thread_roots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .release_weak_entries(GcBox::has_dead_flag);
+    }
+}
@@ -15,10 +15,11 @@ use std::sync::Arc;
 use std::thread::ThreadId;
 
 use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU64, AtomicU8, AtomicUsize, Ordering};
-use std::sync::{Condvar, Mutex, OnceLock, PoisonError};
+use std::sync::{Condvar, Mutex, OnceLock, PoisonError, RwLock};
 
 use sys_alloc::{Mmap, MmapOptions};
 
+use crate::gc::{gc_fence, gc_ordering};
 use crate::handles::{AsyncScopeData, AsyncScopeEntry, LocalHandles};
 use crate::ptr::GcBox;
 
@@ -26,6 +27,54 @@ use crate::ptr::GcBox;
 /// Prevents unbounded memory growth when many threads mutate shared GC objects.
 const MAX_CROSS_THREAD_SATB_SIZE: usize = 1024 * 1024;
 
+/// Pages the generational barrier batches before taking the dirty-page lock.
+const DIRTY_PAGE_BATCH_LEN: usize = 16;
+
+/// Minimum initial capacity of a thread's SATB overflow buffer.
+const SATB_OVERFLOW_BUFFER_CAPACITY: usize = 64;
+
+/// Initial capacity of the SATB overflow buffer behind a SATB buffer of
+/// `satb_capacity` values: room for two overflows.
+const fn satb_overflow_buffer_capacity(satb_capacity: usize) -> usize {
+    let capacity = satb_capacity.saturating_mul(2);
+    if capacity > SATB_OVERFLOW_BUFFER_CAPACITY {
+        capacity
+    } else {
+        SATB_OVERFLOW_BUFFER_CAPACITY
+    }
+}
+
+/// A per-thread buffer whose capacity grows past this multiple of its
+/// configured capacity is shrunk back after the next collection.
+const BUFFER_SHRINK_FACTOR: usize = 4;
+
+/// Most fields recorded for one dirty large object. Past this, minor GC
+/// traces the whole object instead.
+const MAX_DIRTY_FIELDS: usize = 16;
+
+/// Scans one `GcCell` field for minor GC: marks the `Gc`s it holds.
+pub type FieldScanFn = unsafe fn(*const u8, &mut crate::trace::GcVisitor);
+
+/// A `GcCell` written on a dirty large object.
+///
+/// Minor GC scans just the recorded fields of an object instead of tracing
+/// all of it, see [`LocalHeap::record_dirty_field`].
+#[derive(Debug, Clone, Copy)]
+pub struct DirtyField {
+    /// Offset of the `GcCell` from the start of the object's `GcBox`.
+    pub offset: usize,
+    /// Scans the cell.
+    pub scan: FieldScanFn,
+}
+
+/// Release the excess capacity a burst left in `buffer`, keeping `capacity`
+/// (or its current length, if larger).
+fn shrink_buffer<T>(buffer: &mut Vec<T>, capacity: usize) {
+    if buffer.capacity() > capacity * BUFFER_SHRINK_FACTOR {
+        buffer.shrink_to(capacity);
+    }
+}
+
 /// Global SATB buffer for cross-thread mutations.
 /// When a mutation occurs on a different thread than the allocating thread,
 /// the SATB old value is recorded here instead of the thread-local buffer.
@@ -119,6 +168,13 @@ pub(crate) struct CrossThreadRootTable {
     /// Strong handle root entries: maps `HandleId` -> raw `GcBox` pointer.
     /// These are treated as roots during GC marking.
     pub(crate) strong: HashMap<HandleId, NonNull<GcBox<()>>>,
+    /// Roots held by `GcRoot`s and `GcThreadLocalRoot`s on this thread.
+    /// Unlike `strong`, they are never migrated to the orphan table: they die
+    /// with the thread.
+    pub(crate) thread_local: HashMap<HandleId, NonNull<GcBox<()>>>,
+    /// Targets of `WeakHandle`s created on this thread. Marking never reads
+    /// them; each entry owns one weak count on its `GcBox`.
+    pub(crate) weak: HashMap<HandleId, NonNull<GcBox<()>>>,
 }
 
 impl CrossThreadRootTable {
@@ -128,9 +184,26 @@ impl CrossThreadRootTable {
         Self {
             next_id: 0,
             strong: HashMap::new(),
+            thread_local: HashMap::new(),
+            weak: HashMap::new(),
         }
     }
 
+    /// Remove the weak entries whose `GcBox` satisfies `dead`, releasing the
+    /// weak count each one owns.
+    pub(crate) fn release_weak_entries(&mut self, dead: impl Fn(&GcBox<()>) -> bool) {
+        self.weak.retain(|_, ptr| {
+            // SAFETY: the entry's weak count keeps the `GcBox` allocated.
+            unsafe {
+                if !dead(&*ptr.as_ptr()) {
+                    return true;
+                }
+                let _ = GcBox::dec_weak_raw(ptr.as_ptr());
+            }
+            false
+        });
+    }
+
     /// Allocate a new unique handle ID.
     #[must_use]
     #[allow(dead_code, clippy::missing_const_for_fn)]
@@ -380,6 +453,8 @@ pub struct ThreadControlBlock {
     ///
     /// Lock ordering: `LocalHeap` → `GlobalMarkState` → `GcRequest` → `CrossThreadRootTable`
     pub(crate) cross_thread_roots: Mutex<CrossThreadRootTable>,
+    /// The thread that created this control block, for diagnostics.
+    pub(crate) thread: std::thread::Thread,
 }
 
 #[allow(clippy::non_send_fields_in_send_ty)]
@@ -396,6 +471,18 @@ impl Default for ThreadControlBlock {
 }
 
 impl ThreadControlBlock {
+    /// Wake the thread if it is parked at a safe point.
+    ///
+    /// Holds the park mutex while notifying, so the wakeup cannot fall
+    /// between the thread's check of `gc_requested` and its wait.
+    pub fn unpark(&self) {
+        let _guard = self
+            .park_mutex
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.park_cond.notify_all();
+    }
+
     /// Create a new `ThreadControlBlock` with an uninitialized heap.
     /// The heap must be initialized separately.
     #[must_use]
@@ -416,6 +503,7 @@ impl ThreadControlBlock {
             remembered_buffer_capacity: 32,
             stealing_allowed: true,
             cross_thread_roots: Mutex::new(CrossThreadRootTable::new()),
+            thread: std::thread::current(),
         }
     }
 
@@ -538,7 +626,8 @@ impl ThreadControlBlock {
     /// Iterate cross-thread handle roots for GC marking.
     ///
     /// This is called during the mark phase to ensure objects referenced
-    /// by cross-thread handles are kept alive.
+    /// by cross-thread handles and by `GcRoot`s are kept alive. `WeakHandle`
+    /// entries are not roots and are skipped.
     ///
     /// # Safety
     ///
@@ -550,7 +639,7 @@ impl ThreadControlBlock {
         F: FnMut(*const crate::ptr::GcBox<()>),
     {
         let roots = self.cross_thread_roots.lock().unwrap();
-        for ptr in roots.strong.values() {
+        for ptr in roots.strong.values().chain(roots.thread_local.values()) {
             // SAFETY: ptr validity is guaranteed because the handle registered
             // it before releasing the lock, and the GC holds the lock now,
             // so no concurrent Drop can remove it mid-iteration.
@@ -602,6 +691,7 @@ impl ThreadControlBlock {
     pub fn reset_slice_counters(&mut self) {
         self.marked_this_slice = 0;
         self.remembered_buffer.clear();
+        shrink_buffer(&mut self.remembered_buffer, self.remembered_buffer_capacity);
     }
 
     /// Check if work-stealing is allowed for this thread.
@@ -711,17 +801,31 @@ pub static GC_REQUESTED: AtomicBool = AtomicBool::new(false);
 /// Check if GC has been requested and handle the rendezvous if so.
 /// This is the fast-path check inserted into allocation code.
 /// Uses Acquire ordering to ensure we see the complete GC request state.
+#[inline]
 pub fn check_safepoint() {
     // CRITICAL FIX: Prevent deadlock when Drop handlers allocate during GC
     // If we're already collecting, we must NOT enter rendezvous or we'll
     // deadlock waiting for gc_requested to become false (only collector can clear it)
-    if GC_REQUESTED.load(Ordering::Acquire) && !crate::gc::is_collecting() {
+    // A live `NoGcGuard` defers the rendezvous until it is dropped.
+    if GC_REQUESTED.load(Ordering::Acquire)
+        && !crate::gc::is_collecting()
+        && !crate::gc::is_gc_paused()
+    {
         enter_rendezvous();
     }
 }
 
+/// Hand the pages this thread's barriers batched to its dirty list in one
+/// go, before it lets a collector at its heap.
+fn flush_barrier_batch(tcb: &ThreadControlBlock) {
+    // SAFETY: the heap belongs to the current thread, which is not collecting.
+    unsafe { (*tcb.heap.get()).flush_dirty_page_batch() };
+}
+
 /// Called when a thread reaches a safe point and GC is requested.
 /// Performs the cooperative rendezvous protocol.
+#[cold]
+#[inline(never)]
 #[allow(clippy::significant_drop_tightening)]
 fn enter_rendezvous() {
     let Some(tcb) = current_thread_control_block() else {
@@ -746,6 +850,7 @@ fn enter_rendezvous() {
         return;
     }
 
+    flush_barrier_batch(&tcb);
     let mut roots = Vec::new();
     unsafe {
         crate::stack::spill_registers_and_scan(|ptr, _addr, _is_reg| {
@@ -760,10 +865,65 @@ fn enter_rendezvous() {
         .active_count
         .fetch_sub(1, Ordering::SeqCst);
 
+    park_until_resumed(&tcb);
+}
+
+/// Park the current thread at a safe point until the collection is over,
+/// leaving it counted as active again.
+///
+/// The request can be withdrawn before any waker sees the thread parked, and
+/// wakers only restore threads they find at the safe point. A thread nobody
+/// restored restores itself, unless a new request arrived meanwhile, in
+/// which case its stored roots are still current and it parks again. A
+/// thread that was restored leaves even if the next request already set
+/// `gc_requested` again, since that request counts it as running.
+///
+/// If the GC watchdog is enabled and the thread stays parked past its
+/// timeout, the watchdog reports and then aborts or resumes all threads.
+#[allow(clippy::significant_drop_tightening)]
+fn park_until_resumed(tcb: &ThreadControlBlock) {
+    let mut parked_at = std::time::Instant::now();
+    loop {
+        if !wait_while_requested(tcb, parked_at) {
+            crate::gc::watchdog_expired(parked_at.elapsed());
+            parked_at = std::time::Instant::now();
+            continue;
+        }
+
+        // Requests and wakers both hold the registry lock.
+        let registry = thread_registry().lock().unwrap();
+        if tcb.state.load(Ordering::Acquire) != THREAD_STATE_SAFEPOINT {
+            return;
+        }
+        if !tcb.gc_requested.load(Ordering::Acquire) {
+            tcb.state.store(THREAD_STATE_EXECUTING, Ordering::Release);
+            registry.active_count.fetch_add(1, Ordering::SeqCst);
+            return;
+        }
+    }
+}
+
+/// Wait on the park condition while the thread is parked and a collection is
+/// requested. Returns `false` if the watchdog timeout, counted from
+/// `parked_at`, ran out first.
+#[allow(clippy::significant_drop_tightening)]
+fn wait_while_requested(tcb: &ThreadControlBlock, parked_at: std::time::Instant) -> bool {
+    let timeout = crate::gc::watchdog_timeout();
     let mut guard = tcb.park_mutex.lock().unwrap();
-    while tcb.gc_requested.load(Ordering::Acquire) {
-        guard = tcb.park_cond.wait(guard).unwrap();
+    while tcb.gc_requested.load(Ordering::Acquire)
+        && tcb.state.load(Ordering::Acquire) == THREAD_STATE_SAFEPOINT
+    {
+        let Some(timeout) = timeout else {
+            guard = tcb.park_cond.wait(guard).unwrap();
+            continue;
+        };
+        let remaining = timeout.saturating_sub(parked_at.elapsed());
+        if remaining.is_zero() {
+            return false;
+        }
+        guard = tcb.park_cond.wait_timeout(guard, remaining).unwrap().0;
     }
+    true
 }
 
 /// Signal all threads waiting at safe points to resume.
@@ -785,7 +945,7 @@ pub fn resume_all_threads() {
     for tcb in &registry.threads {
         if tcb.state.load(Ordering::Acquire) == THREAD_STATE_SAFEPOINT {
             tcb.gc_requested.store(false, Ordering::Release);
-            tcb.park_cond.notify_all();
+            tcb.unpark();
             tcb.state.store(THREAD_STATE_EXECUTING, Ordering::Release);
             woken_count += 1;
         }
@@ -853,6 +1013,7 @@ pub fn wait_for_gc_complete() {
     // This ensures that when collector sees active_count == 1, all threads have
     // already stored their complete stack roots. Otherwise, collector may read
     // empty/incomplete roots and miss live objects, causing memory corruption.
+    flush_barrier_batch(&tcb);
     let mut roots = Vec::new();
     unsafe {
         crate::stack::spill_registers_and_scan(|ptr, _addr, _is_reg| {
@@ -868,12 +1029,83 @@ pub fn wait_for_gc_complete() {
         .active_count
         .fetch_sub(1, Ordering::SeqCst);
 
-    let mut guard = tcb.park_mutex.lock().unwrap();
-    while tcb.gc_requested.load(Ordering::Acquire) {
-        guard = tcb.park_cond.wait(guard).unwrap();
+    park_until_resumed(&tcb);
+}
+
+/// Move the current thread to INACTIVE.
+///
+/// An inactive thread no longer counts towards `active_count`, so collections
+/// can proceed without it reaching a safe point. The thread's stack roots are
+/// captured first, as at a rendezvous. While inactive, the thread must not
+/// touch any `Gc` object.
+///
+/// Does nothing if the thread is already INACTIVE.
+///
+/// # Panics
+///
+/// Panics if the thread registry lock is poisoned.
+pub fn enter_inactive() {
+    let Some(tcb) = current_thread_control_block() else {
+        return;
+    };
+    if tcb.state.load(Ordering::Acquire) == THREAD_STATE_INACTIVE {
+        return;
+    }
+
+    flush_barrier_batch(&tcb);
+    let mut roots = Vec::new();
+    unsafe {
+        crate::stack::spill_registers_and_scan(|ptr, _addr, _is_reg| {
+            roots.push(ptr as *const u8);
+        });
+    }
+    *tcb.stack_roots.lock().unwrap() = roots;
+
+    // Hold the registry lock so a concurrent handshake sees the state and the
+    // count change together.
+    let registry = thread_registry().lock().unwrap();
+    // A running thread can still read SAFEPOINT if it passed a rendezvous
+    // whose request was already withdrawn. It is not counted as active then,
+    // and nobody will wake it, so it moves straight to INACTIVE.
+    if tcb.state.swap(THREAD_STATE_INACTIVE, Ordering::AcqRel) == THREAD_STATE_EXECUTING {
+        registry.active_count.fetch_sub(1, Ordering::SeqCst);
     }
 }
 
+/// Move the current thread from INACTIVE back to EXECUTING.
+///
+/// Waits until no collection is requested or running, so the thread never
+/// resumes mutating a heap the collector is still working on.
+///
+/// Does nothing if the thread is not INACTIVE.
+///
+/// # Panics
+///
+/// Panics if the thread registry lock is poisoned.
+#[allow(clippy::significant_drop_tightening)]
+pub fn leave_inactive() {
+    let Some(tcb) = current_thread_control_block() else {
+        return;
+    };
+    if tcb.state.load(Ordering::Acquire) != THREAD_STATE_INACTIVE {
+        return;
+    }
+
+    loop {
+        {
+            let registry = thread_registry().lock().unwrap();
+            if !GC_REQUESTED.load(Ordering::Acquire) && !registry.is_gc_in_progress() {
+                tcb.gc_requested.store(false, Ordering::Release);
+                tcb.state.store(THREAD_STATE_EXECUTING, Ordering::Release);
+                registry.active_count.fetch_add(1, Ordering::SeqCst);
+                break;
+            }
+        }
+        std::thread::yield_now();
+    }
+    tcb.stack_roots.lock().unwrap().clear();
+}
+
 /// Clear the GC request flag after collection is complete.
 ///
 /// # Panics
@@ -900,6 +1132,63 @@ pub fn get_all_thread_control_blocks() -> Vec<std::sync::Arc<ThreadControlBlock>
     thread_registry().lock().unwrap().threads.clone()
 }
 
+/// Number of threads currently registered with the GC.
+///
+/// Intended for embedders checking that their threads are visible to the
+/// collector; a thread is registered the first time it touches its heap.
+///
+/// # Panics
+///
+/// Panics if the thread registry lock is poisoned.
+#[must_use]
+pub fn debug_registered_thread_count() -> usize {
+    thread_registry().lock().unwrap().threads.len()
+}
+
+/// Number of live `LocalHeap`s that have allocated at least one page.
+#[cfg(debug_assertions)]
+static HEAPS_WITH_PAGES: AtomicUsize = AtomicUsize::new(0);
+
+/// `LocalHeap::owns_pages`: no pages allocated yet.
+#[cfg(debug_assertions)]
+const OWNS_NO_PAGES: u8 = 0;
+/// `LocalHeap::owns_pages`: counted in `HEAPS_WITH_PAGES`.
+#[cfg(debug_assertions)]
+const OWNS_PAGES: u8 = 1;
+/// `LocalHeap::owns_pages`: deliberately unregistered by `reset_for_testing`.
+#[cfg(debug_assertions)]
+const OWNS_PAGES_EXEMPT: u8 = 2;
+
+/// Assert that every heap owning GC pages belongs to a registered thread.
+///
+/// A heap that allocates without being registered has roots the collector
+/// never scans, so its objects would be swept while still in use.
+///
+/// # Panics
+///
+/// Panics if some heap owns GC pages without being registered.
+#[cfg(debug_assertions)]
+pub fn debug_assert_heaps_registered() {
+    let registry = thread_registry()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    // Read under the registry lock: threads release their count under it when
+    // they exit, and a registered heap sets its flag before bumping the count.
+    let owning = HEAPS_WITH_PAGES.load(Ordering::SeqCst);
+    let registered = registry
+        .threads
+        .iter()
+        .filter(|tcb| unsafe { (*tcb.heap.get()).owns_pages.load(Ordering::SeqCst) == OWNS_PAGES })
+        .count();
+    drop(registry);
+    assert!(
+        owning <= registered,
+        "{owning} heap(s) own GC pages but only {registered} belong to registered threads; \
+         a thread allocated without registering with the GC, so its roots are invisible \
+         to the collector"
+    );
+}
+
 /// Get stack roots from a thread control block.
 /// Returns the captured stack roots and clears the buffer.
 ///
@@ -954,6 +1243,41 @@ pub const PAGE_FLAG_NEEDS_SWEEP: u8 = 0x04;
 pub const PAGE_FLAG_ALL_DEAD: u8 = 0x08;
 /// Flag: Page is in the dirty pages list (old generation with dirty objects).
 pub const PAGE_FLAG_DIRTY_LISTED: u8 = 0x10;
+/// Flag: Large object pages come from the global allocator rather than a
+/// dedicated mapping.
+#[cfg(feature = "large-object-malloc")]
+pub const PAGE_FLAG_MALLOC: u8 = 0x20;
+
+/// Largest large-object footprint (header plus object, rounded up to whole
+/// pages) that is served by the global allocator.
+///
+/// Anything bigger still gets its own mapping, so huge objects are returned
+/// to the OS as soon as they die.
+#[cfg(feature = "large-object-malloc")]
+pub const MAX_MALLOC_LARGE_OBJECT_SIZE: usize = 64 * 1024;
+
+/// Byte written over the value area of freed slots when the `poison-freed`
+/// feature is enabled.
+#[cfg(feature = "poison-freed")]
+pub const POISON_BYTE: u8 = 0xDE;
+
+// The free-list link lives at the start of a freed slot, inside the object
+// header, so poisoning everything after the header leaves it intact.
+#[cfg(feature = "poison-freed")]
+const _: () =
+    assert!(std::mem::size_of::<Option<u16>>() <= std::mem::size_of::<crate::ptr::GcBox<()>>());
+
+/// Overwrite the value area of a freed small-object slot with [`POISON_BYTE`].
+///
+/// # Safety
+///
+/// `obj_ptr` must point at a freed slot of `block_size` bytes.
+#[cfg(feature = "poison-freed")]
+pub(crate) const unsafe fn poison_freed_slot(obj_ptr: *mut u8, block_size: usize) {
+    let header = std::mem::size_of::<crate::ptr::GcBox<()>>();
+    // SAFETY: the caller guarantees the whole slot is ours to overwrite.
+    unsafe { std::ptr::write_bytes(obj_ptr.add(header), POISON_BYTE, block_size - header) };
+}
 
 /// Maximum number of u64 words in a bitmap to support 64KB pages with 16-byte blocks.
 pub const BITMAP_SIZE: usize = 64;
@@ -1012,6 +1336,15 @@ pub struct PageHeader {
     /// Index of first free slot in free list (non-atomic, single-threaded).
     #[cfg(not(feature = "lazy-sweep"))]
     pub free_list_head: u16,
+    /// Drop function shared by every object on the page (`thin-headers`).
+    #[cfg(feature = "thin-headers")]
+    pub drop_fn: unsafe fn(*mut u8),
+    /// Trace function shared by every object on the page (`thin-headers`).
+    #[cfg(feature = "thin-headers")]
+    pub trace_fn: unsafe fn(*const u8, &mut crate::trace::GcVisitor),
+    /// Type of every object on the page (`thin-headers` with `type-tracking`).
+    #[cfg(all(feature = "thin-headers", feature = "type-tracking"))]
+    pub type_info: fn() -> TypeInfo,
 }
 
 impl PageHeader {
@@ -1039,12 +1372,44 @@ impl PageHeader {
         (page_size() - Self::header_size(block_size)) / block_size
     }
 
+    /// The functions shared by every object on this page.
+    #[cfg(feature = "thin-headers")]
+    #[inline]
+    pub(crate) fn fns(&self) -> ObjectFns {
+        ObjectFns {
+            drop_fn: self.drop_fn,
+            trace_fn: self.trace_fn,
+            #[cfg(feature = "type-tracking")]
+            type_info: self.type_info,
+        }
+    }
+
+    /// Check whether objects using `fns` may be allocated on this page.
+    ///
+    /// Always true unless `thin-headers` makes pages type-homogeneous.
+    #[inline]
+    #[cfg_attr(
+        not(feature = "thin-headers"),
+        allow(clippy::unused_self, clippy::missing_const_for_fn)
+    )]
+    pub(crate) fn holds(&self, fns: ObjectFns) -> bool {
+        #[cfg(feature = "thin-headers")]
+        {
+            self.fns().key() == fns.key()
+        }
+        #[cfg(not(feature = "thin-headers"))]
+        {
+            let _ = fns;
+            true
+        }
+    }
+
     /// Check if an object at the given index is marked.
     #[must_use]
     pub fn is_marked(&self, index: usize) -> bool {
         let word = index / 64;
         let bit = index % 64;
-        (self.mark_bitmap[word].load(Ordering::Acquire) & (1 << bit)) != 0
+        (self.mark_bitmap[word].load(gc_ordering(Ordering::Acquire)) & (1 << bit)) != 0
     }
 
     /// Set the mark bit for an object (atomic, suitable for concurrent marking).
@@ -1053,7 +1418,7 @@ impl PageHeader {
         let word = index / 64;
         let bit = index % 64;
         let mask = 1u64 << bit;
-        let old = self.mark_bitmap[word].fetch_or(mask, Ordering::AcqRel);
+        let old = self.mark_bitmap[word].fetch_or(mask, gc_ordering(Ordering::AcqRel));
         (old & mask) == 0
     }
 
@@ -1074,15 +1439,15 @@ impl PageHeader {
         let word = index / 64;
         let bit = index % 64;
         let mask = 1u64 << bit;
-        let old = self.mark_bitmap[word].load(Ordering::Acquire);
+        let old = self.mark_bitmap[word].load(gc_ordering(Ordering::Acquire));
         if (old & mask) != 0 {
             return Ok(false);
         }
         match self.mark_bitmap[word].compare_exchange(
             old,
             old | mask,
-            Ordering::AcqRel,
-            Ordering::Acquire,
+            gc_ordering(Ordering::AcqRel),
+            gc_ordering(Ordering::Acquire),
         ) {
             Ok(_) => Ok(true),
             Err(_) => Err(()),
@@ -1094,7 +1459,7 @@ impl PageHeader {
     #[must_use]
     pub fn is_fully_marked(&self) -> bool {
         for word_idx in 0..BITMAP_SIZE {
-            let mark_word = self.mark_bitmap[word_idx].load(Ordering::Acquire);
+            let mark_word = self.mark_bitmap[word_idx].load(gc_ordering(Ordering::Acquire));
             let alloc_word = self.allocated_bitmap[word_idx].load(Ordering::Acquire);
             if (mark_word & alloc_word) != alloc_word {
                 return false;
@@ -1108,7 +1473,7 @@ impl PageHeader {
     pub fn clear_mark(&mut self, index: usize) {
         let word = index / 64;
         let bit = index % 64;
-        self.mark_bitmap[word].fetch_and(!(1u64 << bit), Ordering::Release);
+        self.mark_bitmap[word].fetch_and(!(1u64 << bit), gc_ordering(Ordering::Release));
     }
 
     /// Clear the mark bit atomically (takes `&self` for concurrent use).
@@ -1117,13 +1482,13 @@ impl PageHeader {
     pub fn clear_mark_atomic(&self, index: usize) {
         let word = index / 64;
         let bit = index % 64;
-        self.mark_bitmap[word].fetch_and(!(1u64 << bit), Ordering::Release);
+        self.mark_bitmap[word].fetch_and(!(1u64 << bit), gc_ordering(Ordering::Release));
     }
 
     /// Clear all mark bits.
     pub fn clear_all_marks(&mut self) {
         for word in &self.mark_bitmap {
-            word.store(0u64, Ordering::Release);
+            word.store(0u64, gc_ordering(Ordering::Release));
         }
     }
 
@@ -1132,14 +1497,14 @@ impl PageHeader {
     pub fn is_dirty(&self, index: usize) -> bool {
         let word = index / 64;
         let bit = index % 64;
-        (self.dirty_bitmap[word].load(Ordering::Acquire) & (1 << bit)) != 0
+        (self.dirty_bitmap[word].load(gc_ordering(Ordering::Acquire)) & (1 << bit)) != 0
     }
 
     /// Set the dirty bit for an object at the given index.
     pub fn set_dirty(&mut self, index: usize) {
         let word = index / 64;
         let bit = index % 64;
-        self.dirty_bitmap[word].fetch_or(1u64 << bit, Ordering::AcqRel);
+        self.dirty_bitmap[word].fetch_or(1u64 << bit, gc_ordering(Ordering::AcqRel));
     }
 
     /// Clear the dirty bit for an object at the given index.
@@ -1147,13 +1512,13 @@ impl PageHeader {
     pub fn clear_dirty(&mut self, index: usize) {
         let word = index / 64;
         let bit = index % 64;
-        self.dirty_bitmap[word].fetch_and(!(1u64 << bit), Ordering::Release);
+        self.dirty_bitmap[word].fetch_and(!(1u64 << bit), gc_ordering(Ordering::Release));
     }
 
     /// Clear all dirty bits.
     pub fn clear_all_dirty(&mut self) {
         for word in &self.dirty_bitmap {
-            word.store(0u64, Ordering::Release);
+            word.store(0u64, gc_ordering(Ordering::Release));
         }
     }
 
@@ -1177,6 +1542,19 @@ impl PageHeader {
             .fetch_or(PAGE_FLAG_DIRTY_LISTED, Ordering::Release);
     }
 
+    /// Set the dirty-listed flag, returning `false` if it was already set.
+    ///
+    /// # Memory Ordering
+    /// Uses `AcqRel` so exactly one of several racing callers claims the page.
+    #[inline]
+    pub fn try_set_dirty_listed(&self) -> bool {
+        (self
+            .flags
+            .fetch_or(PAGE_FLAG_DIRTY_LISTED, Ordering::AcqRel)
+            & PAGE_FLAG_DIRTY_LISTED)
+            == 0
+    }
+
     /// Clear the dirty-listed flag (called during GC scan).
     ///
     /// # Memory Ordering
@@ -1374,6 +1752,69 @@ impl PageHeader {
 // Segment - Size-class based memory pool
 // ============================================================================
 
+/// The type of an object's value, recorded under `type-tracking`.
+#[cfg(feature = "type-tracking")]
+#[derive(Clone, Copy, Debug)]
+pub struct TypeInfo {
+    /// `std::any::type_name` of the value.
+    pub name: &'static str,
+    /// `TypeId` of the value.
+    pub id: std::any::TypeId,
+}
+
+#[cfg(feature = "type-tracking")]
+impl TypeInfo {
+    /// The info for `T`.
+    #[must_use]
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        Self {
+            name: std::any::type_name::<T>(),
+            id: std::any::TypeId::of::<T>(),
+        }
+    }
+}
+
+/// The type-erased drop and trace functions of the objects in an allocation.
+///
+/// Without `thin-headers` every `GcBox` carries its own copy and pages accept
+/// any type. With it, each small page serves a single pair, stored once in its
+/// `PageHeader`, so allocation has to find a page (and TLAB) for that pair.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(feature = "thin-headers"), allow(dead_code))]
+pub(crate) struct ObjectFns {
+    pub(crate) drop_fn: unsafe fn(*mut u8),
+    pub(crate) trace_fn: unsafe fn(*const u8, &mut crate::trace::GcVisitor),
+    /// Type of the value, for reclaimed-object histograms and heap walks.
+    #[cfg(feature = "type-tracking")]
+    pub(crate) type_info: fn() -> TypeInfo,
+}
+
+impl ObjectFns {
+    /// For raw allocations that are not `GcBox`es: nothing to drop or trace.
+    pub(crate) const UNTYPED: Self = Self {
+        drop_fn: GcBox::<()>::no_op_drop,
+        trace_fn: GcBox::<()>::no_op_trace,
+        #[cfg(feature = "type-tracking")]
+        type_info: || TypeInfo {
+            name: "<untyped>",
+            id: std::any::TypeId::of::<Self>(),
+        },
+    };
+
+    /// Identity of the functions, for matching pages and keying parked TLABs.
+    ///
+    /// The type takes part when tracked, so types whose drop and trace
+    /// functions were merged by the compiler still get separate pages.
+    #[cfg(feature = "thin-headers")]
+    fn key(self) -> (usize, usize, usize) {
+        #[cfg(feature = "type-tracking")]
+        let type_info = self.type_info as usize;
+        #[cfg(not(feature = "type-tracking"))]
+        let type_info = 0;
+        (self.drop_fn as usize, self.trace_fn as usize, type_info)
+    }
+}
+
 // ============================================================================
 // Tlab - Thread-Local Allocation Buffer
 // ============================================================================
@@ -1405,9 +1846,9 @@ impl Tlab {
     /// Try to allocate from the TLAB (Fast Path).
     ///
     /// Returns `Some(ptr)` if successful, `None` if the TLAB is exhausted.
+    /// This is a pure bump; the safepoint poll is done by `LocalHeap::alloc`.
     #[inline]
     pub fn alloc(&mut self, block_size: usize) -> Option<NonNull<u8>> {
-        check_safepoint();
         let ptr = self.bump_ptr;
         // Check if we have enough space.
         // We use wrapping_add and compare as usize to avoid UB with ptr.add(block_size)
@@ -1440,6 +1881,17 @@ impl Tlab {
     }
 }
 
+impl Tlab {
+    /// Whether this TLAB may bump-allocate objects using `fns`.
+    #[cfg(feature = "thin-headers")]
+    #[inline]
+    fn serves(&self, fns: ObjectFns) -> bool {
+        // SAFETY: TLAB pages are owned by the heap holding this TLAB.
+        self.current_page
+            .is_none_or(|page| unsafe { (*page.as_ptr()).holds(fns) })
+    }
+}
+
 impl Default for Tlab {
     fn default() -> Self {
         Self::new()
@@ -1510,10 +1962,218 @@ const fn compute_class_index(size: usize) -> usize {
     }
 }
 
-/// Map `block_size` (16, 32, ..., 2048) to class index 0..7.
+/// Map a small page's `block_size` to its class index 0..7.
 #[must_use]
-pub(crate) const fn block_size_to_class_index(block_size: usize) -> usize {
-    (block_size.trailing_zeros().saturating_sub(4)) as usize
+pub(crate) fn block_size_to_class_index(block_size: usize) -> usize {
+    if !custom_size_classes() {
+        return (block_size.trailing_zeros().saturating_sub(4)) as usize;
+    }
+    active_size_classes()
+        .iter()
+        .position(|&class| class == block_size)
+        .unwrap_or(NUM_SIZE_CLASSES - 1)
+}
+
+// ============================================================================
+// HeapConfig - Size classes chosen at startup
+// ============================================================================
+
+/// Number of small size classes, and so of TLABs per heap.
+pub const NUM_SIZE_CLASSES: usize = SIZE_CLASSES.len();
+
+/// Size classes in effect, fixed when the first `LocalHeap` is created.
+static SIZE_CLASS_TABLE: OnceLock<SizeClassTable> = OnceLock::new();
+
+/// The contents of `SIZE_CLASS_TABLE`.
+///
+/// `custom` is set together with the table, so a thread that sees the
+/// configured classes also sees that they are configured.
+struct SizeClassTable {
+    /// Block sizes, padded with `MAX_SMALL_OBJECT_SIZE` for shorter
+    /// configurations.
+    classes: [usize; NUM_SIZE_CLASSES],
+    /// Whether `classes` differs from `SIZE_CLASSES`. While false,
+    /// allocation keeps the compile-time [`SizeClass`] routing.
+    custom: bool,
+}
+
+impl SizeClassTable {
+    const DEFAULT: Self = Self {
+        classes: SIZE_CLASSES,
+        custom: false,
+    };
+}
+
+/// Whether [`configure_heap`] set classes other than `SIZE_CLASSES`.
+#[inline]
+fn custom_size_classes() -> bool {
+    SIZE_CLASS_TABLE.get().is_some_and(|table| table.custom)
+}
+
+/// Heap settings applied by [`configure_heap`].
+///
+/// # Choosing size classes
+///
+/// Every small object is rounded up to the smallest class that fits it, so
+/// classes far apart waste memory inside each slot: with the default
+/// powers of two, a 40-byte object takes a 64-byte slot. Adding classes
+/// such as 48 cuts that waste, but each class has its own TLAB and its own
+/// pages, so more classes also leave more partly filled pages, one per
+/// class and thread. There are at most [`NUM_SIZE_CLASSES`] classes; pick
+/// them where the workload's object sizes cluster.
+///
+/// A class that is not a power of two only guarantees 16-byte alignment to
+/// its objects. Types with stricter alignment skip it for the next
+/// power-of-two class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapConfig<'a> {
+    /// Block sizes of the small size classes, strictly increasing, each a
+    /// multiple of 16, ending with [`MAX_SMALL_OBJECT_SIZE`].
+    pub size_classes: &'a [usize],
+}
+
+impl Default for HeapConfig<'_> {
+    fn default() -> Self {
+        Self {
+            size_classes: &SIZE_CLASSES,
+        }
+    }
+}
+
+/// Why [`configure_heap`] refused a configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapConfigError {
+    /// A heap was already created, or the heap already configured.
+    AlreadyInitialized,
+    /// The size classes break one of the rules on
+    /// [`HeapConfig::size_classes`], or there are more than
+    /// [`NUM_SIZE_CLASSES`] of them.
+    InvalidSizeClasses,
+}
+
+impl std::fmt::Display for HeapConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyInitialized => f.write_str("the heap is already initialized"),
+            Self::InvalidSizeClasses => write!(
+                f,
+                "size classes must be 1 to {NUM_SIZE_CLASSES} strictly increasing multiples \
+                 of 16 ending with {MAX_SMALL_OBJECT_SIZE}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HeapConfigError {}
+
+/// Set the heap configuration, which takes effect for every thread.
+///
+/// The configuration is consumed when the first `LocalHeap` is created, so
+/// this must run before any thread allocates a `Gc`; afterwards it returns
+/// [`HeapConfigError::AlreadyInitialized`].
+///
+/// # Errors
+///
+/// Returns an error if a heap already exists, or if the size classes are
+/// invalid.
+///
+/// # Examples
+///
+/// ```
+/// use rudo_gc::{configure_heap, Gc, HeapConfig};
+///
+/// configure_heap(&HeapConfig {
+///     size_classes: &[16, 32, 48, 64, 128, 256, 512, 2048],
+/// })
+/// .unwrap();
+/// assert_eq!(rudo_gc::size_classes()[2], 48);
+/// let _ = Gc::new([0u8; 40]);
+/// ```
+pub fn configure_heap(config: &HeapConfig<'_>) -> Result<(), HeapConfigError> {
+    let classes = config.size_classes;
+    let valid = (1..=NUM_SIZE_CLASSES).contains(&classes.len())
+        && classes.last() == Some(&MAX_SMALL_OBJECT_SIZE)
+        && classes.iter().all(|&class| class >= 16 && class % 16 == 0)
+        && classes.windows(2).all(|pair| pair[0] < pair[1]);
+    if !valid {
+        return Err(HeapConfigError::InvalidSizeClasses);
+    }
+
+    let mut table = [MAX_SMALL_OBJECT_SIZE; NUM_SIZE_CLASSES];
+    table[..classes.len()].copy_from_slice(classes);
+    SIZE_CLASS_TABLE
+        .set(SizeClassTable {
+            classes: table,
+            custom: table != SIZE_CLASSES,
+        })
+        .map_err(|_| HeapConfigError::AlreadyInitialized)
+}
+
+/// The small size classes in effect.
+///
+/// [`SIZE_CLASSES`] unless [`configure_heap`] set others. Calling this
+/// fixes the configuration, like creating a heap does.
+#[must_use]
+pub fn size_classes() -> &'static [usize] {
+    let table = active_size_classes();
+    let len = table
+        .iter()
+        .position(|&class| class == MAX_SMALL_OBJECT_SIZE)
+        .map_or(NUM_SIZE_CLASSES, |last| last + 1);
+    &table[..len]
+}
+
+/// The size class table, fixing the default if nothing was configured.
+fn active_size_classes() -> &'static [usize; NUM_SIZE_CLASSES] {
+    &SIZE_CLASS_TABLE
+        .get_or_init(|| SizeClassTable::DEFAULT)
+        .classes
+}
+
+/// Block size of the class at `class_index`.
+#[inline]
+pub(crate) fn size_class_at(class_index: usize) -> usize {
+    match SIZE_CLASS_TABLE.get() {
+        Some(table) if table.custom => table.classes[class_index],
+        _ => SIZE_CLASSES[class_index],
+    }
+}
+
+/// Alignment every object in a class's slots gets: the slot size for a
+/// power of two, which pages align to, and the 16 of the page's header
+/// alignment otherwise.
+const fn size_class_alignment(block_size: usize) -> usize {
+    if block_size.is_power_of_two() {
+        block_size
+    } else {
+        16
+    }
+}
+
+/// Class index and block size for a small object of `size` bytes.
+///
+/// Checks at runtime whether [`configure_heap`] set classes. The default
+/// classes are then routed by arithmetic that folds away for a constant
+/// `size`; configured ones are searched for the smallest class that fits
+/// and is aligned enough.
+#[inline]
+fn small_size_class(size: usize, align: usize) -> (usize, usize) {
+    if custom_size_classes() {
+        configured_size_class(size, align)
+    } else {
+        (compute_class_index(size), compute_size_class(size))
+    }
+}
+
+#[inline(never)]
+fn configured_size_class(size: usize, align: usize) -> (usize, usize) {
+    let table = active_size_classes();
+    table
+        .iter()
+        .position(|&class| class >= size && size_class_alignment(class) >= align)
+        .map_or((NUM_SIZE_CLASSES - 1, MAX_SMALL_OBJECT_SIZE), |index| {
+            (index, table[index])
+        })
 }
 
 // ============================================================================
@@ -1544,10 +2204,6 @@ pub struct GlobalSegmentManager {
     /// Quarantined pages (bad stack conflict).
     quarantined: Vec<Mmap>,
 
-    /// Large object tracking map.
-    /// Map from page address to its corresponding large object head, size, and `header_size`.
-    pub large_object_map: HashMap<usize, (usize, usize, usize)>,
-
     /// Orphan pages keyed by page address for O(1) lookup in `find_gc_box_from_orphan`.
     pub orphan_by_addr: HashMap<usize, OrphanPage>,
 }
@@ -1566,6 +2222,239 @@ pub fn segment_manager() -> &'static Mutex<GlobalSegmentManager> {
     SEGMENT_MANAGER.get_or_init(|| Mutex::new(GlobalSegmentManager::new()))
 }
 
+/// Page memory all heaps together may map, in bytes; 0 means no limit.
+static HEAP_LIMIT: AtomicUsize = AtomicUsize::new(0);
+
+/// Bytes of heap pages currently mapped by all threads, orphaned pages
+/// included. Quarantined pages are not counted.
+static MAPPED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Cap the page memory all threads' heaps may map together. `None`, the
+/// default, leaves only the OS to refuse.
+///
+/// The limit applies to [`total_reserved_size`](crate::total_reserved_size);
+/// [`current_reserved_size`](crate::current_reserved_size) reports only the
+/// calling thread's share of it.
+///
+/// A heap that would take the total past the limit is treated as out of memory: it
+/// runs an emergency major collection and retries, and if that did not free
+/// enough, calls the [`set_oom_handler`] handler or panics.
+pub fn set_heap_limit(limit: Option<usize>) {
+    HEAP_LIMIT.store(limit.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Bytes of heap pages currently mapped by all threads.
+pub(crate) fn mapped_bytes() -> usize {
+    MAPPED_BYTES.load(Ordering::Relaxed)
+}
+
+/// The limit set by [`set_heap_limit`], if any.
+#[must_use]
+pub fn heap_limit() -> Option<usize> {
+    match HEAP_LIMIT.load(Ordering::Relaxed) {
+        0 => None,
+        limit => Some(limit),
+    }
+}
+
+/// The failed request passed to the handler set by [`set_oom_handler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OomInfo {
+    /// Bytes of pages the heap tried to map.
+    pub requested: usize,
+    /// Bytes of pages all heaps already held, as reported by
+    /// [`total_reserved_size`](crate::total_reserved_size).
+    pub reserved: usize,
+    /// The limit set by [`set_heap_limit`], if any. `None`, or a limit with
+    /// room to spare, means the OS refused the memory.
+    pub limit: Option<usize>,
+}
+
+/// Callback registered with [`set_oom_handler`].
+type OomHandler = Arc<dyn Fn(&OomInfo) + Send + Sync>;
+
+static OOM_HANDLER: Mutex<Option<OomHandler>> = Mutex::new(None);
+
+/// Call `f` when a heap runs out of memory, instead of panicking at once.
+///
+/// A heap that cannot map more pages, because the OS refused or
+/// [`set_heap_limit`] would be exceeded, first runs a major collection and
+/// retries. If that fails too, `f` runs on the allocating thread; once it
+/// returns the heap retries a last time, and panics if still out of memory.
+/// So `f` can enforce a quota its own way, by aborting, by unwinding with
+/// a panic of its choosing, or by raising the limit or freeing other memory
+/// so the retry succeeds.
+///
+/// `f` runs in the middle of an allocation and must not allocate `Gc`
+/// objects. Replaces any earlier handler; the default is none.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{set_heap_limit, set_oom_handler, OomInfo};
+///
+/// set_oom_handler(|info: &OomInfo| {
+///     eprintln!("script exceeded its {:?} byte quota", info.limit);
+///     std::process::abort();
+/// });
+/// set_heap_limit(Some(64 * 1024 * 1024));
+/// # rudo_gc::clear_oom_handler();
+/// # set_heap_limit(None);
+/// ```
+pub fn set_oom_handler(f: impl Fn(&OomInfo) + Send + Sync + 'static) {
+    *OOM_HANDLER.lock().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(f));
+}
+
+/// Remove the handler set by [`set_oom_handler`].
+pub fn clear_oom_handler() {
+    *OOM_HANDLER.lock().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Large object tracking map, shared by all threads: page address to the
+/// large object's head page address, value size and `header_size`.
+///
+/// It sits behind its own read-write lock rather than the segment manager's
+/// mutex: write barriers and pointer lookups read it constantly, while only
+/// large allocations and their release write it.
+static LARGE_OBJECT_MAP: OnceLock<RwLock<HashMap<usize, (usize, usize, usize)>>> = OnceLock::new();
+
+/// Lowest page address ever entered in `LARGE_OBJECT_MAP`, and one past the
+/// highest, so that lookups for other memory skip the lock.
+static LARGE_OBJECT_LOW: AtomicUsize = AtomicUsize::new(usize::MAX);
+static LARGE_OBJECT_HIGH: AtomicUsize = AtomicUsize::new(0);
+
+fn large_object_map() -> &'static RwLock<HashMap<usize, (usize, usize, usize)>> {
+    LARGE_OBJECT_MAP.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Look up the large object covering the page at `page_addr`.
+///
+/// Returns the head page address, value size and `header_size` from the
+/// single record of which pages belong to large objects on any thread.
+/// Pages outside every large object ever allocated are rejected without
+/// taking its lock.
+#[must_use]
+pub fn large_object_entry(page_addr: usize) -> Option<(usize, usize, usize)> {
+    if page_addr < LARGE_OBJECT_LOW.load(Ordering::Acquire)
+        || page_addr >= LARGE_OBJECT_HIGH.load(Ordering::Acquire)
+    {
+        return None;
+    }
+    large_object_map()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&page_addr)
+        .copied()
+}
+
+/// Record the `pages` pages of the large object whose head page is at
+/// `head_addr`.
+fn register_large_object(head_addr: usize, pages: usize, size: usize, header_size: usize) {
+    let end = head_addr + pages * page_size();
+    // Widen the bounds first, so a lookup that finds the entry also passes
+    // them.
+    LARGE_OBJECT_LOW.fetch_min(head_addr, Ordering::AcqRel);
+    LARGE_OBJECT_HIGH.fetch_max(end, Ordering::AcqRel);
+    let mut map = large_object_map()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    for page in (head_addr..end).step_by(page_size()) {
+        map.insert(page, (head_addr, size, header_size));
+    }
+}
+
+/// Forget the `pages` pages of the large object whose head page is at
+/// `head_addr`.
+pub(crate) fn unregister_large_object(head_addr: usize, pages: usize) {
+    let mut map = large_object_map()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    for p in 0..pages {
+        map.remove(&(head_addr + p * page_size()));
+    }
+}
+
+/// Return the memory of a large object page to wherever it came from.
+///
+/// # Safety
+///
+/// `header` must point at the `PageHeader` of a live large-object page whose
+/// footprint is `alloc_size` bytes, and the page must not be used afterwards.
+pub unsafe fn release_large_pages(header: *mut u8, alloc_size: usize) {
+    #[cfg(feature = "large-object-malloc")]
+    {
+        // SAFETY: the caller guarantees `header` points at a live page header.
+        #[allow(clippy::cast_ptr_alignment)]
+        let flags = unsafe { (*header.cast::<PageHeader>()).flags.load(Ordering::Acquire) };
+        if flags & PAGE_FLAG_MALLOC != 0 {
+            // SAFETY: `acquire_large_pages` allocated the page with this layout.
+            unsafe {
+                let layout = std::alloc::Layout::from_size_align_unchecked(alloc_size, page_size());
+                std::alloc::dealloc(header, layout);
+            }
+            return;
+        }
+    }
+    // SAFETY: the page is a dedicated mapping of `alloc_size` bytes.
+    unsafe { unmap_pages(header, alloc_size) };
+}
+
+/// Unmap pages obtained from [`LocalHeap::map_pages`] and take them off the
+/// process-wide total checked against [`heap_limit`].
+///
+/// # Safety
+///
+/// `addr` must be the start of a dedicated mapping of `size` bytes that is
+/// not used afterwards.
+unsafe fn unmap_pages(addr: *mut u8, size: usize) {
+    // SAFETY: guaranteed by the caller.
+    unsafe { sys_alloc::Mmap::from_raw(addr, size) };
+    MAPPED_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// Pages held by an [`FfiLock`](crate::FfiLock), with a lock count per page.
+///
+/// Keyed by page header address. Small-object pages can carry several locks.
+static FFI_PINNED_PAGES: OnceLock<parking_lot::Mutex<HashMap<usize, usize>>> = OnceLock::new();
+
+/// Number of pages in `FFI_PINNED_PAGES`, so sweeps skip the lock when none are pinned.
+static FFI_PINNED_PAGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn ffi_pinned_pages() -> &'static parking_lot::Mutex<HashMap<usize, usize>> {
+    FFI_PINNED_PAGES.get_or_init(|| parking_lot::Mutex::new(HashMap::new()))
+}
+
+/// Keep the page at `header` mapped until a matching [`unpin_page_for_ffi`].
+#[allow(clippy::significant_drop_tightening)]
+pub fn pin_page_for_ffi(header: usize) {
+    let mut pages = ffi_pinned_pages().lock();
+    let count = pages.entry(header).or_insert(0);
+    if *count == 0 {
+        FFI_PINNED_PAGE_COUNT.fetch_add(1, Ordering::AcqRel);
+    }
+    *count += 1;
+}
+
+/// Release one pin taken by [`pin_page_for_ffi`].
+#[allow(clippy::significant_drop_tightening)]
+pub fn unpin_page_for_ffi(header: usize) {
+    let mut pages = ffi_pinned_pages().lock();
+    if let Some(count) = pages.get_mut(&header) {
+        *count -= 1;
+        if *count == 0 {
+            pages.remove(&header);
+            FFI_PINNED_PAGE_COUNT.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Whether an `FfiLock` currently keeps the page at `header` from being released.
+#[must_use]
+pub fn is_page_pinned_for_ffi(header: usize) -> bool {
+    FFI_PINNED_PAGE_COUNT.load(Ordering::Acquire) != 0
+        && ffi_pinned_pages().lock().contains_key(&header)
+}
+
 impl GlobalSegmentManager {
     /// Create a new segment manager.
     #[must_use]
@@ -1573,7 +2462,6 @@ impl GlobalSegmentManager {
         Self {
             free_pages: Vec::new(),
             quarantined: Vec::new(),
-            large_object_map: HashMap::new(),
             orphan_by_addr: HashMap::new(),
         }
     }
@@ -1586,24 +2474,29 @@ impl GlobalSegmentManager {
     ///
     /// Panics if the OS fails to map the requested memory.
     pub fn allocate_page(&mut self, size: usize, boundary: usize) -> (NonNull<u8>, usize) {
+        self.try_allocate_page(size, boundary)
+            .unwrap_or_else(|e| panic!("Failed to map memory: {e}"))
+    }
+
+    /// Like [`Self::allocate_page`], but returns the error if the OS fails
+    /// to map the requested memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from mapping the memory.
+    pub fn try_allocate_page(
+        &mut self,
+        size: usize,
+        boundary: usize,
+    ) -> std::io::Result<(NonNull<u8>, usize)> {
         // Mask to hide our own variables from conservative stack scanning (registers)
         const MASK: usize = 0x5555_5555_5555_5555;
 
         loop {
             // 1. Request memory from OS with Address Space Coloring hint
-            // Boxing the Mmap moves the raw pointer value to the heap,
-            // so it doesn't appear on the stack (only the pointer to the box does).
-            let mmap = Box::new(unsafe {
-                MmapOptions::new()
-                    .len(size)
-                    .with_hint(HEAP_HINT_ADDRESS)
-                    .map_anon()
-                    .unwrap_or_else(|e| panic!("Failed to map memory: {e}"))
-            });
+            let (mmap, masked_start, masked_end) = Self::map_masked(size, MASK)?;
 
             // 2. Check for False Roots on Stack
-            // Use helper to keep `ptr` scope small
-            let (masked_start, masked_end) = Self::calculate_masked_range(&mmap, size, MASK);
 
             // Clear registers to ensure `ptr` doesn't linger in callee-saved registers.
             unsafe { crate::stack::clear_registers() };
@@ -1620,15 +2513,35 @@ impl GlobalSegmentManager {
 
             // 4. Success! Convert to raw pointer and return.
             let (raw_ptr, len) = mmap.into_raw();
-            return (unsafe { NonNull::new_unchecked(raw_ptr) }, len);
+            return Ok((unsafe { NonNull::new_unchecked(raw_ptr) }, len));
         }
     }
 
-    /// Helper to calculate masked range.
+    /// Unmap the pages quarantined for colliding with stack values.
+    ///
+    /// They are only kept so the OS does not hand the same addresses back;
+    /// when memory runs out, getting it back matters more.
+    pub fn release_quarantined(&mut self) {
+        self.quarantined.clear();
+    }
+
+    /// Map `size` bytes near the heap hint and return the masked range.
+    ///
+    /// Boxing the Mmap moves the raw pointer value to the heap, so it doesn't
+    /// appear on the stack (only the pointer to the box does). The pointer is
+    /// only ever unmasked in here: a helper taking `&Mmap` gets its argument
+    /// promoted by the optimizer, which loads the pointer into a callee-saved
+    /// register of the caller, where the stack check then finds it.
     #[inline(never)]
-    fn calculate_masked_range(mmap: &Mmap, size: usize, mask: usize) -> (usize, usize) {
+    fn map_masked(size: usize, mask: usize) -> std::io::Result<(Box<Mmap>, usize, usize)> {
+        let mmap = Box::new(unsafe {
+            MmapOptions::new()
+                .len(size)
+                .with_hint(HEAP_HINT_ADDRESS)
+                .map_anon()?
+        });
         let ptr = mmap.ptr() as usize;
-        (ptr ^ mask, (ptr + size) ^ mask)
+        Ok((mmap, ptr ^ mask, (ptr + size) ^ mask))
     }
 
     /// Check if any value on the current stack falls within [start, end).
@@ -1682,7 +2595,9 @@ impl Default for GlobalSegmentManager {
 /// Handles allocation requests from the thread, using TLABs for speed
 /// and getting new pages from the `GlobalSegmentManager`.
 pub struct LocalHeap {
-    /// TLABs for each small size class.
+    /// TLABs for each small size class, named for the default
+    /// [`SIZE_CLASSES`]. With classes set by [`configure_heap`], the `i`th
+    /// field serves class index `i` whatever its block size.
     pub tlab_16: Tlab,
     /// TLAB for 32-byte size class.
     pub tlab_32: Tlab,
@@ -1699,6 +2614,11 @@ pub struct LocalHeap {
     /// TLAB for 2048-byte size class.
     pub tlab_2048: Tlab,
 
+    /// TLABs set aside when another type took over their size class, keyed
+    /// by class index and `ObjectFns::key` (`thin-headers`).
+    #[cfg(feature = "thin-headers")]
+    parked_tlabs: HashMap<(usize, (usize, usize, usize)), Tlab>,
+
     /// All pages owned by this heap (small and large).
     /// Used for sweeping.
     pub pages: Vec<NonNull<PageHeader>>,
@@ -1715,14 +2635,9 @@ pub struct LocalHeap {
     /// Merging them is better for simple iteration.
     /// But large objects have different headers... wait, no, same header structure, distinct flag.
     /// So unified list is fine.
-
     // We retain `large_objects` separately if we want to quickly identify them without checking flags?
     // Nah, flag check is fast.
 
-    /// Map from page address to its corresponding large object head.
-    /// Still useful for interior pointers.
-    pub large_object_map: HashMap<usize, (usize, usize, usize)>,
-
     // Stats
     young_allocated: usize,
     old_allocated: usize,
@@ -1739,6 +2654,13 @@ pub struct LocalHeap {
     /// Cleared at the end of each minor GC cycle.
     dirty_pages: parking_lot::Mutex<Vec<NonNull<PageHeader>>>,
 
+    /// Pages newly dirtied by this thread's barriers that have not been
+    /// moved to `dirty_pages` yet. They are flagged dirty-listed already, so
+    /// the barrier's flag check dedups them without the lock; every reader
+    /// of `dirty_pages` flushes this first. Like the TLABs, only the owning
+    /// thread touches it, or a collector while that thread is stopped.
+    dirty_page_batch: UnsafeCell<Vec<NonNull<PageHeader>>>,
+
     /// Snapshot for lock-free scanning during GC.
     dirty_pages_snapshot: Vec<NonNull<PageHeader>>,
 
@@ -1748,6 +2670,10 @@ pub struct LocalHeap {
     /// History of dirty page counts (last 4 cycles).
     dirty_page_history: [usize; 4],
 
+    /// Fields written on dirty large objects, keyed by page address. A dirty
+    /// large object without an entry is traced whole.
+    dirty_fields: parking_lot::Mutex<HashMap<usize, Vec<DirtyField>>>,
+
     /// Per-thread remembered buffer for incremental GC write barrier.
     /// Batched page recording to reduce lock contention.
     remembered_buffer: Vec<NonNull<PageHeader>>,
@@ -1787,12 +2713,29 @@ pub struct LocalHeap {
     /// Populated when `set_needs_sweep` is called; pruned lazily during iteration.
     #[cfg(feature = "lazy-sweep")]
     pub(crate) pending_sweep_by_class: [Vec<NonNull<PageHeader>>; 8],
+
+    /// Whether this heap is counted in `HEAPS_WITH_PAGES` (`OWNS_*`).
+    #[cfg(debug_assertions)]
+    owns_pages: AtomicU8,
+
+    /// Unreachable objects whose `drop_fn` was deferred out of the sweep.
+    /// Drained on the owning thread by `safepoint`/`yield_now`.
+    pub(crate) deferred_finalizers: Vec<NonNull<crate::ptr::GcBox<()>>>,
+
+    /// Bytes of pages `release_empty_pages` unmapped since the owning
+    /// thread last recorded a collection's metrics.
+    bytes_returned: usize,
+
+    /// Allocations left before the allocation profiler takes its next
+    /// sample; 0 while profiling is off.
+    alloc_sample_countdown: u32,
 }
 
-/// SAFETY: The only field that makes `LocalHeap` auto-!Sync is `UnsafeCell<T>` where
-/// `T` is `Tlab` (`tlab_16` through `tlab_2048`). TLABs are thread-local by design
-/// and are only accessed from the owning thread. The GC does not access TLABs during
-/// STW (it only clears marks and sweeps pages). During STW pauses, all mutator threads
+/// SAFETY: The only fields that make `LocalHeap` auto-!Sync are `UnsafeCell<T>` where
+/// `T` is `Tlab` (`tlab_16` through `tlab_2048`), and `dirty_page_batch`. Both are
+/// thread-local by design and are only accessed from the owning thread. The GC does
+/// not access TLABs during STW (it only clears marks and sweeps pages), and only
+/// flushes `dirty_page_batch` once the owning thread is stopped. During STW pauses, all mutator threads
 /// are suspended, so there is no concurrent access to any `LocalHeap` field. The GC
 /// accesses `LocalHeap` through `&mut` references exclusively during STW when no other
 /// thread can access it.
@@ -1802,6 +2745,12 @@ impl LocalHeap {
     /// Create a new empty heap.
     #[must_use]
     pub fn new() -> Self {
+        // The first heap fixes the size classes.
+        active_size_classes();
+        let satb_buffer_capacity = crate::gc::incremental::IncrementalMarkState::global()
+            .config()
+            .satb_buffer_len
+            .max(1);
         Self {
             tlab_16: Tlab::new(),
             tlab_32: Tlab::new(),
@@ -1811,22 +2760,27 @@ impl LocalHeap {
             tlab_512: Tlab::new(),
             tlab_1024: Tlab::new(),
             tlab_2048: Tlab::new(),
+            #[cfg(feature = "thin-headers")]
+            parked_tlabs: HashMap::new(),
             pages: Vec::new(),
             small_pages: HashSet::new(),
-            large_object_map: HashMap::new(),
             young_allocated: 0,
             old_allocated: 0,
             min_addr: usize::MAX,
             max_addr: 0,
             dirty_pages: parking_lot::Mutex::new(Vec::with_capacity(64)),
+            dirty_page_batch: UnsafeCell::new(Vec::with_capacity(DIRTY_PAGE_BATCH_LEN)),
             dirty_pages_snapshot: Vec::new(),
             avg_dirty_pages: 16,
             dirty_page_history: [16; 4],
+            dirty_fields: parking_lot::Mutex::new(HashMap::new()),
             remembered_buffer: Vec::with_capacity(32),
             remembered_buffer_capacity: 32,
-            satb_old_values: Vec::with_capacity(32),
-            satb_buffer_capacity: 32,
-            satb_overflow_buffer: Vec::with_capacity(64),
+            satb_old_values: Vec::with_capacity(satb_buffer_capacity),
+            satb_buffer_capacity,
+            satb_overflow_buffer: Vec::with_capacity(satb_overflow_buffer_capacity(
+                satb_buffer_capacity,
+            )),
             free_list_preferred: [None; 8],
             pages_by_class: std::array::from_fn(|_| Vec::new()),
             pages_with_free_slots: std::array::from_fn(|_| Vec::new()),
@@ -1834,9 +2788,87 @@ impl LocalHeap {
             pending_sweep_cursor: [0; 8],
             #[cfg(feature = "lazy-sweep")]
             pending_sweep_by_class: std::array::from_fn(|_| Vec::new()),
+            #[cfg(debug_assertions)]
+            owns_pages: AtomicU8::new(OWNS_NO_PAGES),
+            deferred_finalizers: Vec::new(),
+            bytes_returned: 0,
+            alloc_sample_countdown: 0,
         }
     }
 
+    /// Count this heap in `HEAPS_WITH_PAGES` once it owns a page.
+    #[cfg(debug_assertions)]
+    fn note_owns_pages(&self) {
+        if self
+            .owns_pages
+            .compare_exchange(
+                OWNS_NO_PAGES,
+                OWNS_PAGES,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            )
+            .is_ok()
+        {
+            HEAPS_WITH_PAGES.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Stop counting this heap in `HEAPS_WITH_PAGES`.
+    #[cfg(debug_assertions)]
+    fn release_owns_pages(&self) {
+        if self
+            .owns_pages
+            .compare_exchange(
+                OWNS_PAGES,
+                OWNS_NO_PAGES,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            )
+            .is_ok()
+        {
+            HEAPS_WITH_PAGES.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Exclude this heap from the registration check for good.
+    #[cfg(debug_assertions)]
+    pub(crate) fn exempt_owns_pages(&self) {
+        if self.owns_pages.swap(OWNS_PAGES_EXEMPT, Ordering::SeqCst) == OWNS_PAGES {
+            HEAPS_WITH_PAGES.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Swap this heap's pages and allocation state with `other`'s.
+    ///
+    /// The registration-check state stays where it is, since it describes
+    /// whether the slot holding the heap belongs to a registered thread.
+    pub(crate) fn swap_contents(&mut self, other: &mut Self) {
+        std::mem::swap(self, other);
+        #[cfg(debug_assertions)]
+        std::mem::swap(self.owns_pages.get_mut(), other.owns_pages.get_mut());
+        // Profiling belongs to the thread, whichever heap it allocates in.
+        std::mem::swap(
+            &mut self.alloc_sample_countdown,
+            &mut other.alloc_sample_countdown,
+        );
+    }
+
+    /// Sample the `n`th allocation from now for the allocation profiler, or
+    /// none if `n` is 0.
+    pub(crate) const fn set_alloc_sample_countdown(&mut self, n: u32) {
+        self.alloc_sample_countdown = n;
+    }
+
+    /// Whether any slot on this heap's pages is still allocated.
+    #[must_use]
+    pub fn has_allocated_objects(&self) -> bool {
+        self.all_pages().any(|page_ptr| {
+            // SAFETY: Page pointers in the heap are always valid.
+            let header = unsafe { page_ptr.as_ref() };
+            (0..header.obj_count as usize).any(|i| header.is_allocated(i))
+        })
+    }
+
     /// Update the address range of the heap.
     const fn update_range(&mut self, addr: usize, size: usize) {
         if addr < self.min_addr {
@@ -1854,35 +2886,55 @@ impl LocalHeap {
         addr >= self.min_addr && addr < self.max_addr
     }
 
-    /// Add page to dirty list if not present. Philosophy: mutex only on slow path.
+    /// Add page to dirty list if not present. Philosophy: mutex only when a
+    /// batch of newly dirtied pages is full.
     ///
     /// Fast path when already listed (flag check, no lock).
     ///
     /// # Safety
-    /// Caller must ensure header points to a valid `PageHeader`.
-    #[allow(clippy::significant_drop_tightening)]
+    /// Caller must ensure header points to a valid `PageHeader`, and must be
+    /// the thread that owns this heap or a collector that has stopped it.
     #[inline]
     pub unsafe fn add_to_dirty_pages(&self, header: NonNull<PageHeader>) {
-        // Fast path: already in list
+        // Fast path: already in list or batch
         // SAFETY: Caller guarantees header is valid
         if unsafe { (*header.as_ptr()).is_dirty_listed() } {
             return;
         }
-
-        self.add_to_dirty_pages_slow(header);
+
+        // SAFETY: Caller guarantees exclusive use of the batch.
+        unsafe { self.add_to_dirty_pages_slow(header) };
+    }
+
+    /// Slow path: claim the page and batch it for the dirty list.
+    /// Marked cold to improve I-cache locality of the hot path.
+    ///
+    /// # Safety
+    /// Same as [`add_to_dirty_pages`](Self::add_to_dirty_pages).
+    #[cold]
+    unsafe fn add_to_dirty_pages_slow(&self, header: NonNull<PageHeader>) {
+        // SAFETY: Caller guarantees header is valid
+        if unsafe { (*header.as_ptr()).try_set_dirty_listed() } {
+            // SAFETY: Caller guarantees no one else uses the batch.
+            let batch = unsafe { &mut *self.dirty_page_batch.get() };
+            batch.push(header);
+            if batch.len() >= DIRTY_PAGE_BATCH_LEN {
+                self.dirty_pages.lock().append(batch);
+            }
+        }
     }
 
-    /// Slow path: acquire lock and add page to dirty list.
-    /// Marked cold to improve I-cache locality of the hot path.
-    #[cold]
-    #[allow(clippy::significant_drop_tightening)]
-    fn add_to_dirty_pages_slow(&self, header: NonNull<PageHeader>) {
-        let mut dirty_pages = self.dirty_pages.lock();
-        // SAFETY: Caller guarantees header is valid
-        if unsafe { !(*header.as_ptr()).is_dirty_listed() } {
-            dirty_pages.push(header);
-            // SAFETY: Caller guarantees header is valid
-            unsafe { (*header.as_ptr()).set_dirty_listed() };
+    /// Move the batched newly dirtied pages to the dirty list, under one
+    /// lock.
+    ///
+    /// Must be called on the thread that owns this heap, or by a collector
+    /// that has stopped it.
+    #[inline]
+    pub(crate) fn flush_dirty_page_batch(&self) {
+        // SAFETY: per the contract above, no barrier is filling the batch.
+        let batch = unsafe { &mut *self.dirty_page_batch.get() };
+        if !batch.is_empty() {
+            self.dirty_pages.lock().append(batch);
         }
     }
 
@@ -1896,6 +2948,7 @@ impl LocalHeap {
     /// # Returns
     /// Number of pages in the snapshot
     pub fn take_dirty_pages_snapshot(&mut self) -> usize {
+        self.flush_dirty_page_batch();
         let mut dirty_pages = self.dirty_pages.lock();
         let capacity = self.avg_dirty_pages.max(16);
         self.dirty_pages_snapshot = Vec::with_capacity(capacity);
@@ -1921,6 +2974,7 @@ impl LocalHeap {
     /// the returned pages too (bug45).
     #[inline]
     pub fn drain_dirty_pages_overflow(&self) -> Vec<NonNull<PageHeader>> {
+        self.flush_dirty_page_batch();
         let mut guard = self.dirty_pages.lock();
         std::mem::take(&mut *guard)
     }
@@ -1940,7 +2994,62 @@ impl LocalHeap {
 
     /// Get count of dirty pages (for debugging/metrics and tests).
     pub fn dirty_pages_count(&self) -> usize {
-        self.dirty_pages.lock().len()
+        // SAFETY: only reads the length; the batch is written by this
+        // heap's own thread.
+        self.dirty_pages.lock().len() + unsafe { (*self.dirty_page_batch.get()).len() }
+    }
+
+    /// Record a write to the large object on `header`. Must be called before
+    /// the barrier sets the object's dirty bit.
+    ///
+    /// `field` is the `GcCell` written, or `None` if the barrier does not
+    /// know it. While every write since the object became dirty names a
+    /// field, and there are at most `MAX_DIRTY_FIELDS` of them, minor GC
+    /// scans only those fields; otherwise it traces the whole object.
+    ///
+    /// # Safety
+    /// Caller must ensure header points to a valid large-object `PageHeader`.
+    pub unsafe fn record_dirty_field(
+        &self,
+        header: NonNull<PageHeader>,
+        field: Option<DirtyField>,
+    ) {
+        let page = header.as_ptr() as usize;
+        // SAFETY: Caller guarantees header is valid
+        let was_dirty = unsafe { (*header.as_ptr()).is_dirty(0) };
+        let mut fields = self.dirty_fields.lock();
+        match field {
+            Some(field) if !was_dirty => {
+                fields.insert(page, vec![field]);
+            }
+            Some(field) => {
+                // No entry means an earlier write already needs the whole object.
+                if let Some(recorded) = fields.get_mut(&page) {
+                    if recorded.iter().all(|f| f.offset != field.offset) {
+                        if recorded.len() < MAX_DIRTY_FIELDS {
+                            recorded.push(field);
+                        } else {
+                            fields.remove(&page);
+                        }
+                    }
+                }
+            }
+            None => {
+                fields.remove(&page);
+            }
+        }
+    }
+
+    /// Take the fields recorded for the dirty large object on `header`.
+    /// Returns `None` if the whole object has to be traced.
+    pub fn take_dirty_fields(&self, header: NonNull<PageHeader>) -> Option<Vec<DirtyField>> {
+        self.dirty_fields.lock().remove(&(header.as_ptr() as usize))
+    }
+
+    /// Forget the fields recorded for every page, after dirty bits were
+    /// cleared.
+    pub fn clear_dirty_fields(&self) {
+        self.dirty_fields.lock().clear();
     }
 
     /// Record a page in the remembered buffer for incremental GC.
@@ -2031,14 +3140,31 @@ impl LocalHeap {
 
     /// Flush the cross-thread SATB buffer (main + overflow).
     /// Called during GC to process cross-thread mutations.
+    ///
+    /// Addresses that no longer resolve to an allocated slot of a GC page are
+    /// skipped, so a slot freed between recording and flushing is never marked.
     #[must_use]
     pub fn flush_cross_thread_satb_buffer() -> Vec<NonNull<GcBox<()>>> {
         let mut main = std::mem::take(&mut *CROSS_THREAD_SATB_BUFFER.lock());
         let overflow = std::mem::take(&mut *CROSS_THREAD_SATB_OVERFLOW_BUFFER.lock());
         main.extend(overflow);
-        main.into_iter()
+        let recorded = main.len();
+        let flushed: Vec<_> = main
+            .into_iter()
+            // SAFETY: addresses were recorded from `GcBox`es on GC pages by
+            // `push_cross_thread_satb`.
+            .filter(|&addr| unsafe { is_allocated_gc_box(addr) })
             .filter_map(|addr| NonNull::new(addr as *mut GcBox<()>))
-            .collect()
+            .collect();
+
+        #[cfg(feature = "tracing")]
+        if flushed.len() < recorded {
+            crate::gc::tracing::log_cross_thread_satb_skipped(recorded - flushed.len());
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = recorded;
+
+        flushed
     }
 
     /// Push a GC pointer to the cross-thread SATB buffer.
@@ -2059,6 +3185,7 @@ impl LocalHeap {
             }
             overflow.push(gc_ptr.as_ptr() as usize);
             drop(overflow);
+            crate::gc::incremental::note_satb_overflow();
             crate::gc::incremental::IncrementalMarkState::global()
                 .request_fallback(crate::gc::incremental::FallbackReason::SatbBufferOverflow);
             return false;
@@ -2068,6 +3195,7 @@ impl LocalHeap {
     }
 
     fn satb_buffer_overflowed(&mut self) -> bool {
+        crate::gc::incremental::note_satb_overflow();
         self.satb_overflow_buffer.append(&mut self.satb_old_values);
         crate::gc::incremental::IncrementalMarkState::global()
             .request_fallback(crate::gc::incremental::FallbackReason::SatbBufferOverflow);
@@ -2093,81 +3221,232 @@ impl LocalHeap {
         std::mem::take(&mut self.satb_overflow_buffer)
     }
 
+    /// Get the SATB overflow buffer's current capacity.
+    #[must_use]
+    pub fn satb_overflow_buffer_capacity(&self) -> usize {
+        self.satb_overflow_buffer.capacity()
+    }
+
+    /// Get the number of old values the SATB buffer holds before it
+    /// overflows.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn satb_buffer_capacity(&self) -> usize {
+        self.satb_buffer_capacity
+    }
+
+    /// Set the number of old values the SATB buffer holds before it
+    /// overflows, at least one.
+    ///
+    /// Values already buffered are kept, in either buffer; a buffer already
+    /// past a smaller capacity overflows on its next record.
+    pub fn set_satb_buffer_capacity(&mut self, capacity: usize) {
+        self.satb_buffer_capacity = capacity.max(1);
+        let additional = self
+            .satb_buffer_capacity
+            .saturating_sub(self.satb_old_values.len());
+        self.satb_old_values.reserve(additional);
+        let overflow_capacity = satb_overflow_buffer_capacity(self.satb_buffer_capacity);
+        let additional = overflow_capacity.saturating_sub(self.satb_overflow_buffer.len());
+        self.satb_overflow_buffer.reserve(additional);
+    }
+
+    /// Post-collection cleanup of the per-thread write barrier buffers.
+    ///
+    /// SATB values only matter to the marking cycle that recorded them, so
+    /// once no incremental mark is in progress, leftovers are discarded. Any
+    /// buffer a burst grew far past its configured capacity is then shrunk
+    /// back, so a one-time spike doesn't stay allocated for the thread's
+    /// lifetime.
+    pub fn shrink_buffers(&mut self) {
+        if !crate::gc::incremental::is_incremental_marking_active() {
+            self.satb_old_values.clear();
+            self.satb_overflow_buffer.clear();
+        }
+        shrink_buffer(&mut self.remembered_buffer, self.remembered_buffer_capacity);
+        shrink_buffer(&mut self.satb_old_values, self.satb_buffer_capacity);
+        shrink_buffer(
+            &mut self.satb_overflow_buffer,
+            satb_overflow_buffer_capacity(self.satb_buffer_capacity),
+        );
+    }
+
     /// Allocate space for a value of type T.
     ///
     /// Returns a pointer to uninitialized memory.
     ///
+    /// Only the safepoint poll and the TLAB bump are inlined into callers;
+    /// everything else (large objects, free lists, lazy sweep, new pages) lives
+    /// in the out-of-line [`Self::alloc_refill`].
+    ///
     /// # Panics
     ///
     /// Panics if the type's alignment exceeds the size class alignment.
     /// This should be extremely rare in practice since size classes are
     /// powers of two starting at 16.
+    #[inline]
     pub fn alloc<T>(&mut self) -> NonNull<u8> {
+        self.alloc_typed::<T>(ObjectFns::UNTYPED)
+    }
+
+    /// Allocate space for a `GcBox` whose drop and trace functions are `fns`.
+    ///
+    /// Identical to [`Self::alloc`] unless `thin-headers` is enabled, in which
+    /// case the object goes on a page that serves `fns`.
+    #[inline]
+    pub(crate) fn alloc_typed<T>(&mut self, fns: ObjectFns) -> NonNull<u8> {
         let size = std::mem::size_of::<T>();
         let align = std::mem::align_of::<T>();
 
-        if size > MAX_SMALL_OBJECT_SIZE {
-            let ptr = self.alloc_large(size, align);
-            self.young_allocated += size;
-            return ptr;
+        // One poll per allocation, whichever path ends up serving it.
+        check_safepoint();
+
+        if self.alloc_sample_countdown != 0 {
+            self.alloc_sample_countdown -= 1;
+            if self.alloc_sample_countdown == 0 {
+                self.alloc_sample_countdown = crate::alloc_profile::record_sample(size);
+            }
+        }
+
+        if size <= MAX_SMALL_OBJECT_SIZE {
+            // Validate alignment - size class must satisfy alignment requirement.
+            // Picking the class reads the configured table at runtime: one
+            // load and branch per allocation with the default classes, whose
+            // routing and this check then fold away for a given T, and a
+            // search of the table with classes set by `configure_heap`.
+            let (class_index, size_class) = small_size_class(size, align);
+            assert!(
+                size_class_alignment(size_class) >= align,
+                "Type alignment ({align}) exceeds size class ({size_class}). \
+                 Consider using a larger wrapper type."
+            );
+
+            #[cfg(feature = "thin-headers")]
+            if !self.tlab_mut(class_index).serves(fns) {
+                self.switch_tlab(class_index, fns);
+            }
+            if let Some(ptr) = self.tlab_mut(class_index).alloc(size_class) {
+                self.young_allocated += size;
+                self.update_range(ptr.as_ptr() as usize & page_mask(), page_size());
+                return ptr;
+            }
         }
 
-        // Validate alignment - size class must satisfy alignment requirement
-        let size_class = compute_size_class(size);
+        self.alloc_refill(size, align, fns)
+    }
+
+    /// Allocate a `size`-byte `GcBox` on its own large-object pages, however
+    /// small it is.
+    ///
+    /// For objects whose size is only known at runtime, such as a
+    /// [`GcSlice`](crate::GcSlice). The page header's `block_size` records the
+    /// full size, so the sweep releases the whole object at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` does not fit the header's 32-bit `block_size`.
+    pub(crate) fn alloc_large_sized(
+        &mut self,
+        size: usize,
+        align: usize,
+        fns: ObjectFns,
+    ) -> NonNull<u8> {
         assert!(
-            size_class >= align,
-            "Type alignment ({align}) exceeds size class ({size_class}). \
-             Consider using a larger wrapper type."
+            u32::try_from(size).is_ok(),
+            "Object size ({size}) exceeds the largest supported allocation"
         );
+        let ptr = self.alloc_large(size, align, fns);
+        self.young_allocated += size;
+        ptr
+    }
 
-        // Try TLAB allocation
-        let class_index = compute_class_index(size);
-        let ptr_opt = match class_index {
-            0 => self.tlab_16.alloc(16),
-            1 => self.tlab_32.alloc(32),
-            2 => self.tlab_64.alloc(64),
-            3 => self.tlab_128.alloc(128),
-            4 => self.tlab_256.alloc(256),
-            5 => self.tlab_512.alloc(512),
-            6 => self.tlab_1024.alloc(1024),
-            _ => self.tlab_2048.alloc(2048),
-        };
+    /// Park the class's current TLAB and bring back the one serving `fns`,
+    /// if any, so interleaved types keep bumping through their own pages.
+    #[cfg(feature = "thin-headers")]
+    #[cold]
+    fn switch_tlab(&mut self, class_index: usize, fns: ObjectFns) {
+        let resumed = self
+            .parked_tlabs
+            .remove(&(class_index, fns.key()))
+            .unwrap_or_default();
+        let parked = std::mem::replace(self.tlab_mut(class_index), resumed);
+        if let Some(page) = parked.current_page {
+            // SAFETY: TLAB pages are owned by this heap.
+            let key = unsafe { (*page.as_ptr()).fns() }.key();
+            self.parked_tlabs.insert((class_index, key), parked);
+        }
+    }
+
+    /// TLAB for a small size class index.
+    #[inline]
+    const fn tlab_mut(&mut self, class_index: usize) -> &mut Tlab {
+        match class_index {
+            0 => &mut self.tlab_16,
+            1 => &mut self.tlab_32,
+            2 => &mut self.tlab_64,
+            3 => &mut self.tlab_128,
+            4 => &mut self.tlab_256,
+            5 => &mut self.tlab_512,
+            6 => &mut self.tlab_1024,
+            _ => &mut self.tlab_2048,
+        }
+    }
 
-        if let Some(ptr) = ptr_opt {
+    /// Allocation slow path, taken when the TLAB cannot serve the request.
+    ///
+    /// Tries, in order: large object pages, the per-class free lists, pages
+    /// pending lazy sweep, and finally `alloc_slow`, which lets an
+    /// incremental collection in progress free memory before mapping a fresh
+    /// page.
+    #[cold]
+    #[inline(never)]
+    fn alloc_refill(&mut self, size: usize, align: usize, fns: ObjectFns) -> NonNull<u8> {
+        if size > MAX_SMALL_OBJECT_SIZE {
+            let ptr = self.alloc_large(size, align, fns);
             self.young_allocated += size;
-            self.update_range(ptr.as_ptr() as usize & page_mask(), page_size());
             return ptr;
         }
 
-        if let Some(ptr) = self.alloc_from_free_list(class_index) {
+        let (class_index, _) = small_size_class(size, align);
+
+        if let Some(ptr) = self.alloc_from_free_list(class_index, fns) {
             self.young_allocated += size;
             self.update_range(ptr.as_ptr() as usize & page_mask(), page_size());
             return ptr;
         }
 
         #[cfg(feature = "lazy-sweep")]
-        if let Some(ptr) = self.alloc_from_pending_sweep(class_index) {
+        if let Some(ptr) = self.alloc_from_pending_sweep(class_index, fns) {
             self.young_allocated += size;
             self.update_range(ptr.as_ptr() as usize & page_mask(), page_size());
             return ptr;
         }
 
-        let ptr = self.alloc_slow(size, class_index);
+        let ptr = self.alloc_slow(size, class_index, fns);
         self.young_allocated += size;
         self.update_range(ptr.as_ptr() as usize & page_mask(), page_size());
         ptr
     }
 
     #[cfg(feature = "lazy-sweep")]
-    fn alloc_from_pending_sweep(&mut self, class_index: usize) -> Option<NonNull<u8>> {
+    fn alloc_from_pending_sweep(
+        &mut self,
+        class_index: usize,
+        fns: ObjectFns,
+    ) -> Option<NonNull<u8>> {
         if crate::gc::sync::GC_MARK_IN_PROGRESS.load(std::sync::atomic::Ordering::Acquire) {
             return None;
         }
 
-        let block_size = SIZE_CLASSES[class_index];
+        let block_size = size_class_at(class_index);
         let mut i = 0;
         while i < self.pending_sweep_by_class[class_index].len() {
             let page_ptr = self.pending_sweep_by_class[class_index][i];
+            // Another type's page: leave it for that type's allocations.
+            if !unsafe { (*page_ptr.as_ptr()).holds(fns) } {
+                i += 1;
+                continue;
+            }
             let matches = unsafe {
                 let header = page_ptr.as_ptr();
                 let hdr = header.read();
@@ -2183,7 +3462,7 @@ impl LocalHeap {
             }
             let reclaimed = unsafe { crate::gc::sweep_specific_page(self, page_ptr, 1) };
             if reclaimed > 0 {
-                if let Some(ptr) = self.alloc_from_free_list(class_index) {
+                if let Some(ptr) = self.alloc_from_free_list(class_index, fns) {
                     return Some(ptr);
                 }
             }
@@ -2204,11 +3483,13 @@ impl LocalHeap {
     /// Uses a per-size-class preferred page cache for O(1) allocation when the
     /// cached page has free slots. Falls back to O(P) scan over `pages_with_free_slots`
     /// (P = pages with space), or O(K) over `pages_by_class` if the free-slots list is empty.
-    fn alloc_from_free_list(&mut self, class_index: usize) -> Option<NonNull<u8>> {
-        let block_size = SIZE_CLASSES[class_index];
+    fn alloc_from_free_list(&mut self, class_index: usize, fns: ObjectFns) -> Option<NonNull<u8>> {
+        let block_size = size_class_at(class_index);
 
         // Fast path: try preferred page first if cached and valid
-        if let Some(page_ptr) = self.free_list_preferred[class_index] {
+        if let Some(page_ptr) = self.free_list_preferred[class_index]
+            .filter(|page| unsafe { (*page.as_ptr()).holds(fns) })
+        {
             if let Some((ptr, exhausted)) =
                 unsafe { Self::try_pop_from_page(page_ptr.as_ptr(), block_size) }
             {
@@ -2227,9 +3508,13 @@ impl LocalHeap {
         }
 
         // O(P) scan over pages that have free slots
-        let i = 0;
+        let mut i = 0;
         while i < self.pages_with_free_slots[class_index].len() {
             let page_ptr = self.pages_with_free_slots[class_index][i];
+            if !unsafe { (*page_ptr.as_ptr()).holds(fns) } {
+                i += 1;
+                continue;
+            }
             if let Some((ptr, exhausted)) =
                 unsafe { Self::try_pop_from_page(page_ptr.as_ptr(), block_size) }
             {
@@ -2246,6 +3531,9 @@ impl LocalHeap {
 
         // Fallback: O(K) scan over all pages (preserves correctness for edge cases)
         for page_ptr in &self.pages_by_class[class_index] {
+            if !unsafe { (*page_ptr.as_ptr()).holds(fns) } {
+                continue;
+            }
             if let Some((ptr, exhausted)) =
                 unsafe { Self::try_pop_from_page(page_ptr.as_ptr(), block_size) }
             {
@@ -2372,30 +3660,29 @@ impl LocalHeap {
     /// Use the snapshot pattern in `sweep_phase1_finalize` instead.
     ///
     /// See `docs/reentrant-alloc-rules.md` for safety guidelines.
-    fn alloc_slow(&mut self, _size: usize, class_index: usize) -> NonNull<u8> {
+    fn alloc_slow(&mut self, _size: usize, class_index: usize, fns: ObjectFns) -> NonNull<u8> {
         check_safepoint();
-        let block_size = match class_index {
-            0 => 16,
-            1 => 32,
-            2 => 64,
-            3 => 128,
-            4 => 256,
-            5 => 512,
-            6 => 1024,
-            _ => 2048,
-        };
+        let block_size = size_class_at(class_index);
 
-        // 1. Request new page from global manager
+        // 1. Let an incremental collection in progress free memory first
+        if crate::gc::advance_incremental_marking(self) {
+            if let Some(ptr) = self.alloc_from_free_list(class_index, fns) {
+                return ptr;
+            }
+            #[cfg(feature = "lazy-sweep")]
+            if let Some(ptr) = self.alloc_from_pending_sweep(class_index, fns) {
+                return ptr;
+            }
+        }
+
+        // 2. Request new page from global manager
         // Create boundary to filter out our own stack frame
         let marker = 0;
         let boundary = std::ptr::addr_of!(marker) as usize;
 
-        let (ptr, _) = segment_manager()
-            .lock()
-            .unwrap_or_else(PoisonError::into_inner)
-            .allocate_page(crate::heap::page_size(), boundary);
+        let ptr = self.map_pages(crate::heap::page_size(), boundary);
 
-        // 2. Initialize Page Header
+        // 3. Initialize Page Header
         // SAFETY: ptr is page-aligned
         #[allow(clippy::cast_ptr_alignment)]
         let header = ptr.cast::<PageHeader>();
@@ -2425,6 +3712,12 @@ impl LocalHeap {
                 free_list_head: AtomicU16::new(PageHeader::FREE_LIST_NONE),
                 #[cfg(not(feature = "lazy-sweep"))]
                 free_list_head: 0,
+                #[cfg(feature = "thin-headers")]
+                drop_fn: fns.drop_fn,
+                #[cfg(feature = "thin-headers")]
+                trace_fn: fns.trace_fn,
+                #[cfg(all(feature = "thin-headers", feature = "type-tracking"))]
+                type_info: fns.type_info,
             });
 
             // Initialize all slots with no-op drop
@@ -2432,31 +3725,23 @@ impl LocalHeap {
                 let obj_ptr = ptr.as_ptr().add(h_size + (i * block_size));
                 #[allow(clippy::cast_ptr_alignment)]
                 let gc_box_ptr = obj_ptr.cast::<crate::ptr::GcBox<()>>();
-                std::ptr::addr_of_mut!((*gc_box_ptr).drop_fn)
-                    .write(crate::ptr::GcBox::<()>::no_op_drop);
-                std::ptr::addr_of_mut!((*gc_box_ptr).trace_fn)
-                    .write(crate::ptr::GcBox::<()>::no_op_trace);
+                crate::ptr::GcBox::<()>::init_free_slot(gc_box_ptr);
             }
         }
+        #[cfg(not(feature = "thin-headers"))]
+        let _ = fns;
 
-        // 3. Update LocalHeap pages list
+        // 4. Update LocalHeap pages list
         // SAFETY: Snapshot pattern in callers makes this safe during GC.
         // See docs/reentrant-alloc-rules.md.
         self.pages.push(header);
+        #[cfg(debug_assertions)]
+        self.note_owns_pages();
         self.small_pages.insert(ptr.as_ptr() as usize);
         self.pages_by_class[class_index].push(header);
 
-        // 4. Update Tlab
-        let tlab = match class_index {
-            0 => &mut self.tlab_16,
-            1 => &mut self.tlab_32,
-            2 => &mut self.tlab_64,
-            3 => &mut self.tlab_128,
-            4 => &mut self.tlab_256,
-            5 => &mut self.tlab_512,
-            6 => &mut self.tlab_1024,
-            _ => &mut self.tlab_2048,
-        };
+        // 5. Update Tlab
+        let tlab = self.tlab_mut(class_index);
 
         tlab.current_page = Some(header);
         unsafe {
@@ -2465,7 +3750,7 @@ impl LocalHeap {
             tlab.bump_end = ptr.as_ptr().add(h_size + obj_count * block_size);
         }
 
-        // 5. Retry allocation (guaranteed to succeed now)
+        // 6. Retry allocation (guaranteed to succeed now)
         tlab.alloc(block_size).unwrap()
     }
 
@@ -2474,7 +3759,7 @@ impl LocalHeap {
     /// # Panics
     ///
     /// Panics if the alignment requirement exceeds the page size.
-    fn alloc_large(&mut self, size: usize, align: usize) -> NonNull<u8> {
+    fn alloc_large(&mut self, size: usize, align: usize, fns: ObjectFns) -> NonNull<u8> {
         check_safepoint();
 
         assert!(
@@ -2492,16 +3777,7 @@ impl LocalHeap {
         let pages_needed = total_size.div_ceil(page_size());
         let alloc_size = pages_needed * page_size();
 
-        // Use safe allocation logic
-        // Create boundary to filter out our own stack frame
-        let marker = 0;
-        let boundary = std::ptr::addr_of!(marker) as usize;
-        let (ptr, _) = segment_manager()
-            .lock()
-            .unwrap_or_else(PoisonError::into_inner)
-            .allocate_page(alloc_size, boundary);
-
-        // ptr is NonNull<u8> already check for null logic inside allocate_safe_page
+        let (ptr, flags) = self.acquire_large_pages(alloc_size);
 
         // SAFETY: ptr is page-aligned, which is more strict than PageHeader's alignment.
         #[allow(clippy::cast_ptr_alignment)]
@@ -2516,7 +3792,7 @@ impl LocalHeap {
                 #[allow(clippy::cast_possible_truncation)]
                 header_size: h_size as u16,
                 generation: AtomicU8::new(0),
-                flags: AtomicU8::new(PAGE_FLAG_LARGE),
+                flags: AtomicU8::new(flags),
                 owner_thread: get_thread_id(),
                 #[cfg(feature = "lazy-sweep")]
                 dead_count: AtomicU16::new(0),
@@ -2529,7 +3805,15 @@ impl LocalHeap {
                 free_list_head: AtomicU16::new(PageHeader::FREE_LIST_NONE),
                 #[cfg(not(feature = "lazy-sweep"))]
                 free_list_head: 0,
+                #[cfg(feature = "thin-headers")]
+                drop_fn: fns.drop_fn,
+                #[cfg(feature = "thin-headers")]
+                trace_fn: fns.trace_fn,
+                #[cfg(all(feature = "thin-headers", feature = "type-tracking"))]
+                type_info: fns.type_info,
             });
+            #[cfg(not(feature = "thin-headers"))]
+            let _ = fns;
             // Mark the single object as allocated
             (*header.as_ptr()).set_allocated(0);
 
@@ -2545,32 +3829,14 @@ impl LocalHeap {
 
         let page_ptr = header; // header is NonNull
         self.pages.push(page_ptr); // Push to unified pages list
+        #[cfg(debug_assertions)]
+        self.note_owns_pages();
 
-        // Register all pages of this large object in the map for interior pointer support.
-        // This allows find_gc_box_from_ptr to find the head GcBox from any interior pointer.
-        // We register this in BOTH local and global map for now?
-        // Actually, interior pointers need to be found from ANY thread potentially...
-        // But conservative stack scanning is usually thread-local stacks finding objects.
-        // If one thread scans stack and finds ptr to object alloc'd by another thread,
-        // it needs the global map if that object spans multiple pages.
-        // For Phase 1, large_object_map is duplicated or split responsibility.
-        // Let's Register in LOCAL map for now as GlobalHeap still exists.
-        // GlobalSegmentManager also has a map, maybe we should register there too?
-        // For strict TLAB, large objects are often alloc'd directly from Global.
-        // Let's verify: GlobalSegmentManager has `large_object_map`.
-        // We should probably optimize this later, but for parity:
+        // Register all pages of this large object in the global map for interior
+        // pointer support. The map is global because conservative scanning on one
+        // thread may find a pointer into a large object allocated by another.
         let header_addr = header.as_ptr() as usize;
-        for p in 0..pages_needed {
-            let page_addr = header_addr + (p * page_size());
-            self.large_object_map
-                .insert(page_addr, (header_addr, size, h_size));
-            // Register in global manager too?
-            segment_manager()
-                .lock()
-                .unwrap_or_else(PoisonError::into_inner)
-                .large_object_map
-                .insert(page_addr, (header_addr, size, h_size));
-        }
+        register_large_object(header_addr, pages_needed, size, h_size);
 
         // Update heap range for conservative scanning
         self.update_range(header_addr, alloc_size);
@@ -2579,6 +3845,105 @@ impl LocalHeap {
         unsafe { NonNull::new_unchecked(gc_box_ptr) }
     }
 
+    /// Obtain page-aligned, zeroed memory for a large object and the page
+    /// flags describing where it came from.
+    fn acquire_large_pages(&mut self, alloc_size: usize) -> (NonNull<u8>, u8) {
+        #[cfg(feature = "large-object-malloc")]
+        if alloc_size <= MAX_MALLOC_LARGE_OBJECT_SIZE {
+            let layout = std::alloc::Layout::from_size_align(alloc_size, page_size())
+                .expect("large object layout overflow");
+            // SAFETY: `alloc_size` is at least one page, so the layout is non-zero.
+            let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+            let Some(ptr) = NonNull::new(ptr) else {
+                std::alloc::handle_alloc_error(layout);
+            };
+            return (ptr, PAGE_FLAG_LARGE | PAGE_FLAG_MALLOC);
+        }
+
+        // Use safe allocation logic
+        // Create boundary to filter out our own stack frame
+        let marker = 0;
+        let boundary = std::ptr::addr_of!(marker) as usize;
+        let ptr = self.map_pages(alloc_size, boundary);
+
+        // ptr is NonNull<u8> already check for null logic inside allocate_safe_page
+        (ptr, PAGE_FLAG_LARGE)
+    }
+
+    /// Map `size` bytes of fresh pages for this heap.
+    ///
+    /// If the OS refuses, or the pages would take all heaps past
+    /// [`heap_limit`], this is the last resort before giving up: unmap the
+    /// quarantined pages, run an emergency major collection of this heap
+    /// to free unreachable large objects, and retry once. If that fails, the
+    /// [`set_oom_handler`] handler gets a turn before a final retry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last retry fails too.
+    fn map_pages(&mut self, size: usize, boundary: usize) -> NonNull<u8> {
+        if let Ok(ptr) = Self::try_map_pages(size, boundary) {
+            return ptr;
+        }
+
+        segment_manager()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .release_quarantined();
+        // This heap is borrowed by the allocation, so it is collected in
+        // place. Allocating mid-way through a sweep or a drop collects
+        // nothing; `collect_for_oom` sees that and returns.
+        crate::gc::collect_for_oom(self);
+
+        let err = match Self::try_map_pages(size, boundary) {
+            Ok(ptr) => return ptr,
+            Err(err) => err,
+        };
+        let handler = OOM_HANDLER
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let Some(handler) = handler else {
+            panic!("rudo-gc: out of memory after an emergency collection: {err}");
+        };
+        handler(&OomInfo {
+            requested: size,
+            reserved: mapped_bytes(),
+            limit: heap_limit(),
+        });
+
+        Self::try_map_pages(size, boundary)
+            .unwrap_or_else(|e| panic!("rudo-gc: out of memory after the OOM handler ran: {e}"))
+    }
+
+    /// One attempt for [`Self::map_pages`].
+    ///
+    /// The pages are counted against the limit before they are mapped, so
+    /// threads mapping at the same time cannot overshoot it together.
+    fn try_map_pages(size: usize, boundary: usize) -> std::io::Result<NonNull<u8>> {
+        let mapped = MAPPED_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        if let Some(limit) = heap_limit() {
+            if mapped > limit {
+                MAPPED_BYTES.fetch_sub(size, Ordering::Relaxed);
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::OutOfMemory,
+                    format!("heap limit of {limit} bytes reached"),
+                ));
+            }
+        }
+        let result = segment_manager()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .try_allocate_page(size, boundary);
+        match result {
+            Ok((ptr, _)) => Ok(ptr),
+            Err(err) => {
+                MAPPED_BYTES.fetch_sub(size, Ordering::Relaxed);
+                Err(err)
+            }
+        }
+    }
+
     /// Get total bytes allocated.
     #[must_use]
     pub const fn total_allocated(&self) -> usize {
@@ -2597,6 +3962,100 @@ impl LocalHeap {
         self.old_allocated
     }
 
+    /// Get bytes of page memory held by this heap, including free slots.
+    #[must_use]
+    pub fn reserved_bytes(&self) -> usize {
+        self.pages
+            .iter()
+            .map(|page| unsafe {
+                let header = page.as_ptr();
+                if (*header).is_large_object() {
+                    ((*header).header_size as usize + (*header).block_size as usize)
+                        .next_multiple_of(page_size())
+                } else {
+                    page_size()
+                }
+            })
+            .sum()
+    }
+
+    /// Unmap empty small-object pages, keeping up to `keep_per_class` of them
+    /// in each size class for reuse. Returns the bytes given back to the OS.
+    ///
+    /// A page is empty when none of its slots is allocated, so nothing can
+    /// point into it; dead objects waiting for a lazy sweep still count as
+    /// allocated. TLAB pages, pages held by an `FfiLock` and pages on the
+    /// dirty list are kept.
+    pub fn release_empty_pages(&mut self, keep_per_class: usize) -> usize {
+        let mut in_use: HashSet<usize> = HashSet::new();
+        for class_index in 0..NUM_SIZE_CLASSES {
+            if let Some(page) = self.tlab_mut(class_index).current_page {
+                in_use.insert(page.as_ptr() as usize);
+            }
+        }
+        #[cfg(feature = "thin-headers")]
+        in_use.extend(
+            self.parked_tlabs
+                .values()
+                .filter_map(|tlab| tlab.current_page)
+                .map(|page| page.as_ptr() as usize),
+        );
+
+        let mut released: HashSet<usize> = HashSet::new();
+        for pages in &self.pages_by_class {
+            let empty = pages.iter().filter(|page| {
+                let addr = page.as_ptr() as usize;
+                // SAFETY: the heap's pages stay mapped until released here.
+                let header = unsafe { &*page.as_ptr() };
+                !in_use.contains(&addr)
+                    && !header.is_dirty_listed()
+                    && !is_page_pinned_for_ffi(addr)
+                    && header
+                        .allocated_bitmap
+                        .iter()
+                        .all(|word| word.load(Ordering::Acquire) == 0)
+            });
+            released.extend(
+                empty
+                    .skip(keep_per_class)
+                    .map(|page| page.as_ptr() as usize),
+            );
+        }
+        if released.is_empty() {
+            return 0;
+        }
+
+        let keep = |page: &NonNull<PageHeader>| !released.contains(&(page.as_ptr() as usize));
+        self.pages.retain(keep);
+        self.small_pages.retain(|addr| !released.contains(addr));
+        for class_index in 0..NUM_SIZE_CLASSES {
+            self.pages_by_class[class_index].retain(keep);
+            self.pages_with_free_slots[class_index].retain(keep);
+            #[cfg(feature = "lazy-sweep")]
+            self.pending_sweep_by_class[class_index].retain(keep);
+            if self.free_list_preferred[class_index].is_some_and(|page| !keep(&page)) {
+                self.free_list_preferred[class_index] = None;
+            }
+        }
+        self.dirty_pages_snapshot.retain(keep);
+        self.remembered_buffer.retain(keep);
+
+        let size = page_size();
+        for &addr in &released {
+            // SAFETY: each page is a dedicated one-page mapping no longer
+            // reachable from this heap.
+            unsafe { unmap_pages(addr as *mut u8, size) };
+        }
+        self.bytes_returned += released.len() * size;
+        released.len() * size
+    }
+
+    /// Bytes [`release_empty_pages`](Self::release_empty_pages) unmapped
+    /// since the last call, resetting the count.
+    pub const fn take_bytes_returned(&mut self) -> usize {
+        std::mem::replace(&mut self.bytes_returned, 0)
+    }
+
     /// Update allocation counters given a change in young/old bytes.
     /// This is used by the collector during promotion and sweeping.
     pub const fn update_allocated_bytes(&mut self, young: usize, old: usize) {
@@ -2609,6 +4068,16 @@ impl LocalHeap {
         self.pages.iter().copied()
     }
 
+    /// [`large_object_entry`], answered without the global map's lock for
+    /// this heap's own small pages, which are never part of a large object.
+    #[must_use]
+    pub fn large_object_entry(&self, page_addr: usize) -> Option<(usize, usize, usize)> {
+        if self.small_pages.contains(&page_addr) {
+            return None;
+        }
+        large_object_entry(page_addr)
+    }
+
     /// Get large object pages (now just filtered from all pages, or tracked if we want).
     /// If we need specifically large objects, we can check flags.
     /// Or we can keep `large_objects` list if needed for the map management.
@@ -2642,7 +4111,9 @@ impl LocalHeap {
 
     /// Get the size class index for a type.
     ///
-    /// This is useful for debugging and verifying `BiBOP` routing.
+    /// This is useful for debugging and verifying `BiBOP` routing. It
+    /// reports the default [`SIZE_CLASSES`] routing, ignoring classes set
+    /// by [`configure_heap`].
     ///
     /// # Returns
     ///
@@ -2661,6 +4132,9 @@ impl LocalHeap {
 
     /// Get the segment index and size class name for debugging.
     ///
+    /// Like [`Self::size_class_for`], this follows the default
+    /// [`SIZE_CLASSES`] routing.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -2707,28 +4181,20 @@ impl LocalHeap {
 
         let page_addr = addr & page_mask();
 
-        if let Some(&(header_addr, size, header_size)) = self.large_object_map.get(&page_addr) {
+        if let Some((header_addr, size, header_size)) = self.large_object_entry(page_addr) {
             let total_size = header_size + size;
             let alloc_size = total_size.div_ceil(page_size()) * page_size();
 
             let gc_box_ptr = addr as *mut crate::ptr::GcBox<()>;
             if !(*gc_box_ptr).has_dead_flag() {
-                ((*gc_box_ptr).drop_fn)(addr as *mut u8);
+                (crate::ptr::GcBox::drop_fn_of(gc_box_ptr))(addr as *mut u8);
             }
 
-            for p in 0..(alloc_size / page_size()) {
-                let page = header_addr + (p * page_size());
-                self.large_object_map.remove(&page);
-                segment_manager()
-                    .lock()
-                    .unwrap_or_else(PoisonError::into_inner)
-                    .large_object_map
-                    .remove(&page);
-            }
+            unregister_large_object(header_addr, alloc_size / page_size());
 
             // Deallocate the memory
             unsafe {
-                sys_alloc::Mmap::from_raw(addr as *mut u8, alloc_size);
+                release_large_pages(header_addr as *mut u8, alloc_size);
             }
         } else if self.small_pages.contains(&page_addr) {
             // It's a small object - find the page header
@@ -2748,7 +4214,7 @@ impl LocalHeap {
                             #[allow(clippy::cast_ptr_alignment)]
                             let gc_box_ptr = obj_ptr.cast::<crate::ptr::GcBox<()>>();
                             if !unsafe { (*gc_box_ptr).has_dead_flag() } {
-                                unsafe { ((*gc_box_ptr).drop_fn)(obj_ptr) };
+                                unsafe { (crate::ptr::GcBox::drop_fn_of(gc_box_ptr))(obj_ptr) };
                             }
 
                             // Clear DEAD_FLAG, GEN_OLD_FLAG, UNDER_CONSTRUCTION_FLAG, and is_dropping so
@@ -2764,6 +4230,8 @@ impl LocalHeap {
                             unsafe {
                                 let mut next_head = (*header).free_list_head();
                                 obj_ptr.cast::<Option<u16>>().write_unaligned(next_head);
+                                #[cfg(feature = "poison-freed")]
+                                poison_freed_slot(obj_ptr, block_size);
                                 // Push to free list atomically using CAS
                                 loop {
                                     let old = next_head.unwrap_or(u16::MAX);
@@ -2815,6 +4283,9 @@ pub fn simple_write_barrier(ptr: *const u8) {
     }
 
     let ptr_addr = ptr as usize;
+    // Young objects skip the barrier only outside incremental marking: SATB
+    // has to see mutations to every object while marking runs.
+    let marking = crate::gc::incremental::is_write_barrier_active();
     with_heap(|heap| {
         if ptr_addr < heap.min_addr || ptr_addr >= heap.max_addr {
             return;
@@ -2824,13 +4295,13 @@ pub fn simple_write_barrier(ptr: *const u8) {
 
         unsafe {
             let (header, index) =
-                if let Some(&(head_addr, size, h_size)) = heap.large_object_map.get(&page_addr) {
+                if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
                     if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
                         return;
                     }
                     let h_ptr = head_addr as *mut PageHeader;
 
-                    // Validate MAGIC to ensure the large_object_map entry is valid (bug190, bug308).
+                    // Validate MAGIC to ensure the large object map entry is valid (bug190, bug308).
                     if (*h_ptr).magic != MAGIC_GC_PAGE {
                         return;
                     }
@@ -2843,7 +4314,8 @@ pub fn simple_write_barrier(ptr: *const u8) {
                     let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
                     // Cache flag to avoid TOCTOU between check and barrier (bug149).
                     let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h_ptr).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                    let young = (*h_ptr).generation.load(Ordering::Acquire) == 0;
+                    if young && !has_gen_old && !marking {
                         return;
                     }
                     (NonNull::new_unchecked(h_ptr), 0_usize)
@@ -2872,7 +4344,8 @@ pub fn simple_write_barrier(ptr: *const u8) {
                     }
                     // Cache flag to avoid TOCTOU between check and barrier (bug149).
                     let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h.as_ptr()).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                    let young = (*h.as_ptr()).generation.load(Ordering::Acquire) == 0;
+                    if young && !has_gen_old && !marking {
                         return;
                     }
                     (h, index)
@@ -2883,6 +4356,9 @@ pub fn simple_write_barrier(ptr: *const u8) {
                 return;
             }
 
+            if (*header.as_ptr()).is_large_object() {
+                heap.record_dirty_field(header, None);
+            }
             (*header.as_ptr()).set_dirty(index);
             heap.add_to_dirty_pages(header);
         }
@@ -2897,10 +4373,19 @@ pub fn simple_write_barrier(ptr: *const u8) {
 /// # Arguments
 /// * `ptr` - Raw pointer to the `GcCell` (field being mutated)
 /// * `context` - Context string for panic message (e.g., `"borrow_mut"`)
-/// * `incremental_active` - Whether incremental marking is active
+/// * `incremental_active` - Whether incremental marking is active. Young objects
+///   are only skipped while it is not, since SATB must see every mutation.
+/// * `scan` - Scans the `GcCell`, letting minor GC rescan just this field
+///   when the cell belongs to a large object. `None` if the cell can't be
+///   scanned on its own.
 #[allow(dead_code, clippy::too_many_lines)]
 #[inline]
-pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_active: bool) {
+pub fn gc_cell_validate_and_barrier(
+    ptr: *const u8,
+    context: &str,
+    incremental_active: bool,
+    scan: Option<FieldScanFn>,
+) {
     if ptr.is_null() {
         return;
     }
@@ -2917,16 +4402,16 @@ pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_a
 
         unsafe {
             // Tail pages of multi-page large objects have no PageHeader; ptr_to_page_header
-            // would yield garbage. Check large_object_map first (see find_gc_box_from_ptr).
-            let (h, index) = if let Some(&(head_addr, size, h_size)) =
-                heap.large_object_map.get(&page_addr)
+            // would yield garbage. Check the large object map first (see find_gc_box_from_ptr).
+            let (h, index, field) = if let Some((head_addr, size, h_size)) =
+                heap.large_object_entry(page_addr)
             {
                 if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
                     return;
                 }
                 let h_ptr = head_addr as *mut PageHeader;
 
-                // Validate MAGIC to ensure the large_object_map entry is valid (bug190).
+                // Validate MAGIC to ensure the large object map entry is valid (bug190).
                 if (*h_ptr).magic != MAGIC_GC_PAGE {
                     return;
                 }
@@ -2939,7 +4424,8 @@ pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_a
                 // Skip barrier only if page is young AND object has no gen_old_flag (bug71).
                 // Cache flag to avoid TOCTOU between check and barrier (bug114).
                 let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                if (*h_ptr).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                let young = (*h_ptr).generation.load(Ordering::Acquire) == 0;
+                if young && !has_gen_old && !incremental_active {
                     return;
                 }
                 let owner = (*h_ptr).owner_thread;
@@ -2960,7 +4446,11 @@ pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_a
                      3. Dispatch mutations back to the main thread via channel\n\
                      4. Use single-threaded Tokio runtime"
                 );
-                (NonNull::new_unchecked(h_ptr), 0_usize)
+                let field = scan.map(|scan| DirtyField {
+                    offset: ptr_addr - gc_box_addr as usize,
+                    scan,
+                });
+                (NonNull::new_unchecked(h_ptr), 0_usize, field)
             } else {
                 let header = ptr_to_page_header(ptr);
                 let h = header.as_ptr();
@@ -3012,10 +4502,11 @@ pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_a
                 let gc_box_addr =
                     (header_page_addr + header_size + index * block_size) as *const GcBox<()>;
                 let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                if (*h).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                let young = (*h).generation.load(Ordering::Acquire) == 0;
+                if young && !has_gen_old && !incremental_active {
                     return;
                 }
-                (header, index)
+                (header, index, None)
             };
 
             // Skip if slot was swept; avoids corrupting dirty tracking with reused slot (bug364).
@@ -3023,11 +4514,14 @@ pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_a
                 return;
             }
 
+            if (*h.as_ptr()).is_large_object() {
+                heap.record_dirty_field(h, field);
+            }
             (*h.as_ptr()).set_dirty(index);
             heap.add_to_dirty_pages(h);
 
             if incremental_active {
-                std::sync::atomic::fence(Ordering::AcqRel);
+                gc_fence(Ordering::AcqRel);
                 heap.record_in_remembered_buffer(h);
             }
         }
@@ -3038,7 +4532,8 @@ pub fn gc_cell_validate_and_barrier(ptr: *const u8, context: &str, incremental_a
 ///
 /// # Arguments
 /// * `ptr` - Raw pointer to the field being mutated (not the containing object)
-/// * `incremental_active` - Whether incremental marking is active (do remembered buffer)
+/// * `incremental_active` - Whether incremental marking is active (do remembered buffer).
+///   Young objects are only skipped while it is not, since SATB must see every mutation.
 #[allow(dead_code)]
 #[inline]
 pub fn unified_write_barrier(ptr: *const u8, incremental_active: bool) {
@@ -3056,13 +4551,13 @@ pub fn unified_write_barrier(ptr: *const u8, incremental_active: bool) {
 
         unsafe {
             let (header, index) =
-                if let Some(&(head_addr, size, h_size)) = heap.large_object_map.get(&page_addr) {
+                if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
                     if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
                         return;
                     }
                     let h_ptr = head_addr as *mut PageHeader;
 
-                    // Validate MAGIC to ensure the large_object_map entry is valid (bug190).
+                    // Validate MAGIC to ensure the large object map entry is valid (bug190).
                     if (*h_ptr).magic != MAGIC_GC_PAGE {
                         return;
                     }
@@ -3074,7 +4569,8 @@ pub fn unified_write_barrier(ptr: *const u8, incremental_active: bool) {
                     let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
                     // Cache flag to avoid TOCTOU between check and barrier (bug133).
                     let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h_ptr).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                    let young = (*h_ptr).generation.load(Ordering::Acquire) == 0;
+                    if young && !has_gen_old && !incremental_active {
                         return;
                     }
                     (NonNull::new_unchecked(h_ptr), 0_usize)
@@ -3103,7 +4599,8 @@ pub fn unified_write_barrier(ptr: *const u8, incremental_active: bool) {
                         (header_page_addr + header_size + index * block_size) as *const GcBox<()>;
                     // Cache flag to avoid TOCTOU between check and barrier (bug133).
                     let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h.as_ptr()).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                    let young = (*h.as_ptr()).generation.load(Ordering::Acquire) == 0;
+                    if young && !has_gen_old && !incremental_active {
                         return;
                     }
                     (h, index)
@@ -3113,11 +4610,14 @@ pub fn unified_write_barrier(ptr: *const u8, incremental_active: bool) {
                 return;
             }
 
+            if (*header.as_ptr()).is_large_object() {
+                heap.record_dirty_field(header, None);
+            }
             (*header.as_ptr()).set_dirty(index);
             heap.add_to_dirty_pages(header);
 
             if incremental_active {
-                std::sync::atomic::fence(Ordering::AcqRel);
+                gc_fence(Ordering::AcqRel);
                 heap.record_in_remembered_buffer(header);
             }
         }
@@ -3139,6 +4639,9 @@ pub fn incremental_write_barrier(ptr: *const u8) {
     }
 
     let ptr_addr = ptr as usize;
+    // Young objects skip the barrier only outside incremental marking: SATB
+    // has to see mutations to every object while marking runs.
+    let marking = crate::gc::incremental::is_write_barrier_active();
     with_heap(|heap| {
         if ptr_addr < heap.min_addr || ptr_addr >= heap.max_addr {
             return;
@@ -3147,20 +4650,20 @@ pub fn incremental_write_barrier(ptr: *const u8) {
         // SAFETY: This fence synchronizes with the GC thread to ensure
         // that all prior writes are visible before we record in the remembered set.
         // Required for SATB correctness in incremental GC.
-        std::sync::atomic::fence(Ordering::AcqRel);
+        gc_fence(Ordering::AcqRel);
 
         let page_addr = ptr_addr & page_mask();
 
         unsafe {
             // Tail pages of multi-page large objects have no PageHeader; ptr_to_page_header
-            // would yield garbage. Check large_object_map first (see find_gc_box_from_ptr).
+            // would yield garbage. Check the large object map first (see find_gc_box_from_ptr).
             let (header, index) =
-                if let Some(&(head_addr, size, h_size)) = heap.large_object_map.get(&page_addr) {
+                if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
                     if ptr_addr < head_addr + h_size || ptr_addr >= head_addr + h_size + size {
                         return;
                     }
                     let h_ptr = head_addr as *mut PageHeader;
-                    // Validate MAGIC to ensure the large_object_map entry is valid (bug190).
+                    // Validate MAGIC to ensure the large object map entry is valid (bug190).
                     if (*h_ptr).magic != MAGIC_GC_PAGE {
                         return;
                     }
@@ -3170,7 +4673,8 @@ pub fn incremental_write_barrier(ptr: *const u8) {
                     }
                     let gc_box_addr = (head_addr + h_size) as *const GcBox<()>;
                     let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h_ptr).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                    let young = (*h_ptr).generation.load(Ordering::Acquire) == 0;
+                    if young && !has_gen_old && !marking {
                         return;
                     }
                     if !(*h_ptr).is_allocated(0) {
@@ -3206,7 +4710,8 @@ pub fn incremental_write_barrier(ptr: *const u8) {
                     let gc_box_addr =
                         (header_page_addr + header_size + index * block_size) as *const GcBox<()>;
                     let has_gen_old = (*gc_box_addr).has_gen_old_flag();
-                    if (*h.as_ptr()).generation.load(Ordering::Acquire) == 0 && !has_gen_old {
+                    let young = (*h.as_ptr()).generation.load(Ordering::Acquire) == 0;
+                    if young && !has_gen_old && !marking {
                         return;
                     }
                     (h, index)
@@ -3230,6 +4735,9 @@ impl Default for LocalHeap {
 
 impl Drop for LocalHeap {
     fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        self.release_owns_pages();
+
         let current_thread = std::thread::current().id();
 
         let mut manager = segment_manager()
@@ -3271,7 +4779,6 @@ impl Drop for LocalHeap {
         }
         drop(manager);
 
-        self.large_object_map.clear();
         self.small_pages.clear();
     }
 }
@@ -3282,6 +4789,10 @@ impl Drop for LocalHeap {
 ///
 /// Panics if the segment manager lock is poisoned.
 pub fn sweep_orphan_pages() {
+    #[cfg(feature = "tracing")]
+    let _span =
+        crate::tracing::internal::trace_step(crate::tracing::internal::GcStep::SweepOrphans);
+
     let mut manager = segment_manager()
         .lock()
         .unwrap_or_else(PoisonError::into_inner);
@@ -3327,7 +4838,7 @@ pub fn sweep_orphan_pages() {
             })
         };
 
-        if has_survivors || has_weak_refs {
+        if has_survivors || has_weak_refs || is_page_pinned_for_ffi(header as usize) {
             (*header).clear_all_marks();
             true
         } else {
@@ -3352,7 +4863,7 @@ pub fn sweep_orphan_pages() {
                 #[allow(clippy::cast_ptr_alignment)]
                 let gc_box_ptr = obj_ptr.cast::<crate::ptr::GcBox<()>>();
                 if !(*gc_box_ptr).has_dead_flag() {
-                    ((*gc_box_ptr).drop_fn)(obj_ptr);
+                    (crate::ptr::GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
                 }
             } else {
                 let block_size = (*header).block_size as usize;
@@ -3365,7 +4876,7 @@ pub fn sweep_orphan_pages() {
                         #[allow(clippy::cast_ptr_alignment)]
                         let gc_box_ptr = obj_ptr.cast::<crate::ptr::GcBox<()>>();
                         if !(*gc_box_ptr).has_dead_flag() {
-                            ((*gc_box_ptr).drop_fn)(obj_ptr);
+                            (crate::ptr::GcBox::drop_fn_of(gc_box_ptr))(obj_ptr);
                         }
                     }
                 }
@@ -3373,21 +4884,18 @@ pub fn sweep_orphan_pages() {
         }
     }
 
-    // Phase 2: Reclaim memory and clean up large_object_map entries.
+    // Phase 2: Reclaim memory and clean up large object map entries.
     for (addr, size, is_large, header_addr) in to_reclaim {
         unsafe {
-            sys_alloc::Mmap::from_raw(addr as *mut u8, size);
+            if is_large {
+                release_large_pages(addr as *mut u8, size);
+            } else {
+                unmap_pages(addr as *mut u8, size);
+            }
         }
 
         if is_large {
-            let mut manager = segment_manager()
-                .lock()
-                .unwrap_or_else(PoisonError::into_inner);
-            let ps = page_size();
-            for p in 0..(size / ps) {
-                let page_addr = header_addr + (p * ps);
-                manager.large_object_map.remove(&page_addr);
-            }
+            unregister_large_object(header_addr, size / page_size());
         }
     }
 }
@@ -3442,7 +4950,22 @@ impl ThreadLocalHeap {
 
 impl Drop for ThreadLocalHeap {
     fn drop(&mut self) {
+        // Deferred finalizers belong to this thread; run them while it is
+        // still registered and its pages are not yet orphaned.
+        unsafe { crate::gc::drain_deferred_finalizers_at_exit(self.tcb.heap.get()) };
         let thread_id = std::thread::current().id();
+        // Thread-local roots end with the thread; their owners may not have
+        // been destroyed yet, but nothing on this thread can reach them now.
+        // Weak handles can no longer be upgraded either.
+        {
+            let mut roots = self
+                .tcb
+                .cross_thread_roots
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            roots.thread_local.clear();
+            roots.release_weak_entries(|_| true);
+        }
         migrate_roots_to_orphan(&self.tcb, thread_id);
 
         let mut registry = thread_registry()
@@ -3451,6 +4974,12 @@ impl Drop for ThreadLocalHeap {
         if self.tcb.state.load(Ordering::SeqCst) == THREAD_STATE_EXECUTING {
             registry.active_count.fetch_sub(1, Ordering::SeqCst);
         }
+        // Stop counting our pages while still registered, so the heap is never
+        // seen owning pages without a registry entry.
+        #[cfg(debug_assertions)]
+        unsafe {
+            (*self.tcb.heap.get()).release_owns_pages();
+        }
         registry.unregister_thread(&self.tcb);
     }
 }
@@ -3540,8 +5069,9 @@ pub unsafe fn mark_page_dirty_for_ptr(ptr: *const u8) {
     HEAP.with(|local| {
         let heap = unsafe { &mut *local.tcb.heap.get() };
 
-        // Handle large objects: ptr may be in any page of a multi-page large object
-        if let Some(&(head_addr, size, h_size)) = heap.large_object_map.get(&page_addr) {
+        // Handle large objects from any thread: ptr may be in any page of a
+        // multi-page large object
+        if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
             if ptr_addr >= head_addr + h_size && ptr_addr < head_addr + h_size + size {
                 let header = unsafe { NonNull::new_unchecked(head_addr as *mut PageHeader) };
                 // Skip if slot was swept; avoids corrupting dirty tracking with reused slot (bug215).
@@ -3566,25 +5096,6 @@ pub unsafe fn mark_page_dirty_for_ptr(ptr: *const u8) {
                     heap.add_to_dirty_pages(header);
                 }
             }
-            return;
-        }
-
-        // Cross-thread: ptr may be in another thread's large object (segment_manager has global map)
-        if let Some(&(head_addr, size, h_size)) = segment_manager()
-            .lock()
-            .unwrap_or_else(PoisonError::into_inner)
-            .large_object_map
-            .get(&page_addr)
-        {
-            if ptr_addr >= head_addr + h_size && ptr_addr < head_addr + h_size + size {
-                let header = unsafe { NonNull::new_unchecked(head_addr as *mut PageHeader) };
-                // Skip if slot was swept; avoids corrupting dirty tracking with reused slot (bug215).
-                unsafe {
-                    if (*header.as_ptr()).is_allocated(0) {
-                        heap.add_to_dirty_pages(header);
-                    }
-                }
-            }
         }
     });
 }
@@ -3655,6 +5166,20 @@ pub unsafe fn ptr_to_object_index(ptr: *const u8) -> Option<usize> {
 // Removed duplicate definitions of ptr_to_page_header, is_gc_pointer, ptr_to_object_index
 // (The new NonNull versions are defined above)
 
+/// Whether `addr` lies in an allocated slot of a GC page.
+///
+/// # Safety
+///
+/// The page containing `addr` must still be mapped.
+unsafe fn is_allocated_gc_box(addr: usize) -> bool {
+    // SAFETY: `ptr_to_object_index` checks the page magic before using the
+    // header; the caller guarantees the page is mapped.
+    unsafe {
+        ptr_to_object_index(addr as *const u8)
+            .is_some_and(|idx| (*ptr_to_page_header(addr as *const u8).as_ptr()).is_allocated(idx))
+    }
+}
+
 /// Validate that a pointer is within a GC-managed page.
 ///
 /// # Safety
@@ -3709,7 +5234,7 @@ pub unsafe fn find_gc_box_from_ptr(
         // 3. Check large object map first (handles multi-page objects and avoids reading uninit tail pages)
         let page_addr = addr & crate::heap::page_mask();
         let (header_ptr_to_use, block_size_to_use, header_size_to_use, offset_to_use) =
-            if let Some(&(head_addr, size, h_size)) = heap.large_object_map.get(&page_addr) {
+            if let Some((head_addr, size, h_size)) = heap.large_object_entry(page_addr) {
                 let h_ptr = head_addr as *mut PageHeader;
 
                 // Recover provenance for Miri
@@ -3810,7 +5335,7 @@ pub unsafe fn find_gc_box_from_ptr(
 ///
 /// This is the fallback path for `find_gc_box_from_ptr` when no live
 /// `LocalHeap` contains the target address. It handles:
-/// - Large objects: via `segment_manager().large_object_map`
+/// - Large objects: via [`large_object_entry`]
 /// - Small objects: via `segment_manager().orphan_by_addr`
 ///
 /// # Safety
@@ -3827,14 +5352,8 @@ pub unsafe fn find_gc_box_from_orphan(ptr: *const u8) -> Option<NonNull<crate::p
 
     let page_addr = addr & page_mask();
 
-    let manager = segment_manager()
-        .lock()
-        .unwrap_or_else(PoisonError::into_inner);
-
-    // 1. Try global large_object_map (covers multi-page large objects from any thread)
-    if let Some(&(head_addr, size, h_size)) = manager.large_object_map.get(&page_addr) {
-        drop(manager); // release lock before pointer arithmetic
-
+    // 1. Try the global large object map (covers multi-page large objects from any thread)
+    if let Some((head_addr, size, h_size)) = large_object_entry(page_addr) {
         if addr < head_addr + h_size {
             return None; // points into header area
         }
@@ -3847,6 +5366,10 @@ pub unsafe fn find_gc_box_from_orphan(ptr: *const u8) -> Option<NonNull<crate::p
         return Some(unsafe { NonNull::new_unchecked(obj_ptr.cast::<crate::ptr::GcBox<()>>()) });
     }
 
+    let manager = segment_manager()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
     // 2. Try orphan small pages (O(1) lookup)
     if let Some(orphan) = manager.orphan_by_addr.get(&page_addr) {
         if !orphan.is_large {
@@ -3917,7 +5440,6 @@ fn clear_local_heap() {
 
         heap.pages.clear();
         heap.small_pages.clear();
-        heap.large_object_map.clear();
         for vec in &mut heap.pages_by_class {
             vec.clear();
         }
@@ -3935,6 +5457,7 @@ fn clear_local_heap() {
 
         // Clear dirty page tracking to prevent dangling pointers and stale state
         heap.dirty_pages.lock().clear();
+        heap.dirty_page_batch.get_mut().clear();
         heap.dirty_pages_snapshot.clear();
     });
 }
@@ -3973,23 +5496,34 @@ pub unsafe fn reset_for_testing() {
     if let Some(registry) = THREAD_REGISTRY.get() {
         let result = registry.lock();
         if let Ok(mut guard) = result {
+            // These heaps stay alive but are no longer registered on purpose.
+            #[cfg(debug_assertions)]
+            for tcb in &guard.threads {
+                unsafe { (*tcb.heap.get()).exempt_owns_pages() };
+            }
             guard.threads.clear();
             guard.active_count.store(0, Ordering::SeqCst);
             guard.gc_in_progress.store(false, Ordering::SeqCst);
         }
     }
 
+    large_object_map()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+
     // Clear segment manager (handle PoisonError gracefully)
     if let Some(manager) = SEGMENT_MANAGER.get() {
         let result = manager.lock();
         if let Ok(mut guard) = result {
             guard.free_pages.clear();
             guard.quarantined.clear();
-            guard.large_object_map.clear();
             guard.orphan_by_addr.clear();
         }
     }
 
     // Clear current thread's local heap
     clear_local_heap();
+    #[cfg(debug_assertions)]
+    HEAP.with(|local| unsafe { (*local.tcb.heap.get()).exempt_owns_pages() });
 }
@@ -61,15 +61,25 @@
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 #![allow(clippy::clone_on_copy)]
 
+mod alloc_profile;
+mod builder;
 pub mod cell;
+mod deep_eq;
+mod ffi;
 pub mod gc;
+mod gc_heap;
 pub mod handles;
 mod metrics;
+#[cfg(feature = "gc-mutation-log")]
+mod mutation_log;
+mod pressure;
 mod ptr;
 mod scan;
+mod slice;
 mod stack;
 mod trace;
 mod trace_closure;
+mod traverse;
 
 pub mod sync;
 
@@ -79,6 +89,9 @@ mod tracing;
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+#[cfg(feature = "rayon")]
+pub mod rayon;
+
 /// `BiBOP` memory management internals.
 ///
 /// This module is public for testing and advanced use cases.
@@ -86,12 +99,21 @@ pub mod tokio;
 pub mod heap;
 
 // Re-export public API
+pub use alloc_profile::{start_alloc_profiling, stop_alloc_profiling, AllocProfile, AllocSample};
 pub use cell::GcCell;
-pub use cell::{GcCapture, GcThreadSafeCell, GcThreadSafeRefMut};
+pub use cell::{
+    BorrowState, GcCapture, GcRef, GcThreadSafeCell, GcThreadSafeRefMut, GcWeakCell, GcWeakVec,
+    NoGcPointers,
+};
+pub use deep_eq::{deep_eq, DeepEq, DeepEqContext};
+pub use ffi::FfiLock;
 pub use gc::incremental::{
-    is_incremental_marking_active, is_write_barrier_active, mark_new_object_black,
+    clear_fallback_logger, incremental_mark_progress, incremental_worklist_len,
+    is_incremental_marking_active, is_write_barrier_active, last_fallback_reason,
+    mark_new_object_black, satb_overflow_count, set_fallback_logger, set_satb_buffer_capacity,
     IncrementalConfig, IncrementalMarkState, MarkPhase, MarkSliceResult, MarkStats,
 };
+pub use gc_heap::GcHeap;
 
 #[cfg(feature = "debug-suspicious-sweep")]
 pub use gc::{
@@ -138,7 +160,9 @@ pub fn get_incremental_config() -> gc::incremental::IncrementalConfig {
 ///
 /// This function allows the GC to run during long-running computations,
 /// which is particularly useful when incremental marking is enabled.
-/// When incremental marking is not active, this is a no-op.
+/// It also runs any finalizers queued by deferred finalization (see
+/// [`set_deferred_finalization`]). Otherwise, when incremental marking is not
+/// active, this is a no-op.
 ///
 /// # Examples
 ///
@@ -157,7 +181,10 @@ pub fn get_incremental_config() -> gc::incremental::IncrementalConfig {
 /// }
 /// ```
 pub fn yield_now() {
-    if crate::gc::incremental::is_incremental_marking_active() {
+    crate::gc::run_deferred_finalizers();
+    if crate::gc::incremental::is_incremental_marking_active()
+        && !crate::gc::is_collector_thread_cycle_active()
+    {
         let config = get_incremental_config();
         let budget = config.increment_size;
         crate::heap::with_heap(|heap| {
@@ -166,28 +193,56 @@ pub fn yield_now() {
     }
 }
 pub use gc::{
-    collect, collect_full, default_collect_condition, safepoint, set_collect_condition,
-    set_gc_enabled, CollectInfo, PerThreadMarkQueue, StealQueue,
+    alloc_counters, collect, collect_full, collect_full_within, collect_if, collect_large_objects,
+    collector_thread_stats, current_gc_policy_config, default_collect_condition,
+    enable_collector_thread, enable_collector_thread_with, gc_critical, gc_thread_report,
+    heap_trim_threshold, is_collect_requested, is_collector_thread_enabled,
+    is_deferred_finalization_enabled, is_gc_paused, last_gc_watchdog_report, pause_time_target,
+    request_collect_deferred, run_deferred_finalizers, safepoint, set_collect_condition,
+    set_collect_every_n_allocations, set_deferred_finalization, set_gc_enabled, set_gc_policy,
+    set_gc_watchdog_action, set_gc_watchdog_timeout, set_heap_trim_threshold,
+    set_pause_time_target, trim_heap, AllocCounters, CollectInfo, CollectWithinResult,
+    CollectorThread, CollectorThreadConfig, CollectorThreadStats, GcPolicy, GcPolicyConfig,
+    GcWatchdogAction, NoGcGuard, PerThreadMarkQueue, StealQueue,
 };
 pub use handles::{
-    AsyncHandle, AsyncHandleGuard, AsyncHandleScope, EscapeableHandleScope, Handle, HandleScope,
-    MaybeHandle, SealedHandleScope,
+    AsyncHandle, AsyncHandleGuard, AsyncHandleScope, EscapeableHandleScope, GcChain, GcIter,
+    GcRoot, GcThreadLocalRoot, Handle, HandleScope, MaybeHandle, SealedHandleScope, WeakHandle,
+};
+pub use heap::{
+    clear_oom_handler, configure_heap, heap_limit, set_heap_limit, set_oom_handler, size_classes,
+    HeapConfig, HeapConfigError, OomInfo,
 };
 pub use metrics::{
-    current_heap_size, current_old_size, current_young_size, gc_history, global_metrics,
-    last_gc_metrics, CollectionType, FallbackReason, GcHistory, GcMetrics, GlobalMetrics,
+    clear_gc_observer, cumulative_gc_stats, current_heap_size, current_old_size,
+    current_reserved_size, current_young_size, gc_history, gc_metrics_prometheus, global_metrics,
+    last_gc_metrics, set_gc_observer, total_reserved_size, CollectionType, FallbackReason,
+    GcHistory, GcMetrics, GcStatsSnapshot, GlobalMetrics,
+};
+
+pub use builder::GcBuilder;
+#[cfg(feature = "type-tracking")]
+pub use metrics::last_collection_reclaimed_by_type;
+#[cfg(feature = "gc-mutation-log")]
+pub use mutation_log::{clear_mutation_log, mutation_log, MutationRecord, MUTATION_LOG_CAPACITY};
+pub use pressure::{
+    register_memory_pressure_handler, register_memory_pressure_handler_at, MemoryPressureHandler,
 };
-pub use ptr::{Ephemeron, Gc, GcBox, Weak};
+pub use ptr::{Ephemeron, Gc, GcBox, Weak, WeakGc, MAX_REF_COUNT};
 pub use scan::scan_heap_region_conservatively;
-pub use trace::{Trace, Visitor};
-pub use trace_closure::TraceClosure;
+pub use slice::GcSlice;
+pub use trace::{Trace, TraceLeaf, TraceOrder, Visitor};
+pub use trace_closure::{GcRoots, TraceClosure, TraceClosureBuilder};
+#[cfg(feature = "type-tracking")]
+pub use traverse::find_objects_of_type;
+pub use traverse::{is_reachable, retaining_path, GcTraversal, SubgraphStats};
 
 #[cfg(feature = "tracing")]
 pub use tracing::GcId;
 
 // Re-export derive macros when feature is enabled
 #[cfg(feature = "derive")]
-pub use rudo_gc_derive::Trace;
+pub use rudo_gc_derive::{DeepEq, GcTransparent, Trace};
 
 #[doc(hidden)]
 pub mod test_util {
@@ -227,6 +282,20 @@ pub mod test_util {
         unsafe { crate::stack::clear_registers() };
     }
 
+    /// Overwrite the strong and weak counts of `gc`'s object.
+    ///
+    /// Lets tests drive a count to [`MAX_REF_COUNT`](crate::MAX_REF_COUNT)
+    /// without creating that many references.
+    ///
+    /// # Safety
+    ///
+    /// The real counts must be restored before the object is dropped or
+    /// collected.
+    pub unsafe fn set_ref_counts<T: crate::Trace>(gc: &crate::Gc<T>, strong: usize, weak: usize) {
+        // SAFETY: the caller restores the counts before the object goes away.
+        unsafe { (*gc.raw_ptr()).set_counts(strong, weak) };
+    }
+
     /// Reset all global GC state for test isolation.
     ///
     /// This function clears:
@@ -236,6 +305,8 @@ pub mod test_util {
     /// - Page size cache
     /// - Test roots
     ///
+    /// Metrics are kept; call [`reset_metrics`] as well to clear them.
+    ///
     /// Call this at the start of each test to ensure clean state:
     ///
     /// ```ignore
@@ -252,6 +323,24 @@ pub mod test_util {
         clear_test_roots();
         crate::gc::incremental::IncrementalMarkState::global().reset();
     }
+
+    /// Like [`reset`], and guaranteed to keep
+    /// [`global_metrics`](crate::global_metrics),
+    /// [`gc_history`](crate::gc_history) and
+    /// [`last_gc_metrics`](crate::last_gc_metrics).
+    ///
+    /// Lets a multi-phase benchmark start each phase from an empty heap while
+    /// the collection statistics keep adding up across phases.
+    pub fn reset_preserving_metrics() {
+        reset();
+    }
+
+    /// Clear [`global_metrics`](crate::global_metrics),
+    /// [`gc_history`](crate::gc_history) and this thread's
+    /// [`last_gc_metrics`](crate::last_gc_metrics), which [`reset`] keeps.
+    pub fn reset_metrics() {
+        crate::metrics::reset_metrics();
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
 //! GC metrics and statistics.
 
 use std::cell::{Cell, UnsafeCell};
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
 /// Re-export `FallbackReason` from incremental module.
@@ -22,7 +23,7 @@ pub struct GcMetrics {
     pub objects_surviving: usize,
     /// Type of collection (Minor or Major).
     pub collection_type: CollectionType,
-    /// Total collections since process start.
+    /// Collections recorded on this thread so far, counting this one.
     pub total_collections: usize,
     /// Duration of the clear phase.
     pub clear_duration: Duration,
@@ -40,6 +41,12 @@ pub struct GcMetrics {
     pub fallback_occurred: bool,
     /// Reason for fallback, if any.
     pub fallback_reason: FallbackReason,
+    /// Bytes of this thread's empty pages returned to the OS since its
+    /// previous collection, by [`trim_heap`](crate::trim_heap) or by an
+    /// automatic trim. A collection that another thread runs trims this
+    /// thread's heap too; those bytes are reported here at this thread's
+    /// next collection.
+    pub bytes_returned: usize,
 }
 
 impl Default for GcMetrics {
@@ -68,6 +75,7 @@ impl GcMetrics {
             slices_executed: 0,
             fallback_occurred: false,
             fallback_reason: FallbackReason::None,
+            bytes_returned: 0,
         }
     }
 }
@@ -196,6 +204,7 @@ pub struct GlobalMetrics {
     bytes_reclaimed: AtomicUsize,
     objects_reclaimed: AtomicUsize,
     pause_ns: AtomicU64,
+    max_pause_ns: AtomicU64,
     fallbacks: AtomicUsize,
 }
 
@@ -218,6 +227,7 @@ impl GlobalMetrics {
             bytes_reclaimed: AtomicUsize::new(0),
             objects_reclaimed: AtomicUsize::new(0),
             pause_ns: AtomicU64::new(0),
+            max_pause_ns: AtomicU64::new(0),
             fallbacks: AtomicUsize::new(0),
         }
     }
@@ -271,12 +281,82 @@ impl GlobalMetrics {
         Duration::from_nanos(self.pause_ns.load(Ordering::Relaxed))
     }
 
+    /// Returns the longest single GC pause.
+    #[inline]
+    #[must_use]
+    pub fn max_pause_time(&self) -> Duration {
+        Duration::from_nanos(self.max_pause_ns.load(Ordering::Relaxed))
+    }
+
     /// Returns the total number of STW fallbacks from incremental marking.
     #[inline]
     #[must_use]
     pub fn total_fallbacks(&self) -> usize {
         self.fallbacks.load(Ordering::Relaxed)
     }
+
+    /// Copy every counter into a [`GcStatsSnapshot`].
+    ///
+    /// Each counter is read on its own, so a collection finishing meanwhile
+    /// may show up in some fields and not others.
+    #[must_use]
+    pub fn snapshot(&self) -> GcStatsSnapshot {
+        GcStatsSnapshot {
+            total_collections: self.total_collections(),
+            minor_collections: self.total_minor_collections(),
+            major_collections: self.total_major_collections(),
+            incremental_collections: self.total_incremental_collections(),
+            total_pause_time: self.total_pause_time(),
+            max_pause_time: self.max_pause_time(),
+            total_bytes_reclaimed: self.total_bytes_reclaimed(),
+            total_objects_reclaimed: self.total_objects_reclaimed(),
+            total_fallbacks: self.total_fallbacks(),
+        }
+    }
+
+    /// Zero every counter, as if no collection had run yet.
+    fn reset(&self) {
+        for counter in [
+            &self.collections,
+            &self.minor_collections,
+            &self.major_collections,
+            &self.incremental_collections,
+            &self.bytes_reclaimed,
+            &self.objects_reclaimed,
+            &self.fallbacks,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.pause_ns.store(0, Ordering::Relaxed);
+        self.max_pause_ns.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Cumulative GC statistics across all threads at one point in time.
+///
+/// Unlike [`GcMetrics`], which describes a single collection, these are
+/// totals since process start (or the last metrics reset). Take two
+/// snapshots and subtract to measure a stretch of the program.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStatsSnapshot {
+    /// Collections of any type.
+    pub total_collections: usize,
+    /// Minor collections.
+    pub minor_collections: usize,
+    /// Major stop-the-world collections.
+    pub major_collections: usize,
+    /// Incremental major collections.
+    pub incremental_collections: usize,
+    /// Time spent in GC pauses.
+    pub total_pause_time: Duration,
+    /// Longest single GC pause.
+    pub max_pause_time: Duration,
+    /// Bytes reclaimed.
+    pub total_bytes_reclaimed: usize,
+    /// Objects reclaimed.
+    pub total_objects_reclaimed: usize,
+    /// Incremental collections that fell back to stop-the-world.
+    pub total_fallbacks: usize,
 }
 
 static GLOBAL_METRICS: GlobalMetrics = GlobalMetrics::new();
@@ -299,6 +379,26 @@ pub fn global_metrics() -> &'static GlobalMetrics {
     &GLOBAL_METRICS
 }
 
+/// Get a snapshot of the cumulative GC statistics.
+///
+/// Shorthand for `global_metrics().snapshot()`.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{collect_full, cumulative_gc_stats};
+///
+/// let before = cumulative_gc_stats();
+/// collect_full();
+/// let after = cumulative_gc_stats();
+/// assert!(after.total_collections > before.total_collections);
+/// assert!(after.max_pause_time >= before.max_pause_time);
+/// ```
+#[must_use]
+pub fn cumulative_gc_stats() -> GcStatsSnapshot {
+    GLOBAL_METRICS.snapshot()
+}
+
 /// Get the current heap size for this thread.
 ///
 /// Returns the total bytes allocated in this thread's heap,
@@ -365,6 +465,136 @@ pub fn current_old_size() -> usize {
         .unwrap_or(0)
 }
 
+/// Get the page memory reserved by this thread's heap.
+///
+/// Unlike [`current_heap_size`], this includes free slots on the pages the
+/// heap holds. Returns 0 if the current thread doesn't have a heap.
+#[must_use]
+pub fn current_reserved_size() -> usize {
+    crate::heap::HEAP
+        .try_with(|h| unsafe { &*h.tcb.heap.get() }.reserved_bytes())
+        .unwrap_or(0)
+}
+
+/// Get the page memory mapped by all threads' heaps, including pages
+/// orphaned by threads that have exited.
+///
+/// This is the total [`set_heap_limit`](crate::set_heap_limit) applies to.
+/// Large objects the `large-object-malloc` feature places with the system
+/// allocator are not included.
+#[must_use]
+pub fn total_reserved_size() -> usize {
+    crate::heap::mapped_bytes()
+}
+
+/// Render GC statistics in the Prometheus text exposition format.
+///
+/// Cumulative counters come from [`global_metrics`] and the last pause from
+/// [`gc_history`]. Reserved page memory is [`total_reserved_size`], summed
+/// over every thread; live bytes are only the calling thread's, since other
+/// threads' allocation counters cannot be read while they run. All metric
+/// names are prefixed with `rudo_gc_`.
+///
+/// # Example
+///
+/// ```
+/// let text = rudo_gc::gc_metrics_prometheus();
+/// assert!(text.contains("# TYPE rudo_gc_collections_total counter"));
+/// ```
+#[must_use]
+pub fn gc_metrics_prometheus() -> String {
+    use std::fmt::{Display, Write};
+
+    /// Write one metric family: its `HELP` and `TYPE` lines, then one sample
+    /// per `(labels, value)` pair.
+    fn family(
+        out: &mut String,
+        name: &str,
+        kind: &str,
+        help: &str,
+        samples: &[(&str, &dyn Display)],
+    ) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {kind}");
+        for (labels, value) in samples {
+            let _ = writeln!(out, "{name}{labels} {value}");
+        }
+    }
+
+    let g = global_metrics();
+    let last_pause = gc_history()
+        .recent(1)
+        .first()
+        .map_or(Duration::ZERO, |m| m.duration);
+    let mut out = String::new();
+
+    family(
+        &mut out,
+        "rudo_gc_collections_total",
+        "counter",
+        "Garbage collections performed, by type.",
+        &[
+            ("{type=\"minor\"}", &g.total_minor_collections()),
+            ("{type=\"major\"}", &g.total_major_collections()),
+            ("{type=\"incremental\"}", &g.total_incremental_collections()),
+        ],
+    );
+    family(
+        &mut out,
+        "rudo_gc_incremental_fallbacks_total",
+        "counter",
+        "Incremental collections that fell back to stop-the-world marking.",
+        &[("", &g.total_fallbacks())],
+    );
+    family(
+        &mut out,
+        "rudo_gc_reclaimed_bytes_total",
+        "counter",
+        "Bytes reclaimed by garbage collection.",
+        &[("", &g.total_bytes_reclaimed())],
+    );
+    family(
+        &mut out,
+        "rudo_gc_reclaimed_objects_total",
+        "counter",
+        "Objects reclaimed by garbage collection.",
+        &[("", &g.total_objects_reclaimed())],
+    );
+    family(
+        &mut out,
+        "rudo_gc_pause_seconds_total",
+        "counter",
+        "Time spent in garbage collection pauses.",
+        &[("", &g.total_pause_time().as_secs_f64())],
+    );
+    family(
+        &mut out,
+        "rudo_gc_last_pause_seconds",
+        "gauge",
+        "Duration of the most recent garbage collection pause.",
+        &[("", &last_pause.as_secs_f64())],
+    );
+    family(
+        &mut out,
+        "rudo_gc_heap_live_bytes",
+        "gauge",
+        "Bytes allocated in the calling thread's heap, by generation.",
+        &[
+            ("{generation=\"young\"}", &current_young_size()),
+            ("{generation=\"old\"}", &current_old_size()),
+        ],
+    );
+    family(
+        &mut out,
+        "rudo_gc_heap_reserved_bytes",
+        "gauge",
+        "Page memory mapped by all threads' heaps.",
+        &[("", &total_reserved_size())],
+    );
+
+    out
+}
+
 /// Ring buffer size for GC history.
 const HISTORY_SIZE: usize = 64;
 
@@ -442,6 +672,11 @@ impl GcHistory {
         }
     }
 
+    /// Forget every recorded snapshot.
+    fn clear(&self) {
+        self.write_idx.store(0, Ordering::Release);
+    }
+
     /// Get the total number of metrics recorded.
     ///
     /// This may exceed `HISTORY_SIZE` if more collections have occurred
@@ -572,6 +807,74 @@ pub fn gc_history() -> &'static GcHistory {
 thread_local! {
     static LAST_METRICS: Cell<GcMetrics> = const { Cell::new(GcMetrics::new()) };
     static TOTAL_COLLECTIONS: Cell<usize> = const { Cell::new(0) };
+    /// Metrics recorded on this thread and not yet passed to the observer.
+    static PENDING_OBSERVATIONS: Cell<Vec<GcMetrics>> = const { Cell::new(Vec::new()) };
+}
+
+/// Callback registered with [`set_gc_observer`].
+type GcObserver = Arc<dyn Fn(&GcMetrics) + Send + Sync>;
+
+static GC_OBSERVER: Mutex<Option<GcObserver>> = Mutex::new(None);
+
+/// Whether an observer is set, so collections skip the lock otherwise.
+static HAS_GC_OBSERVER: AtomicBool = AtomicBool::new(false);
+
+/// Call `f` with the metrics of every collection, the same `GcMetrics`
+/// that [`last_gc_metrics`] returns afterwards on the collecting thread.
+///
+/// A lighter alternative to the `tracing` feature for feeding collections
+/// into an application's own telemetry. `f` runs on the thread that
+/// collected, after the collection is over and mutators have resumed, so it
+/// may allocate and even collect. Replaces any earlier observer.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static SEEN: AtomicUsize = AtomicUsize::new(0);
+///
+/// rudo_gc::set_gc_observer(|_metrics| {
+///     SEEN.fetch_add(1, Ordering::Relaxed);
+/// });
+/// rudo_gc::collect_full();
+/// assert!(SEEN.load(Ordering::Relaxed) >= 1);
+/// rudo_gc::clear_gc_observer();
+/// ```
+pub fn set_gc_observer(f: impl Fn(&GcMetrics) + Send + Sync + 'static) {
+    *GC_OBSERVER.lock().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(f));
+    HAS_GC_OBSERVER.store(true, Ordering::Release);
+}
+
+/// Remove the observer set by [`set_gc_observer`].
+pub fn clear_gc_observer() {
+    HAS_GC_OBSERVER.store(false, Ordering::Release);
+    *GC_OBSERVER.lock().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Pass the metrics recorded on this thread since the last call to the
+/// observer, if one is set.
+///
+/// Called where a collection has fully ended: at the end of the public
+/// collection entry points, after an allocation that may have collected,
+/// and once a collector-thread pause resumes the mutators.
+pub fn notify_gc_observer() {
+    if crate::gc::is_collecting() {
+        return;
+    }
+    let pending = PENDING_OBSERVATIONS.with(Cell::take);
+    if pending.is_empty() {
+        return;
+    }
+    let observer = GC_OBSERVER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    if let Some(observer) = observer {
+        for metrics in &pending {
+            observer(metrics);
+        }
+    }
 }
 
 /// Get metrics from the last garbage collection.
@@ -586,6 +889,8 @@ pub fn record_metrics(metrics: GcMetrics) {
     let updated_metrics = LAST_METRICS.with(|cell| {
         let mut m = metrics;
         m.total_collections = TOTAL_COLLECTIONS.with(Cell::get);
+        m.bytes_returned = crate::heap::try_with_heap(crate::heap::LocalHeap::take_bytes_returned)
+            .unwrap_or_default();
         cell.set(m);
         m
     });
@@ -596,14 +901,13 @@ pub fn record_metrics(metrics: GcMetrics) {
         .fetch_add(updated_metrics.bytes_reclaimed, Ordering::Relaxed);
     g.objects_reclaimed
         .fetch_add(updated_metrics.objects_reclaimed, Ordering::Relaxed);
-    g.pause_ns.fetch_add(
-        updated_metrics
-            .duration
-            .as_nanos()
-            .try_into()
-            .unwrap_or(u64::MAX),
-        Ordering::Relaxed,
-    );
+    let pause_ns = updated_metrics
+        .duration
+        .as_nanos()
+        .try_into()
+        .unwrap_or(u64::MAX);
+    g.pause_ns.fetch_add(pause_ns, Ordering::Relaxed);
+    g.max_pause_ns.fetch_max(pause_ns, Ordering::Relaxed);
     match updated_metrics.collection_type {
         CollectionType::Minor => {
             g.minor_collections.fetch_add(1, Ordering::Relaxed);
@@ -621,6 +925,76 @@ pub fn record_metrics(metrics: GcMetrics) {
     }
 
     GC_HISTORY.push(updated_metrics);
+    crate::gc::adapt_to_pause(&updated_metrics);
+    if HAS_GC_OBSERVER.load(Ordering::Acquire) {
+        PENDING_OBSERVATIONS.with(|pending| {
+            let mut metrics = pending.take();
+            metrics.push(updated_metrics);
+            pending.set(metrics);
+        });
+    }
+
+    #[cfg(feature = "type-tracking")]
+    publish_reclaimed_by_type();
+}
+
+/// Clear the cumulative metrics, the history and this thread's last metrics.
+///
+/// Used by [`test_util::reset_metrics`](crate::test_util::reset_metrics).
+pub fn reset_metrics() {
+    GLOBAL_METRICS.reset();
+    GC_HISTORY.clear();
+    LAST_METRICS.with(|cell| cell.set(GcMetrics::new()));
+    TOTAL_COLLECTIONS.with(|c| c.set(0));
+    PENDING_OBSERVATIONS.with(Cell::take);
+}
+
+#[cfg(feature = "type-tracking")]
+thread_local! {
+    /// Per-type counts for the collection in progress.
+    static RECLAIMING_BY_TYPE: std::cell::RefCell<std::collections::HashMap<&'static str, usize>> =
+        std::cell::RefCell::new(std::collections::HashMap::new());
+    /// Per-type counts published by the last completed collection.
+    static LAST_RECLAIMED_BY_TYPE: std::cell::RefCell<Vec<(&'static str, usize)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Count an unreachable object found by the current collection.
+///
+/// Objects whose value is already dead, or being dropped or queued for
+/// finalization by an earlier sweep, were counted before and are skipped.
+///
+/// # Safety
+///
+/// `gc_box` must point to an allocated `GcBox` slot in a GC page.
+#[cfg(feature = "type-tracking")]
+pub unsafe fn note_reclaimed(gc_box: *const crate::ptr::GcBox<()>) {
+    let name = unsafe {
+        if (*gc_box).has_dead_flag() || (*gc_box).dropping_state() != 0 {
+            return;
+        }
+        crate::ptr::GcBox::type_info_of(gc_box).name
+    };
+    RECLAIMING_BY_TYPE.with(|counts| *counts.borrow_mut().entry(name).or_insert(0) += 1);
+}
+
+#[cfg(feature = "type-tracking")]
+fn publish_reclaimed_by_type() {
+    let mut reclaimed: Vec<_> =
+        RECLAIMING_BY_TYPE.with(|counts| counts.take().into_iter().collect());
+    reclaimed.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    LAST_RECLAIMED_BY_TYPE.with(|last| *last.borrow_mut() = reclaimed);
+}
+
+/// Objects reclaimed by the last collection on this thread, grouped by type.
+///
+/// Entries are `(std::any::type_name, count)`, most reclaimed first. Only
+/// objects whose values the collector itself dropped are counted; objects
+/// freed by their last `Gc` going away are not.
+#[cfg(feature = "type-tracking")]
+#[must_use]
+pub fn last_collection_reclaimed_by_type() -> Vec<(&'static str, usize)> {
+    LAST_RECLAIMED_BY_TYPE.with(|last| last.borrow().clone())
 }
 
 #[cfg(test)]
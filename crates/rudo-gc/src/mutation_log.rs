@@ -0,0 +1,182 @@
+//! Per-thread log of `Gc` edge mutations made through [`GcCell::borrow_mut`].
+//!
+//! Enabled by the `gc-mutation-log` feature. Every `borrow_mut` of a cell
+//! inside a `Gc` object captures the `Gc` pointers the cell holds, the same
+//! way the SATB barrier does. The pointers it holds afterwards can only be
+//! read once the returned guard is gone, so the entry stays pending until the
+//! next `borrow_mut` or [`mutation_log`] call on the thread finds the cell
+//! unborrowed. Mutations that leave the pointers unchanged are discarded.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ptr::NonNull;
+
+use crate::cell::{GcCapture, GcCell};
+use crate::ptr::GcBox;
+
+/// Number of records kept per thread; the oldest are discarded first.
+pub const MUTATION_LOG_CAPACITY: usize = 4096;
+
+/// A `GcCell` mutation that changed which `Gc` pointers the cell holds.
+///
+/// Addresses are `GcBox` addresses, as returned by
+/// [`Weak::raw_addr`](crate::Weak::raw_addr) and
+/// [`retaining_path`](crate::retaining_path).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationRecord {
+    /// The object holding the mutated cell.
+    pub container: usize,
+    /// Byte offset of the cell from `container`, identifying the field.
+    pub field_offset: usize,
+    /// The `Gc` pointers the cell held before the mutation.
+    pub old: Vec<usize>,
+    /// The `Gc` pointers the cell held after the mutation.
+    pub new: Vec<usize>,
+}
+
+type CaptureFn = Box<dyn Fn(&mut Vec<NonNull<GcBox<()>>>) -> bool>;
+
+struct Entry {
+    record: MutationRecord,
+    /// Generation of `container` when the mutation started, to tell it apart
+    /// from a later object reusing the slot.
+    generation: u32,
+    /// Reads the cell's pointers into `record.new` once it is unborrowed.
+    /// `None` once the entry is resolved.
+    capture: Option<CaptureFn>,
+}
+
+thread_local! {
+    static LOG: RefCell<VecDeque<Entry>> = const { RefCell::new(VecDeque::new()) };
+}
+
+fn addrs(ptrs: &[NonNull<GcBox<()>>]) -> Vec<usize> {
+    ptrs.iter().map(|ptr| ptr.as_ptr() as usize).collect()
+}
+
+/// Whether `container` is still the live object with `generation`.
+fn is_same_object(container: usize, generation: u32) -> bool {
+    crate::heap::try_with_heap(|heap| {
+        // SAFETY: `find_gc_box_from_ptr` accepts arbitrary addresses.
+        let found = unsafe { crate::heap::find_gc_box_from_ptr(heap, container as *const u8) };
+        found.is_some_and(|gc_box| {
+            // SAFETY: `find_gc_box_from_ptr` only returns allocated slots.
+            let gc_box = unsafe { gc_box.as_ref() };
+            std::ptr::from_ref(gc_box) as usize == container
+                && !gc_box.has_dead_flag()
+                && gc_box.dropping_state() == 0
+                && gc_box.generation() == generation
+        })
+    })
+    .unwrap_or(false)
+}
+
+/// Fill in the new pointers of every pending entry whose cell is no longer
+/// borrowed, dropping entries whose object died first or whose pointers did
+/// not change.
+fn resolve(log: &mut VecDeque<Entry>) {
+    log.retain_mut(|entry| {
+        let Some(capture) = &entry.capture else {
+            return true;
+        };
+        if !is_same_object(entry.record.container, entry.generation) {
+            return false;
+        }
+        let mut new = Vec::new();
+        if !capture(&mut new) {
+            return true;
+        }
+        entry.record.new = addrs(&new);
+        entry.capture = None;
+        entry.record.new != entry.record.old
+    });
+}
+
+/// Start a log entry for a `borrow_mut` of `cell`.
+///
+/// Cells outside the GC heap are not logged.
+pub fn record<T: GcCapture + ?Sized>(cell: &GcCell<T>) {
+    let cell_ptr = std::ptr::from_ref(cell);
+    let Some(container) = crate::heap::try_with_heap(|heap| {
+        // SAFETY: `find_gc_box_from_ptr` accepts arbitrary addresses.
+        unsafe { crate::heap::find_gc_box_from_ptr(heap, cell_ptr.cast::<u8>()) }
+    })
+    .flatten() else {
+        return;
+    };
+
+    let mut old = Vec::new();
+    if !cell.capture_unborrowed(&mut old) {
+        // `borrow_mut` is about to panic.
+        return;
+    }
+
+    let capture: Box<dyn Fn(&mut Vec<NonNull<GcBox<()>>>) -> bool + '_> =
+        // SAFETY: only called after `is_same_object` confirmed the object
+        // holding the cell is still alive.
+        Box::new(move |ptrs| unsafe { (*cell_ptr).capture_unborrowed(ptrs) });
+    // SAFETY: see above; the closure never runs once the cell may be gone.
+    let capture: CaptureFn = unsafe { std::mem::transmute(capture) };
+
+    // SAFETY: `find_gc_box_from_ptr` only returns allocated slots.
+    let generation = unsafe { container.as_ref() }.generation();
+    let container = container.as_ptr() as usize;
+    let entry = Entry {
+        record: MutationRecord {
+            container,
+            field_offset: cell_ptr.cast::<u8>() as usize - container,
+            old: addrs(&old),
+            new: Vec::new(),
+        },
+        generation,
+        capture: Some(capture),
+    };
+
+    LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        resolve(&mut log);
+        if log.len() == MUTATION_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(entry);
+    });
+}
+
+/// The `Gc` edge mutations recorded on this thread, oldest first.
+///
+/// Mutations whose `borrow_mut` guard is still alive are not included yet.
+/// At most [`MUTATION_LOG_CAPACITY`] records are kept.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{mutation_log, Gc, GcCell, Trace};
+///
+/// #[derive(Trace)]
+/// struct Node { next: GcCell<Option<Gc<u32>>> }
+///
+/// let node = Gc::new(Node { next: GcCell::new(None) });
+/// let target = Gc::new(7_u32);
+/// *node.next.borrow_mut() = Some(target.clone());
+///
+/// let log = mutation_log();
+/// assert_eq!(log.len(), 1);
+/// assert!(log[0].old.is_empty());
+/// assert_eq!(log[0].new, [Gc::downgrade(&target).raw_addr()]);
+/// ```
+#[must_use]
+pub fn mutation_log() -> Vec<MutationRecord> {
+    LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        resolve(&mut log);
+        log.iter()
+            .filter(|entry| entry.capture.is_none())
+            .map(|entry| entry.record.clone())
+            .collect()
+    })
+}
+
+/// Discard this thread's mutation log, including pending entries.
+pub fn clear_mutation_log() {
+    LOG.with(|log| log.borrow_mut().clear());
+}
@@ -0,0 +1,219 @@
+//! Collecting in response to OS memory pressure.
+//!
+//! On Linux, [`register_memory_pressure_handler`] installs a PSI (pressure
+//! stall information) trigger and listens for it on a background thread.
+//! When the kernel reports memory stalls, the listener calls
+//! [`request_collect_deferred`](crate::request_collect_deferred), so the next
+//! mutator safepoint runs a full collection instead of waiting for the usual
+//! allocation heuristics, giving containers near their memory limit a chance
+//! to shed garbage before the OOM killer steps in.
+//!
+//! On other platforms registration succeeds but never fires.
+
+use std::io;
+use std::path::Path;
+
+/// System-wide memory PSI file.
+#[cfg(target_os = "linux")]
+const PROC_MEMORY_PRESSURE: &str = "/proc/pressure/memory";
+
+/// Fire when some task stalls on memory for 150ms within a 2s window.
+///
+/// Unprivileged processes may only use windows that are multiples of 2s.
+#[cfg(target_os = "linux")]
+const PSI_TRIGGER: &[u8] = b"some 150000 2000000\0";
+
+/// A running memory-pressure listener.
+///
+/// Dropping the handler stops the listener thread and removes the trigger.
+#[derive(Debug)]
+pub struct MemoryPressureHandler {
+    /// Write end of the stop pipe; closing it wakes the listener.
+    #[cfg(target_os = "linux")]
+    stop: Option<std::os::fd::OwnedFd>,
+    #[cfg(target_os = "linux")]
+    listener: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Drop for MemoryPressureHandler {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        {
+            drop(self.stop.take());
+            if let Some(listener) = self.listener.take() {
+                let _ = listener.join();
+            }
+        }
+    }
+}
+
+/// Request a collection whenever the system reports memory pressure.
+///
+/// Uses the system-wide `/proc/pressure/memory` PSI file on Linux (kernel 4.20
+/// or newer with PSI enabled); on other platforms this is a no-op. Inside a
+/// cgroup v2 container, prefer [`register_memory_pressure_handler_at`] with
+/// the cgroup's own `memory.pressure` file.
+///
+/// # Errors
+///
+/// Returns an error if the PSI file cannot be opened or the trigger is
+/// rejected (e.g. PSI is disabled), or if the listener thread cannot start.
+pub fn register_memory_pressure_handler() -> io::Result<MemoryPressureHandler> {
+    #[cfg(target_os = "linux")]
+    {
+        register_memory_pressure_handler_at(PROC_MEMORY_PRESSURE)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(MemoryPressureHandler {})
+    }
+}
+
+/// Like [`register_memory_pressure_handler`], but listens on a specific PSI
+/// file, such as `/sys/fs/cgroup/<group>/memory.pressure`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or the trigger is rejected,
+/// or if the listener thread cannot start.
+pub fn register_memory_pressure_handler_at(
+    path: impl AsRef<Path>,
+) -> io::Result<MemoryPressureHandler> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::io::Write;
+
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)?;
+        file.write_all(PSI_TRIGGER)?;
+        // PSI signals a triggered threshold with POLLPRI.
+        spawn_listener(
+            file.into(),
+            libc::POLLPRI,
+            crate::gc::request_collect_deferred,
+        )
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        Ok(MemoryPressureHandler {})
+    }
+}
+
+/// Start a thread that calls `on_pressure` each time `source` polls with
+/// `events`, until the returned handler is dropped.
+#[cfg(target_os = "linux")]
+fn spawn_listener(
+    source: std::os::fd::OwnedFd,
+    events: libc::c_short,
+    on_pressure: fn(),
+) -> io::Result<MemoryPressureHandler> {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    let mut pipe = [0; 2];
+    if unsafe { libc::pipe2(pipe.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `pipe2` succeeded, so both descriptors are open and ours.
+    let (stop_rx, stop_tx) =
+        unsafe { (OwnedFd::from_raw_fd(pipe[0]), OwnedFd::from_raw_fd(pipe[1])) };
+
+    let listener = std::thread::Builder::new()
+        .name("rudo-gc-memory-pressure".into())
+        .spawn(move || {
+            let mut fds = [
+                libc::pollfd {
+                    fd: source.as_raw_fd(),
+                    events,
+                    revents: 0,
+                },
+                libc::pollfd {
+                    fd: stop_rx.as_raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+            ];
+            loop {
+                if unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) } < 0 {
+                    if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return;
+                }
+                // The handler was dropped, or the source went away (PSI
+                // reports POLLERR once its cgroup is removed).
+                if fds[1].revents != 0 || fds[0].revents & (libc::POLLERR | libc::POLLNVAL) != 0 {
+                    return;
+                }
+                if fds[0].revents & events != 0 {
+                    if events & libc::POLLIN != 0 {
+                        drain(&source);
+                    }
+                    on_pressure();
+                }
+            }
+        })?;
+
+    Ok(MemoryPressureHandler {
+        stop: Some(stop_tx),
+        listener: Some(listener),
+    })
+}
+
+/// Consume pending readable data so a level-triggered source stops polling.
+#[cfg(target_os = "linux")]
+fn drain(source: &std::os::fd::OwnedFd) {
+    use std::os::fd::AsRawFd;
+
+    let mut buf = [0u8; 64];
+    unsafe { libc::read(source.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) };
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::os::fd::{FromRawFd, OwnedFd};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    static NOTIFICATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Production callback plus a counter that parallel collections can't reset.
+    fn on_pressure() {
+        NOTIFICATIONS.fetch_add(1, Ordering::SeqCst);
+        crate::gc::request_collect_deferred();
+    }
+
+    #[test]
+    fn test_pressure_notification_requests_collection() {
+        // A pipe stands in for the PSI file: writing to it is the notification.
+        let mut pipe = [0; 2];
+        assert_eq!(
+            unsafe { libc::pipe2(pipe.as_mut_ptr(), libc::O_CLOEXEC) },
+            0
+        );
+        let (rx, tx) = unsafe { (OwnedFd::from_raw_fd(pipe[0]), OwnedFd::from_raw_fd(pipe[1])) };
+
+        let handler = spawn_listener(rx, libc::POLLIN, on_pressure).unwrap();
+        let mut file = std::fs::File::from(tx);
+        std::io::Write::write_all(&mut file, b"!").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while NOTIFICATIONS.load(Ordering::SeqCst) == 0 {
+            assert!(
+                Instant::now() < deadline,
+                "pressure did not request a collection"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        drop(handler);
+        // The notification was drained, so it fired exactly once.
+        assert_eq!(NOTIFICATIONS.load(Ordering::SeqCst), 1);
+
+        // The requested collection runs at the next safepoint.
+        crate::gc::safepoint();
+        assert!(!crate::gc::is_collect_requested());
+    }
+}
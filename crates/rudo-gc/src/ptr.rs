@@ -14,8 +14,8 @@ use std::sync::atomic::{AtomicPtr, AtomicU32, AtomicUsize, Ordering};
 use crate::cell::GcCapture;
 use crate::gc::incremental::mark_new_object_black;
 use crate::gc::notify_dropped_gc;
-use crate::heap::{with_heap, LocalHeap};
-use crate::trace::{GcVisitor, Trace, Visitor};
+use crate::heap::{with_heap, ObjectFns};
+use crate::trace::{GcVisitor, Trace, Visitor, VisitorKind};
 
 /// Minimum valid heap address.
 ///
@@ -38,9 +38,17 @@ pub struct GcBox<T: Trace + ?Sized> {
     /// Uses `AtomicUsize` for thread-safe weak reference counting.
     weak_count: AtomicUsize,
     /// Type-erased destructor for the value.
+    ///
+    /// With `thin-headers` this lives in the `PageHeader` instead; see
+    /// [`GcBox::drop_fn_of`].
+    #[cfg(not(feature = "thin-headers"))]
     pub(crate) drop_fn: unsafe fn(*mut u8),
     /// Type-erased trace function for the value.
+    #[cfg(not(feature = "thin-headers"))]
     pub(crate) trace_fn: unsafe fn(*const u8, &mut GcVisitor),
+    /// Type of the value (`type-tracking`); see [`GcBox::type_info_of`].
+    #[cfg(all(feature = "type-tracking", not(feature = "thin-headers")))]
+    type_info: fn() -> crate::heap::TypeInfo,
     /// Flag indicating the object is being dropped (prevents `weak::upgrade` race).
     is_dropping: AtomicUsize,
     /// Per-object generation. Incremented on each allocation to detect slot reuse (bug347).
@@ -50,6 +58,26 @@ pub struct GcBox<T: Trace + ?Sized> {
     value: T,
 }
 
+/// The most strong or weak references a single object can have.
+///
+/// The weak count shares its word with three flag bits, so both counts are
+/// capped at `usize::MAX >> 3`. Going past the cap aborts the process, as
+/// `Arc` does, instead of wrapping around to a small count that would free
+/// an object still in use.
+pub const MAX_REF_COUNT: usize = usize::MAX >> 3;
+
+/// Abort on a reference count passing [`MAX_REF_COUNT`].
+///
+/// Only reachable by leaking references (`mem::forget` in a loop), so there
+/// is nothing to recover; unwinding could run drops that rely on the count.
+#[cold]
+#[inline(never)]
+fn ref_count_overflow() -> ! {
+    std::process::abort()
+}
+
+const _: () = assert!(MAX_REF_COUNT == !GcBox::<()>::FLAGS_MASK);
+
 impl<T: Trace + ?Sized> GcBox<T> {
     /// Bit mask for the "value dead" flag (highest bit).
     const DEAD_FLAG: usize = 1 << (usize::BITS - 1);
@@ -101,7 +129,7 @@ impl<T: Trace + ?Sized> GcBox<T> {
     /// Uses CAS to prevent races with concurrent upgrade.
     /// The failure ordering is Acquire to ensure we see other threads' state changes.
     #[inline]
-    fn try_mark_dropping(&self) -> bool {
+    pub(crate) fn try_mark_dropping(&self) -> bool {
         self.is_dropping
             .compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire)
             .is_ok()
@@ -132,17 +160,15 @@ impl<T: Trace + ?Sized> GcBox<T> {
 
     /// Increment the reference count.
     /// Uses Relaxed ordering since this is just a counter increment.
+    ///
+    /// Aborts the process if the count would exceed [`MAX_REF_COUNT`].
     pub fn inc_ref(&self) {
-        // Saturating add to prevent overflow - saturates at isize::MAX
-        self.ref_count
-            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
-                if count == usize::MAX {
-                    None // Stay at MAX
-                } else {
-                    Some(count.saturating_add(1))
-                }
-            })
-            .ok();
+        // Like `Arc`, check after the add: reaching `usize::MAX` from here
+        // would take more threads racing past the check than can exist.
+        let old = self.ref_count.fetch_add(1, Ordering::Relaxed);
+        if old >= MAX_REF_COUNT {
+            ref_count_overflow();
+        }
     }
 
     /// Try to increment `ref_count` only if it is currently > 0.
@@ -151,15 +177,18 @@ impl<T: Trace + ?Sized> GcBox<T> {
     /// another thread has just dropped (`ref_count` 1->0).
     #[inline]
     pub(crate) fn try_inc_ref_if_nonzero(&self) -> bool {
-        self.ref_count
+        let Ok(old) = self
+            .ref_count
             .fetch_update(Ordering::Acquire, Ordering::Acquire, |c| {
-                if c > 0 && c < usize::MAX {
-                    Some(c + 1)
-                } else {
-                    None
-                }
+                (c > 0).then(|| c + 1)
             })
-            .is_ok()
+        else {
+            return false;
+        };
+        if old >= MAX_REF_COUNT {
+            ref_count_overflow();
+        }
+        true
     }
 
     /// Decrement the reference count. Returns true if count reached zero.
@@ -194,11 +223,8 @@ impl<T: Trace + ?Sized> GcBox<T> {
                     // SAFETY: We're the last reference and marked as dropping,
                     // safe to drop. The drop function handles value dropping.
                     unsafe {
-                        (this.drop_fn)(self_ptr.cast::<u8>());
+                        drop_last_ref(self_ptr.cast::<GcBox<()>>());
                     }
-                    // Ensure ref_count reflects "count reached zero" semantics (bug263).
-                    // Release ordering: drop_fn happens-before other threads observe ref_count==0.
-                    this.ref_count.store(0, Ordering::Release);
                     return true;
                 }
                 // CAS failed - another thread beat us to marking
@@ -242,6 +268,23 @@ impl<T: Trace + ?Sized> GcBox<T> {
         &self.value
     }
 
+    /// Overwrite the strong and weak counts, keeping the flag bits.
+    ///
+    /// For tests that need counts no real program reaches.
+    ///
+    /// # Safety
+    ///
+    /// The object must not be dropped, collected or freed while the counts
+    /// disagree with the references that actually exist.
+    pub(crate) unsafe fn set_counts(&self, strong: usize, weak: usize) {
+        self.ref_count.store(strong, Ordering::Release);
+        self.weak_count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                Some((current & Self::FLAGS_MASK) | (weak & !Self::FLAGS_MASK))
+            })
+            .ok();
+    }
+
     /// Get the weak reference count.
     pub fn weak_count(&self) -> usize {
         self.weak_count.load(Ordering::Relaxed) & !Self::FLAGS_MASK
@@ -264,18 +307,19 @@ impl<T: Trace + ?Sized> GcBox<T> {
     /// Increment the weak reference count.
     /// Uses Relaxed ordering since weak count is advisory only.
     /// Uses `fetch_update` to avoid lost updates under concurrent `inc_weak` calls.
+    ///
+    /// Aborts the process if the count would exceed [`MAX_REF_COUNT`], rather
+    /// than carry into the flag bits above it.
     pub fn inc_weak(&self) {
-        self.weak_count
-            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
-                let flags = current & Self::FLAGS_MASK;
-                let count = current & !Self::FLAGS_MASK;
-                if count == usize::MAX {
-                    None // Stay at MAX (overflow protection)
-                } else {
-                    Some(flags | (count + 1))
-                }
-            })
-            .ok();
+        let updated =
+            self.weak_count
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                    let count = current & !Self::FLAGS_MASK;
+                    (count < MAX_REF_COUNT).then_some(current + 1)
+                });
+        if updated.is_err() {
+            ref_count_overflow();
+        }
     }
 
     /// Try to increment `ref_count` atomically when it is currently zero.
@@ -527,9 +571,127 @@ impl<T: Trace + ?Sized> GcBox<T> {
     pub(crate) fn clear_is_dropping(&self) {
         self.is_dropping.store(0, Ordering::Release);
     }
+
+    /// The type-erased drop function for the object at `this`.
+    ///
+    /// With `thin-headers` the function is shared through the page header, so
+    /// an object whose value is not live (dead or still under construction)
+    /// gets the no-op instead; without it, the per-object field is swapped to
+    /// the no-op by [`retire_fns`](Self::retire_fns).
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a `GcBox` slot in a GC page.
+    #[inline]
+    pub(crate) unsafe fn drop_fn_of(this: *const Self) -> unsafe fn(*mut u8) {
+        #[cfg(not(feature = "thin-headers"))]
+        unsafe {
+            (*this).drop_fn
+        }
+        #[cfg(feature = "thin-headers")]
+        unsafe {
+            if (*this).has_live_value() {
+                (*crate::heap::ptr_to_page_header(this.cast::<u8>()).as_ptr()).drop_fn
+            } else {
+                GcBox::<()>::no_op_drop
+            }
+        }
+    }
+
+    /// The type-erased trace function for the object at `this`.
+    ///
+    /// See [`drop_fn_of`](Self::drop_fn_of).
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a `GcBox` slot in a GC page.
+    #[inline]
+    pub(crate) unsafe fn trace_fn_of(this: *const Self) -> unsafe fn(*const u8, &mut GcVisitor) {
+        #[cfg(not(feature = "thin-headers"))]
+        unsafe {
+            (*this).trace_fn
+        }
+        #[cfg(feature = "thin-headers")]
+        unsafe {
+            if (*this).has_live_value() {
+                (*crate::heap::ptr_to_page_header(this.cast::<u8>()).as_ptr()).trace_fn
+            } else {
+                GcBox::<()>::no_op_trace
+            }
+        }
+    }
+
+    /// The type of the value in the object at `this`.
+    ///
+    /// Only meaningful while the value is live; with `thin-headers` the type
+    /// is read from the page header.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a `GcBox` slot in a GC page.
+    #[cfg(feature = "type-tracking")]
+    #[inline]
+    pub(crate) unsafe fn type_info_of(this: *const Self) -> crate::heap::TypeInfo {
+        #[cfg(not(feature = "thin-headers"))]
+        unsafe {
+            ((*this).type_info)()
+        }
+        #[cfg(feature = "thin-headers")]
+        unsafe {
+            ((*crate::heap::ptr_to_page_header(this.cast::<u8>()).as_ptr()).type_info)()
+        }
+    }
+
+    /// Retire the object's drop and trace functions once its value is dropped.
+    ///
+    /// With `thin-headers` there is nothing to swap: the `DEAD_FLAG` that every
+    /// caller sets alongside this makes [`drop_fn_of`](Self::drop_fn_of) and
+    /// [`trace_fn_of`](Self::trace_fn_of) return the no-ops.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid `GcBox`.
+    #[inline]
+    pub(crate) const unsafe fn retire_fns(this: *mut Self) {
+        #[cfg(not(feature = "thin-headers"))]
+        unsafe {
+            (*this).drop_fn = GcBox::<()>::no_op_drop;
+            (*this).trace_fn = GcBox::<()>::no_op_trace;
+        }
+        #[cfg(feature = "thin-headers")]
+        let _ = this;
+    }
+
+    /// Whether the value has been written and not yet dropped.
+    #[cfg(feature = "thin-headers")]
+    #[inline]
+    fn has_live_value(&self) -> bool {
+        self.weak_count.load(Ordering::Acquire) & (Self::DEAD_FLAG | Self::UNDER_CONSTRUCTION_FLAG)
+            == 0
+    }
 }
 
-impl<T: Trace> GcBox<T> {
+impl<T: Trace + 'static> GcBox<T> {
+    /// The drop and trace functions for a `GcBox<T>`, used to pick its page.
+    pub(crate) const fn object_fns() -> ObjectFns {
+        ObjectFns {
+            drop_fn: Self::drop_fn_for,
+            trace_fn: Self::trace_fn_for,
+            #[cfg(feature = "type-tracking")]
+            type_info: crate::heap::TypeInfo::of::<T>,
+        }
+    }
+
+    /// Offset of the value from the start of the `GcBox`.
+    pub(crate) const fn value_offset() -> usize {
+        std::mem::offset_of!(Self, value)
+    }
+
+    /// Allocate uninitialized space for a `GcBox<T>` on this thread's heap.
+    pub(crate) fn allocate() -> NonNull<u8> {
+        with_heap(|heap| heap.alloc_typed::<Self>(Self::object_fns()))
+    }
+
     /// Type-erased drop function for any Sized T.
     pub(crate) unsafe fn drop_fn_for(ptr: *mut u8) {
         // SAFETY: The caller must ensure ptr points to a GcBox<T> where T: Sized.
@@ -550,19 +712,27 @@ impl<T: Trace> GcBox<T> {
             // which checks the dead flag and returns early if set.
             (*gc_box).set_dead();
 
+            // The collector reclaims objects without their strong count
+            // reaching zero; their callbacks are dropped unrun.
+            drop(take_last_strong_drop(gc_box.cast()));
+
             std::ptr::drop_in_place(std::ptr::addr_of_mut!((*gc_box).value));
 
             // Mark as in final dropping phase AFTER value is dropped.
             // This prevents any further reentrancy attempts.
             (*gc_box).set_final_dropping();
-            (*gc_box).drop_fn = GcBox::<()>::no_op_drop;
-            (*gc_box).trace_fn = GcBox::<()>::no_op_trace;
+            Self::retire_fns(gc_box);
         }
     }
 
     /// Type-erased trace function for any Sized T.
     pub(crate) unsafe fn trace_fn_for(ptr: *const u8, visitor: &mut GcVisitor) {
         let gc_box = ptr.cast::<Self>();
+        if visitor.kind == VisitorKind::Order {
+            // SAFETY: The caller ensures ptr points to a valid GcBox<T>
+            visitor.order = unsafe { (*gc_box).value.trace_order() };
+            return;
+        }
         // SAFETY: The caller ensures ptr points to a valid GcBox<T>
         unsafe {
             (*gc_box).value.trace(visitor);
@@ -605,12 +775,48 @@ impl<T: Trace> GcBox<T> {
 }
 
 impl GcBox<()> {
+    /// The value's [`Trace::trace_order`](crate::Trace::trace_order), asked
+    /// through its trace function with `visitor`, an `Order` visitor.
+    /// Dropped values report the default.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to an allocated `GcBox`.
+    pub(crate) unsafe fn trace_order_of(
+        this: *mut Self,
+        visitor: &mut GcVisitor,
+    ) -> crate::trace::TraceOrder {
+        visitor.order = crate::trace::TraceOrder::DEFAULT;
+        // SAFETY: the caller guarantees `this` is allocated.
+        unsafe { (Self::trace_fn_of(this))(this.cast::<u8>(), visitor) };
+        visitor.order
+    }
+
     /// A no-op drop function for already-dropped objects.
     pub(crate) const unsafe fn no_op_drop(_ptr: *mut u8) {}
 
     /// A no-op trace function for already-dropped objects.
     pub(crate) const unsafe fn no_op_trace(_ptr: *const u8, _visitor: &mut GcVisitor) {}
 
+    /// Make a never-allocated slot safe to find by scanning: its drop and
+    /// trace functions must be no-ops until a value is written.
+    ///
+    /// # Safety
+    /// The pointer must be valid for writes and properly aligned.
+    #[inline]
+    pub(crate) unsafe fn init_free_slot(ptr: *mut Self) {
+        // SAFETY: Caller guarantees ptr is valid and properly aligned.
+        unsafe {
+            #[cfg(not(feature = "thin-headers"))]
+            {
+                std::ptr::addr_of_mut!((*ptr).drop_fn).write(Self::no_op_drop);
+                std::ptr::addr_of_mut!((*ptr).trace_fn).write(Self::no_op_trace);
+            }
+            #[cfg(feature = "thin-headers")]
+            std::ptr::addr_of_mut!((*ptr).weak_count).write(AtomicUsize::new(Self::DEAD_FLAG));
+        }
+    }
+
     /// Initialize the `GcBox` header at the given raw pointer.
     /// Used by `alloc_large` when pre-initializing memory for defense-in-depth.
     /// Caller must ensure ptr is valid and points to uninitialized `GcBox` memory.
@@ -622,9 +828,16 @@ impl GcBox<()> {
         // SAFETY: Caller guarantees ptr is valid and properly aligned.
         unsafe {
             std::ptr::addr_of_mut!((*ptr).ref_count).write(AtomicUsize::new(1));
-            std::ptr::addr_of_mut!((*ptr).weak_count).write(AtomicUsize::new(0));
-            std::ptr::addr_of_mut!((*ptr).drop_fn).write(Self::no_op_drop);
-            std::ptr::addr_of_mut!((*ptr).trace_fn).write(Self::no_op_trace);
+            #[cfg(not(feature = "thin-headers"))]
+            {
+                std::ptr::addr_of_mut!((*ptr).weak_count).write(AtomicUsize::new(0));
+                std::ptr::addr_of_mut!((*ptr).drop_fn).write(Self::no_op_drop);
+                std::ptr::addr_of_mut!((*ptr).trace_fn).write(Self::no_op_trace);
+            }
+            // The page's functions apply to every slot, so mark the slot dead
+            // until the value is written.
+            #[cfg(feature = "thin-headers")]
+            std::ptr::addr_of_mut!((*ptr).weak_count).write(AtomicUsize::new(Self::DEAD_FLAG));
             std::ptr::addr_of_mut!((*ptr).is_dropping).write(AtomicUsize::new(0));
             std::ptr::addr_of_mut!((*ptr).generation).write(AtomicU32::new(1));
         }
@@ -1025,6 +1238,159 @@ unsafe impl<T: Trace + 'static> Send for GcBoxWeakRef<T> {}
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl<T: Trace + 'static> Sync for GcBoxWeakRef<T> {}
 
+/// Objects whose last `Gc` was dropped while this thread was already running
+/// a drop function, waiting for the outermost drop to run them.
+#[derive(Default)]
+struct DropQueue {
+    draining: bool,
+    pending: Vec<(NonNull<GcBox<()>>, unsafe fn(*mut u8))>,
+    /// Keeps the collector away while anything is queued: a queued object is
+    /// unmarked and skipped by the sweep, so nothing would keep its children
+    /// alive.
+    ///
+    /// The guard pauses collection on every thread, not just this one, until
+    /// the whole queue has drained. The collector can't see another thread's
+    /// queue, so it has no narrower way to keep the children alive.
+    no_gc: Option<crate::gc::NoGcGuard>,
+}
+
+thread_local! {
+    static DROP_QUEUE: std::cell::RefCell<DropQueue> = std::cell::RefCell::default();
+}
+
+/// A callback registered with [`Gc::on_last_strong_drop`].
+type LastStrongDrop = Box<dyn FnOnce() + Send>;
+
+/// Callbacks registered with [`Gc::on_last_strong_drop`], by `GcBox` address.
+static LAST_STRONG_DROP: std::sync::Mutex<
+    Option<std::collections::HashMap<usize, Vec<LastStrongDrop>>>,
+> = std::sync::Mutex::new(None);
+
+/// Objects with entries in `LAST_STRONG_DROP`, so that drops can skip the
+/// lock while there are none.
+static LAST_STRONG_DROP_OBJECTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Remove the callbacks registered for the object at `ptr`.
+fn take_last_strong_drop(ptr: *const GcBox<()>) -> Vec<LastStrongDrop> {
+    if LAST_STRONG_DROP_OBJECTS.load(Ordering::Acquire) == 0 {
+        return Vec::new();
+    }
+    let mut table = LAST_STRONG_DROP
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let Some(callbacks) = table.as_mut().and_then(|map| map.remove(&(ptr as usize))) else {
+        return Vec::new();
+    };
+    drop(table);
+    LAST_STRONG_DROP_OBJECTS.fetch_sub(1, Ordering::AcqRel);
+    callbacks
+}
+
+/// Run the drop function of an object whose last `Gc` was just dropped.
+///
+/// Dropping a value drops its `Gc` fields, which may be last references
+/// themselves, so running drop functions directly recurses once per link of
+/// a `Gc`-linked list and overflows the stack on long lists. Instead, a drop
+/// that starts inside another one on the same thread is queued, and the
+/// outermost drop runs the queue in a loop.
+///
+/// The object is flagged dead before anything else, so `Weak::upgrade` on it
+/// returns `None` from then on, including from inside its own `Drop`.
+///
+/// # Safety
+///
+/// `ptr` must point to a live `GcBox` that the caller has just marked as
+/// dropping.
+unsafe fn drop_last_ref(ptr: *mut GcBox<()>) {
+    // The value is still intact; weak references already see it as dropping.
+    for callback in take_last_strong_drop(ptr) {
+        callback();
+    }
+
+    // SAFETY: the caller guarantees `ptr` is live.
+    let drop_fn = unsafe { GcBox::drop_fn_of(ptr) };
+    let entry = unsafe { (NonNull::new_unchecked(ptr), drop_fn) };
+    // A queued object also needs the flag so that a sweep running first
+    // leaves its slot alone until the drop function has run.
+    // SAFETY: the caller guarantees `ptr` is live.
+    unsafe { (*ptr).set_dead() };
+
+    let queued = DROP_QUEUE
+        .try_with(|queue| {
+            let mut queue = queue.borrow_mut();
+            if !queue.draining {
+                queue.draining = true;
+                return false;
+            }
+            queue.no_gc.get_or_insert_with(crate::gc::NoGcGuard::new);
+            queue.pending.push(entry);
+            true
+        })
+        .unwrap_or(false);
+    if queued {
+        return;
+    }
+
+    let draining = Draining;
+    // SAFETY: the caller guarantees `ptr` is live.
+    unsafe { run_drop_fn(entry) };
+    drop(draining);
+}
+
+/// Runs the rest of the drop queue and leaves draining mode when the
+/// outermost [`drop_last_ref`] returns.
+///
+/// This also runs when a drop function panics. Otherwise the queue would stay
+/// in draining mode, and its `NoGcGuard` would keep the collector away for
+/// good. The queued drops still run, as a slice's elements still drop after
+/// one of them panics.
+///
+/// Dropping a long `Gc`-linked list therefore holds off collection on every
+/// thread until its last node has dropped; the skipped collection runs when
+/// the guard goes.
+struct Draining;
+
+impl Drop for Draining {
+    fn drop(&mut self) {
+        while let Some(entry) = DROP_QUEUE
+            .try_with(|queue| queue.borrow_mut().pending.pop())
+            .ok()
+            .flatten()
+        {
+            // SAFETY: queued objects stay allocated until their drop function
+            // has run.
+            unsafe { run_drop_fn(entry) };
+        }
+
+        // Dropping the guard may collect, which runs more drop functions, so
+        // the queue must not be borrowed at that point.
+        let no_gc = DROP_QUEUE
+            .try_with(|queue| {
+                let mut queue = queue.borrow_mut();
+                queue.draining = false;
+                queue.no_gc.take()
+            })
+            .ok()
+            .flatten();
+        drop(no_gc);
+    }
+}
+
+/// Run one drop function for [`drop_last_ref`].
+///
+/// # Safety
+///
+/// The object must be live and flagged dead, with its drop function not yet
+/// run.
+unsafe fn run_drop_fn((ptr, drop_fn): (NonNull<GcBox<()>>, unsafe fn(*mut u8))) {
+    unsafe {
+        drop_fn(ptr.as_ptr().cast::<u8>());
+        // Ensure ref_count reflects "count reached zero" semantics (bug263).
+        // Release ordering: drop_fn happens-before other threads observe ref_count==0.
+        (*ptr.as_ptr()).ref_count.store(0, Ordering::Release);
+    }
+}
+
 // ============================================================================
 // Nullable - A nullable pointer to unsized types
 // ============================================================================
@@ -1102,6 +1468,14 @@ impl<T: ?Sized> PartialEq for Nullable<T> {
     }
 }
 
+/// Low bit set in a `Gc` word that holds an immediate integer instead of a
+/// pointer. `GcBox`es are at least 16-byte aligned, so no pointer has it.
+const INT_TAG: usize = 0b1;
+
+/// Shift of the immediate integer within a tagged word, above the four
+/// alignment bits.
+const INT_SHIFT: u32 = 4;
+
 /// An atomic nullable pointer.
 /// Uses `AtomicUsize` to store the pointer as a raw usize.
 #[derive(Debug)]
@@ -1134,14 +1508,32 @@ impl<T: Sized> AtomicNullable<T> {
         self.ptr.store(0, Ordering::Relaxed);
     }
 
+    /// Create from a raw word, which may be an immediate tagged with
+    /// [`INT_TAG`].
+    pub(crate) const fn from_raw(word: usize) -> Self {
+        Self {
+            ptr: AtomicUsize::new(word),
+            _marker: PhantomData,
+        }
+    }
+
     /// Load the value with the given ordering.
+    ///
+    /// A word holding an immediate integer loads as null, since it points to
+    /// nothing.
     #[must_use]
     #[allow(clippy::ptr_as_ptr)]
     pub fn load(&self, ordering: Ordering) -> Nullable<T> {
         let addr = self.ptr.load(ordering);
+        let addr = if addr & INT_TAG == 0 { addr } else { 0 };
         Nullable::from_ptr(addr as *mut T)
     }
 
+    /// Load the raw word, including any immediate tag.
+    pub(crate) fn load_raw(&self, ordering: Ordering) -> usize {
+        self.ptr.load(ordering)
+    }
+
     /// Store a value with the given ordering.
     #[allow(clippy::ptr_as_ptr)]
     pub fn store(&self, ptr: Nullable<T>, ordering: Ordering) {
@@ -1224,24 +1616,209 @@ impl<T: Trace> Gc<T> {
         }
 
         // Allocate space in the heap
-        let ptr = with_heap(LocalHeap::alloc::<GcBox<T>>);
+        let ptr = GcBox::<T>::allocate();
+
+        // SAFETY: We just allocated this memory
+        let gc = unsafe { Self::init_allocated(ptr, value) };
+        crate::gc::notify_allocated();
+        gc
+    }
+
+    /// Create garbage-collected copies of plain-old-data values serialized
+    /// as raw bytes.
+    ///
+    /// `bytes` holds `bytes.len() / size_of::<T>()` consecutive values in
+    /// `T`'s in-memory representation, with no alignment requirement. All
+    /// objects are allocated under a single heap access and filled by
+    /// copying straight out of `bytes`, which makes this a fast path for
+    /// deserializing large blobs of same-type nodes.
+    ///
+    /// As with any `Vec<Gc<T>>`, the returned vector's buffer is not traced;
+    /// keep it rooted (for example in a `Gc<Vec<Gc<T>>>`) across collections.
+    ///
+    /// # Safety
+    ///
+    /// Every `size_of::<T>()`-byte chunk of `bytes` must be a valid value of
+    /// `T`, such as bytes previously copied out of a `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is zero-sized or `bytes.len()` is not a multiple of
+    /// `size_of::<T>()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    ///
+    /// let values = [1u32, 2, 3];
+    /// let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    ///
+    /// // SAFETY: every 4-byte chunk is a valid `u32`.
+    /// let gcs = unsafe { Gc::<u32>::alloc_from_bytes(&bytes) };
+    /// assert_eq!(gcs.iter().map(|gc| **gc).collect::<Vec<_>>(), values);
+    /// ```
+    #[must_use]
+    pub unsafe fn alloc_from_bytes(bytes: &[u8]) -> Vec<Self>
+    where
+        T: Copy,
+    {
+        let size = std::mem::size_of::<T>();
+        assert!(
+            size != 0,
+            "Gc::alloc_from_bytes: zero-sized types are not supported"
+        );
+        assert!(
+            bytes.len() % size == 0,
+            "Gc::alloc_from_bytes: {} bytes is not a whole number of {size}-byte values",
+            bytes.len()
+        );
+
+        with_heap(|heap| {
+            bytes
+                .chunks_exact(size)
+                .map(|chunk| {
+                    let ptr = heap.alloc_typed::<GcBox<T>>(GcBox::<T>::object_fns());
+                    // SAFETY: The caller guarantees the chunk holds a valid `T`,
+                    // and we just allocated `ptr`.
+                    unsafe {
+                        let value = chunk.as_ptr().cast::<T>().read_unaligned();
+                        Self::init_allocated(ptr, value)
+                    }
+                })
+                .collect()
+        })
+    }
 
+    /// Byte offsets of the `Gc` fields stored inline in a `T`.
+    ///
+    /// The offsets are found by running `T`'s `Trace` impl over an all-zero
+    /// instance with a visitor that records where each visited `Gc` lives,
+    /// relative to the start of the value. They are sorted and deduplicated,
+    /// giving a per-type pointer map for precise stack maps or FFI type
+    /// descriptors. `Gc`s behind indirection (such as in a `Vec`), and
+    /// fields a zeroed instance does not trace (such as a `None`
+    /// `Option<Gc<_>>`), are not reported.
+    ///
+    /// # Safety
+    ///
+    /// The all-zero bit pattern must be a valid `T`. The zeroed instance is
+    /// only traced, never dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, Trace};
+    ///
+    /// #[derive(Trace)]
+    /// #[repr(C)]
+    /// struct Pair {
+    ///     id: u64,
+    ///     left: Gc<u64>,
+    ///     right: Gc<u64>,
+    /// }
+    ///
+    /// // SAFETY: a zeroed `Gc` is a null (dead) handle and `u64` accepts zero.
+    /// let offsets = unsafe { Gc::<Pair>::pointer_offsets() };
+    /// assert_eq!(offsets, [8, 16]);
+    /// ```
+    #[must_use]
+    pub unsafe fn pointer_offsets() -> Vec<usize> {
+        struct OffsetRecorder {
+            base: usize,
+            size: usize,
+            offsets: Vec<usize>,
+        }
+
+        impl Visitor for OffsetRecorder {
+            fn visit<U: Trace>(&mut self, gc: &Gc<U>) {
+                let offset = (std::ptr::from_ref(gc) as usize).wrapping_sub(self.base);
+                if offset < self.size {
+                    self.offsets.push(offset);
+                }
+            }
+
+            unsafe fn visit_region(&mut self, _ptr: *const u8, _len: usize) {}
+        }
+
+        let zeroed = std::mem::MaybeUninit::<T>::zeroed();
+        let mut recorder = OffsetRecorder {
+            base: zeroed.as_ptr() as usize,
+            size: std::mem::size_of::<T>(),
+            offsets: Vec::new(),
+        };
+        // SAFETY: The caller guarantees the zeroed bytes are a valid `T`.
+        unsafe { zeroed.assume_init_ref() }.trace(&mut recorder);
+
+        recorder.offsets.sort_unstable();
+        recorder.offsets.dedup();
+        recorder.offsets
+    }
+
+    /// Write a new `GcBox` holding `value` into freshly allocated space.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from allocating a `GcBox<T>` and be uninitialized.
+    pub(crate) unsafe fn init_allocated(ptr: NonNull<u8>, value: T) -> Self {
         // Initialize the GcBox
         let gc_box = ptr.as_ptr().cast::<GcBox<T>>();
-        // SAFETY: We just allocated this memory
+        // SAFETY: Caller guarantees ptr is freshly allocated for a GcBox<T>
         unsafe {
             gc_box.write(GcBox {
                 ref_count: AtomicUsize::new(1),
                 weak_count: AtomicUsize::new(0),
+                #[cfg(not(feature = "thin-headers"))]
                 drop_fn: GcBox::<T>::drop_fn_for,
+                #[cfg(not(feature = "thin-headers"))]
                 trace_fn: GcBox::<T>::trace_fn_for,
+                #[cfg(all(feature = "type-tracking", not(feature = "thin-headers")))]
+                type_info: crate::heap::TypeInfo::of::<T>,
                 is_dropping: AtomicUsize::new(0),
                 generation: AtomicU32::new(1),
                 value,
             });
         }
 
-        let gc_box_ptr = unsafe { NonNull::new_unchecked(gc_box) };
+        // SAFETY: the whole `GcBox` was just written.
+        unsafe { Self::publish_allocated(ptr) }
+    }
+
+    /// Write the header of a `GcBox` whose value was already written in place.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from allocating a `GcBox<T>`, and its value must be
+    /// fully initialized.
+    pub(crate) unsafe fn init_header_in_place(ptr: NonNull<u8>) -> Self {
+        let gc_box = ptr.as_ptr().cast::<GcBox<T>>();
+        // SAFETY: Caller guarantees ptr is an allocated GcBox<T>; only the
+        // header fields are written, leaving the value untouched.
+        unsafe {
+            std::ptr::addr_of_mut!((*gc_box).ref_count).write(AtomicUsize::new(1));
+            std::ptr::addr_of_mut!((*gc_box).weak_count).write(AtomicUsize::new(0));
+            #[cfg(not(feature = "thin-headers"))]
+            {
+                std::ptr::addr_of_mut!((*gc_box).drop_fn).write(GcBox::<T>::drop_fn_for);
+                std::ptr::addr_of_mut!((*gc_box).trace_fn).write(GcBox::<T>::trace_fn_for);
+            }
+            #[cfg(all(feature = "type-tracking", not(feature = "thin-headers")))]
+            std::ptr::addr_of_mut!((*gc_box).type_info).write(crate::heap::TypeInfo::of::<T>);
+            std::ptr::addr_of_mut!((*gc_box).is_dropping).write(AtomicUsize::new(0));
+            std::ptr::addr_of_mut!((*gc_box).generation).write(AtomicU32::new(1));
+        }
+
+        // SAFETY: header and value are both initialized now.
+        unsafe { Self::publish_allocated(ptr) }
+    }
+
+    /// Hand out the first `Gc` to a fully initialized `GcBox`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a fully initialized, newly allocated `GcBox<T>`.
+    unsafe fn publish_allocated(ptr: NonNull<u8>) -> Self {
+        let gc_box_ptr = unsafe { NonNull::new_unchecked(ptr.as_ptr().cast::<GcBox<T>>()) };
 
         // Mark as black (live) during incremental marking
         // This is the SATB "black allocation" optimization
@@ -1266,7 +1843,7 @@ impl<T: Trace> Gc<T> {
     /// ZSTs don't need heap allocation - we use a sentinel address.
     #[allow(clippy::items_after_statements, clippy::cast_ptr_alignment)]
     fn new_zst(_value: T) -> Self {
-        debug_assert!(std::mem::size_of::<T>() == 0);
+        debug_assert_eq!(std::mem::size_of::<T>(), 0);
 
         // For ZSTs, we use a special sentinel address that's:
         // 1. Non-null (so we can distinguish from dead Gc)
@@ -1286,7 +1863,7 @@ impl<T: Trace> Gc<T> {
             let ptr = ZST_SINGLETON.load(Ordering::Acquire);
 
             if ptr.is_null() {
-                let alloc_ptr = with_heap(LocalHeap::alloc::<GcBox<()>>);
+                let alloc_ptr = GcBox::<()>::allocate();
                 let gc_box = alloc_ptr.as_ptr().cast::<GcBox<()>>();
 
                 // SAFETY: We just allocated this memory. The value is a ZST (unit type).
@@ -1296,8 +1873,12 @@ impl<T: Trace> Gc<T> {
                     gc_box.write(GcBox {
                         ref_count: AtomicUsize::new(1),
                         weak_count: AtomicUsize::new(1),
+                        #[cfg(not(feature = "thin-headers"))]
                         drop_fn: GcBox::<()>::drop_fn_for,
+                        #[cfg(not(feature = "thin-headers"))]
                         trace_fn: GcBox::<()>::trace_fn_for,
+                        #[cfg(all(feature = "type-tracking", not(feature = "thin-headers")))]
+                        type_info: crate::heap::TypeInfo::of::<()>,
                         is_dropping: AtomicUsize::new(0),
                         generation: AtomicU32::new(1),
                         value: (),
@@ -1352,48 +1933,51 @@ impl<T: Trace> Gc<T> {
         }
     }
 
-    #[deprecated(
-        since = "0.0.1",
-        note = "This function is non-functional: the closure receives a null Gc. Use `new_cyclic_weak` instead."
-    )]
-    #[allow(unused)]
-    #[must_use]
-    #[doc(hidden)]
-    pub fn new_cyclic<F: FnOnce(Self) -> T>(data_fn: F) -> Self {
-        let ptr = with_heap(LocalHeap::alloc::<GcBox<T>>);
-        let gc_box = ptr.as_ptr().cast::<GcBox<T>>();
-
-        let dead_gc = Self {
-            ptr: AtomicNullable::null(),
-            _marker: PhantomData,
-        };
-
-        let value = data_fn(dead_gc);
-
-        unsafe {
-            gc_box.write(GcBox {
-                ref_count: AtomicUsize::new(1),
-                weak_count: AtomicUsize::new(0),
-                drop_fn: GcBox::<T>::drop_fn_for,
-                trace_fn: GcBox::<T>::trace_fn_for,
-                is_dropping: AtomicUsize::new(0),
-                generation: AtomicU32::new(1),
-                value,
-            });
-        }
-
-        let gc_box_ptr = unsafe { NonNull::new_unchecked(gc_box) };
-
-        let gc = Self {
-            ptr: AtomicNullable::new(gc_box_ptr),
-            _marker: PhantomData,
-        };
-
-        unsafe {
-            rehydrate_self_refs(gc_box_ptr, &(*gc_box).value);
-        }
-
-        gc
+    /// Create a value that holds a weak reference to its own allocation,
+    /// like `std::rc::Rc::new_cyclic`.
+    ///
+    /// The slot is allocated first and `data_fn` gets a [`Weak`] to it,
+    /// which it can clone into the value. Until `data_fn` returns and the
+    /// value is written, upgrading that `Weak` yields `None`; afterwards it
+    /// upgrades to the returned `Gc`. If `data_fn` panics, the slot is
+    /// reclaimed and weak references cloned from the handle stay dead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a zero-sized type (ZST).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, Trace, Weak};
+    ///
+    /// #[derive(Trace)]
+    /// struct Node {
+    ///     me: Weak<Node>,
+    ///     data: i32,
+    /// }
+    ///
+    /// let node = Gc::new_cyclic(|me: &Weak<Node>| Node {
+    ///     me: me.clone(),
+    ///     data: 42,
+    /// });
+    /// assert!(Gc::ptr_eq(&node.me.upgrade().unwrap(), &node));
+    /// ```
+    #[track_caller]
+    pub fn new_cyclic<F>(data_fn: F) -> Self
+    where
+        F: FnOnce(&Weak<T>) -> T,
+    {
+        Self::new_cyclic_weak(|weak| {
+            // The handle from `new_cyclic_weak` is not counted. Count it, so
+            // dropping it here, or while unwinding, leaves the count of its
+            // clones intact.
+            if let Some(ptr) = weak.ptr.load(Ordering::Relaxed).as_option() {
+                // SAFETY: the slot was just allocated and its header written.
+                unsafe { (*ptr.as_ptr()).inc_weak() };
+            }
+            data_fn(&weak)
+        })
     }
 
     /// Create a self-referential garbage-collected value using a Weak reference.
@@ -1467,7 +2051,7 @@ impl<T: Trace> Gc<T> {
             }
         }
 
-        let raw_ptr = with_heap(LocalHeap::alloc::<GcBox<T>>);
+        let raw_ptr = GcBox::<T>::allocate();
         let gc_box = raw_ptr.as_ptr().cast::<GcBox<T>>();
 
         let gc_box_ptr = unsafe { NonNull::new_unchecked(gc_box) };
@@ -1487,14 +2071,21 @@ impl<T: Trace> Gc<T> {
                 std::ptr::addr_of_mut!((*gc_box).weak_count),
                 AtomicUsize::new(GcBox::<T>::UNDER_CONSTRUCTION_FLAG),
             );
+            #[cfg(not(feature = "thin-headers"))]
             std::ptr::write(
                 std::ptr::addr_of_mut!((*gc_box).drop_fn),
                 GcBox::<T>::drop_fn_for,
             );
+            #[cfg(not(feature = "thin-headers"))]
             std::ptr::write(
                 std::ptr::addr_of_mut!((*gc_box).trace_fn),
                 GcBox::<T>::trace_fn_for,
             );
+            #[cfg(all(feature = "type-tracking", not(feature = "thin-headers")))]
+            std::ptr::write(
+                std::ptr::addr_of_mut!((*gc_box).type_info),
+                crate::heap::TypeInfo::of::<T>,
+            );
             std::ptr::write(
                 std::ptr::addr_of_mut!((*gc_box).is_dropping),
                 AtomicUsize::new(0),
@@ -1528,10 +2119,12 @@ impl<T: Trace> Gc<T> {
         #[cfg(feature = "debug-suspicious-sweep")]
         crate::gc::record_young_object(gc_box_ptr.as_ptr() as *const u8);
 
-        Self {
+        let gc = Self {
             ptr: AtomicNullable::new(gc_box_ptr),
             _marker: PhantomData,
-        }
+        };
+        crate::gc::notify_allocated();
+        gc
     }
 
     /// Create a `Gc<T>` from a raw pointer to its `GcBox`.
@@ -1699,7 +2292,8 @@ impl<T: Trace> Gc<T> {
         let other_ptr = other.ptr.load(Ordering::Acquire);
 
         if this_ptr.is_null() || other_ptr.is_null() {
-            return this_ptr.as_ptr() == other_ptr.as_ptr();
+            // Immediate integers are equal when their values are.
+            return this.ptr.load_raw(Ordering::Acquire) == other.ptr.load_raw(Ordering::Acquire);
         }
 
         let this_gc_box_ptr = this_ptr.as_ptr();
@@ -1722,10 +2316,15 @@ impl<T: Trace> Gc<T> {
 
     /// Get the current reference count.
     ///
+    /// An immediate integer from [`Gc::new_int`] has no count and reports 1.
+    ///
     /// # Panics
     ///
     /// Panics if the Gc is dead or in dropping state.
     pub fn ref_count(gc: &Self) -> NonZeroUsize {
+        if gc.immediate().is_some() {
+            return NonZeroUsize::MIN;
+        }
         let ptr = gc.ptr.load(Ordering::Acquire);
         assert!(
             !ptr.is_null(),
@@ -1752,36 +2351,375 @@ impl<T: Trace> Gc<T> {
         }
     }
 
-    /// Get the current weak reference count.
+    /// Get the number of strong references to this allocation.
+    ///
+    /// This is a `Relaxed` snapshot meant for debugging and test assertions:
+    /// other threads may clone or drop `Gc`s at any moment, so under
+    /// concurrent mutation the value is only approximate. Use
+    /// [`Gc::ref_count`] when the count must be synchronized.
+    ///
+    /// An immediate integer from [`Gc::new_int`] has no count and reports 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Gc is dead or in dropping state.
+    #[must_use]
+    pub fn strong_count(this: &Self) -> usize {
+        if this.immediate().is_some() {
+            return 1;
+        }
+        let ptr = this.ptr.load(Ordering::Acquire);
+        assert!(
+            !ptr.is_null(),
+            "Gc::strong_count: cannot get strong_count of a dead Gc"
+        );
+        let gc_box_ptr = ptr.as_ptr();
+        unsafe {
+            if let Some(idx) = crate::heap::ptr_to_object_index(gc_box_ptr as *const u8) {
+                let header = crate::heap::ptr_to_page_header(gc_box_ptr as *const u8);
+                assert!(
+                    (*header.as_ptr()).is_allocated(idx),
+                    "Gc::strong_count: slot has been swept and reused"
+                );
+            }
+        }
+        unsafe {
+            assert!(
+                !(*gc_box_ptr).has_dead_flag()
+                    && (*gc_box_ptr).dropping_state() == 0
+                    && !(*gc_box_ptr).is_under_construction(),
+                "Gc::strong_count: cannot get strong_count of a dead, dropping, or under construction Gc"
+            );
+            (*gc_box_ptr).ref_count.load(Ordering::Relaxed)
+        }
+    }
+
+    /// Get the number of weak references to this allocation.
+    ///
+    /// Like [`Gc::strong_count`], this is a `Relaxed` snapshot and only
+    /// approximate under concurrent mutation.
+    ///
+    /// An immediate integer from [`Gc::new_int`] can't be downgraded and
+    /// reports 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Gc is dead or in dropping state.
+    #[must_use]
+    pub fn weak_count(this: &Self) -> usize {
+        if this.immediate().is_some() {
+            return 0;
+        }
+        let ptr = this.ptr.load(Ordering::Acquire);
+        assert!(
+            !ptr.is_null(),
+            "Gc::weak_count: cannot get weak_count of a dead Gc"
+        );
+        let gc_box_ptr = ptr.as_ptr();
+        unsafe {
+            if let Some(idx) = crate::heap::ptr_to_object_index(gc_box_ptr as *const u8) {
+                let header = crate::heap::ptr_to_page_header(gc_box_ptr as *const u8);
+                assert!(
+                    (*header.as_ptr()).is_allocated(idx),
+                    "Gc::weak_count: slot has been swept and reused"
+                );
+            }
+        }
+        unsafe {
+            assert!(
+                !(*gc_box_ptr).has_dead_flag()
+                    && (*gc_box_ptr).dropping_state() == 0
+                    && !(*gc_box_ptr).is_under_construction(),
+                "Gc::weak_count: cannot get weak_count of a dead, dropping, or under construction Gc"
+            );
+            (*gc_box_ptr).weak_count()
+        }
+    }
+
+    /// Whether this is the only reference to its allocation: no other `Gc`,
+    /// cross-thread handle or weak reference points to it.
+    ///
+    /// Returns `false` for dead `Gc`s, immediate integers and zero-sized
+    /// values, which share one immortal allocation.
+    #[must_use]
+    pub fn is_unique(&self) -> bool {
+        let ptr = self.ptr.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return false;
+        }
+        // SAFETY: A live `Gc` keeps its `GcBox` allocated.
+        let gc_box = unsafe { &*ptr.as_ptr() };
+        !gc_box.has_dead_flag()
+            && gc_box.dropping_state() == 0
+            && !gc_box.is_under_construction()
+            && gc_box.ref_count.load(Ordering::Acquire) == 1
+            && gc_box.weak_count() == 0
+    }
+
+    /// Move the value out of the GC heap into a `Box`, if this is the only
+    /// reference to it (see [`is_unique`](Self::is_unique)). Otherwise the
+    /// `Gc` is dropped and `None` is returned.
+    ///
+    /// The value is not dropped, and its slot is freed immediately, or by
+    /// the next sweep if incremental marking is in progress. The inverse is
+    /// `Gc::new(*boxed)`.
+    ///
+    /// Any `Gc` fields of the value are not traced while it lives in the
+    /// `Box`, just as in a `Vec<Gc<T>>`: the objects they point to can be
+    /// collected unless something else keeps them reachable.
+    ///
+    /// A [`GcSlice`](crate::GcSlice) keeps its elements past the end of the
+    /// value, so it cannot be moved out and always gives `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    ///
+    /// let gc = Gc::new(String::from("detached"));
+    /// let boxed = gc.into_box().unwrap();
+    /// assert_eq!(*boxed, "detached");
+    ///
+    /// let shared = Gc::new(1);
+    /// let _other = shared.clone();
+    /// assert!(shared.into_box().is_none());
+    /// ```
+    #[must_use]
+    pub fn into_box(self) -> Option<Box<T>> {
+        self.take_unique().ok().map(Box::new)
+    }
+
+    /// Move the value out of the GC heap, if this is the only reference to
+    /// it (see [`is_unique`](Self::is_unique)). Otherwise `this` is returned
+    /// unchanged.
+    ///
+    /// The value is not dropped, and its slot is freed immediately. Like
+    /// [`Gc::into_box`], this fails for a [`GcSlice`](crate::GcSlice).
+    ///
+    /// An object may sit on a mark worklist while a collection or
+    /// incremental marking is in progress, so this always fails then; see
+    /// [`is_collecting`](crate::gc::is_collecting).
+    ///
+    /// # Errors
+    ///
+    /// Returns `this` if it is not unique or a collection is in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    ///
+    /// let gc = Gc::new(String::from("owned"));
+    /// assert_eq!(Gc::try_unwrap(gc).unwrap(), "owned");
+    ///
+    /// let shared = Gc::new(1);
+    /// let other = shared.clone();
+    /// let shared = Gc::try_unwrap(shared).unwrap_err();
+    /// assert!(Gc::ptr_eq(&shared, &other));
+    /// ```
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        if crate::gc::incremental::is_incremental_marking_active() || crate::gc::is_collecting() {
+            return Err(this);
+        }
+        this.take_unique()
+    }
+
+    /// Mutable access to the value, if this is the only reference to it
+    /// (see [`is_unique`](Self::is_unique)), like `Rc::get_mut`.
+    ///
+    /// Returns `None` for a `GcSlice`, whose elements lie past the end of the
+    /// value, so that it cannot be swapped with another slice.
+    ///
+    /// No `GcCell` is needed: uniqueness rules out other readers. The write
+    /// barriers still run, because a unique object may already be black
+    /// during incremental marking or old under generational collection.
+    /// The `Gc` pointers the value holds now are recorded as SATB old
+    /// values, and the object is dirtied for the next minor collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    ///
+    /// let mut gc = Gc::new(vec![1, 2]);
+    /// Gc::get_mut(&mut gc).unwrap().push(3);
+    /// assert_eq!(*gc, [1, 2, 3]);
+    ///
+    /// let other = gc.clone();
+    /// assert!(Gc::get_mut(&mut gc).is_none());
+    /// drop(other);
+    /// assert!(Gc::get_mut(&mut gc).is_some());
+    /// ```
+    #[must_use]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        struct SatbRecorder {
+            old_values: Vec<NonNull<GcBox<()>>>,
+        }
+
+        impl Visitor for SatbRecorder {
+            fn visit<U: Trace>(&mut self, gc: &Gc<U>) {
+                if let Some(ptr) = gc.ptr.load(Ordering::Acquire).as_option() {
+                    self.old_values.push(ptr.cast());
+                }
+            }
+
+            unsafe fn visit_region(&mut self, _ptr: *const u8, _len: usize) {}
+        }
+
+        if !this.is_unique() || this.has_trailing_elements() {
+            return None;
+        }
+        let gc_box_ptr = this.raw_ptr();
+
+        // Cache barrier states once, as `GcCell::borrow_mut` does.
+        let incremental_active = crate::gc::incremental::is_incremental_marking_active();
+        let generational_active = crate::gc::incremental::is_generational_barrier_active();
+
+        if incremental_active {
+            let mut recorder = SatbRecorder {
+                old_values: Vec::new(),
+            };
+            // SAFETY: `this` keeps the `GcBox` allocated.
+            unsafe { (*gc_box_ptr).value.trace(&mut recorder) };
+            if !recorder.old_values.is_empty() {
+                with_heap(|heap| {
+                    for gc_ptr in recorder.old_values {
+                        if !heap.record_satb_old_value(gc_ptr) {
+                            crate::gc::incremental::IncrementalMarkState::global()
+                                .request_fallback(
+                                    crate::gc::incremental::FallbackReason::SatbBufferOverflow,
+                                );
+                            break;
+                        }
+                    }
+                });
+            }
+        }
+
+        // SAFETY: `this` keeps the `GcBox` allocated.
+        let value = unsafe { std::ptr::addr_of_mut!((*gc_box_ptr).value) };
+        if generational_active || incremental_active {
+            crate::heap::gc_cell_validate_and_barrier(
+                value.cast::<u8>().cast_const(),
+                "Gc::get_mut",
+                incremental_active,
+                None,
+            );
+        }
+
+        // SAFETY: This is the only reference, and `&mut this` keeps it so
+        // for the lifetime of the borrow.
+        Some(unsafe { &mut *value })
+    }
+
+    /// Whether the value has elements stored past its end, such as a
+    /// `GcSlice`. Such a value cannot be moved out of, or swapped within, its
+    /// allocation. Only those make a large object bigger than its `GcBox`.
+    fn has_trailing_elements(&self) -> bool {
+        // SAFETY: `self` keeps the page allocated.
+        unsafe {
+            let header = crate::heap::ptr_to_page_header(self.raw_ptr() as *const u8).as_ptr();
+            (*header).is_large_object()
+                && (*header).block_size as usize != std::mem::size_of::<GcBox<T>>()
+        }
+    }
+
+    /// Shared by [`Gc::into_box`] and [`Gc::try_unwrap`]: move the value
+    /// out if this is the only reference, freeing the slot unless marking
+    /// or a sweep may still reach it.
+    fn take_unique(self) -> Result<T, Self> {
+        if !self.is_unique() {
+            return Err(self);
+        }
+        if self.has_trailing_elements() {
+            return Err(self);
+        }
+        let gc_box_ptr = self.raw_ptr();
+        // SAFETY: `self` keeps the `GcBox` allocated.
+        if !unsafe { (*gc_box_ptr).try_mark_dropping() } {
+            return Err(self);
+        }
+        std::mem::forget(self);
+        drop(take_last_strong_drop(gc_box_ptr.cast()));
+
+        // SAFETY: This was the only reference and is now marked dropping, so
+        // nothing else reads the value. The flags make the sweep treat the
+        // slot as already dropped, as after `drop_fn_for`.
+        unsafe {
+            (*gc_box_ptr).set_dead();
+            let value = std::ptr::read(std::ptr::addr_of!((*gc_box_ptr).value));
+            (*gc_box_ptr).set_final_dropping();
+            GcBox::retire_fns(gc_box_ptr);
+            (*gc_box_ptr).ref_count.store(0, Ordering::Release);
+
+            // Marking may still hold this object on a worklist, and a running
+            // collection may be sweeping its page; leave those to the sweep.
+            if !crate::gc::incremental::is_incremental_marking_active()
+                && !crate::gc::is_collecting()
+            {
+                with_heap(|heap| heap.dealloc(NonNull::new_unchecked(gc_box_ptr.cast::<u8>())));
+            }
+            Ok(value)
+        }
+    }
+
+    /// Register `f` to run when the last `Gc` to this object is dropped.
+    ///
+    /// `f` runs as soon as the strong count reaches zero, before the value is
+    /// dropped and whether or not `Weak`s remain; those already fail to
+    /// upgrade while it runs. Memory is reclaimed later, as usual. Callbacks
+    /// run in registration order, on the thread that drops the last `Gc`.
+    ///
+    /// An object reclaimed by the collector, such as one in an unreachable
+    /// cycle, never reaches a strong count of zero: its callbacks are dropped
+    /// without running, as are those of an object moved out by
+    /// [`Gc::into_box`]. Does nothing for a `Gc` holding an immediate integer.
     ///
     /// # Panics
     ///
     /// Panics if the Gc is dead or in dropping state.
-    pub fn weak_count(gc: &Self) -> usize {
-        let ptr = gc.ptr.load(Ordering::Acquire);
-        assert!(
-            !ptr.is_null(),
-            "Gc::weak_count: cannot get weak_count of a dead Gc"
-        );
-        let gc_box_ptr = ptr.as_ptr();
-        unsafe {
-            if let Some(idx) = crate::heap::ptr_to_object_index(gc_box_ptr as *const u8) {
-                let header = crate::heap::ptr_to_page_header(gc_box_ptr as *const u8);
-                assert!(
-                    (*header.as_ptr()).is_allocated(idx),
-                    "Gc::weak_count: slot has been swept and reused"
-                );
-            }
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let closed = Arc::new(AtomicBool::new(false));
+    /// let socket = Gc::new(42);
+    /// let weak = Gc::downgrade(&socket);
+    /// let flag = closed.clone();
+    /// socket.on_last_strong_drop(Box::new(move || flag.store(true, Ordering::SeqCst)));
+    ///
+    /// drop(socket);
+    /// assert!(closed.load(Ordering::SeqCst));
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn on_last_strong_drop(&self, f: Box<dyn FnOnce() + Send>) {
+        let ptr = self.ptr.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return;
         }
+        let gc_box_ptr = ptr.as_ptr();
+        // SAFETY: `self` keeps the `GcBox` allocated.
         unsafe {
             assert!(
-                !(*gc_box_ptr).has_dead_flag()
-                    && (*gc_box_ptr).dropping_state() == 0
-                    && !(*gc_box_ptr).is_under_construction(),
-                "Gc::weak_count: cannot get weak_count of a dead, dropping, or under construction Gc"
+                !(*gc_box_ptr).has_dead_flag() && (*gc_box_ptr).dropping_state() == 0,
+                "Gc::on_last_strong_drop: cannot register on a dead or dropping Gc"
             );
-            (*gc_box_ptr).weak_count()
         }
+
+        LAST_STRONG_DROP
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get_or_insert_with(std::collections::HashMap::new)
+            .entry(gc_box_ptr as usize)
+            .or_insert_with(|| {
+                LAST_STRONG_DROP_OBJECTS.fetch_add(1, Ordering::AcqRel);
+                Vec::new()
+            })
+            .push(f);
     }
 
     /// Create a `Weak<T>` pointer to this allocation.
@@ -1848,7 +2786,63 @@ impl<T: Trace> Gc<T> {
         }
     }
 
-    /// Check if this Gc is "dead" (refers to a collected value).
+    /// Create a `Gc` holding `value` inline, without allocating.
+    ///
+    /// The integer is stored in the pointer word itself, tagged so the
+    /// collector never takes it for an object: it costs no heap space, is
+    /// never traced, and clones and drops without touching a reference
+    /// count. Read it back with [`Gc::as_int`]. There is no `T` behind it, so
+    /// dereferencing it panics, and [`Gc::is_dead_or_unrooted`] reports it as
+    /// holding no object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    ///
+    /// let small: Gc<String> = Gc::new_int(-7);
+    /// assert_eq!(small.as_int(), Some(-7));
+    /// assert_eq!(Gc::new(String::new()).as_int(), None);
+    /// ```
+    #[cfg(target_pointer_width = "64")]
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn new_int(value: i32) -> Self {
+        Self {
+            ptr: AtomicNullable::from_raw(((value as u32 as usize) << INT_SHIFT) | INT_TAG),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The integer stored by [`Gc::new_int`], or `None` if this `Gc` points
+    /// to an object.
+    #[cfg(target_pointer_width = "64")]
+    #[must_use]
+    pub fn as_int(&self) -> Option<i32> {
+        self.immediate()
+    }
+
+    /// The immediate integer, if any. Immediates only exist on 64-bit
+    /// targets, but the comparison traits check for them everywhere.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub(crate) fn immediate(&self) -> Option<i32> {
+        let word = self.ptr.load_raw(Ordering::Acquire);
+        (word & INT_TAG != 0).then_some((word >> INT_SHIFT) as u32 as i32)
+    }
+
+    /// Orders `self` and `other` if either is an immediate integer:
+    /// integers by value, before every object. `None` if both are objects.
+    fn cmp_immediates(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self.immediate(), other.immediate()) {
+            (None, None) => None,
+            (Some(_), None) => Some(std::cmp::Ordering::Less),
+            (None, Some(_)) => Some(std::cmp::Ordering::Greater),
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+        }
+    }
+
+    /// Check if this Gc is "dead" (refers to a collected value) or holds an
+    /// immediate integer rather than an object.
     pub fn is_dead_or_unrooted(gc: &Self) -> bool {
         gc.ptr.load(Ordering::Acquire).is_null()
     }
@@ -1913,6 +2907,64 @@ impl<T: Trace> Gc<T> {
 }
 
 impl<T: Trace + 'static> Gc<T> {
+    /// Iterate over every `Gc<T>` reachable from this one, in breadth-first order.
+    ///
+    /// Each allocation is yielded exactly once, starting with `self`, so cycles
+    /// terminate. Edges through objects of other types are followed but those
+    /// objects are not yielded. See [`GcTraversal`](crate::GcTraversal).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, GcCell, Trace};
+    ///
+    /// #[derive(Trace)]
+    /// struct Node { next: GcCell<Option<Gc<Node>>> }
+    ///
+    /// let a = Gc::new(Node { next: GcCell::new(None) });
+    /// let b = Gc::new(Node { next: GcCell::new(Some(a.clone())) });
+    /// *a.next.borrow_mut() = Some(b.clone());
+    ///
+    /// assert_eq!(a.traverse().count(), 2);
+    /// ```
+    #[must_use]
+    pub fn traverse(&self) -> crate::GcTraversal<T> {
+        crate::GcTraversal::new(self)
+    }
+
+    /// Count the objects reachable from this one and the heap space they
+    /// occupy.
+    ///
+    /// Follows `Trace` edges through objects of every type, visiting each
+    /// allocation once, so cycles terminate. Sizes are whole heap slots
+    /// including the object header, which is what the subgraph costs the
+    /// heap. Returns all zeros for a dead or null `Gc`. Like
+    /// [`traverse`](Self::traverse), the walk does not touch mark bits and
+    /// may run at any time between collections.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, GcCell, Trace};
+    ///
+    /// #[derive(Trace)]
+    /// struct Node { next: GcCell<Option<Gc<Node>>> }
+    ///
+    /// let b = Gc::new(Node { next: GcCell::new(None) });
+    /// let a = Gc::new(Node { next: GcCell::new(Some(b.clone())) });
+    ///
+    /// let stats = a.subgraph_stats();
+    /// assert_eq!(stats.object_count, 2);
+    /// assert_eq!(stats.max_depth, 1);
+    /// ```
+    #[must_use]
+    pub fn subgraph_stats(&self) -> crate::SubgraphStats {
+        if Self::is_dead_or_unrooted(self) {
+            return crate::SubgraphStats::default();
+        }
+        crate::traverse::subgraph_stats(self.as_non_null().cast())
+    }
+
     /// Creates a cross-thread handle to this GC object.
     ///
     /// The handle is `Send + Sync` and can be sent to any thread.
@@ -1995,6 +3047,35 @@ impl<T: Trace + 'static> Gc<T> {
         handle
     }
 
+    /// Lock this object for use by foreign code, such as a DMA buffer.
+    ///
+    /// The returned [`FfiLock`](crate::FfiLock) roots the object, keeps it
+    /// alive after every other `Gc` is dropped, and stops the collector from
+    /// releasing its page. The object's address is stable, so
+    /// [`FfiLock::as_ptr`](crate::FfiLock::as_ptr) can be handed to C for the
+    /// lock's lifetime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a GC-registered thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::Gc;
+    ///
+    /// let gc = Gc::new([0u64; 8]);
+    /// let lock = gc.lock_for_ffi();
+    /// let ptr = lock.as_ptr();
+    /// drop(gc);
+    /// rudo_gc::collect_full();
+    /// assert_eq!(unsafe { (*ptr)[0] }, 0);
+    /// ```
+    #[must_use]
+    pub fn lock_for_ffi(&self) -> crate::FfiLock<T> {
+        crate::FfiLock::new(self)
+    }
+
     /// Creates a weak cross-thread handle that doesn't prevent collection.
     ///
     /// Resolve returns `None` if the object has been collected.
@@ -2061,15 +3142,63 @@ impl<T: Trace + 'static> Gc<T> {
     }
 }
 
+impl<T: Trace + Clone> Gc<T> {
+    /// Allocate a modified copy of this value, leaving the original untouched.
+    ///
+    /// The value is cloned, `setter` is applied to the clone, and the result
+    /// is moved into a fresh `Gc`. Cloning a `Gc` field only copies the
+    /// pointer, so every child the setter doesn't replace is shared between
+    /// the old and new versions. This is the functional-update pattern for
+    /// immutable graphs: unlike [`GcCell`](crate::cell::GcCell), nothing is
+    /// ever mutated in place, even when `self` is the only reference.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, Trace};
+    ///
+    /// #[derive(Trace, Clone)]
+    /// struct Point { x: i32, label: Gc<String> }
+    ///
+    /// let p = Gc::new(Point { x: 1, label: Gc::new("origin".to_string()) });
+    /// let q = p.with_field(|point| point.x = 2);
+    ///
+    /// assert_eq!((p.x, q.x), (1, 2));
+    /// assert!(Gc::ptr_eq(&p.label, &q.label));
+    /// ```
+    #[must_use]
+    pub fn with_field(&self, setter: impl FnOnce(&mut T)) -> Self {
+        let mut value = T::clone(self);
+        setter(&mut value);
+        Self::new(value)
+    }
+}
+
 impl<T: Trace> Deref for Gc<T> {
     type Target = T;
 
     /// Matches `try_deref`: checks page `is_allocated` (slot not swept/reused) then `GcBox` flags.
+    ///
+    /// In debug builds the page header magic is validated as well, so a `Gc` that points
+    /// outside a GC page (heap corruption, stale raw pointer) panics with its address instead
+    /// of silently skipping the allocation check.
     #[inline]
     fn deref(&self) -> &Self::Target {
         let ptr = self.ptr.load(Ordering::Acquire);
-        assert!(!ptr.is_null(), "Gc::deref: cannot dereference a null Gc");
+        if ptr.is_null() {
+            assert!(
+                self.ptr.load_raw(Ordering::Acquire) & INT_TAG == 0,
+                "Gc::deref: cannot dereference an immediate integer Gc"
+            );
+            panic!("Gc::deref: cannot dereference a null Gc");
+        }
         let gc_box_ptr = ptr.as_ptr();
+        #[cfg(debug_assertions)]
+        // SAFETY: Every live GcBox sits inside a mapped GC page, so reading the header at the
+        // page-aligned address is valid; a mismatch is exactly the corruption we report.
+        unsafe {
+            debug_validate_gc_box(gc_box_ptr as *const u8, "Gc::deref");
+        }
         unsafe {
             if let Some(idx) = crate::heap::ptr_to_object_index(gc_box_ptr as *const u8) {
                 let header = crate::heap::ptr_to_page_header(gc_box_ptr as *const u8);
@@ -2092,11 +3221,14 @@ impl<T: Trace> Deref for Gc<T> {
 }
 
 impl<T: Trace> Clone for Gc<T> {
+    /// Aborts the process if the object already has [`MAX_REF_COUNT`]
+    /// strong references.
     fn clone(&self) -> Self {
         let ptr = self.ptr.load(Ordering::Acquire);
         if ptr.is_null() {
+            // Keeps an immediate integer, if any.
             return Self {
-                ptr: AtomicNullable::null(),
+                ptr: self.ptr.clone(),
                 _marker: PhantomData,
             };
         }
@@ -2172,9 +3304,14 @@ impl<T: Trace> Drop for Gc<T> {
     }
 }
 
+/// Immediate integers compare, order, hash and display by value, and never
+/// equal an object.
 impl<T: Trace + PartialEq> PartialEq for Gc<T> {
     fn eq(&self, other: &Self) -> bool {
-        **self == **other
+        match (self.immediate(), other.immediate()) {
+            (None, None) => **self == **other,
+            (a, b) => a == b,
+        }
     }
 }
 
@@ -2182,6 +3319,10 @@ impl<T: Trace + Eq> Eq for Gc<T> {}
 
 impl<T: Trace + std::fmt::Debug> std::fmt::Debug for Gc<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[cfg(target_pointer_width = "64")]
+        if let Some(value) = self.as_int() {
+            return write!(f, "Gc(<int {value}>)");
+        }
         if self.ptr.load(Ordering::Acquire).is_null() {
             write!(f, "Gc(<dead>)")
         } else {
@@ -2198,7 +3339,10 @@ impl<T: Trace> std::fmt::Pointer for Gc<T> {
 
 impl<T: Trace + std::fmt::Display> std::fmt::Display for Gc<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&**self, f)
+        match self.immediate() {
+            Some(value) => std::fmt::Display::fmt(&value, f),
+            None => std::fmt::Display::fmt(&**self, f),
+        }
     }
 }
 
@@ -2210,19 +3354,24 @@ impl<T: Trace + Default> Default for Gc<T> {
 
 impl<T: Trace + std::hash::Hash> std::hash::Hash for Gc<T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        (**self).hash(state);
+        match self.immediate() {
+            Some(value) => value.hash(state),
+            None => (**self).hash(state),
+        }
     }
 }
 
 impl<T: Trace + PartialOrd> PartialOrd for Gc<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        (**self).partial_cmp(&**other)
+        self.cmp_immediates(other)
+            .or_else(|| (**self).partial_cmp(&**other))
     }
 }
 
 impl<T: Trace + Ord> Ord for Gc<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (**self).cmp(&**other)
+        self.cmp_immediates(other)
+            .unwrap_or_else(|| (**self).cmp(&**other))
     }
 }
 
@@ -2232,12 +3381,18 @@ impl<T: Trace> From<T> for Gc<T> {
     }
 }
 
+/// # Panics
+///
+/// Panics on an immediate integer, which has no `T` to borrow.
 impl<T: Trace> AsRef<T> for Gc<T> {
     fn as_ref(&self) -> &T {
         self
     }
 }
 
+/// # Panics
+///
+/// Panics on an immediate integer, which has no `T` to borrow.
 impl<T: Trace> std::borrow::Borrow<T> for Gc<T> {
     fn borrow(&self) -> &T {
         self
@@ -2285,9 +3440,31 @@ pub struct Weak<T: Trace + 'static> {
 }
 
 impl<T: Trace> Weak<T> {
+    /// Constructs a dangling `Weak<T>` that points to no allocation, like
+    /// `std::rc::Weak::new`.
+    ///
+    /// [`upgrade`](Self::upgrade) always returns `None`. Useful as a
+    /// placeholder in cyclic structures whose target does not exist yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::Weak;
+    ///
+    /// let weak: Weak<i32> = Weak::new();
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ptr: AtomicNullable::null(),
+        }
+    }
+
     /// Attempt to upgrade to a strong `Gc<T>` reference.
     ///
-    /// Returns `None` if the value has been collected.
+    /// Returns `None` if the value has been collected or is being dropped,
+    /// which includes calls from the value's own `Drop` implementation.
     ///
     /// # Examples
     ///
@@ -2300,6 +3477,44 @@ impl<T: Trace> Weak<T> {
     /// assert!(weak.upgrade().is_some());
     /// ```
     pub fn upgrade(&self) -> Option<Gc<T>> {
+        self.upgrade_with(&mut None)
+    }
+
+    /// Upgrade every weak reference in `weaks`, skipping those whose value
+    /// is gone.
+    ///
+    /// Returns the live values in the order of `weaks`. Equivalent to calling
+    /// [`upgrade`](Self::upgrade) on each, but the list of registered heaps
+    /// that validates pointers from other threads is fetched once for the
+    /// whole batch rather than once per reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, Weak};
+    ///
+    /// let a = Gc::new(1);
+    /// let b = Gc::new(2);
+    /// let weaks = [Gc::downgrade(&a), Weak::default(), Gc::downgrade(&b)];
+    ///
+    /// let live: Vec<i32> = Weak::upgrade_all(&weaks).iter().map(|gc| **gc).collect();
+    /// assert_eq!(live, [1, 2]);
+    /// ```
+    #[must_use]
+    pub fn upgrade_all(weaks: &[Self]) -> Vec<Gc<T>> {
+        let mut threads = None;
+        weaks
+            .iter()
+            .filter_map(|weak| weak.upgrade_with(&mut threads))
+            .collect()
+    }
+
+    /// `upgrade`, sharing the registered thread list in `threads` with other
+    /// upgrades in the same batch.
+    fn upgrade_with(
+        &self,
+        threads: &mut Option<Vec<std::sync::Arc<crate::heap::ThreadControlBlock>>>,
+    ) -> Option<Gc<T>> {
         let ptr = self.ptr.load(Ordering::Acquire).as_option()?;
 
         // Validate pointer before any use (alignment, minimum address, GC box validity).
@@ -2312,7 +3527,7 @@ impl<T: Trace> Weak<T> {
         if addr < MIN_VALID_HEAP_ADDRESS {
             return None;
         }
-        if !is_gc_box_pointer_valid(addr) {
+        if !is_gc_box_pointer_valid_in(addr, threads) {
             return None;
         }
 
@@ -2350,15 +3565,15 @@ impl<T: Trace> Weak<T> {
                     return None;
                 }
 
-                if current_count == usize::MAX {
-                    return None;
+                if current_count >= MAX_REF_COUNT {
+                    ref_count_overflow();
                 }
 
                 if gc_box
                     .ref_count
                     .compare_exchange_weak(
                         current_count,
-                        current_count.saturating_add(1),
+                        current_count + 1,
                         Ordering::AcqRel,
                         Ordering::Relaxed,
                     )
@@ -2464,15 +3679,19 @@ impl<T: Trace> Weak<T> {
                 }
 
                 let current_count = gc_box.ref_count.load(Ordering::Acquire);
-                if current_count == 0 || current_count == usize::MAX {
+                if current_count == 0 {
                     return None;
                 }
 
+                if current_count >= MAX_REF_COUNT {
+                    ref_count_overflow();
+                }
+
                 if gc_box
                     .ref_count
                     .compare_exchange_weak(
                         current_count,
-                        current_count.saturating_add(1),
+                        current_count + 1,
                         Ordering::AcqRel,
                         Ordering::Relaxed,
                     )
@@ -2798,6 +4017,15 @@ impl<T: Trace> Clone for Weak<T> {
 
 #[inline]
 pub fn is_gc_box_pointer_valid(ptr_addr: usize) -> bool {
+    is_gc_box_pointer_valid_in(ptr_addr, &mut None)
+}
+
+/// `is_gc_box_pointer_valid`, fetching the registered thread list into
+/// `threads` on first use so later calls can reuse it.
+fn is_gc_box_pointer_valid_in(
+    ptr_addr: usize,
+    threads: &mut Option<Vec<std::sync::Arc<crate::heap::ThreadControlBlock>>>,
+) -> bool {
     let ptr = ptr_addr as *const u8;
 
     // Fast path: current thread heap.
@@ -2810,7 +4038,7 @@ pub fn is_gc_box_pointer_valid(ptr_addr: usize) -> bool {
     }
 
     // Cross-thread path: scan all registered heaps.
-    for tcb in crate::heap::get_all_thread_control_blocks() {
+    for tcb in threads.get_or_insert_with(crate::heap::get_all_thread_control_blocks) {
         // SAFETY: We only do read-only heap metadata checks here.
         let heap = unsafe { &*tcb.heap.get() };
         if unsafe { crate::heap::find_gc_box_from_ptr(heap, ptr).is_some() } {
@@ -2821,6 +4049,34 @@ pub fn is_gc_box_pointer_valid(ptr_addr: usize) -> bool {
     false
 }
 
+/// Debug-only integrity check for a `GcBox` address.
+///
+/// Panics with the offending address if the enclosing page does not carry
+/// `MAGIC_GC_PAGE`, or if the slot is not marked allocated in the page bitmap.
+///
+/// # Safety
+///
+/// `ptr` must be non-null and its page-aligned address must be readable.
+#[cfg(debug_assertions)]
+#[inline]
+pub unsafe fn debug_validate_gc_box(ptr: *const u8, context: &str) {
+    // SAFETY: Caller guarantees the page-aligned header address is readable.
+    unsafe {
+        let header = crate::heap::ptr_to_page_header(ptr);
+        let magic = (*header.as_ptr()).magic;
+        assert!(
+            magic == crate::heap::MAGIC_GC_PAGE,
+            "{context}: {ptr:p} is not inside a GC page (page magic {magic:#x}, heap corrupted?)"
+        );
+        let allocated = crate::heap::ptr_to_object_index(ptr)
+            .is_some_and(|idx| (*header.as_ptr()).is_allocated(idx));
+        assert!(
+            allocated,
+            "{context}: {ptr:p} does not refer to an allocated slot (freed or corrupted)"
+        );
+    }
+}
+
 impl<T: Trace> Drop for Weak<T> {
     fn drop(&mut self) {
         let ptr = self.ptr.load(Ordering::Acquire);
@@ -2903,18 +4159,157 @@ unsafe impl<T: Trace> Trace for Weak<T> {
     fn trace(&self, _visitor: &mut impl crate::trace::Visitor) {
         // Weak references do not need to be traced.
         // They don't keep the value alive, so tracing them would be incorrect.
+        // This also covers dangling ones from `Weak::new`.
     }
 }
 
 impl<T: Trace> Default for Weak<T> {
     /// Constructs a new `Weak<T>` that is dangling (cannot be upgraded).
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// `WeakGc<T>` - Field type that stores any assigned `Gc` weakly
+// ============================================================================
+
+/// A field type that holds a [`Weak`] reference and downgrades on assignment.
+///
+/// Back-edges such as a child's link to its parent must be weak, or the tree
+/// becomes a cycle that only a full collection can reclaim. Declaring the
+/// field as `WeakGc<T>` makes the downgrade part of the type:
+/// `node.parent = parent.clone().into()` stores a weak reference, and there
+/// is no way to store a strong one by mistake.
+///
+/// `WeakGc<T>` dereferences to [`Weak<T>`], so [`upgrade`](Weak::upgrade)
+/// and [`is_alive`](Weak::is_alive) are available directly.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, GcCell, Trace, WeakGc};
+///
+/// #[derive(Trace)]
+/// struct Node {
+///     parent: GcCell<WeakGc<Node>>,
+///     children: GcCell<Vec<Gc<Node>>>,
+/// }
+///
+/// let new_node = || Node {
+///     parent: GcCell::new(WeakGc::new()),
+///     children: GcCell::new(Vec::new()),
+/// };
+/// let root = Gc::new(new_node());
+/// let child = Gc::new(new_node());
+/// *child.parent.borrow_mut() = root.clone().into();
+/// root.children.borrow_mut().push(child.clone());
+///
+/// assert!(Gc::ptr_eq(&child.parent.borrow().upgrade().unwrap(), &root));
+/// ```
+pub struct WeakGc<T: Trace + 'static> {
+    weak: Weak<T>,
+}
+
+impl<T: Trace + 'static> WeakGc<T> {
+    /// Creates a `WeakGc` that points to nothing and never upgrades.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { weak: Weak::new() }
+    }
+
+    /// Unwraps the underlying weak reference.
+    #[must_use]
+    pub fn into_weak(self) -> Weak<T> {
+        self.weak
+    }
+}
+
+impl<T: Trace + 'static> Deref for WeakGc<T> {
+    type Target = Weak<T>;
+
+    #[inline]
+    fn deref(&self) -> &Weak<T> {
+        &self.weak
+    }
+}
+
+impl<T: Trace + 'static> From<Gc<T>> for WeakGc<T> {
+    /// Downgrades `gc`, dropping the strong reference.
+    fn from(gc: Gc<T>) -> Self {
+        Self::from(&gc)
+    }
+}
+
+impl<T: Trace + 'static> From<&Gc<T>> for WeakGc<T> {
+    fn from(gc: &Gc<T>) -> Self {
         Self {
-            ptr: AtomicNullable::null(),
+            weak: Gc::downgrade(gc),
+        }
+    }
+}
+
+impl<T: Trace + 'static> From<Option<Gc<T>>> for WeakGc<T> {
+    /// Downgrades the `Gc`, or creates an empty `WeakGc` for `None`.
+    fn from(gc: Option<Gc<T>>) -> Self {
+        gc.map_or_else(Self::new, Self::from)
+    }
+}
+
+impl<T: Trace + 'static> From<Weak<T>> for WeakGc<T> {
+    fn from(weak: Weak<T>) -> Self {
+        Self { weak }
+    }
+}
+
+impl<T: Trace + 'static> From<&WeakGc<T>> for Option<Gc<T>> {
+    /// Upgrades the reference; `None` if the value is gone.
+    fn from(weak: &WeakGc<T>) -> Self {
+        weak.upgrade()
+    }
+}
+
+impl<T: Trace + 'static> Clone for WeakGc<T> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: self.weak.clone(),
         }
     }
 }
 
+impl<T: Trace + 'static> Default for WeakGc<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for WeakGc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeakGc")
+            .field("alive", &self.weak.is_alive())
+            .finish()
+    }
+}
+
+unsafe impl<T: Trace + 'static> Trace for WeakGc<T> {
+    #[inline]
+    fn trace(&self, visitor: &mut impl crate::trace::Visitor) {
+        self.weak.trace(visitor);
+    }
+}
+
+impl<T: Trace + 'static> GcCapture for WeakGc<T> {
+    #[inline]
+    fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
+        &[]
+    }
+
+    #[inline]
+    fn capture_gc_ptrs_into(&self, ptrs: &mut Vec<NonNull<GcBox<()>>>) {
+        self.weak.capture_gc_ptrs_into(ptrs);
+    }
+}
+
 // ============================================================================
 // `Ephemeron<K, V>` - Key-value pair where value is only reachable if key is
 // ============================================================================
@@ -3155,38 +4550,3 @@ unsafe impl<T: Trace + Send + Sync> Sync for Weak<T> {}
 unsafe impl<K: Trace + Send + Sync, V: Trace + Send + Sync> Send for Ephemeron<K, V> {}
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl<K: Trace + Send + Sync, V: Trace + Send + Sync> Sync for Ephemeron<K, V> {}
-
-// ============================================================================
-// Helper functions
-// ============================================================================
-
-/// Rehydrate dead self-references in a value.
-fn rehydrate_self_refs<T: Trace>(_target: NonNull<GcBox<T>>, value: &T) {
-    struct Rehydrator;
-
-    impl Visitor for Rehydrator {
-        fn visit<U: Trace>(&mut self, gc: &Gc<U>) {
-            if gc.ptr.load(Ordering::Relaxed).is_null() {
-                // FIXME: Self-referential cycle support is not implemented.
-                //
-                // Rehydration requires type information to ensure we only
-                // rehydrate dead Gc<T> references that point to the same
-                // allocation. Due to type erasure in our current design,
-                // we cannot safely verify type compatibility here.
-                //
-                // Potential solutions:
-                // 1. Store a unique allocation ID in GcBox for comparison
-                // 2. Use runtime type information (RTTI)
-                // 3. Require users to manually rehydrate after construction
-                //
-                // Until this is implemented, new_cyclic should be considered
-                // non-functional and should not be used.
-            }
-        }
-
-        unsafe fn visit_region(&mut self, _ptr: *const u8, _len: usize) {}
-    }
-
-    let mut rehydrator = Rehydrator;
-    value.trace(&mut rehydrator);
-}
@@ -0,0 +1,105 @@
+//! Rayon thread pool integration for rudo-gc.
+//!
+//! Rayon workers are long-lived and spend most of their time parked waiting
+//! for jobs. A worker that has ever touched a `Gc` owns a heap and is counted
+//! as executing, so a parked worker would keep every other thread from
+//! running a full multi-threaded collection.
+//!
+//! With this module, workers sit in the `THREAD_STATE_INACTIVE` state between
+//! jobs and only count as executing while running code wrapped in
+//! [`with_gc`] (or one of the wrappers built on it, such as [`join`]).
+//!
+//! # Enabling Rayon Support
+//!
+//! ```toml
+//! [dependencies]
+//! rudo-gc = { version = "0.8", features = ["derive", "rayon"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use rudo_gc::rayon::GcThreadPoolBuilderExt;
+//! use rudo_gc::Gc;
+//!
+//! let pool = rayon::ThreadPoolBuilder::new()
+//!     .num_threads(2)
+//!     .gc_handlers()
+//!     .build()
+//!     .unwrap();
+//!
+//! let (a, b) = pool.install(|| {
+//!     rudo_gc::rayon::join(|| *Gc::new(20), || *Gc::new(22))
+//! });
+//! assert_eq!(a + b, 42);
+//! ```
+
+use std::cell::Cell;
+
+use ::rayon::ThreadPoolBuilder;
+
+thread_local! {
+    /// Nesting depth of [`with_gc`] on this thread.
+    static GC_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Extension trait installing rudo-gc handlers on a rayon pool builder.
+pub trait GcThreadPoolBuilderExt {
+    /// Register each worker with the collector as soon as it starts, and mark
+    /// it inactive until it runs a [`with_gc`] region.
+    ///
+    /// This replaces any start handler set earlier on the builder.
+    #[must_use]
+    fn gc_handlers(self) -> Self;
+}
+
+impl<S> GcThreadPoolBuilderExt for ThreadPoolBuilder<S> {
+    fn gc_handlers(self) -> Self {
+        self.start_handler(|_| {
+            crate::heap::enter_inactive();
+        })
+    }
+}
+
+/// Run `f` with the current rayon worker counted as executing.
+///
+/// Any code on a worker that creates, reads or drops `Gc` values must run
+/// inside this function. When the outermost region on a worker ends, the
+/// worker goes back to the inactive state so that parked workers never hold
+/// up a collection. Outside a rayon pool, `f` simply runs.
+pub fn with_gc<R>(f: impl FnOnce() -> R) -> R {
+    struct Region {
+        outermost: bool,
+    }
+
+    impl Drop for Region {
+        fn drop(&mut self) {
+            GC_DEPTH.with(|depth| depth.set(depth.get() - 1));
+            if self.outermost && ::rayon::current_thread_index().is_some() {
+                crate::heap::enter_inactive();
+            }
+        }
+    }
+
+    let outermost = GC_DEPTH.with(|depth| {
+        let outer = depth.get() == 0;
+        depth.set(depth.get() + 1);
+        outer
+    });
+    if outermost {
+        crate::heap::leave_inactive();
+    }
+    let _region = Region { outermost };
+    f()
+}
+
+/// [`rayon::join`](::rayon::join) with both closures wrapped in [`with_gc`].
+pub fn join<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    ::rayon::join(|| with_gc(oper_a), || with_gc(oper_b))
+}
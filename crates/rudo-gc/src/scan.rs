@@ -56,6 +56,11 @@ pub unsafe fn scan_heap_region_conservatively(
                     crate::trace::VisitorKind::Minor => {
                         crate::gc::mark_object_minor(gc_box, visitor);
                     }
+                    crate::trace::VisitorKind::Remember => {
+                        crate::gc::remember_young_ref(gc_box, visitor);
+                    }
+                    // Conservative hits carry no type, so traversal cannot follow them.
+                    crate::trace::VisitorKind::Traverse | crate::trace::VisitorKind::Order => {}
                 }
             }
         }
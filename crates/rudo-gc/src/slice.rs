@@ -0,0 +1,151 @@
+//! Garbage-collected slices stored inline in a single allocation.
+//!
+//! `Gc<T>` needs a sized `T`, so a slice of GC values is usually a
+//! `Gc<Vec<T>>`: one object for the vector and a second, malloc'd buffer for
+//! its elements. A [`GcSlice`] instead keeps its length and elements in the
+//! `GcBox` itself, on dedicated large-object pages, so there is one allocation
+//! and no indirection between the `Gc` and the elements.
+
+use std::ops::Deref;
+
+use crate::ptr::GcBox;
+use crate::trace::{Trace, Visitor};
+use crate::Gc;
+
+/// A fixed-length slice allocated inline in a `Gc`.
+///
+/// Create one with [`Gc::new_slice`] or [`Gc::from_slice`]. A
+/// `Gc<GcSlice<T>>` dereferences to `[T]`, and tracing visits every element.
+///
+/// A `GcSlice` only exists inside its `GcBox`: the elements follow the value
+/// in memory, so it cannot be constructed, moved or cloned on its own.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, GcSlice};
+///
+/// let squares: Gc<GcSlice<u64>> = Gc::new_slice(4, |i| (i * i) as u64);
+/// assert_eq!(&squares[..], &[0, 1, 4, 9]);
+///
+/// let names = Gc::from_slice(&[Gc::new("a"), Gc::new("b")]);
+/// assert_eq!(*names[1], "b");
+/// ```
+#[repr(C)]
+pub struct GcSlice<T> {
+    len: usize,
+    items: [T; 0],
+}
+
+impl<T> GcSlice<T> {
+    const fn items_ptr(&self) -> *const T {
+        std::ptr::addr_of!(self.items).cast::<T>()
+    }
+}
+
+impl<T> Deref for GcSlice<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: the first `len` elements after the value are initialized.
+        unsafe { std::slice::from_raw_parts(self.items_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for GcSlice<T> {
+    fn drop(&mut self) {
+        let items = std::ptr::addr_of_mut!(self.items).cast::<T>();
+        // SAFETY: the first `len` elements are initialized and dropped once.
+        unsafe { std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(items, self.len)) };
+    }
+}
+
+// SAFETY: every initialized element is traced.
+unsafe impl<T: Trace> Trace for GcSlice<T> {
+    fn trace(&self, visitor: &mut impl Visitor) {
+        for item in &**self {
+            item.trace(visitor);
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for GcSlice<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&**self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for GcSlice<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for GcSlice<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for GcSlice<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<T: Trace + 'static> Gc<GcSlice<T>> {
+    /// Allocate a slice of `len` elements, the `i`th initialized to `init(i)`.
+    ///
+    /// The slice gets its own large-object pages, even when short, so prefer
+    /// a `Gc<Vec<T>>` for many small slices.
+    ///
+    /// If `init` panics, the elements created so far are dropped with the
+    /// slice. `init` may allocate and trigger collections: the slice traces
+    /// only the elements initialized so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice does not fit in a single allocation.
+    #[must_use]
+    pub fn new_slice(len: usize, mut init: impl FnMut(usize) -> T) -> Self {
+        let items_offset =
+            GcBox::<GcSlice<T>>::value_offset() + std::mem::offset_of!(GcSlice<T>, items);
+        let size = std::mem::size_of::<T>()
+            .checked_mul(len)
+            .and_then(|bytes| bytes.checked_add(items_offset))
+            .expect("Gc::new_slice: slice too large")
+            .max(std::mem::size_of::<GcBox<GcSlice<T>>>());
+        let align = std::mem::align_of::<GcBox<GcSlice<T>>>();
+
+        let ptr = crate::heap::with_heap(|heap| {
+            heap.alloc_large_sized(size, align, GcBox::<GcSlice<T>>::object_fns())
+        });
+        // SAFETY: `ptr` is a fresh allocation big enough for the header and
+        // `len` elements.
+        let gc = unsafe { Self::init_allocated(ptr, GcSlice { len: 0, items: [] }) };
+        crate::gc::notify_allocated();
+
+        let slice = gc.raw_ptr().cast::<u8>();
+        for i in 0..len {
+            let item = init(i);
+            // SAFETY: element `i` lies within the allocation and is
+            // uninitialized. The length only grows once it is written, so a
+            // collection or panic in `init` never sees it half-built.
+            unsafe {
+                slice.add(items_offset).cast::<T>().add(i).write(item);
+                (*slice
+                    .add(GcBox::<GcSlice<T>>::value_offset())
+                    .cast::<GcSlice<T>>())
+                .len = i + 1;
+            }
+        }
+        gc
+    }
+
+    /// Allocate a slice holding clones of `items`.
+    ///
+    /// See [`Gc::new_slice`].
+    #[must_use]
+    pub fn from_slice(items: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::new_slice(items.len(), |i| items[i].clone())
+    }
+}
@@ -159,6 +159,14 @@ pub fn get_stack_bounds() -> StackBounds {
 /// This ensures all callee-saved registers are flushed to the
 /// stack, allowing a conservative scan to find roots that might only exist
 /// in registers.
+///
+/// Register spilling is implemented for:
+/// - `x86_64`: RBP and R12-R15 (see the RBX note below).
+/// - `aarch64` (Linux, macOS, Windows): X19-X28, the frame pointer (X29), the
+///   link register (X30) and the low halves of the callee-saved D8-D15.
+///
+/// On other architectures only the stack itself is scanned, so a root held
+/// solely in a callee-saved register may be missed.
 #[inline(never)]
 pub unsafe fn spill_registers_and_scan<F>(mut scan_fn: F)
 where
@@ -191,35 +199,28 @@ where
     #[cfg(all(target_arch = "x86_64", not(miri)))]
     std::hint::black_box(&regs);
 
+    // For aarch64, AAPCS64 makes X19-X28, X29 (FP) and D8-D15 callee-saved;
+    // X30 (LR) is included as it may hold a stale pointer-sized value too.
+    // Registers are stored straight to memory: with `out(reg)` operands the
+    // compiler may pick one of X19-X28 as an output and overwrite it before
+    // it has been read.
     #[cfg(all(target_arch = "aarch64", not(miri)))]
-    let mut regs = [0usize; 12];
+    let mut regs = [0usize; 20];
     #[cfg(all(target_arch = "aarch64", not(miri)))]
     unsafe {
         std::arch::asm!(
-            "mov {0}, x19",
-            "mov {1}, x20",
-            "mov {2}, x21",
-            "mov {3}, x22",
-            "mov {4}, x23",
-            "mov {5}, x24",
-            "mov {6}, x25",
-            "mov {7}, x26",
-            "mov {8}, x27",
-            "mov {9}, x28",
-            "mov {10}, x29",
-            "mov {11}, x30",
-            out(reg) regs[0],
-            out(reg) regs[1],
-            out(reg) regs[2],
-            out(reg) regs[3],
-            out(reg) regs[4],
-            out(reg) regs[5],
-            out(reg) regs[6],
-            out(reg) regs[7],
-            out(reg) regs[8],
-            out(reg) regs[9],
-            out(reg) regs[10],
-            out(reg) regs[11],
+            "stp x19, x20, [{0}]",
+            "stp x21, x22, [{0}, #16]",
+            "stp x23, x24, [{0}, #32]",
+            "stp x25, x26, [{0}, #48]",
+            "stp x27, x28, [{0}, #64]",
+            "stp x29, x30, [{0}, #80]",
+            "stp d8, d9, [{0}, #96]",
+            "stp d10, d11, [{0}, #112]",
+            "stp d12, d13, [{0}, #128]",
+            "stp d14, d15, [{0}, #144]",
+            in(reg) regs.as_mut_ptr(),
+            options(nostack, preserves_flags),
         );
     }
     #[cfg(all(target_arch = "aarch64", not(miri)))]
@@ -270,6 +271,9 @@ where
 /// This is used by the allocator to ensure that the pointer to the newly
 /// allocated page does not remain in a register (where it would be caught
 /// by `spill_registers_and_scan` as a conflict).
+///
+/// On `aarch64` the caller-saved X0-X17 are cleared. X18 is left alone: it is
+/// the platform register on macOS and Windows and must not be written.
 #[inline(never)]
 pub unsafe fn clear_registers() {
     #[cfg(all(target_arch = "x86_64", not(miri)))]
@@ -306,7 +310,25 @@ pub unsafe fn clear_registers() {
             "movz x15, #0",
             "movz x16, #0",
             "movz x17, #0",
-            "movz x18, #0",
+            out("x0") _,
+            out("x1") _,
+            out("x2") _,
+            out("x3") _,
+            out("x4") _,
+            out("x5") _,
+            out("x6") _,
+            out("x7") _,
+            out("x8") _,
+            out("x9") _,
+            out("x10") _,
+            out("x11") _,
+            out("x12") _,
+            out("x13") _,
+            out("x14") _,
+            out("x15") _,
+            out("x16") _,
+            out("x17") _,
+            options(nostack, preserves_flags),
         );
     }
     // Miri/Other arch: Rely on optimization barrier or dummy work
@@ -4,6 +4,7 @@
 //! - [`GcTokioExt`] trait with [`root_guard()`][GcTokioExt::root_guard] and [`yield_now()`][GcTokioExt::yield_now]
 //! - [`GcRootSet`] for process-level root tracking
 //! - [`GcRootGuard`] for RAII root registration
+//! - [`spawn_blocking`] for handing a set of `Gc`s to a blocking task
 //!
 //! # Enabling Tokio Support
 //!
@@ -88,9 +89,11 @@ pub trait GcTokioExt: Trace + Send + Sync {
     /// ```
     fn root_guard(&self) -> GcRootGuard;
 
-    /// Yields execution back to the tokio scheduler.
+    /// Lets the GC make progress, then yields back to the tokio scheduler.
     ///
-    /// This allows the GC to run during long-running computations. Call this
+    /// Does what [`rudo_gc::yield_now`](crate::yield_now) does, running one
+    /// incremental mark slice when marking is active, before yielding. This
+    /// allows the GC to run during long-running computations. Call this
     /// periodically to prevent GC pauses from causing latency spikes.
     ///
     /// # Panics
@@ -112,6 +115,14 @@ pub trait GcTokioExt: Trace + Send + Sync {
     /// }
     /// ```
     async fn yield_now(&self);
+
+    /// Yields execution back to the tokio scheduler without running any GC
+    /// work.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a tokio runtime context.
+    async fn yield_scheduler_only(&self);
 }
 
 #[cfg(feature = "tokio")]
@@ -127,6 +138,74 @@ impl<T: Trace + Send + Sync> GcTokioExt for Gc<T> {
     }
 
     async fn yield_now(&self) {
+        crate::yield_now();
         task::yield_now().await;
     }
+
+    async fn yield_scheduler_only(&self) {
+        task::yield_now().await;
+    }
+}
+
+#[cfg(feature = "tokio")]
+/// Runs `f` on Tokio's blocking thread pool with `roots`, keeping every `Gc`
+/// in `roots` rooted until it returns.
+///
+/// `roots` is any traceable value: a single `Gc`, a tuple of them, a `Vec`,
+/// or a struct deriving [`Trace`]. The blocking thread is not a GC thread, so
+/// neither its stack nor a thread-local handle is visible to collections on
+/// the thread that owns the objects. Instead, each `Gc` that `roots` holds
+/// directly is registered in the owning thread's cross-thread root table,
+/// like a [`GcHandle`](crate::handles::GcHandle), for as long as the closure
+/// runs; whatever they reference stays alive through them. Other `Gc`s the
+/// closure captures are not rooted, so pass everything the task uses in
+/// `roots`.
+///
+/// # Panics
+///
+/// Panics if called outside a GC thread or outside a Tokio runtime.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{Gc, Trace};
+///
+/// #[derive(Trace)]
+/// struct Data { value: i32 }
+///
+/// let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// rt.block_on(async {
+///     let a = Gc::new(Data { value: 40 });
+///     let b = Gc::new(Data { value: 2 });
+///     let sum = rudo_gc::tokio::spawn_blocking((a, b), |(a, b)| a.value + b.value)
+///         .await
+///         .unwrap();
+///     assert_eq!(sum, 42);
+/// });
+/// ```
+pub fn spawn_blocking<T, F, R>(roots: T, f: F) -> task::JoinHandle<R>
+where
+    T: Trace + Send + Sync + 'static,
+    F: FnOnce(T) -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let mut visitor = crate::trace::GcVisitor::new(crate::trace::VisitorKind::Traverse);
+    roots.trace(&mut visitor);
+    let handles: Vec<_> = visitor
+        .discovered
+        .iter()
+        .map(|&(ptr, _)| {
+            // SAFETY: `roots` holds a strong reference to each pointer it
+            // reports. The borrowed `Gc` is never dropped.
+            let gc = std::mem::ManuallyDrop::new(unsafe {
+                Gc::<()>::from_raw(ptr.as_ptr().cast::<u8>().cast_const())
+            });
+            gc.cross_thread_handle()
+        })
+        .collect();
+    task::spawn_blocking(move || {
+        let result = f(roots);
+        drop(handles);
+        result
+    })
 }
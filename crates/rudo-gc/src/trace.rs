@@ -50,6 +50,24 @@ use crate::Gc;
 ///     }
 /// }
 /// ```
+///
+/// # Trait Objects
+///
+/// There is no `Gc<dyn Trait>`: `Gc<T>` needs a sized `T`, and `trace` takes
+/// a generic visitor, so a trait built on `Trace` is not object safe.
+/// `#[derive(Trace)]` rejects such fields with an error that points at them.
+/// Use an enum over the concrete types instead.
+///
+/// ```compile_fail
+/// use rudo_gc::{Gc, Trace};
+///
+/// trait Widget: Trace {}
+///
+/// #[derive(Trace)]
+/// struct Container {
+///     items: Vec<Gc<dyn Widget>>,
+/// }
+/// ```
 pub unsafe trait Trace {
     /// Visit all `Gc` pointers contained within this value.
     ///
@@ -57,6 +75,32 @@ pub unsafe trait Trace {
     /// must visit ALL Gc fields, including those inside nested structs, enums,
     /// and collections.
     fn trace(&self, visitor: &mut impl Visitor);
+
+    /// Finalization priority of this value.
+    ///
+    /// When a sweep finalizes several unreachable objects together, those
+    /// with a higher order are dropped first, so an object whose `Drop`
+    /// must run before its dependents' (say, a database connection that
+    /// query caches flush through) can report a higher order than them.
+    /// Objects of equal order are dropped in heap order.
+    ///
+    /// The order is read when the object is finalized. It applies to the
+    /// eager sweep and to deferred finalization; pages left to lazy sweeping
+    /// are finalized page by page as allocation sweeps them.
+    fn trace_order(&self) -> TraceOrder {
+        TraceOrder::DEFAULT
+    }
+}
+
+/// Finalization priority reported by [`Trace::trace_order`]; higher orders
+/// are finalized first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TraceOrder(pub i32);
+
+impl TraceOrder {
+    /// The order of every type that does not override
+    /// [`Trace::trace_order`].
+    pub const DEFAULT: Self = Self(0);
 }
 
 /// A visitor that traverses the object graph during garbage collection.
@@ -88,6 +132,13 @@ pub enum VisitorKind {
     Major,
     /// Minor GC (Mark only Young, stop at Old).
     Minor,
+    /// Promotion rescan: count references into the young generation without marking.
+    Remember,
+    /// Graph traversal: record each edge and its target type without marking.
+    Traverse,
+    /// Finalization order query: record the object's `Trace::trace_order`
+    /// without visiting its fields.
+    Order,
 }
 
 /// A concrete visitor struct used by the GC.
@@ -110,6 +161,12 @@ pub struct GcVisitor {
     pub(crate) worklist: Vec<(std::ptr::NonNull<crate::ptr::GcBox<()>>, u32)>,
     /// Count of objects marked during this collection.
     pub(crate) objects_marked: usize,
+    /// Count of young-generation references seen by a `Remember` pass.
+    pub(crate) young_refs: usize,
+    /// Edges found by a `Traverse` pass, with the `TypeId` of each target.
+    pub(crate) discovered: Vec<(std::ptr::NonNull<crate::ptr::GcBox<()>>, std::any::TypeId)>,
+    /// Order reported to an `Order` query.
+    pub(crate) order: TraceOrder,
 }
 
 /// A visitor for concurrent/parallel garbage collection marking.
@@ -155,6 +212,9 @@ impl<'a> GcVisitorConcurrent<'a> {
     /// routes the reference to that thread's work queue for efficient
     /// load balancing.
     fn route_reference<T: Trace>(&mut self, gc: &Gc<T>) {
+        if Gc::is_dead_or_unrooted(gc) {
+            return;
+        }
         let raw = Gc::<T>::as_ptr(gc);
         if raw.is_null() {
             return;
@@ -242,19 +302,82 @@ unsafe impl<T: Trace> Trace for Gc<T> {
 // Trace implementations for primitive types
 // ============================================================================
 
-macro_rules! impl_trace_for_primitives {
-    ($($t:ty),* $(,)?) => {
+/// A type that holds no `Gc` pointers and needs no tracing.
+///
+/// Leaves get a `Trace` impl whose `trace` does nothing. Primitives, strings
+/// and common std value types are leaves already; [`impl_trace_leaf!`] makes
+/// a type of your own one, with no `#[derive(Trace)]` needed.
+///
+/// There is no blanket `impl<T: TraceLeaf> Trace for T`: it would overlap
+/// the `Trace` impls for `Box<T>`, `&T` and `&mut T`, since another crate
+/// could implement `TraceLeaf` for `Box<Local>`. The macro writes both impls
+/// instead, so a type implements `TraceLeaf` only alongside an empty
+/// `Trace`.
+///
+/// # Safety
+///
+/// No value of the type may contain a `Gc`, directly or through any field.
+pub unsafe trait TraceLeaf: Trace {}
+
+/// Implements [`TraceLeaf`] and an empty [`Trace`] for types that hold no
+/// `Gc` pointers.
+///
+/// The list starts with `unsafe`, since the macro implements two unsafe
+/// traits on the caller's word.
+///
+/// # Examples
+///
+/// ```
+/// use rudo_gc::{impl_trace_leaf, Gc};
+///
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// // SAFETY: `Point` holds no `Gc` pointers.
+/// impl_trace_leaf!(unsafe Point);
+///
+/// let point = Gc::new(Point { x: 1.0, y: 2.0 });
+/// assert_eq!(point.x + point.y, 3.0);
+/// ```
+///
+/// Leaving out `unsafe` is an error:
+///
+/// ```compile_fail
+/// use rudo_gc::impl_trace_leaf;
+///
+/// struct Point {
+///     x: f64,
+/// }
+///
+/// impl_trace_leaf!(Point);
+/// ```
+///
+/// # Safety
+///
+/// Only use this macro for types that truly contain no `Gc<T>` fields. The
+/// collector never sees pointers behind a leaf and will free what they
+/// point to.
+#[macro_export]
+macro_rules! impl_trace_leaf {
+    (unsafe $($t:ty),* $(,)?) => {
         $(
-            // SAFETY: Primitive types contain no Gc pointers
-            unsafe impl Trace for $t {
+            // SAFETY: the caller guarantees the type holds no Gc pointers.
+            unsafe impl $crate::TraceLeaf for $t {}
+
+            // SAFETY: a leaf holds no Gc pointers, so there is nothing to visit.
+            unsafe impl $crate::Trace for $t {
                 #[inline]
-                fn trace(&self, _visitor: &mut impl Visitor) {}
+                fn trace(&self, _visitor: &mut impl $crate::Visitor) {}
             }
         )*
     };
 }
 
-impl_trace_for_primitives! {
+// SAFETY: none of these types can hold a Gc.
+impl_trace_leaf! {
+    unsafe
     // Signed integers
     i8, i16, i32, i64, i128, isize,
     // Unsigned integers
@@ -474,7 +597,25 @@ unsafe impl<T: Trace> Trace for BinaryHeap<T> {
     }
 }
 
+/// Marks the pages holding `entries` dirty, once per run of entries on the
+/// same page. Hash tables and B-trees keep their entries in one table or
+/// many nodes rather than at `as_ptr()`, so each entry is located directly.
+fn mark_entry_pages_dirty(entries: impl IntoIterator<Item = *const u8>) {
+    let mask = crate::heap::page_mask();
+    let mut last_page = None;
+    for ptr in entries {
+        let page = ptr as usize & mask;
+        if last_page != Some(page) {
+            last_page = Some(page);
+            unsafe {
+                crate::heap::mark_page_dirty_for_ptr(ptr);
+            }
+        }
+    }
+}
+
 // SAFETY: HashMap traces both keys and values
+/// Additionally marks the pages of the map's table as dirty so GC will scan it.
 unsafe impl<K: Trace, V: Trace, S: BuildHasher> Trace for HashMap<K, V, S> {
     #[inline]
     fn trace(&self, visitor: &mut impl Visitor) {
@@ -482,20 +623,32 @@ unsafe impl<K: Trace, V: Trace, S: BuildHasher> Trace for HashMap<K, V, S> {
             k.trace(visitor);
             v.trace(visitor);
         }
+        mark_entry_pages_dirty(self.iter().flat_map(|(k, v)| {
+            [
+                std::ptr::from_ref(k).cast::<u8>(),
+                std::ptr::from_ref(v).cast::<u8>(),
+            ]
+        }));
     }
 }
 
 // SAFETY: HashSet traces all elements
+/// Additionally marks the pages of the set's table as dirty so GC will scan it.
 unsafe impl<T: Trace, S: BuildHasher> Trace for HashSet<T, S> {
     #[inline]
     fn trace(&self, visitor: &mut impl Visitor) {
         for item in self {
             item.trace(visitor);
         }
+        mark_entry_pages_dirty(
+            self.iter()
+                .map(|item| std::ptr::from_ref(item).cast::<u8>()),
+        );
     }
 }
 
 // SAFETY: BTreeMap traces all key-value pairs
+/// Additionally marks the pages of the map's nodes as dirty so GC will scan them.
 unsafe impl<K: Trace, V: Trace> Trace for BTreeMap<K, V> {
     #[inline]
     fn trace(&self, visitor: &mut impl Visitor) {
@@ -503,16 +656,27 @@ unsafe impl<K: Trace, V: Trace> Trace for BTreeMap<K, V> {
             k.trace(visitor);
             v.trace(visitor);
         }
+        mark_entry_pages_dirty(self.iter().flat_map(|(k, v)| {
+            [
+                std::ptr::from_ref(k).cast::<u8>(),
+                std::ptr::from_ref(v).cast::<u8>(),
+            ]
+        }));
     }
 }
 
 // SAFETY: BTreeSet traces all elements
+/// Additionally marks the pages of the set's nodes as dirty so GC will scan them.
 unsafe impl<T: Trace> Trace for BTreeSet<T> {
     #[inline]
     fn trace(&self, visitor: &mut impl Visitor) {
         for item in self {
             item.trace(visitor);
         }
+        mark_entry_pages_dirty(
+            self.iter()
+                .map(|item| std::ptr::from_ref(item).cast::<u8>()),
+        );
     }
 }
 
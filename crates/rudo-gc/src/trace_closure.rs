@@ -1,6 +1,12 @@
 //! A wrapper for closures that explicitly captures and traces dependencies.
 
+use std::mem::ManuallyDrop;
+use std::ptr::NonNull;
+
+use crate::cell::GcCapture;
+use crate::ptr::GcBox;
 use crate::trace::{Trace, Visitor};
+use crate::Gc;
 
 /// A wrapper for a closure that captures and traces dependencies.
 ///
@@ -19,6 +25,37 @@ impl<C, D> TraceClosure<C, D> {
     }
 }
 
+impl TraceClosure<(), GcRoots> {
+    /// Start a `TraceClosure` whose dependencies are a set of `Gc` roots of
+    /// any types, for closures and trait objects that cannot derive
+    /// [`Trace`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rudo_gc::{Gc, TraceClosure};
+    ///
+    /// let name = Gc::new(String::from("button"));
+    /// let clicks = Gc::new(std::cell::Cell::new(0u32));
+    ///
+    /// let (n, c) = (name.clone(), clicks.clone());
+    /// let on_click = Gc::new(
+    ///     TraceClosure::builder()
+    ///         .root(&name)
+    ///         .root(&clicks)
+    ///         .build(Box::new(move || c.set(c.get() + n.len() as u32)) as Box<dyn Fn()>),
+    /// );
+    /// on_click.call();
+    /// assert_eq!(clicks.get(), 6);
+    /// ```
+    #[must_use]
+    pub const fn builder() -> TraceClosureBuilder {
+        TraceClosureBuilder {
+            roots: GcRoots::new(),
+        }
+    }
+}
+
 impl<C: Fn(), D> TraceClosure<C, D> {
     /// Call the inner closure.
     pub fn call(&self) {
@@ -31,3 +68,117 @@ unsafe impl<C, D: Trace> Trace for TraceClosure<C, D> {
         self.deps.trace(visitor);
     }
 }
+
+/// Collects the roots of a [`TraceClosure`]; see
+/// [`TraceClosure::builder`].
+#[derive(Debug, Default)]
+pub struct TraceClosureBuilder {
+    roots: GcRoots,
+}
+
+impl TraceClosureBuilder {
+    /// Add `gc` to the roots the closure keeps alive.
+    #[must_use]
+    pub fn root<T: Trace + 'static>(mut self, gc: &Gc<T>) -> Self {
+        self.roots.push(gc);
+        self
+    }
+
+    /// Wrap `closure`, keeping every added root alive for as long as the
+    /// `TraceClosure` is.
+    pub fn build<C>(self, closure: C) -> TraceClosure<C, GcRoots> {
+        TraceClosure::new(self.roots, closure)
+    }
+}
+
+/// A set of `Gc` roots of mixed types, stored as type-erased `GcBox`
+/// pointers.
+///
+/// Each root holds a strong reference, released when the set is dropped,
+/// and is visited whenever the set is traced, in minor and major marking
+/// alike. Visitors see each root as a `Gc<()>`, so those that inspect the
+/// type, such as [`Gc::traverse`], report it as `()`.
+pub struct GcRoots {
+    ptrs: Vec<NonNull<GcBox<()>>>,
+    /// Drops the strong reference held for the root at the same index.
+    releases: Vec<unsafe fn(NonNull<GcBox<()>>)>,
+}
+
+impl GcRoots {
+    /// Create an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ptrs: Vec::new(),
+            releases: Vec::new(),
+        }
+    }
+
+    /// Add `gc` to the set, holding a strong reference to it.
+    pub fn push<T: Trace + 'static>(&mut self, gc: &Gc<T>) {
+        unsafe fn release<T: Trace + 'static>(ptr: NonNull<GcBox<()>>) {
+            // SAFETY: `push` leaked a strong reference to this `GcBox<T>`.
+            drop(unsafe { Gc::<T>::from_raw(ptr.as_ptr().cast::<u8>().cast_const()) });
+        }
+
+        let gc = ManuallyDrop::new(gc.clone());
+        let Some(ptr) = NonNull::new(gc.raw_ptr()) else {
+            return;
+        };
+        self.ptrs.push(ptr.cast());
+        self.releases.push(release::<T>);
+    }
+
+    /// The number of roots in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ptrs.len()
+    }
+
+    /// Whether the set has no roots.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ptrs.is_empty()
+    }
+}
+
+impl Default for GcRoots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for GcRoots {
+    fn drop(&mut self) {
+        for (&ptr, release) in self.ptrs.iter().zip(&self.releases) {
+            // SAFETY: each pointer's strong reference is released once.
+            unsafe { release(ptr) };
+        }
+    }
+}
+
+unsafe impl Trace for GcRoots {
+    fn trace(&self, visitor: &mut impl Visitor) {
+        for &ptr in &self.ptrs {
+            // SAFETY: the set holds a strong reference, so the `GcBox` is
+            // live. The handle is never dropped, leaving the count alone.
+            let gc = ManuallyDrop::new(unsafe {
+                Gc::<()>::from_raw(ptr.as_ptr().cast::<u8>().cast_const())
+            });
+            visitor.visit(&*gc);
+        }
+    }
+}
+
+impl GcCapture for GcRoots {
+    #[inline]
+    fn capture_gc_ptrs(&self) -> &[NonNull<GcBox<()>>] {
+        &self.ptrs
+    }
+}
+
+impl std::fmt::Debug for GcRoots {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcRoots").field("len", &self.len()).finish()
+    }
+}
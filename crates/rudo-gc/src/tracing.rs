@@ -19,6 +19,20 @@ pub mod internal {
         Sweep,
     }
 
+    /// Expensive steps within a GC phase, traced as spans nested in it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GcStep {
+        /// Mark everything reachable from one heap's roots, following
+        /// references into other heaps.
+        MarkHeap,
+        /// Find roots (stacks, registers, handles) and mark them.
+        ScanRoots,
+        /// Trace the object graph from the marked roots.
+        TraceGraph,
+        /// Reclaim pages left behind by terminated threads.
+        SweepOrphans,
+    }
+
     /// Stable identifier for a GC run.
     ///
     /// This ID is used to correlate all events within a single garbage
@@ -62,6 +76,20 @@ pub mod internal {
         span!(Level::DEBUG, "gc_phase", phase = ?phase).entered()
     }
 
+    /// Create a span for a step within a GC phase.
+    ///
+    /// Each step gets its own span name, so flamegraph tools that group by
+    /// name attribute pause time to it.
+    pub fn trace_step(step: GcStep) -> span::EnteredSpan {
+        match step {
+            GcStep::MarkHeap => span!(Level::DEBUG, "gc_mark_heap"),
+            GcStep::ScanRoots => span!(Level::DEBUG, "gc_scan_roots"),
+            GcStep::TraceGraph => span!(Level::DEBUG, "gc_trace_graph"),
+            GcStep::SweepOrphans => span!(Level::DEBUG, "gc_sweep_orphans"),
+        }
+        .entered()
+    }
+
     /// Log the start of a GC phase.
     pub fn log_phase_start(phase: GcPhase, bytes_before: usize) {
         tracing::debug!(phase = ?phase, bytes_before, "phase_start");
@@ -0,0 +1,288 @@
+//! Breadth-first iteration over `Gc` object graphs.
+//!
+//! [`GcTraversal`] enumerates edges with the collector's own `Trace`/`Visitor`
+//! machinery, using a [`VisitorKind::Traverse`] visitor that records edges
+//! instead of setting mark bits, so it is safe to run between collections.
+//! [`is_reachable`] and [`retaining_path`] answer point queries the same way,
+//! and [`Gc::subgraph_stats`] measures everything reachable.
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::mem::ManuallyDrop;
+use std::ptr::NonNull;
+
+use crate::ptr::GcBox;
+use crate::trace::{GcVisitor, Trace, VisitorKind};
+use crate::Gc;
+
+/// Iterator returned by [`Gc::traverse`].
+///
+/// Yields every `Gc<T>` reachable from the starting pointer exactly once, in
+/// breadth-first order by the number of `Gc<T>` hops. Allocations of other
+/// types are walked through but not yielded. Pointers hidden from `Trace`
+/// (for example, only found by conservative scanning) are not followed.
+///
+/// Queued nodes are held as strong references, so mutating the graph while
+/// iterating is safe; edges added to a node after it was yielded are not seen.
+pub struct GcTraversal<T: Trace + 'static> {
+    queue: VecDeque<Gc<T>>,
+    visited: HashSet<usize>,
+    visitor: GcVisitor,
+}
+
+impl<T: Trace + 'static> GcTraversal<T> {
+    pub(crate) fn new(start: &Gc<T>) -> Self {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        if !Gc::is_dead_or_unrooted(start) {
+            visited.insert(start.raw_ptr() as usize);
+            queue.push_back(start.clone());
+        }
+        Self {
+            queue,
+            visited,
+            visitor: GcVisitor::new(VisitorKind::Traverse),
+        }
+    }
+
+    /// Queue the unvisited `Gc<T>` neighbours of `node`, expanding edges
+    /// through objects of other types on the way.
+    fn enqueue_neighbours(&mut self, node: &Gc<T>) {
+        let mut pending: Vec<NonNull<GcBox<()>>> = vec![node.as_non_null().cast()];
+        while let Some(ptr) = pending.pop() {
+            // SAFETY: `ptr` is `node` or was reached from it through `Trace`
+            // during this call, and nothing is freed while we hold `node`.
+            unsafe { (GcBox::trace_fn_of(ptr.as_ptr()))(ptr.as_ptr().cast(), &mut self.visitor) };
+
+            for (target, type_id) in self.visitor.discovered.drain(..) {
+                if !is_live(target) || !self.visited.insert(target.as_ptr() as usize) {
+                    continue;
+                }
+                if type_id == TypeId::of::<T>() {
+                    // SAFETY: the edge was recorded as a `Gc<T>`; the borrowed
+                    // pointer is cloned and never dropped.
+                    let gc = ManuallyDrop::new(unsafe {
+                        Gc::<T>::from_raw(target.as_ptr().cast_const().cast())
+                    });
+                    self.queue.push_back(Gc::clone(&gc));
+                } else {
+                    pending.push(target);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Trace + 'static> Iterator for GcTraversal<T> {
+    type Item = Gc<T>;
+
+    fn next(&mut self) -> Option<Gc<T>> {
+        let node = self.queue.pop_front()?;
+        self.enqueue_neighbours(&node);
+        Some(node)
+    }
+}
+
+impl<T: Trace + 'static> std::fmt::Debug for GcTraversal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcTraversal")
+            .field("queued", &self.queue.len())
+            .field("visited", &self.visited.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether the allocation behind an edge may still be traced.
+fn is_live(target: NonNull<GcBox<()>>) -> bool {
+    // SAFETY: `target` came from a live `Gc` field.
+    unsafe {
+        let gc_box = &*target.as_ptr();
+        !gc_box.has_dead_flag() && gc_box.dropping_state() == 0 && !gc_box.is_under_construction()
+    }
+}
+
+/// Returns `true` if `to` can be reached from `from` by following `Trace`
+/// edges.
+///
+/// The walk records edges instead of setting mark bits, so it does not
+/// disturb the collector and can run at any time between collections. An
+/// object is reachable from itself. See [`retaining_path`] for the chain of
+/// objects that keeps `to` alive.
+#[must_use]
+pub fn is_reachable<A: Trace + 'static, B: Trace + 'static>(from: &Gc<A>, to: &Gc<B>) -> bool {
+    retaining_path(from, to).is_some()
+}
+
+/// Finds a shortest chain of `Trace` edges from `from` to `to`.
+///
+/// Returns the `GcBox` addresses along the chain, starting with `from` and
+/// ending with `to`, or `None` if `to` is not reachable. Addresses match
+/// [`Weak::raw_addr`](crate::Weak::raw_addr), so they can be tied back to
+/// known objects while investigating why something is still alive.
+///
+/// # Example
+///
+/// ```
+/// use rudo_gc::{retaining_path, Gc, GcCell, Trace};
+///
+/// #[derive(Trace)]
+/// struct Node { next: GcCell<Option<Gc<Node>>> }
+///
+/// let c = Gc::new(Node { next: GcCell::new(None) });
+/// let b = Gc::new(Node { next: GcCell::new(Some(c.clone())) });
+/// let a = Gc::new(Node { next: GcCell::new(Some(b.clone())) });
+///
+/// assert_eq!(retaining_path(&a, &c).map(|path| path.len()), Some(3));
+/// assert_eq!(retaining_path(&c, &a), None);
+/// ```
+#[must_use]
+pub fn retaining_path<A: Trace + 'static, B: Trace + 'static>(
+    from: &Gc<A>,
+    to: &Gc<B>,
+) -> Option<Vec<usize>> {
+    if Gc::is_dead_or_unrooted(from) || Gc::is_dead_or_unrooted(to) {
+        return None;
+    }
+    let start = from.raw_ptr() as usize;
+    let goal = to.raw_ptr() as usize;
+
+    // Each visited address maps to the address it was first reached from.
+    let mut parents: HashMap<usize, usize> = HashMap::from([(start, start)]);
+    let mut queue: VecDeque<NonNull<GcBox<()>>> = VecDeque::from([from.as_non_null().cast()]);
+    let mut visitor = GcVisitor::new(VisitorKind::Traverse);
+
+    while let Some(ptr) = queue.pop_front() {
+        let addr = ptr.as_ptr() as usize;
+        if addr == goal {
+            let mut path = vec![addr];
+            let mut current = addr;
+            while current != start {
+                current = parents[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        // SAFETY: `ptr` was reached from `from` through `Trace`, and nothing
+        // is freed while the caller holds `from`.
+        unsafe { (GcBox::trace_fn_of(ptr.as_ptr()))(ptr.as_ptr().cast(), &mut visitor) };
+
+        for (target, _) in visitor.discovered.drain(..) {
+            let target_addr = target.as_ptr() as usize;
+            if is_live(target) && !parents.contains_key(&target_addr) {
+                parents.insert(target_addr, addr);
+                queue.push_back(target);
+            }
+        }
+    }
+    None
+}
+
+/// Size of the object graph reachable from a `Gc`, as returned by
+/// [`Gc::subgraph_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SubgraphStats {
+    /// Number of distinct allocations reachable, including the start.
+    pub object_count: usize,
+    /// Sum of the heap slots those allocations occupy, headers included.
+    pub total_bytes: usize,
+    /// Most `Trace` edges on the shortest path from the start to any
+    /// reachable allocation; 0 if the start references nothing.
+    pub max_depth: usize,
+}
+
+/// Walk everything reachable from `start`, breadth first.
+pub fn subgraph_stats(start: NonNull<GcBox<()>>) -> SubgraphStats {
+    let mut stats = SubgraphStats::default();
+    let mut visited: HashSet<usize> = HashSet::from([start.as_ptr() as usize]);
+    let mut queue: VecDeque<(NonNull<GcBox<()>>, usize)> = VecDeque::from([(start, 0)]);
+    let mut visitor = GcVisitor::new(VisitorKind::Traverse);
+
+    while let Some((ptr, depth)) = queue.pop_front() {
+        stats.object_count += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        // SAFETY: `ptr` is `start` or was reached from it through `Trace`, and
+        // nothing is freed while the caller holds `start`. Every allocation,
+        // large ones included, starts on a page whose header records its size.
+        unsafe {
+            let header = crate::heap::ptr_to_page_header(ptr.as_ptr().cast_const().cast());
+            stats.total_bytes += (*header.as_ptr()).block_size as usize;
+            (GcBox::trace_fn_of(ptr.as_ptr()))(ptr.as_ptr().cast(), &mut visitor);
+        }
+
+        for (target, _) in visitor.discovered.drain(..) {
+            if is_live(target) && visited.insert(target.as_ptr() as usize) {
+                queue.push_back((target, depth + 1));
+            }
+        }
+    }
+    stats
+}
+
+/// Finds every live `T` allocated on this thread's heap.
+///
+/// Walks all of the heap's pages and returns a new `Gc` for each object whose
+/// recorded type is `T`, in no particular order. A pending lazy sweep is
+/// finished first so that garbage the collector already condemned is never
+/// revived. Unreachable cycles it has not found yet are still returned; run
+/// [`collect_full`](crate::collect_full) first to leave them out.
+///
+/// Only this thread's heap is walked; objects allocated by other threads are
+/// not seen, so call it on each thread whose objects should be counted. A
+/// `T` from another thread's heap need not be `Send`, so it could not be
+/// handed out here. The walk does not stop the world: only this thread
+/// allocates into its heap, and collections, the only thing that frees its
+/// objects, are paused with a [`NoGcGuard`](crate::NoGcGuard) until it ends.
+///
+/// Meant for debugging, such as counting the `Connection`s still alive; the
+/// walk visits every object on the heap. Requires the `type-tracking` feature.
+#[cfg(feature = "type-tracking")]
+#[must_use]
+pub fn find_objects_of_type<T: Trace + 'static>() -> Vec<Gc<T>> {
+    use crate::heap::{LocalHeap, PageHeader};
+
+    let _no_gc = crate::gc::NoGcGuard::new();
+    let marking = crate::gc::incremental::is_incremental_marking_active();
+    crate::heap::with_heap(|heap: &mut LocalHeap| {
+        #[cfg(feature = "lazy-sweep")]
+        let _ = crate::gc::sweep_pending(heap, usize::MAX);
+
+        let mut found = Vec::new();
+        for page in heap.all_pages() {
+            // SAFETY: the heap's pages stay mapped, and with collections paused
+            // no allocated slot is freed during the walk.
+            unsafe {
+                let header = page.as_ptr();
+                let header_size = PageHeader::header_size((*header).block_size as usize);
+                let slots = if (*header).is_large_object() {
+                    0..1
+                } else {
+                    0..(*header).obj_count as usize
+                };
+                for i in slots {
+                    if !(*header).is_large_object() && !(*header).is_allocated(i) {
+                        continue;
+                    }
+                    let offset = header_size + i * (*header).block_size as usize;
+                    #[allow(clippy::cast_ptr_alignment)]
+                    let gc_box = header.cast::<u8>().add(offset).cast::<GcBox<()>>();
+                    let target = NonNull::new_unchecked(gc_box);
+                    if !is_live(target) || GcBox::type_info_of(gc_box).id != TypeId::of::<T>() {
+                        continue;
+                    }
+                    // Handing out a pointer creates a root the collector's
+                    // snapshot may have missed.
+                    if marking {
+                        crate::gc::incremental::mark_new_object_black(gc_box.cast());
+                    }
+                    // SAFETY: the object's recorded type is `T`; the borrowed
+                    // pointer is cloned and never dropped.
+                    let gc = ManuallyDrop::new(Gc::<T>::from_raw(gc_box.cast_const().cast()));
+                    found.push(Gc::clone(&gc));
+                }
+            }
+        }
+        found
+    })
+}
@@ -0,0 +1,45 @@
+//! Tests for `alloc_counters`, the per-thread `Gc` lifecycle snapshot.
+
+use rudo_gc::{alloc_counters, collect_full, Gc};
+
+#[test]
+fn test_alloc_counter_deltas() {
+    let root = Gc::new(0_u64);
+    let before = alloc_counters();
+
+    let kept: Vec<_> = (0..3).map(Gc::new).collect();
+    for _ in 0..2 {
+        // Dropping a clone leaves `root` alive, so it counts as a drop.
+        drop(Gc::clone(&root));
+    }
+
+    let delta = alloc_counters().since(&before).unwrap();
+    assert_eq!(delta.created, 3);
+    assert_eq!(delta.dropped, 2);
+    assert_eq!(delta.existing, 3);
+    drop(kept);
+}
+
+#[test]
+fn test_alloc_counters_out_of_order_is_none() {
+    let before = alloc_counters();
+    let _value = Gc::new(3_u64);
+    let after = alloc_counters();
+
+    assert_eq!(before.since(&after), None);
+    assert!(after.since(&before).is_some());
+}
+
+#[test]
+fn test_alloc_counters_survive_collection() {
+    let root = Gc::new(1_u64);
+    let before = alloc_counters();
+    drop(Gc::clone(&root));
+    collect_full();
+    let _extra = Gc::new(2_u64);
+
+    // Collections reset the collector's drop count, not these counters.
+    let delta = alloc_counters().since(&before).unwrap();
+    assert_eq!(delta.created, 1);
+    assert_eq!(delta.dropped, 1);
+}
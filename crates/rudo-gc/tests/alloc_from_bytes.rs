@@ -0,0 +1,52 @@
+//! Tests for `Gc::alloc_from_bytes`.
+
+use rudo_gc::{collect_full, Gc, Trace};
+
+#[derive(Trace, Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct PodNode {
+    id: u64,
+    parent: u32,
+    weight: f32,
+}
+
+fn to_bytes(nodes: &[PodNode]) -> Vec<u8> {
+    // SAFETY: `PodNode` is `repr(C)` without padding, so every byte is initialized.
+    unsafe { std::slice::from_raw_parts(nodes.as_ptr().cast::<u8>(), std::mem::size_of_val(nodes)) }
+        .to_vec()
+}
+
+#[test]
+fn test_alloc_from_bytes_round_trips_pod_nodes() {
+    let nodes: Vec<PodNode> = (0..1_000)
+        .map(|i| PodNode {
+            id: i,
+            parent: u32::try_from(i / 2).unwrap(),
+            #[allow(clippy::cast_precision_loss)]
+            weight: i as f32 * 0.5,
+        })
+        .collect();
+
+    // Offset by one byte so the values are read from unaligned storage.
+    let mut buffer = vec![0u8];
+    buffer.extend(to_bytes(&nodes));
+
+    // SAFETY: the bytes were copied out of `PodNode`s.
+    let gcs = Gc::new(unsafe { Gc::<PodNode>::alloc_from_bytes(&buffer[1..]) });
+    assert_eq!(gcs.len(), nodes.len());
+
+    collect_full();
+    assert!(gcs.iter().zip(&nodes).all(|(gc, node)| **gc == *node));
+}
+
+#[test]
+#[should_panic(expected = "not a whole number")]
+fn test_alloc_from_bytes_rejects_partial_values() {
+    let bytes = to_bytes(&[PodNode {
+        id: 1,
+        parent: 0,
+        weight: 1.0,
+    }]);
+    // SAFETY: the panic fires before any value is read.
+    let _ = unsafe { Gc::<PodNode>::alloc_from_bytes(&bytes[..bytes.len() - 1]) };
+}
@@ -0,0 +1,63 @@
+//! Tests for the sampling allocation profiler.
+
+use rudo_gc::{start_alloc_profiling, stop_alloc_profiling, Gc};
+
+#[inline(never)]
+fn hot_site(out: &mut Vec<Gc<u64>>) {
+    for i in 0..900 {
+        out.push(Gc::new(i));
+    }
+}
+
+#[inline(never)]
+fn cold_site(out: &mut Vec<Gc<u64>>) {
+    for i in 0..100 {
+        out.push(Gc::new(i));
+    }
+}
+
+fn samples_from(profile: &rudo_gc::AllocProfile, site: &str) -> usize {
+    profile
+        .samples
+        .iter()
+        .filter(|sample| sample.frames.last().is_some_and(|f| f.contains(site)))
+        .map(|sample| sample.count)
+        .sum()
+}
+
+#[test]
+fn test_hot_site_dominates_profile() {
+    let mut values = Vec::new();
+    start_alloc_profiling(10);
+    hot_site(&mut values);
+    cold_site(&mut values);
+    let profile = stop_alloc_profiling();
+
+    assert_eq!(profile.sample_rate, 10);
+    assert_eq!(profile.total_samples(), 100);
+    assert_eq!(samples_from(&profile, "hot_site"), 90);
+    assert_eq!(samples_from(&profile, "cold_site"), 10);
+    assert!(profile.samples[0]
+        .frames
+        .last()
+        .unwrap()
+        .contains("hot_site"));
+    assert!(profile.samples[0].bytes >= 90 * std::mem::size_of::<u64>());
+
+    let folded = profile.to_folded();
+    assert!(folded
+        .lines()
+        .any(|line| line.contains("hot_site") && line.ends_with(" 90")));
+    assert_eq!(values.len(), 1000);
+}
+
+#[test]
+fn test_stopped_profiler_takes_no_samples() {
+    start_alloc_profiling(1);
+    let _ = stop_alloc_profiling();
+    let mut values = Vec::new();
+    cold_site(&mut values);
+
+    assert_eq!(stop_alloc_profiling(), rudo_gc::AllocProfile::default());
+    assert_eq!(values.len(), 100);
+}
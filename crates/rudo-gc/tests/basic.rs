@@ -37,6 +37,21 @@ fn test_ref_count() {
     assert_eq!(Gc::ref_count(&x).get(), 1);
 }
 
+#[test]
+fn test_strong_and_weak_count() {
+    let x = Gc::new(String::from("counted"));
+    let y = x.clone();
+    let weak = Gc::downgrade(&x);
+    assert_eq!(Gc::strong_count(&x), 2);
+    assert_eq!(Gc::weak_count(&x), 1);
+
+    drop(y);
+    assert_eq!(Gc::strong_count(&x), 1);
+
+    drop(weak);
+    assert_eq!(Gc::weak_count(&x), 0);
+}
+
 #[test]
 fn test_drop_and_collect() {
     let x = Gc::new(42);
@@ -0,0 +1,27 @@
+//! Tests that collections shrink per-thread buffers grown by a burst.
+
+use rudo_gc::heap::with_heap;
+use rudo_gc::{collect_full, Gc, GcBox};
+use std::ptr::NonNull;
+
+#[test]
+fn test_satb_overflow_buffer_shrinks_after_collection() {
+    let value = Gc::new(0_u64);
+    #[allow(clippy::cast_ptr_alignment)]
+    let gc_box = NonNull::new(Gc::internal_ptr(&value).cast::<GcBox<()>>().cast_mut()).unwrap();
+
+    // Every full SATB buffer spills into the overflow buffer.
+    with_heap(|heap| {
+        for _ in 0..10_000 {
+            let _ = heap.record_satb_old_value(gc_box);
+        }
+    });
+    let grown = with_heap(|heap| heap.satb_overflow_buffer_capacity());
+    assert!(grown >= 9_000, "burst should grow the buffer, got {grown}");
+
+    collect_full();
+
+    let shrunk = with_heap(|heap| heap.satb_overflow_buffer_capacity());
+    assert!(shrunk <= 64, "expected the buffer to shrink, got {shrunk}");
+    assert_eq!(*value, 0);
+}
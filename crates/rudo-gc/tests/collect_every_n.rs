@@ -0,0 +1,23 @@
+//! Tests for `set_collect_every_n_allocations`.
+
+use rudo_gc::{global_metrics, set_collect_every_n_allocations, Gc};
+
+#[test]
+fn test_collects_every_n_allocations() {
+    set_collect_every_n_allocations(100);
+    let before = global_metrics().total_collections();
+
+    // Kept alive so that no drop-driven collection interferes.
+    let values: Vec<Gc<u64>> = (0..250).map(Gc::new).collect();
+    assert_eq!(global_metrics().total_collections() - before, 2);
+
+    set_collect_every_n_allocations(0);
+    let before = global_metrics().total_collections();
+    let more: Vec<Gc<u64>> = (0..250).map(Gc::new).collect();
+    assert_eq!(global_metrics().total_collections() - before, 0);
+
+    assert_eq!(
+        values.iter().zip(&more).filter(|(a, b)| **a == **b).count(),
+        250
+    );
+}
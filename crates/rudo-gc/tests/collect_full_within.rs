@@ -0,0 +1,243 @@
+//! Tests for deadline-bounded full collections with `collect_full_within`.
+//!
+//! Kept in its own binary because it drives the global marking state.
+
+mod common;
+
+use std::sync::atomic::Ordering;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rudo_gc::gc::incremental::{
+    execute_snapshot, FallbackReason, IncrementalConfig, IncrementalMarkState, MarkPhase,
+};
+use rudo_gc::heap::{with_heap, LocalHeap};
+use rudo_gc::{
+    collect_full_within, last_gc_metrics, test_util, CollectWithinResult, CollectionType, Gc,
+    GcCell, Trace,
+};
+
+#[derive(Trace)]
+struct Node {
+    value: usize,
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+/// Leave `count` unreachable nodes for the sliced collection to find.
+fn make_garbage(count: usize) {
+    let node = |value| Node {
+        value,
+        next: GcCell::new(None),
+    };
+    common::make_self_cycles(count, node, |node| &node.next);
+}
+
+fn make_list(len: usize) -> Gc<Node> {
+    let head = Gc::new(Node {
+        value: 0,
+        next: GcCell::new(None),
+    });
+    let mut tail = head.clone();
+    for value in 1..len {
+        let node = Gc::new(Node {
+            value,
+            next: GcCell::new(None),
+        });
+        *tail.next.borrow_mut() = Some(node.clone());
+        tail = node;
+    }
+    head
+}
+
+fn list_len(head: &Gc<Node>) -> usize {
+    let mut len = 1;
+    let mut node = head.clone();
+    loop {
+        let next = node.next.borrow().clone();
+        match next {
+            Some(next) => {
+                assert_eq!(next.value, len);
+                len += 1;
+                node = next;
+            }
+            None => return len,
+        }
+    }
+}
+
+#[test]
+fn test_collect_full_within_resumes_across_calls() {
+    // Each round collects nodes allocated just before it.
+    let _young = common::YoungGarbage::expected();
+    test_util::reset();
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        enabled: true,
+        increment_size: 64,
+        ..IncrementalConfig::default()
+    });
+
+    let live = make_list(2000);
+    make_garbage(2000);
+    unsafe { test_util::clear_registers() };
+
+    // A zero budget still runs one slice per call.
+    assert_eq!(
+        collect_full_within(Duration::ZERO),
+        CollectWithinResult::Pending
+    );
+    assert_eq!(state.phase(), MarkPhase::Marking);
+    let mut slices = state.stats().slices_executed.load(Ordering::Relaxed);
+    let mut calls = 1;
+    while collect_full_within(Duration::ZERO) == CollectWithinResult::Pending {
+        assert_eq!(state.phase(), MarkPhase::Marking);
+        let now = state.stats().slices_executed.load(Ordering::Relaxed);
+        assert!(now > slices, "call {calls} made no progress");
+        slices = now;
+        calls += 1;
+        // The mutator runs between calls; the barriers keep the rest of the
+        // list alive while it is detached.
+        let rest = live.next.borrow_mut().take();
+        *live.next.borrow_mut() = rest;
+    }
+    assert!(calls > 1);
+    assert_eq!(state.phase(), MarkPhase::Idle);
+
+    let metrics = last_gc_metrics();
+    assert_eq!(metrics.collection_type, CollectionType::IncrementalMajor);
+    // The stack is scanned conservatively; a stray word may pin a node.
+    assert!(metrics.objects_reclaimed >= 1990, "{metrics:?}");
+    assert_eq!(list_len(&live), 2000);
+
+    // A generous budget finishes in one call.
+    make_garbage(2000);
+    unsafe { test_util::clear_registers() };
+    assert_eq!(
+        collect_full_within(Duration::from_secs(60)),
+        CollectWithinResult::Complete
+    );
+    assert_eq!(state.phase(), MarkPhase::Idle);
+    let metrics = last_gc_metrics();
+    assert!(metrics.objects_reclaimed >= 1990, "{metrics:?}");
+    assert_eq!(list_len(&live), 2000);
+
+    state.set_config(IncrementalConfig::default());
+    test_util::reset();
+}
+
+#[test]
+fn test_fallback_is_reported() {
+    test_util::reset();
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        enabled: true,
+        increment_size: 64,
+        ..IncrementalConfig::default()
+    });
+    let logged = Arc::new(Mutex::new(Vec::new()));
+    let sink = logged.clone();
+    rudo_gc::set_fallback_logger(move |reason| sink.lock().unwrap().push(reason));
+
+    let live = make_list(2000);
+    assert_eq!(
+        collect_full_within(Duration::ZERO),
+        CollectWithinResult::Pending
+    );
+    assert_eq!(rudo_gc::last_fallback_reason(), None);
+
+    state.request_fallback(FallbackReason::DirtyPagesExceeded);
+    assert_eq!(
+        collect_full_within(Duration::ZERO),
+        CollectWithinResult::Complete
+    );
+    assert_eq!(state.phase(), MarkPhase::Idle);
+    let reasons = logged.lock().unwrap().clone();
+    assert_eq!(reasons, [FallbackReason::DirtyPagesExceeded]);
+    assert_eq!(
+        rudo_gc::last_fallback_reason(),
+        Some(FallbackReason::DirtyPagesExceeded)
+    );
+    assert_eq!(
+        FallbackReason::DirtyPagesExceeded.to_string(),
+        "dirty pages exceeded threshold"
+    );
+    assert_eq!(list_len(&live), 2000);
+
+    rudo_gc::clear_fallback_logger();
+    state.set_config(IncrementalConfig::default());
+    test_util::reset();
+}
+
+#[test]
+fn test_snapshot_under_guard_falls_back() {
+    test_util::reset();
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        enabled: true,
+        ..IncrementalConfig::default()
+    });
+    let live = make_list(100);
+
+    // A registered thread that never reaches a safe point while the
+    // snapshot waits for it.
+    let (registered_tx, registered_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    let other = thread::spawn(move || {
+        let _own = Gc::new(0_u8);
+        registered_tx.send(()).unwrap();
+        let _ = done_rx.recv();
+    });
+    registered_rx.recv().unwrap();
+
+    {
+        let _guard = rudo_gc::NoGcGuard::new();
+        with_heap(|heap: &mut LocalHeap| {
+            let heaps: [&LocalHeap; 1] = [heap];
+            execute_snapshot(&heaps);
+        });
+        assert!(state.fallback_requested());
+        assert_eq!(
+            rudo_gc::last_fallback_reason(),
+            Some(FallbackReason::NoGcGuard)
+        );
+    }
+    done_tx.send(()).unwrap();
+    other.join().unwrap();
+
+    assert_eq!(
+        collect_full_within(Duration::from_secs(60)),
+        CollectWithinResult::Complete
+    );
+    assert_eq!(state.phase(), MarkPhase::Idle);
+    assert_eq!(list_len(&live), 100);
+
+    state.set_config(IncrementalConfig::default());
+    test_util::reset();
+}
+
+#[test]
+fn test_skipped_while_collection_is_paused() {
+    test_util::reset();
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        enabled: true,
+        ..IncrementalConfig::default()
+    });
+
+    {
+        let _guard = rudo_gc::NoGcGuard::new();
+        assert_eq!(
+            collect_full_within(Duration::ZERO),
+            CollectWithinResult::Skipped
+        );
+        assert_eq!(state.phase(), MarkPhase::Idle);
+    }
+    assert_ne!(
+        collect_full_within(Duration::from_secs(60)),
+        CollectWithinResult::Skipped
+    );
+
+    state.set_config(IncrementalConfig::default());
+    test_util::reset();
+}
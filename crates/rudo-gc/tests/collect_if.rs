@@ -0,0 +1,30 @@
+//! Tests for `collect_if`.
+
+use rudo_gc::{collect_full, collect_if, current_heap_size, global_metrics, Gc};
+
+fn collections() -> usize {
+    global_metrics().total_collections()
+}
+
+#[test]
+fn test_collect_if_follows_the_closure() {
+    let _live: Gc<Vec<Gc<[u64; 16]>>> = Gc::new((0..1000).map(|i| Gc::new([i; 16])).collect());
+    collect_full();
+
+    let before = collections();
+    collect_if(|_| false);
+    assert_eq!(collections(), before);
+
+    // The condition may capture state chosen at runtime.
+    let checkpoint = current_heap_size();
+    let mut seen = None;
+    collect_if(|info| {
+        seen = Some(info.heap_size());
+        info.heap_size() > checkpoint
+    });
+    assert_eq!(seen, Some(checkpoint));
+    assert_eq!(collections(), before);
+
+    collect_if(|info| info.heap_size() >= checkpoint);
+    assert_eq!(collections(), before + 1);
+}
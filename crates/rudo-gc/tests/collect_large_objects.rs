@@ -0,0 +1,145 @@
+//! Tests for `collect_large_objects`.
+
+mod common;
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use rudo_gc::heap::{enter_inactive, leave_inactive};
+use rudo_gc::{collect_full, collect_large_objects, Gc, GcCell, Trace};
+
+const BIG: usize = 8;
+const SMALL: usize = 64;
+
+thread_local! {
+    static BIG_DROPS: Cell<usize> = const { Cell::new(0) };
+    static SMALL_DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Too big for a size class, so it gets its own large-object pages.
+#[derive(Trace)]
+struct Big {
+    pixels: [u8; 16 * 1024],
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Big {
+    fn drop(&mut self) {
+        BIG_DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+#[derive(Trace)]
+struct Small {
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Small {
+    fn drop(&mut self) {
+        SMALL_DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+/// Unreachable objects on both kinds of page, so the test can tell which
+/// ones a large-object collection leaves behind.
+fn make_garbage() {
+    let big = |_| Big {
+        pixels: [0; 16 * 1024],
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles(BIG, big, |big| &big.this);
+    let small = |_| Small {
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles(SMALL, small, |small| &small.this);
+}
+
+/// Conservative stack scanning may keep an object or two alive, so the
+/// assertions only require that most of the garbage is collected.
+#[test]
+fn test_collect_large_objects_leaves_small_pages_alone() {
+    // Both collections below sweep garbage made moments before.
+    let _young = common::YoungGarbage::expected();
+    let live = Gc::new(Big {
+        pixels: [7; 16 * 1024],
+        this: GcCell::new(None),
+    });
+    make_garbage();
+    unsafe { rudo_gc::test_util::clear_registers() };
+
+    collect_large_objects();
+    let big = BIG_DROPS.with(Cell::get);
+    assert!(big >= BIG - 1, "reclaimed {big} of {BIG} large objects");
+    assert_eq!(SMALL_DROPS.with(Cell::get), 0);
+    assert_eq!(live.pixels[0], 7);
+
+    // A regular collection still finds the small garbage.
+    collect_full();
+    let small = SMALL_DROPS.with(Cell::get);
+    assert!(
+        small >= SMALL - 2,
+        "reclaimed {small} of {SMALL} small objects"
+    );
+    assert_eq!(live.pixels[16 * 1024 - 1], 7);
+}
+
+static SHARED_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// A large object that can be shared with another thread.
+#[derive(Trace)]
+struct SharedBig {
+    pixels: [u8; 16 * 1024],
+}
+
+impl Drop for SharedBig {
+    fn drop(&mut self) {
+        SHARED_DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[inline(never)]
+fn send_big(tx: &mpsc::Sender<Gc<SharedBig>>) {
+    tx.send(Gc::new(SharedBig {
+        pixels: [9; 16 * 1024],
+    }))
+    .unwrap();
+}
+
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_large_object_held_by_other_thread_survives() {
+    let (tx, rx) = mpsc::channel();
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let dropped = std::thread::scope(|s| {
+        s.spawn(move || {
+            let big: Gc<SharedBig> = rx.recv().unwrap();
+            // Register this thread, then step aside so collections can run
+            // from the stack roots it leaves behind.
+            let _own = Gc::new(0_u8);
+            enter_inactive();
+            ready_tx.send(()).unwrap();
+            let _ = done_rx.recv();
+            leave_inactive();
+            assert_eq!(big.pixels[0], 9);
+        });
+
+        send_big(&tx);
+        ready_rx.recv().unwrap();
+        scrub_stack();
+        unsafe { rudo_gc::test_util::clear_registers() };
+
+        collect_large_objects();
+        let dropped = SHARED_DROPS.load(Ordering::SeqCst);
+        done_tx.send(()).unwrap();
+        dropped
+    });
+    assert_eq!(dropped, 0);
+}
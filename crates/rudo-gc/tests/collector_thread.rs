@@ -0,0 +1,142 @@
+//! Tests for the collector thread.
+//!
+//! Kept in its own binary because it drives the global collector state.
+
+mod common;
+
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rudo_gc::{
+    collector_thread_stats, enable_collector_thread, enable_collector_thread_with, safepoint,
+    CollectorThreadConfig, Gc, GcCell, Trace,
+};
+
+const MUTATORS: usize = 3;
+const CYCLES: usize = 3;
+const LIVE: usize = 64;
+
+static GARBAGE_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Node {
+    value: usize,
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+#[derive(Trace)]
+struct Garbage {
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Garbage {
+    fn drop(&mut self) {
+        GARBAGE_DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Feed the collector thread `count` objects it has to reclaim.
+fn make_garbage(count: usize) {
+    let garbage = |_| Garbage {
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles(count, garbage, |garbage| &garbage.this);
+}
+
+fn tail(head: &Gc<Node>) -> Gc<Node> {
+    let mut node = head.clone();
+    loop {
+        let next = node.next.borrow().clone();
+        match next {
+            Some(next) => node = next,
+            None => return node,
+        }
+    }
+}
+
+/// Moves the first node after `head` to the end of the list. For a moment
+/// the node is reachable from the stack only, and the overwritten links
+/// are what the SATB barrier has to record.
+fn rotate(head: &Gc<Node>) {
+    let first = head.next.borrow_mut().take().expect("list is not empty");
+    let rest = first.next.borrow_mut().take();
+    *head.next.borrow_mut() = rest;
+    *tail(head).next.borrow_mut() = Some(first);
+}
+
+/// Allocates garbage and rewires a live list until `done`, then checks that
+/// the list survived intact. Returns the number of rounds run.
+fn mutator(done: &AtomicBool) -> usize {
+    let head = Gc::new(Node {
+        value: 0,
+        next: GcCell::new(None),
+    });
+    for value in 1..=LIVE {
+        *tail(&head).next.borrow_mut() = Some(Gc::new(Node {
+            value,
+            next: GcCell::new(None),
+        }));
+    }
+
+    let mut rounds = 0;
+    while !done.load(Ordering::Relaxed) {
+        make_garbage(64);
+        rotate(&head);
+        safepoint();
+        rounds += 1;
+    }
+
+    let mut values = Vec::new();
+    let mut node = head;
+    loop {
+        let next = node.next.borrow().clone();
+        let Some(next) = next else { break };
+        values.push(next.value);
+        node = next;
+    }
+    values.sort_unstable();
+    assert_eq!(values, (1..=LIVE).collect::<Vec<_>>());
+    rounds
+}
+
+#[test]
+fn test_collector_thread_reclaims_cycles_while_mutators_run() {
+    // The mutators keep allocating garbage while the collector sweeps.
+    let _young = common::YoungGarbage::expected();
+    let collector = enable_collector_thread_with(CollectorThreadConfig {
+        allocation_threshold: 4096,
+        ..CollectorThreadConfig::default()
+    })
+    .unwrap();
+    assert_eq!(
+        enable_collector_thread().unwrap_err().kind(),
+        io::ErrorKind::AlreadyExists
+    );
+
+    // This thread never touches its heap, so the collector doesn't wait on it.
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let mutators: Vec<_> = (0..MUTATORS)
+            .map(|_| scope.spawn(|| mutator(&done)))
+            .collect();
+        let deadline = Instant::now() + Duration::from_secs(60);
+        while collector_thread_stats().cycles < CYCLES && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        done.store(true, Ordering::Relaxed);
+        for mutator in mutators {
+            assert!(mutator.join().unwrap() > 0);
+        }
+    });
+    drop(collector);
+
+    let stats = collector_thread_stats();
+    assert!(stats.cycles >= CYCLES, "{stats:?}");
+    assert!(stats.pauses > stats.cycles, "{stats:?}");
+    assert!(GARBAGE_DROPS.load(Ordering::Relaxed) > 0, "{stats:?}");
+    // Generous for debug builds on a loaded machine; a stop-the-world
+    // collection of the same heaps is what this guards against.
+    assert!(stats.max_pause < Duration::from_millis(250), "{stats:?}");
+}
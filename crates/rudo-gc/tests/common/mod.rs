@@ -0,0 +1,61 @@
+//! Fixtures shared by the integration tests.
+//!
+//! Each test binary that uses them declares `mod common;`, and may use only
+//! some of them.
+
+#![allow(dead_code)]
+
+use rudo_gc::{Gc, GcCell, Trace};
+
+/// Allocate `count` objects built by `make`, point each at itself through
+/// the cell `this` returns, and drop them.
+///
+/// The self-reference keeps every reference count above zero, so only a
+/// collection can reclaim the objects. Kept out of line so that no pointer
+/// to them lingers in the caller's frame.
+pub fn make_self_cycles<T: Trace + 'static>(
+    count: usize,
+    make: impl FnMut(usize) -> T,
+    this: impl Fn(&T) -> &GcCell<Option<Gc<T>>>,
+) {
+    make_self_cycles_with(count, make, this, |_| {});
+}
+
+/// Like [`make_self_cycles`], but hands each object to `each` before it is
+/// dropped.
+#[inline(never)]
+pub fn make_self_cycles_with<T: Trace + 'static>(
+    count: usize,
+    mut make: impl FnMut(usize) -> T,
+    this: impl Fn(&T) -> &GcCell<Option<Gc<T>>>,
+    mut each: impl FnMut(&Gc<T>),
+) {
+    for i in 0..count {
+        let gc = Gc::new(make(i));
+        *this(&gc).borrow_mut() = Some(gc.clone());
+        each(&gc);
+    }
+}
+
+/// Turns off the `debug-suspicious-sweep` check until dropped.
+///
+/// The check panics when a full collection sweeps a young object, taking it
+/// for a missed root. Tests that collect garbage they just allocated hold
+/// one of these for as long as that garbage may be swept.
+#[must_use]
+pub struct YoungGarbage(());
+
+impl YoungGarbage {
+    pub fn expected() -> Self {
+        #[cfg(feature = "debug-suspicious-sweep")]
+        rudo_gc::set_suspicious_sweep_detection(false);
+        Self(())
+    }
+}
+
+impl Drop for YoungGarbage {
+    fn drop(&mut self) {
+        #[cfg(feature = "debug-suspicious-sweep")]
+        rudo_gc::set_suspicious_sweep_detection(true);
+    }
+}
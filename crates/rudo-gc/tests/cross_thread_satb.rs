@@ -0,0 +1,69 @@
+//! Tests that flushing the cross-thread SATB buffer skips stale addresses.
+
+use std::ptr::NonNull;
+
+use rudo_gc::heap::{page_mask, LocalHeap};
+use rudo_gc::{collect_full, Gc, GcBox, GcCell, Trace};
+
+#[derive(Trace)]
+struct Node {
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+/// Make a self-cycle only the collector can reclaim and return its address,
+/// inverted so that conservative stack scanning does not keep it alive.
+#[inline(never)]
+fn make_garbage() -> usize {
+    let node = Gc::new(Node {
+        next: GcCell::new(None),
+    });
+    *node.next.borrow_mut() = Some(node.clone());
+    !Gc::downgrade(&node).raw_addr()
+}
+
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+fn push(addr: usize) {
+    let ptr = NonNull::new(addr as *mut GcBox<()>).unwrap();
+    assert!(LocalHeap::push_cross_thread_satb(ptr));
+}
+
+#[test]
+fn test_flush_skips_freed_and_non_object_addresses() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let live = Gc::new(Node {
+        next: GcCell::new(None),
+    });
+    let live_addr = Gc::downgrade(&live).raw_addr();
+
+    let freed_addr_inverted = make_garbage();
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+    #[cfg(feature = "lazy-sweep")]
+    rudo_gc::heap::with_heap(|heap| rudo_gc::gc::sweep_pending(heap, usize::MAX));
+
+    // Discard anything recorded before this test.
+    let _ = LocalHeap::flush_cross_thread_satb_buffer();
+    push(live_addr);
+    push(!freed_addr_inverted);
+    // The page header itself is not an object slot.
+    push(live_addr & page_mask());
+
+    let flushed: Vec<usize> = LocalHeap::flush_cross_thread_satb_buffer()
+        .into_iter()
+        .map(|ptr| ptr.as_ptr() as usize)
+        .collect();
+    assert_eq!(flushed, [live_addr]);
+    assert!(LocalHeap::flush_cross_thread_satb_buffer().is_empty());
+    assert!(live.next.borrow().is_none());
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
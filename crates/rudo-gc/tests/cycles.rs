@@ -1,9 +1,8 @@
 //! Cycle collection tests for rudo-gc.
 
-#![allow(deprecated)] // Testing deprecated new_cyclic (should migrate to new_cyclic_weak)
-
-use rudo_gc::{collect, Gc, Trace};
+use rudo_gc::{collect, collect_full, Gc, Trace, Weak};
 use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 
 // ============================================================================
 // T072: Tests for Gc::new_cyclic
@@ -79,30 +78,50 @@ fn test_new_cyclic_doubly_linked() {
     collect();
 }
 
+/// A node holding a weak reference to itself.
+#[derive(Trace)]
+struct WeakSelfNode {
+    value: i32,
+    me: Weak<Self>,
+}
+
 #[test]
 fn test_new_cyclic_with_immediate_self_ref() {
-    // This pattern stores a Gc pointing to self during construction
-    let node = Gc::new_cyclic(|this| {
-        // 'this' is a dead Gc at this point, but we can store it
-        SelfRefNode {
+    let node = Gc::new_cyclic(|me: &Weak<WeakSelfNode>| {
+        // The value does not exist yet, so the handle cannot upgrade.
+        assert!(me.upgrade().is_none());
+        WeakSelfNode {
             value: 100,
-            self_ref: RefCell::new(Some(this)),
+            me: me.clone(),
         }
     });
 
     assert_eq!(node.value, 100);
+    assert!(Gc::ptr_eq(&node.me.upgrade().unwrap(), &node));
+    assert_eq!(Gc::weak_count(&node), 1);
 
-    // The self_ref should point to the same node
-    // Note: Due to rehydration limitations, this may be dead
-    // In a full implementation, it would be rehydrated
-    let inner = node.self_ref.borrow();
-    if let Some(ref inner_gc) = *inner {
-        // The inner Gc might be dead (implementation limitation)
-        // or might point to the same node
-        if !Gc::is_dead_or_unrooted(inner_gc) {
-            assert!(Gc::ptr_eq(&node, inner_gc));
-        }
-    }
+    let weak = Gc::downgrade(&node);
+    drop(node);
+    collect_full();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_new_cyclic_panic_leaves_clones_dead() {
+    let escaped = RefCell::new(None);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Gc::new_cyclic(|me: &Weak<WeakSelfNode>| {
+            *escaped.borrow_mut() = Some(me.clone());
+            panic!("constructor failed");
+        })
+    }));
+    assert!(result.is_err());
+
+    collect_full();
+    let escaped = escaped.into_inner().unwrap();
+    assert!(escaped.upgrade().is_none());
+    drop(escaped);
+    collect_full();
 }
 
 /// A simple node that can form cycles.
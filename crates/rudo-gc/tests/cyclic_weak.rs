@@ -402,3 +402,85 @@ fn test_tree_parent_child() {
         "root"
     );
 }
+
+// ============================================================================
+// Finalization tests
+// ============================================================================
+
+/// Counts of `Finalized` drops that saw their self-weak upgrade to `None`
+/// and to `Some`.
+static SELF_UPGRADE_NONE: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+static SELF_UPGRADE_SOME: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Finalized {
+    self_ref: GcCell<Option<Weak<Finalized>>>,
+    self_strong: GcCell<Option<Gc<Finalized>>>,
+}
+
+impl Drop for Finalized {
+    fn drop(&mut self) {
+        let weak = self.self_ref.borrow();
+        let weak = weak.as_ref().expect("self-weak is set at construction");
+        let counter = if weak.upgrade().is_none() && weak.try_upgrade().is_none() {
+            &SELF_UPGRADE_NONE
+        } else {
+            &SELF_UPGRADE_SOME
+        };
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+fn new_finalized() -> Gc<Finalized> {
+    Gc::new_cyclic_weak(|weak| Finalized {
+        self_ref: GcCell::new(Some(weak)),
+        self_strong: GcCell::new(None),
+    })
+}
+
+/// Make a `Finalized` that keeps itself alive, so only a collection drops it.
+#[inline(never)]
+fn make_finalized_cycle() {
+    let node = new_finalized();
+    *node.self_strong.borrow_mut() = Some(node.clone());
+}
+
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_self_weak_upgrade_in_drop() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let none_before = SELF_UPGRADE_NONE.load(std::sync::atomic::Ordering::SeqCst);
+
+    // Dropped eagerly by its last `Gc`.
+    drop(new_finalized());
+    assert_eq!(
+        SELF_UPGRADE_NONE.load(std::sync::atomic::Ordering::SeqCst) - none_before,
+        1
+    );
+
+    // Dropped by the collector.
+    make_finalized_cycle();
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    rudo_gc::collect_full();
+    #[cfg(feature = "lazy-sweep")]
+    rudo_gc::heap::with_heap(|heap| rudo_gc::gc::sweep_pending(heap, usize::MAX));
+    assert_eq!(
+        SELF_UPGRADE_NONE.load(std::sync::atomic::Ordering::SeqCst) - none_before,
+        2
+    );
+    assert_eq!(
+        SELF_UPGRADE_SOME.load(std::sync::atomic::Ordering::SeqCst),
+        0
+    );
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
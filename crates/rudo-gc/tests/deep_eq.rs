@@ -0,0 +1,112 @@
+//! Tests for structural deep equality over `Gc` graphs.
+
+#![allow(clippy::use_self)]
+
+use rudo_gc::{deep_eq, DeepEq, Gc, GcCell, Trace};
+
+#[derive(Trace, DeepEq)]
+struct Node {
+    value: i32,
+    next: GcCell<Option<Gc<Node>>>,
+}
+
+impl Node {
+    fn new(value: i32) -> Gc<Self> {
+        Gc::new(Self {
+            value,
+            next: GcCell::new(None),
+        })
+    }
+
+    fn link(&self, next: &Gc<Self>) {
+        *self.next.borrow_mut() = Some(next.clone());
+    }
+}
+
+/// Build a ring `values[0] -> values[1] -> ... -> values[0]`.
+fn ring(values: &[i32]) -> Gc<Node> {
+    let nodes: Vec<Gc<Node>> = values.iter().map(|&v| Node::new(v)).collect();
+    for (i, node) in nodes.iter().enumerate() {
+        node.link(&nodes[(i + 1) % nodes.len()]);
+    }
+    nodes[0].clone()
+}
+
+#[derive(Trace, DeepEq)]
+enum Shape {
+    Leaf(i32),
+    Branch { left: Gc<Shape>, right: Gc<Shape> },
+    Empty,
+}
+
+#[test]
+fn test_deep_eq_identical_cyclic_graphs() {
+    let a = ring(&[1, 2, 3]);
+    let b = ring(&[1, 2, 3]);
+    assert!(!Gc::ptr_eq(&a, &b));
+    assert!(deep_eq(&a, &b));
+}
+
+#[test]
+fn test_deep_eq_differing_values_in_cycle() {
+    let a = ring(&[1, 2, 3]);
+    let b = ring(&[1, 2, 4]);
+    assert!(!deep_eq(&a, &b));
+}
+
+#[test]
+fn test_deep_eq_different_cycle_lengths() {
+    // Same values everywhere, but a 1-cycle is not isomorphic to a 2-cycle.
+    let a = ring(&[5]);
+    let b = ring(&[5, 5]);
+    assert!(!deep_eq(&a, &b));
+}
+
+#[test]
+fn test_deep_eq_self_is_equal() {
+    let a = ring(&[7, 8]);
+    assert!(deep_eq(&a, &a));
+}
+
+#[test]
+fn test_deep_eq_respects_sharing() {
+    let leaf = Gc::new(Shape::Leaf(1));
+    let shared = Gc::new(Shape::Branch {
+        left: leaf.clone(),
+        right: leaf,
+    });
+    let distinct = Gc::new(Shape::Branch {
+        left: Gc::new(Shape::Leaf(1)),
+        right: Gc::new(Shape::Leaf(1)),
+    });
+    let shared_again = {
+        let leaf = Gc::new(Shape::Leaf(1));
+        Gc::new(Shape::Branch {
+            left: leaf.clone(),
+            right: leaf,
+        })
+    };
+
+    assert!(deep_eq(&shared, &shared_again));
+    assert!(!deep_eq(&shared, &distinct));
+}
+
+#[test]
+fn test_deep_eq_enum_variants() {
+    let empty = Gc::new(Shape::Empty);
+    let leaf = Gc::new(Shape::Leaf(0));
+    assert!(deep_eq(&empty, &Gc::new(Shape::Empty)));
+    assert!(!deep_eq(&empty, &leaf));
+    assert!(!deep_eq(&leaf, &Gc::new(Shape::Leaf(1))));
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_deep_eq_immediate_ints() {
+    let one: Gc<i32> = Gc::new_int(1);
+    assert!(deep_eq(&one, &Gc::new_int(1)));
+    assert!(!deep_eq(&one, &Gc::new_int(2)));
+    // An immediate never equals an object, even one holding the same value.
+    assert!(!deep_eq(&one, &Gc::new(1)));
+    assert!(!deep_eq(&Gc::new(1), &one));
+}
@@ -0,0 +1,190 @@
+//! Deferred finalization: `Drop` runs at safepoints instead of in the GC pause.
+
+#![allow(clippy::use_self)]
+
+mod common;
+
+use rudo_gc::{
+    collect_full, run_deferred_finalizers, safepoint, set_deferred_finalization, Gc, GcCell, Trace,
+    Visitor,
+};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+const SLOW_DROP: Duration = Duration::from_millis(5);
+const CYCLES: usize = 10;
+
+/// The deferral switch is global; keep tests that flip it from overlapping.
+static MODE_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Simulates an expensive destructor, e.g. closing a socket.
+///
+/// Only drops of objects allocated by the current test thread are counted;
+/// pages orphaned by earlier test threads are finalized in the pause.
+struct SlowDrop {
+    delay: Duration,
+    owner: ThreadId,
+}
+
+unsafe impl Trace for SlowDrop {
+    fn trace(&self, _: &mut impl Visitor) {}
+}
+
+impl Drop for SlowDrop {
+    fn drop(&mut self) {
+        std::thread::sleep(self.delay);
+        if self.owner == thread::current().id() {
+            DROPS.with(|d| d.set(d.get() + 1));
+        }
+    }
+}
+
+#[derive(Trace)]
+#[allow(dead_code)]
+struct Node {
+    next: GcCell<Option<Gc<Node>>>,
+    payload: Gc<SlowDrop>,
+}
+
+/// Build `CYCLES` unreachable self-cycles; reference counting cannot free
+/// them, so their `SlowDrop` payloads are only finalized by the collector.
+///
+/// Conservative stack scanning may keep a cycle or two alive, so assertions
+/// only require that most of them are collected.
+fn make_cyclic_garbage() {
+    let node = |_| Node {
+        next: GcCell::new(None),
+        payload: Gc::new(SlowDrop {
+            delay: SLOW_DROP,
+            owner: thread::current().id(),
+        }),
+    };
+    common::make_self_cycles(CYCLES, node, |node| &node.next);
+}
+
+/// Run finalizers left over from earlier tests and reset the drop counter.
+fn reset() {
+    run_deferred_finalizers();
+    DROPS.with(|d| d.set(0));
+}
+
+fn timed_collect() -> Duration {
+    // Every test collects the cycles it just made.
+    let _young = common::YoungGarbage::expected();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    let start = Instant::now();
+    collect_full();
+    start.elapsed()
+}
+
+#[test]
+fn test_deferred_finalization_moves_drop_out_of_pause() {
+    let _guard = MODE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    // Deferred: the pause only queues the finalizers.
+    reset();
+    set_deferred_finalization(true);
+    make_cyclic_garbage();
+    let deferred_pause = timed_collect();
+    let dropped_in_pause = DROPS.with(Cell::get);
+
+    let start = Instant::now();
+    let ran = run_deferred_finalizers();
+    let finalize_time = start.elapsed();
+    set_deferred_finalization(false);
+
+    assert_eq!(dropped_in_pause, 0);
+    // Each collected cycle queues its node and its payload.
+    assert!(ran >= CYCLES, "only {ran} finalizers were deferred");
+    assert!(DROPS.with(Cell::get) >= CYCLES / 2);
+    assert!(finalize_time >= SLOW_DROP * u32::try_from(DROPS.with(Cell::get)).unwrap());
+
+    // Eager: the same workload runs every destructor inside the pause.
+    DROPS.with(|d| d.set(0));
+    make_cyclic_garbage();
+    let eager_pause = timed_collect();
+    assert!(DROPS.with(Cell::get) >= CYCLES / 2);
+
+    assert!(
+        deferred_pause < eager_pause,
+        "deferred pause {deferred_pause:?} should be shorter than eager pause {eager_pause:?}"
+    );
+}
+
+#[test]
+fn test_deferred_finalizers_run_at_safepoint_and_slots_are_reclaimed() {
+    let _guard = MODE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    reset();
+    set_deferred_finalization(true);
+    make_cyclic_garbage();
+    timed_collect();
+    assert_eq!(DROPS.with(Cell::get), 0);
+
+    safepoint();
+    set_deferred_finalization(false);
+    let dropped = DROPS.with(Cell::get);
+    assert!(dropped >= CYCLES / 2);
+    assert_eq!(run_deferred_finalizers(), 0);
+
+    // Finalized slots are reclaimed by the next sweep without dropping
+    // again; only cycles retained conservatively above may drop now.
+    timed_collect();
+    assert!(DROPS.with(Cell::get) <= CYCLES);
+}
+
+static EXITED_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts drops across threads, unlike `SlowDrop`.
+struct CountedDrop {
+    _value: u64,
+}
+
+unsafe impl Trace for CountedDrop {
+    fn trace(&self, _: &mut impl Visitor) {}
+}
+
+impl Drop for CountedDrop {
+    fn drop(&mut self) {
+        EXITED_DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Trace)]
+#[allow(dead_code)]
+struct CountedNode {
+    next: GcCell<Option<Gc<CountedNode>>>,
+    payload: Gc<CountedDrop>,
+}
+
+fn make_counted_garbage() {
+    let node = |_| CountedNode {
+        next: GcCell::new(None),
+        payload: Gc::new(CountedDrop { _value: 0 }),
+    };
+    common::make_self_cycles(CYCLES, node, |node| &node.next);
+}
+
+#[test]
+fn test_deferred_finalizers_run_when_thread_exits() {
+    let _guard = MODE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    set_deferred_finalization(true);
+    let in_pause = thread::spawn(|| {
+        make_counted_garbage();
+        timed_collect();
+        EXITED_DROPS.load(Ordering::SeqCst)
+    })
+    .join()
+    .unwrap();
+    set_deferred_finalization(false);
+
+    assert_eq!(in_pause, 0);
+    // No safepoint ran on the thread; its exit did.
+    assert!(EXITED_DROPS.load(Ordering::SeqCst) >= CYCLES / 2);
+}
@@ -0,0 +1,86 @@
+//! Debug-mode integrity checks performed by `Gc::deref`.
+//!
+//! In debug builds every dereference validates the page header magic and the
+//! allocation bitmap, so dangling or corrupted pointers panic with their
+//! address instead of reading freed memory.
+
+#![cfg(debug_assertions)]
+
+use std::mem::ManuallyDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use rudo_gc::heap::{page_size, ptr_to_object_index, ptr_to_page_header};
+use rudo_gc::Gc;
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| (*s).to_string()))
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_deref_live_gc_passes_validation() {
+    let gc = Gc::new(42u64);
+    assert_eq!(*gc, 42);
+}
+
+#[test]
+fn test_deref_freed_slot_panics_with_address() {
+    let gc = ManuallyDrop::new(Gc::new(0xDEAD_BEEF_u64));
+    let addr = Gc::internal_ptr(&gc);
+
+    // SAFETY: `addr` is a live GcBox in the current thread's heap; we free the
+    // slot at the bitmap level only and restore it before the Gc is dropped.
+    let (header, idx) = unsafe {
+        let idx = ptr_to_object_index(addr).expect("small object must have an index");
+        let header = ptr_to_page_header(addr);
+        (*header.as_ptr()).clear_allocated(idx);
+        (header, idx)
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| **gc));
+
+    // SAFETY: Same header/index as above; restores the slot so Drop is sound.
+    unsafe {
+        (*header.as_ptr()).set_allocated(idx);
+    }
+    drop(ManuallyDrop::into_inner(gc));
+
+    let payload = result.expect_err("deref of a freed slot must panic");
+    let msg = panic_message(payload.as_ref());
+    assert!(msg.contains("Gc::deref"), "unexpected panic message: {msg}");
+    assert!(
+        msg.contains(&format!("{addr:p}")),
+        "panic message should contain the bad address: {msg}"
+    );
+}
+
+#[test]
+fn test_deref_outside_gc_page_panics_on_bad_magic() {
+    // A zeroed, page-aligned region that was never handed out by the GC heap.
+    let page = page_size();
+    let buffer = vec![0u8; page * 2];
+    let base = buffer.as_ptr() as usize;
+    let aligned = (base + page - 1) & !(page - 1);
+    let fake = (aligned + 64) as *const u8;
+
+    // SAFETY: The pointer is intentionally bogus; it is only dereferenced
+    // through `Gc::deref`, which must reject it before touching the value.
+    // The Gc is never dropped.
+    let gc = ManuallyDrop::new(unsafe { rudo_gc::test_util::from_raw::<u64>(fake) });
+
+    let result = catch_unwind(AssertUnwindSafe(|| **gc));
+    let payload = result.expect_err("deref outside a GC page must panic");
+    let msg = panic_message(payload.as_ref());
+    assert!(
+        msg.contains("page magic"),
+        "unexpected panic message: {msg}"
+    );
+    assert!(
+        msg.contains(&format!("{fake:p}")),
+        "panic message should contain the bad address: {msg}"
+    );
+    drop(buffer);
+}
@@ -215,3 +215,88 @@ fn test_derive_multi_generic() {
     assert_eq!(node.key, "test");
     assert_eq!(node.value, 42);
 }
+
+/// Counts the `Gc` pointers a `Trace` impl visits.
+#[derive(Default)]
+struct CountingVisitor {
+    visited: usize,
+}
+
+impl rudo_gc::Visitor for CountingVisitor {
+    fn visit<T: Trace>(&mut self, _gc: &Gc<T>) {
+        self.visited += 1;
+    }
+
+    unsafe fn visit_region(&mut self, _ptr: *const u8, _len: usize) {}
+}
+
+fn visited<T: Trace>(value: &T) -> usize {
+    let mut visitor = CountingVisitor::default();
+    value.trace(&mut visitor);
+    visitor.visited
+}
+
+/// Not `Trace`: only usable in skipped fields.
+struct Opaque(*const u8);
+
+#[derive(Trace)]
+struct WithSkipped<M> {
+    traced: Gc<i32>,
+    #[rudo_gc(skip)]
+    cached: Gc<i32>,
+    #[rudo_gc(skip)]
+    raw: Opaque,
+    #[rudo_gc(skip)]
+    marker: std::marker::PhantomData<M>,
+}
+
+#[derive(Trace)]
+struct TupleWithSkipped(#[rudo_gc(skip)] Opaque, Gc<i32>);
+
+#[derive(Trace)]
+enum VariantWithSkipped {
+    Named {
+        #[rudo_gc(skip)]
+        raw: Opaque,
+        value: Gc<i32>,
+    },
+    Tuple(Gc<i32>, #[rudo_gc(skip)] Gc<i32>),
+}
+
+#[test]
+fn test_derive_skip_field() {
+    let value = Gc::new(1);
+    let node = WithSkipped::<Opaque> {
+        traced: value.clone(),
+        cached: value.clone(),
+        raw: Opaque(std::ptr::null()),
+        marker: std::marker::PhantomData,
+    };
+    assert_eq!(visited(&node), 1);
+    assert!(node.raw.0.is_null());
+    assert_eq!(*node.cached, 1);
+
+    let tuple = TupleWithSkipped(Opaque(std::ptr::null()), value);
+    assert_eq!(visited(&tuple), 1);
+    assert!(tuple.0 .0.is_null());
+}
+
+#[test]
+fn test_derive_skip_enum_field() {
+    let value = Gc::new(2);
+    let named = VariantWithSkipped::Named {
+        raw: Opaque(std::ptr::null()),
+        value: value.clone(),
+    };
+    assert_eq!(visited(&named), 1);
+    if let VariantWithSkipped::Named { raw, value } = &named {
+        assert!(raw.0.is_null());
+        assert_eq!(**value, 2);
+    }
+
+    let tuple = VariantWithSkipped::Tuple(value.clone(), value);
+    assert_eq!(visited(&tuple), 1);
+    if let VariantWithSkipped::Tuple(_, skipped) = &tuple {
+        assert_eq!(**skipped, 2);
+    }
+}
@@ -0,0 +1,132 @@
+//! Tests for field-level dirty tracking on large objects.
+
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use rudo_gc::cell::GcCapture;
+use rudo_gc::{collect, Gc, GcCell, Trace};
+
+const FIELDS: usize = 32;
+
+/// How often minor GC traced, or scanned on its own, each field.
+struct Counters {
+    traced: [AtomicUsize; FIELDS],
+    scanned: [AtomicUsize; FIELDS],
+}
+
+impl Counters {
+    fn reset(&self) {
+        for count in self.traced.iter().chain(&self.scanned) {
+            count.store(0, Ordering::SeqCst);
+        }
+    }
+
+    fn traced(&self) -> Vec<usize> {
+        self.traced
+            .iter()
+            .map(|c| c.load(Ordering::SeqCst))
+            .collect()
+    }
+
+    fn scanned(&self) -> Vec<usize> {
+        self.scanned
+            .iter()
+            .map(|c| c.load(Ordering::SeqCst))
+            .collect()
+    }
+}
+
+/// A field that reports how the collector visited it.
+struct Spy {
+    index: usize,
+    counters: Arc<Counters>,
+    child: Option<Gc<u64>>,
+}
+
+unsafe impl Trace for Spy {
+    fn trace(&self, visitor: &mut impl rudo_gc::Visitor) {
+        self.counters.traced[self.index].fetch_add(1, Ordering::SeqCst);
+        self.child.trace(visitor);
+    }
+}
+
+impl GcCapture for Spy {
+    fn capture_gc_ptrs(&self) -> &[NonNull<rudo_gc::GcBox<()>>] {
+        &[]
+    }
+
+    fn capture_gc_ptrs_into(&self, ptrs: &mut Vec<NonNull<rudo_gc::GcBox<()>>>) {
+        self.counters.scanned[self.index].fetch_add(1, Ordering::SeqCst);
+        self.child.capture_gc_ptrs_into(ptrs);
+    }
+}
+
+/// Big enough for its own large-object page.
+#[derive(Trace)]
+struct Wide {
+    pixels: [u8; 16 * 1024],
+    fields: [GcCell<Spy>; FIELDS],
+}
+
+/// Allocates a `Wide` and collects until it is old.
+///
+/// Tests reset the counters just before the collection they check:
+/// with `gc-mutation-log`, writes capture the cell too.
+fn old_wide(counters: &Arc<Counters>) -> Gc<Wide> {
+    let wide = Gc::new(Wide {
+        pixels: [0; 16 * 1024],
+        fields: std::array::from_fn(|index| {
+            GcCell::new(Spy {
+                index,
+                counters: counters.clone(),
+                child: None,
+            })
+        }),
+    });
+    collect();
+    collect();
+    wide
+}
+
+fn new_counters() -> Arc<Counters> {
+    Arc::new(Counters {
+        traced: std::array::from_fn(|_| AtomicUsize::new(0)),
+        scanned: std::array::from_fn(|_| AtomicUsize::new(0)),
+    })
+}
+
+#[test]
+fn test_minor_gc_scans_only_written_field() {
+    let counters = new_counters();
+    let wide = old_wide(&counters);
+
+    wide.fields[5].borrow_mut().child = Some(Gc::new(42));
+    counters.reset();
+    collect();
+
+    let mut expected = vec![0; FIELDS];
+    expected[5] = 1;
+    assert_eq!(counters.scanned(), expected);
+    assert_eq!(counters.traced(), vec![0; FIELDS]);
+    assert_eq!(**wide.fields[5].borrow().child.as_ref().unwrap(), 42);
+    assert_eq!(wide.pixels[0], 0);
+}
+
+#[test]
+fn test_minor_gc_traces_whole_object_after_many_writes() {
+    let counters = new_counters();
+    let wide = old_wide(&counters);
+
+    for (value, field) in (0..).zip(&wide.fields) {
+        field.borrow_mut().child = Some(Gc::new(value));
+    }
+    counters.reset();
+    collect();
+
+    assert_eq!(counters.scanned(), vec![0; FIELDS]);
+    assert_eq!(counters.traced(), vec![1; FIELDS]);
+    for (value, field) in (0..).zip(&wide.fields) {
+        assert_eq!(**field.borrow().child.as_ref().unwrap(), value);
+    }
+}
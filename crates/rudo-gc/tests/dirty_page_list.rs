@@ -192,3 +192,46 @@ fn test_dirty_pages_list_bounded() {
         heap.clear_dirty_pages_snapshot();
     });
 }
+
+/// Test that newly dirtied pages are listed once, whether they are still
+/// batched or already flushed to the dirty list.
+#[test]
+fn test_batched_dirty_pages_listed_once() {
+    use rudo_gc::heap::ptr_to_page_header;
+    use std::collections::HashSet;
+
+    // Two per page, so they spread over more pages than one batch holds.
+    let objects: Vec<Gc<[u64; 200]>> = (0..80).map(|i| Gc::new([i; 200])).collect();
+    let pages: HashSet<_> = objects
+        .iter()
+        .map(|obj| unsafe { ptr_to_page_header(rudo_gc::test_util::internal_ptr(obj)) })
+        .collect();
+    assert!(pages.len() > 16, "only {} pages", pages.len());
+
+    rudo_gc::heap::with_heap(|heap: &mut LocalHeap| {
+        heap.take_dirty_pages_snapshot();
+        heap.clear_dirty_pages_snapshot();
+
+        let first = *pages.iter().next().unwrap();
+        unsafe {
+            heap.add_to_dirty_pages(first);
+            heap.add_to_dirty_pages(first);
+        }
+        assert_eq!(heap.dirty_pages_count(), 1);
+
+        for &page in &pages {
+            unsafe { heap.add_to_dirty_pages(page) };
+        }
+        assert_eq!(heap.dirty_pages_count(), pages.len());
+
+        assert_eq!(heap.take_dirty_pages_snapshot(), pages.len());
+        let listed: HashSet<_> = heap.dirty_pages_iter().collect();
+        assert_eq!(listed, pages);
+        for &page in &pages {
+            unsafe { (*page.as_ptr()).clear_dirty_listed() };
+        }
+        heap.clear_dirty_pages_snapshot();
+        assert_eq!(heap.dirty_pages_count(), 0);
+    });
+    assert_eq!(objects[79][199], 79);
+}
@@ -0,0 +1,101 @@
+//! Tests for the emergency collection run when page memory runs out.
+
+use std::sync::{mpsc, Arc, Mutex};
+
+use rudo_gc::{
+    clear_oom_handler, global_metrics, heap_limit, set_heap_limit, set_oom_handler,
+    total_reserved_size, Gc, GcSlice, OomInfo,
+};
+
+/// Allocate a large object and drop it at once, leaving only garbage.
+#[inline(never)]
+fn churn(len: usize) {
+    let slice = Gc::<GcSlice<u64>>::new_slice(len, |_| 7);
+    assert_eq!(slice[len - 1], 7);
+}
+
+#[test]
+fn test_exhausted_heap_limit_collects_and_retries() {
+    // About 1 MiB per object.
+    let len = 128 * 1024;
+    let _live = Gc::new(1u64);
+    churn(len);
+
+    // Room for a few of them, so conservative residue cannot exhaust it.
+    let limit = total_reserved_size() + 4 * (len * 8 + 64 * 1024);
+    set_heap_limit(Some(limit));
+    assert_eq!(heap_limit(), Some(limit));
+
+    let before = global_metrics().total_collections();
+    for _ in 0..32 {
+        churn(len);
+        assert!(total_reserved_size() <= limit);
+    }
+    // Without collecting, 32 objects could not have fit.
+    assert!(global_metrics().total_collections() > before);
+
+    set_heap_limit(None);
+    assert_eq!(heap_limit(), None);
+}
+
+#[test]
+fn test_oom_handler_can_raise_the_limit() {
+    let len = 128 * 1024;
+    let live = Gc::<GcSlice<u64>>::new_slice(len, |_| 1);
+
+    let calls: Arc<Mutex<Vec<OomInfo>>> = Arc::default();
+    let sink = Arc::clone(&calls);
+    set_oom_handler(move |info| {
+        sink.lock().unwrap().push(*info);
+        set_heap_limit(None);
+    });
+
+    // No room for a second live slice, even after collecting.
+    let limit = total_reserved_size() + 64 * 1024;
+    set_heap_limit(Some(limit));
+    let second = Gc::<GcSlice<u64>>::new_slice(len, |_| 2);
+    clear_oom_handler();
+
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].limit, Some(limit));
+    assert!(calls[0].requested >= len * 8);
+    assert!(calls[0].reserved + calls[0].requested > limit);
+    assert_eq!(heap_limit(), None);
+    assert_eq!(live[len - 1], 1);
+    assert_eq!(second[len - 1], 2);
+}
+
+#[test]
+fn test_heap_limit_counts_other_threads() {
+    let len = 128 * 1024;
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    let holder = std::thread::spawn(move || {
+        let slice = Gc::<GcSlice<u64>>::new_slice(len, |_| 3);
+        ready_tx.send(()).unwrap();
+        done_rx.recv().unwrap();
+        assert_eq!(slice[len - 1], 3);
+    });
+    ready_rx.recv().unwrap();
+
+    let calls: Arc<Mutex<Vec<OomInfo>>> = Arc::default();
+    let sink = Arc::clone(&calls);
+    set_oom_handler(move |info| {
+        sink.lock().unwrap().push(*info);
+        set_heap_limit(None);
+    });
+
+    // This heap alone has room; the other thread's slice takes it away.
+    let limit = total_reserved_size() + 64 * 1024;
+    set_heap_limit(Some(limit));
+    let own = Gc::<GcSlice<u64>>::new_slice(len, |_| 4);
+    clear_oom_handler();
+    done_tx.send(()).unwrap();
+    holder.join().unwrap();
+
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 1);
+    assert!(calls[0].reserved >= len * 8);
+    assert_eq!(own[len - 1], 4);
+}
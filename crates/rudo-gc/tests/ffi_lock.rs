@@ -0,0 +1,115 @@
+//! Tests for `Gc::lock_for_ffi`.
+
+use std::cell::Cell;
+
+use rudo_gc::heap::is_page_pinned_for_ffi;
+use rudo_gc::{collect_full, collect_large_objects, FfiLock, Gc, GcCell, Trace};
+
+const LEN: usize = 64 * 1024;
+
+thread_local! {
+    static LOCKED_DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A large-object buffer foreign code could write into.
+#[derive(Trace)]
+struct Buffer {
+    bytes: [Cell<u8>; LEN],
+    locked: bool,
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        if self.locked {
+            LOCKED_DROPS.with(|drops| drops.set(drops.get() + 1));
+        }
+    }
+}
+
+/// Locks a self-cyclic buffer, so only the lock's root keeps it from the
+/// collector, and drops every `Gc` to it.
+///
+/// The lock is boxed so that stack scanning cannot find its pointer.
+#[inline(never)]
+#[allow(clippy::unnecessary_box_returns)]
+fn locked_buffer() -> Box<FfiLock<Buffer>> {
+    let buffer = Gc::new(Buffer {
+        bytes: std::array::from_fn(|_| Cell::new(0)),
+        locked: true,
+        this: GcCell::new(None),
+    });
+    *buffer.this.borrow_mut() = Some(buffer.clone());
+    let lock = Box::new(buffer.lock_for_ffi());
+
+    let bytes = lock.as_ptr().cast::<u8>().cast_mut();
+    for (i, byte) in (0..LEN).zip((0..=u8::MAX).cycle()) {
+        // SAFETY: `bytes` are `Cell`s, and the lock keeps them alive.
+        unsafe { bytes.add(i).write(byte) };
+    }
+    lock
+}
+
+#[inline(never)]
+fn churn() {
+    for _ in 0..8 {
+        let garbage = Gc::new(Buffer {
+            bytes: std::array::from_fn(|_| Cell::new(0xff)),
+            locked: false,
+            this: GcCell::new(None),
+        });
+        *garbage.this.borrow_mut() = Some(garbage.clone());
+    }
+}
+
+fn page_of<T>(ptr: *const T) -> usize {
+    ptr as usize & rudo_gc::heap::page_mask()
+}
+
+#[test]
+fn test_locked_buffer_survives_collection() {
+    LOCKED_DROPS.with(|drops| drops.set(0));
+    let lock = locked_buffer();
+    let page = page_of(lock.as_ptr());
+
+    for _ in 0..3 {
+        collect_full();
+        collect_large_objects();
+        churn();
+    }
+    collect_full();
+
+    assert!(is_page_pinned_for_ffi(page));
+    assert_eq!(LOCKED_DROPS.with(Cell::get), 0);
+    let bytes = lock.as_ptr().cast::<u8>();
+    for (i, byte) in (0..LEN).zip((0..=u8::MAX).cycle()) {
+        // SAFETY: the lock keeps the buffer alive and mapped.
+        assert_eq!(unsafe { bytes.add(i).read() }, byte);
+    }
+    assert!(lock.this.borrow().is_some());
+
+    drop(lock);
+    assert!(!is_page_pinned_for_ffi(page));
+}
+
+#[test]
+fn test_page_stays_pinned_until_every_lock_drops() {
+    let a = Gc::new(1u64);
+    let b = Gc::new(2u64);
+    let first = a.lock_for_ffi();
+    let second = a.lock_for_ffi();
+    let other = b.lock_for_ffi();
+    let page = page_of(first.as_ptr());
+
+    drop(first);
+    assert!(is_page_pinned_for_ffi(page));
+    assert_eq!(*second, 1);
+
+    drop(second);
+    assert_eq!(
+        is_page_pinned_for_ffi(page),
+        page_of(other.as_ptr()) == page
+    );
+    drop(other);
+    assert!(!is_page_pinned_for_ffi(page));
+}
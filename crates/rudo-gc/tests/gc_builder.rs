@@ -0,0 +1,113 @@
+//! Tests for `GcBuilder`, field-by-field construction in a `Gc` slot.
+
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::addr_of_mut;
+
+use rudo_gc::heap::{ptr_to_object_index, ptr_to_page_header};
+use rudo_gc::{collect_full, is_gc_paused, Gc, GcBuilder, Trace};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Trace)]
+struct Counted(u64);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+#[derive(Trace)]
+struct Pair {
+    first: Counted,
+    second: Counted,
+    link: Option<Gc<Self>>,
+}
+
+fn drops() -> usize {
+    DROPS.with(Cell::get)
+}
+
+fn is_slot_allocated(ptr: *const u8) -> bool {
+    unsafe {
+        let index = ptr_to_object_index(ptr).unwrap();
+        (*ptr_to_page_header(ptr).as_ptr()).is_allocated(index)
+    }
+}
+
+fn make_second(fail: bool) -> Counted {
+    assert!(!fail, "second field failed");
+    Counted(2)
+}
+
+#[test]
+fn test_builder_builds_value_in_place() {
+    DROPS.with(|drops| drops.set(0));
+    let tail = Gc::new(Pair {
+        first: Counted(10),
+        second: Counted(20),
+        link: None,
+    });
+
+    let mut builder = GcBuilder::<Pair>::new();
+    assert!(is_gc_paused());
+    let value_ptr = builder.as_mut_ptr();
+    let pair = unsafe {
+        builder
+            .init_field(|p| addr_of_mut!((*p).first), Counted(1))
+            .init_field(|p| addr_of_mut!((*p).second), Counted(2))
+            .init_field(|p| addr_of_mut!((*p).link), Some(tail.clone()));
+        builder.finish()
+    };
+    assert!(!is_gc_paused());
+    assert_eq!(Gc::as_ptr(&pair), value_ptr.cast_const());
+    assert_eq!(pair.first.0 + pair.second.0, 3);
+    assert!(Gc::ptr_eq(pair.link.as_ref().unwrap(), &tail));
+
+    collect_full();
+    assert_eq!(pair.first.0, 1);
+    assert_eq!(drops(), 0);
+
+    drop((pair, tail));
+    assert_eq!(drops(), 4);
+}
+
+#[test]
+fn test_panic_drops_initialized_fields_and_frees_slot() {
+    DROPS.with(|drops| drops.set(0));
+    let mut value_ptr = std::ptr::null_mut();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut builder = GcBuilder::<Pair>::new();
+        value_ptr = builder.as_mut_ptr();
+        assert!(is_slot_allocated(value_ptr.cast()));
+        unsafe {
+            builder.init_field(|p| addr_of_mut!((*p).first), Counted(1));
+            builder.init_field(|p| addr_of_mut!((*p).second), make_second(true));
+            builder.init_field(|p| addr_of_mut!((*p).link), None);
+            builder.finish()
+        }
+    }));
+
+    assert!(result.is_err());
+    // Only the first field was written, and it is dropped exactly once.
+    assert_eq!(drops(), 1);
+    assert!(!is_slot_allocated(value_ptr.cast()));
+    assert!(!is_gc_paused());
+
+    collect_full();
+    assert_eq!(drops(), 1);
+}
+
+#[test]
+#[should_panic(expected = "field written twice")]
+fn test_writing_a_field_twice_panics() {
+    let mut builder = GcBuilder::<Pair>::new();
+    unsafe {
+        builder.init_field(|p| addr_of_mut!((*p).first), Counted(1));
+        builder.init_field(|p| addr_of_mut!((*p).first), Counted(1));
+    }
+}
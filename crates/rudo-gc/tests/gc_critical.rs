@@ -0,0 +1,91 @@
+//! Tests for `NoGcGuard` and `gc_critical`.
+
+use rudo_gc::{
+    collect, collect_full, gc_critical, is_gc_paused, last_gc_metrics, Gc, GcCell, NoGcGuard, Trace,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Guards pause collection on every thread; keep these tests from overlapping.
+static PAUSE_LOCK: Mutex<()> = Mutex::new(());
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Node {
+    value: u64,
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn collections() -> usize {
+    last_gc_metrics().total_collections
+}
+
+#[test]
+fn test_gc_critical_defers_collection_until_guard_drops() {
+    let _lock = PAUSE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    collect_full();
+    let before = collections();
+
+    let value = gc_critical(|| {
+        assert!(is_gc_paused());
+
+        // A self-referencing node stays allocated after its last handle
+        // drops, until a collection finds it unreachable.
+        let node = Gc::new(Node {
+            value: 42,
+            next: GcCell::new(None),
+        });
+        *node.next.borrow_mut() = Some(node.clone());
+        let raw: *const Node = Gc::as_ptr(&node);
+        let drops = DROPS.load(Ordering::SeqCst);
+        drop(node);
+
+        collect();
+        collect_full();
+
+        assert_eq!(collections(), before, "no collection runs inside the guard");
+        assert_eq!(DROPS.load(Ordering::SeqCst), drops);
+        unsafe { (*raw).value }
+    });
+    assert_eq!(value, 42);
+
+    assert!(!is_gc_paused());
+    assert_eq!(
+        collections(),
+        before + 1,
+        "the deferred collections run once"
+    );
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_no_gc_guards_nest() {
+    let _lock = PAUSE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    collect_full();
+    let before = collections();
+
+    let outer = NoGcGuard::new();
+    {
+        let _inner = NoGcGuard::new();
+        collect();
+    }
+    assert!(is_gc_paused());
+    collect();
+    assert_eq!(collections(), before);
+
+    drop(outer);
+    assert!(!is_gc_paused());
+    assert_eq!(collections(), before + 1);
+}
@@ -0,0 +1,126 @@
+//! Tests for independently collected `GcHeap`s.
+
+use rudo_gc::{collect_full, Gc, GcCell, GcHeap, Trace};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Drop counts per test heap, so that tests running in parallel do not mix.
+static DROPS: [AtomicUsize; 5] = [const { AtomicUsize::new(0) }; 5];
+
+#[derive(Trace)]
+struct Node {
+    heap: usize,
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPS[self.heap].fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn drops(heap: usize) -> usize {
+    DROPS[heap].load(Ordering::SeqCst)
+}
+
+const fn node(heap: usize) -> Node {
+    Node {
+        heap,
+        next: GcCell::new(None),
+    }
+}
+
+/// Make a self-cycle in `gc_heap` that only a collection can reclaim.
+#[inline(never)]
+fn make_garbage(gc_heap: &GcHeap, heap: usize) {
+    let garbage = gc_heap.alloc_gc(node(heap));
+    *garbage.next.borrow_mut() = Some(garbage.clone());
+}
+
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_heaps_collect_independently() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let a = GcHeap::new();
+    let b = GcHeap::new();
+    let live_a = a.alloc_gc(node(0));
+    let live_b = b.alloc_gc(node(1));
+    *live_a.next.borrow_mut() = Some(a.alloc_gc(node(0)));
+    make_garbage(&a, 0);
+    make_garbage(&b, 1);
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+
+    assert!(a.contains(&live_a) && !b.contains(&live_a));
+    assert!(b.contains(&live_b) && !a.contains(&live_b));
+
+    // Thread collections do not sweep either heap.
+    collect_full();
+    assert_eq!((drops(0), drops(1)), (0, 0));
+
+    a.collect();
+    assert_eq!((drops(0), drops(1)), (1, 0));
+
+    b.collect();
+    assert_eq!((drops(0), drops(1)), (1, 1));
+
+    // Reachable objects survive their heap's collection.
+    assert_eq!(live_a.next.borrow().as_ref().unwrap().heap, 0);
+    assert_eq!(live_b.heap, 1);
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_thread_heap_is_unaffected() {
+    let heap = GcHeap::new();
+    let in_heap = heap.alloc_gc(node(2));
+    let on_thread = Gc::new(node(2));
+
+    heap.collect();
+    collect_full();
+
+    assert!(heap.contains(&in_heap));
+    assert!(!heap.contains(&on_thread));
+    assert_eq!(drops(2), 0);
+}
+
+/// Point an object on the thread heap at a fresh object in `gc_heap`,
+/// leaving no other reference to it.
+#[inline(never)]
+fn stash_in(gc_heap: &GcHeap, holder: &Gc<Node>, heap: usize) {
+    *holder.next.borrow_mut() = Some(gc_heap.alloc_gc(node(heap)));
+}
+
+#[test]
+fn test_objects_reachable_from_thread_heap_survive() {
+    let heap = GcHeap::new();
+    let holder = Gc::new(node(4));
+    stash_in(&heap, &holder, 4);
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+
+    heap.collect();
+    assert_eq!(drops(4), 0);
+    let stashed = holder.next.borrow().clone().unwrap();
+    assert!(heap.contains(&stashed));
+    assert_eq!(stashed.heap, 4);
+}
+
+#[test]
+#[should_panic(expected = "another heap")]
+fn test_alloc_rejects_foreign_references() {
+    let heap = GcHeap::new();
+    let foreign = Gc::new(node(3));
+    let _ = heap.alloc_gc(Node {
+        heap: 3,
+        next: GcCell::new(Some(foreign)),
+    });
+}
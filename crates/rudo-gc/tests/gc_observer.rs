@@ -0,0 +1,46 @@
+//! Tests for `set_gc_observer`.
+
+use std::sync::{Arc, Mutex};
+
+use rudo_gc::{
+    clear_gc_observer, collect, collect_full, gc::is_collecting, last_gc_metrics, set_gc_observer,
+    CollectionType, Gc, GcMetrics,
+};
+
+#[test]
+fn test_observer_sees_every_collection() {
+    let seen: Arc<Mutex<Vec<GcMetrics>>> = Arc::default();
+    let sink = Arc::clone(&seen);
+    set_gc_observer(move |metrics| {
+        assert!(!is_collecting());
+        // Allocating from the observer is fine.
+        let boxed = Gc::new(metrics.objects_reclaimed);
+        sink.lock().unwrap().push(*metrics);
+        drop(boxed);
+    });
+
+    let _live = Gc::new(vec![Gc::new(1u64)]);
+    collect_full();
+    let first = seen.lock().unwrap()[0];
+    assert_eq!(first.collection_type, CollectionType::Major);
+    assert_eq!(first.total_collections, last_gc_metrics().total_collections);
+
+    collect();
+    let observed: Vec<_> = seen.lock().unwrap().clone();
+    assert_eq!(observed.len(), 2);
+    assert_eq!(
+        observed[1].total_collections,
+        last_gc_metrics().total_collections
+    );
+
+    clear_gc_observer();
+    // Each observer call above allocated a `Gc` and dropped it. Sweeping
+    // that young garbage in a full collection trips the suspicious-sweep
+    // check whenever no stale stack slot happens to keep it alive.
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+    collect_full();
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+    assert_eq!(seen.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,45 @@
+//! Tests for `set_gc_policy` presets.
+//!
+//! The policy is global, so everything runs in one test.
+
+use rudo_gc::{collect_full, current_gc_policy_config, set_gc_policy, Gc, GcPolicy};
+
+#[test]
+fn test_policies_apply_their_presets() {
+    for policy in [GcPolicy::Throughput, GcPolicy::Latency, GcPolicy::Balanced] {
+        set_gc_policy(policy);
+        let preset = policy.config();
+        let current = current_gc_policy_config();
+        assert_eq!(current.major_threshold, preset.major_threshold);
+        assert_eq!(current.young_limit, preset.young_limit);
+        assert_eq!(current.mark_workers, preset.mark_workers);
+        assert_eq!(current.incremental.enabled, preset.incremental.enabled);
+        assert_eq!(
+            current.incremental.increment_size,
+            preset.incremental.increment_size
+        );
+        assert_eq!(
+            current.incremental.slice_timeout_ms,
+            preset.incremental.slice_timeout_ms
+        );
+
+        let kept = Gc::new(7_u64);
+        collect_full();
+        assert_eq!(*kept, 7);
+    }
+
+    let throughput = GcPolicy::Throughput.config();
+    let balanced = GcPolicy::Balanced.config();
+    let latency = GcPolicy::Latency.config();
+    assert!(!throughput.incremental.enabled);
+    assert!(latency.incremental.enabled);
+    assert!(throughput.major_threshold > balanced.major_threshold);
+    assert!(throughput.young_limit > balanced.young_limit);
+    assert!(latency.major_threshold < balanced.major_threshold);
+    assert!(latency.incremental.increment_size < balanced.incremental.increment_size);
+    assert_eq!(
+        balanced.major_threshold,
+        rudo_gc::gc::DEFAULT_MAJOR_THRESHOLD
+    );
+    assert_eq!(balanced.young_limit, rudo_gc::gc::DEFAULT_YOUNG_LIMIT);
+}
@@ -0,0 +1,74 @@
+//! Tests for `GcRoot`, which keeps a `Gc` alive wherever it is stored.
+
+use rudo_gc::{collect_full, Gc, GcRoot, Trace, Visitor};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static BOXED_DROPS: AtomicUsize = AtomicUsize::new(0);
+static CLONED_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Node {
+    value: u64,
+    drops: &'static AtomicUsize,
+}
+
+unsafe impl Trace for Node {
+    fn trace(&self, _: &mut impl Visitor) {}
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Root a node behind a `Box`, where stack scanning cannot see it.
+#[inline(never)]
+fn boxed_root(value: u64, drops: &'static AtomicUsize) -> Box<GcRoot<Node>> {
+    Box::new(GcRoot::new(Gc::new(Node { value, drops })))
+}
+
+/// Overwrite dead stack slots that might still hold the node's address.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+fn collect_unrooted() {
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+    collect_full();
+    collect_full();
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_root_survives_collection_off_stack() {
+    let root = boxed_root(42, &BOXED_DROPS);
+    collect_unrooted();
+    assert_eq!(BOXED_DROPS.load(Ordering::SeqCst), 0);
+    assert_eq!(root.value, 42);
+    assert_eq!(root.get().value, 42);
+
+    // Unrooting hands the `Gc` back; dropping it releases the node.
+    let gc = root.into_inner();
+    assert_eq!(gc.value, 42);
+    drop(gc);
+    assert_eq!(BOXED_DROPS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_cloned_root_outlives_original() {
+    let root = boxed_root(7, &CLONED_DROPS);
+    let clone = Box::new((*root).clone());
+    drop(root);
+    collect_unrooted();
+    assert_eq!(CLONED_DROPS.load(Ordering::SeqCst), 0);
+    assert_eq!(clone.value, 7);
+
+    drop(clone);
+    assert_eq!(CLONED_DROPS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,93 @@
+//! Tests for `GcSlice`, slices allocated inline in a `Gc`.
+
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use rudo_gc::{collect_full, Gc, GcCell, GcSlice, Trace};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Trace)]
+struct Node {
+    id: usize,
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+/// A self-cycle, so only tracing from a root keeps it alive.
+fn node(id: usize) -> Gc<Node> {
+    let node = Gc::new(Node {
+        id,
+        this: GcCell::new(None),
+    });
+    *node.this.borrow_mut() = Some(node.clone());
+    node
+}
+
+#[inline(never)]
+fn node_slice(len: usize) -> Gc<GcSlice<Gc<Node>>> {
+    Gc::new_slice(len, node)
+}
+
+#[test]
+fn test_new_slice_derefs_to_elements() {
+    let slice = Gc::new_slice(10_000, |i| i as u64 * 3);
+    assert_eq!(slice.len(), 10_000);
+    assert_eq!(slice[0], 0);
+    assert_eq!(slice[9_999], 29_997);
+    assert!(slice.iter().enumerate().all(|(i, &v)| v == i as u64 * 3));
+
+    let copy = Gc::from_slice(&slice[..3]);
+    assert_eq!(&copy[..], &[0, 3, 6]);
+    assert_eq!(*copy, *Gc::new_slice(3, |i| i as u64 * 3));
+
+    let empty: Gc<GcSlice<u64>> = Gc::new_slice(0, |_| unreachable!());
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_slice_traces_its_elements() {
+    DROPS.with(|drops| drops.set(0));
+    let slice = node_slice(64);
+
+    collect_full();
+    assert_eq!(DROPS.with(Cell::get), 0);
+    for (id, node) in slice.iter().enumerate() {
+        assert_eq!(node.id, id);
+    }
+}
+
+#[test]
+fn test_slice_cannot_move_into_box() {
+    let slice = Gc::new_slice(4, |i| i);
+    assert!(slice.into_box().is_none());
+}
+
+#[test]
+fn test_slice_has_no_get_mut() {
+    let mut slice = Gc::new_slice(4, |i| i);
+    assert!(Gc::get_mut(&mut slice).is_none());
+}
+
+#[test]
+fn test_panicking_init_drops_built_elements() {
+    DROPS.with(|drops| drops.set(0));
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Gc::new_slice(8, |id| {
+            assert!(id < 5, "init failed");
+            Gc::new(Node {
+                id,
+                this: GcCell::new(None),
+            })
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(DROPS.with(Cell::get), 5);
+}
@@ -106,6 +106,7 @@ fn test_gc_thread_safe_ref_mut_drop_triggers_generational_barrier() {
         increment_size: 1024,
         max_dirty_pages: 64,
         remembered_buffer_len: 32,
+        satb_buffer_len: 32,
         slice_timeout_ms: 10,
     });
 
@@ -433,3 +434,56 @@ fn test_cross_thread_satb_during_active_gc() {
 
     // Test passes if we reach here without memory corruption
 }
+
+/// Two halves a writer updates one at a time; a reader that sees them differ
+/// read a torn value.
+#[derive(Trace, Clone, Copy)]
+struct Halves {
+    low: u64,
+    high: u64,
+}
+
+#[test]
+fn test_read_optimistic_never_sees_torn_writes() {
+    const WRITES: u64 = 20_000;
+    const READERS: usize = 3;
+
+    let cell = GcThreadSafeCell::new(Halves { low: 0, high: 0 });
+    let done = std::sync::atomic::AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        let readers: Vec<_> = (0..READERS)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut last = 0;
+                    let mut reads = 0_u64;
+                    while !done.load(Ordering::Relaxed) {
+                        let (low, high) = cell.read_optimistic(|h| (h.low, h.high));
+                        assert_eq!(low, high, "torn read");
+                        assert!(low >= last, "read went back in time");
+                        last = low;
+                        reads += 1;
+                    }
+                    reads
+                })
+            })
+            .collect();
+
+        for i in 1..=WRITES {
+            let mut halves = cell.borrow_mut_gen_only();
+            halves.low = i;
+            // Widen the window in which the halves differ.
+            for _ in 0..32 {
+                std::hint::black_box(&mut *halves);
+            }
+            halves.high = i;
+        }
+        done.store(true, Ordering::Relaxed);
+
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+    });
+
+    assert_eq!(cell.read_optimistic(|h| h.high), WRITES);
+}
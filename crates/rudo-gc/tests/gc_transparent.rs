@@ -0,0 +1,73 @@
+//! Tests for the `#[derive(GcTransparent)]` macro.
+
+use rudo_gc::cell::GcCapture;
+use rudo_gc::{collect_full, Gc, GcCell, GcTransparent, Trace};
+
+#[derive(Trace)]
+struct Node {
+    value: i32,
+    next: GcCell<Option<TypedNode>>,
+}
+
+#[derive(GcTransparent)]
+#[repr(transparent)]
+struct TypedNode(Gc<Node>);
+
+#[derive(GcTransparent)]
+struct Named<T: Trace + 'static> {
+    inner: Gc<T>,
+}
+
+fn node(value: i32) -> TypedNode {
+    TypedNode(Gc::new(Node {
+        value,
+        next: GcCell::new(None),
+    }))
+}
+
+#[test]
+fn test_deref_and_clone() {
+    let a = node(1);
+    let b = a.clone();
+    let c = node(1);
+
+    assert_eq!(b.value, 1);
+    assert!(TypedNode::ptr_eq(&a, &b));
+    assert!(!TypedNode::ptr_eq(&a, &c));
+    assert_eq!(Gc::ref_count(&a.0).get(), 2);
+}
+
+#[test]
+fn test_survives_collection() {
+    let head = node(1);
+    *head.next.borrow_mut() = Some(node(2));
+
+    collect_full();
+
+    let next = head.next.borrow();
+    assert_eq!(next.as_ref().map(|n| n.value), Some(2));
+}
+
+#[test]
+fn test_capture_forwards_to_inner_gc() {
+    let handle = node(3);
+    let mut ptrs = Vec::new();
+    handle.capture_gc_ptrs_into(&mut ptrs);
+
+    assert_eq!(ptrs.len(), 1);
+    assert_eq!(
+        ptrs[0].as_ptr() as usize,
+        Gc::downgrade(&handle.0).raw_addr()
+    );
+}
+
+#[test]
+fn test_named_generic_field() {
+    let a = Named {
+        inner: Gc::new(5_u32),
+    };
+    let b = a.clone();
+
+    assert_eq!(*b, 5);
+    assert!(Named::ptr_eq(&a, &b));
+}
@@ -0,0 +1,54 @@
+//! Tests for the GC watchdog.
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+use rudo_gc::{
+    last_gc_watchdog_report, safepoint, set_gc_watchdog_action, set_gc_watchdog_timeout, Gc,
+    GcWatchdogAction,
+};
+
+/// A collection request that nobody services must fire the watchdog for the
+/// thread parked on it, and `Resume` must let that thread continue.
+#[test]
+fn test_watchdog_reports_stalled_rendezvous() {
+    set_gc_watchdog_timeout(Duration::from_millis(200));
+    set_gc_watchdog_action(GcWatchdogAction::Resume);
+
+    let registered = Arc::new(Barrier::new(2));
+    let requested = Arc::new(Barrier::new(2));
+    let worker = {
+        let registered = Arc::clone(&registered);
+        let requested = Arc::clone(&requested);
+        thread::Builder::new()
+            .name("stalled-mutator".into())
+            .spawn(move || {
+                let value = Gc::new(7);
+                registered.wait();
+                requested.wait();
+                // Parks here; no collector ever resumes it.
+                safepoint();
+                *value
+            })
+            .unwrap()
+    };
+
+    registered.wait();
+    // Stand in for a collector that requests a handshake and then stalls.
+    rudo_gc::heap::request_gc_handshake();
+    requested.wait();
+    assert_eq!(worker.join().unwrap(), 7);
+
+    set_gc_watchdog_timeout(Duration::ZERO);
+    set_gc_watchdog_action(GcWatchdogAction::Abort);
+    rudo_gc::heap::clear_gc_request();
+
+    let report = last_gc_watchdog_report().expect("watchdog did not fire");
+    assert!(report.contains("\"stalled-mutator\""), "{report}");
+    assert!(
+        report.contains("state=SAFEPOINT gc_requested=true"),
+        "{report}"
+    );
+    assert!(report.contains("GC_REQUESTED=true"), "{report}");
+}
@@ -0,0 +1,77 @@
+//! Tests for `GcWeakCell`, the refillable weak-cache cell.
+
+use rudo_gc::{collect_full, Gc, GcCell, GcWeakCell, Trace};
+use std::cell::Cell;
+
+#[derive(Trace)]
+struct Resource {
+    id: u32,
+}
+
+#[test]
+fn test_get_or_insert_with_recreates_collected_target() {
+    let cache: GcWeakCell<Resource> = GcWeakCell::new();
+    let created = Cell::new(0u32);
+    let make = || {
+        created.set(created.get() + 1);
+        Gc::new(Resource { id: created.get() })
+    };
+
+    let first = cache.get_or_insert_with(make);
+    assert_eq!(first.id, 1);
+    drop(first);
+    collect_full();
+    assert!(cache.get().is_none());
+
+    // The target died: it is recreated, and then shared until it dies too.
+    let second = cache.get_or_insert_with(make);
+    let again = cache.get_or_insert_with(make);
+    assert_eq!(second.id, 2);
+    assert!(Gc::ptr_eq(&second, &again));
+    assert_eq!(created.get(), 2);
+
+    drop(second);
+    drop(again);
+    collect_full();
+    let third = cache.get_or_insert_with(make);
+    assert_eq!(third.id, 3);
+    assert_eq!(created.get(), 3);
+}
+
+#[test]
+fn test_set_and_get() {
+    let cache = GcWeakCell::default();
+    assert!(cache.get().is_none());
+
+    let value = Gc::new(Resource { id: 7 });
+    cache.set(&value);
+    assert!(Gc::ptr_eq(&cache.get().unwrap(), &value));
+
+    let from = GcWeakCell::from_gc(&value);
+    assert_eq!(from.get().unwrap().id, 7);
+}
+
+#[derive(Trace)]
+struct Owner {
+    cache: GcWeakCell<Resource>,
+    keep: GcCell<Option<Gc<Resource>>>,
+}
+
+#[test]
+fn test_weak_cell_inside_gc_does_not_keep_target_alive() {
+    let owner = Gc::new(Owner {
+        cache: GcWeakCell::new(),
+        keep: GcCell::new(None),
+    });
+    let value = owner
+        .cache
+        .get_or_insert_with(|| Gc::new(Resource { id: 1 }));
+    *owner.keep.borrow_mut() = Some(value.clone());
+    drop(value);
+    collect_full();
+    assert_eq!(owner.cache.get().unwrap().id, 1);
+
+    *owner.keep.borrow_mut() = None;
+    collect_full();
+    assert!(owner.cache.get().is_none());
+}
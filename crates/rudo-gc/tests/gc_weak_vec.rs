@@ -0,0 +1,79 @@
+//! Tests for `GcWeakVec`, the self-compacting weak observer list.
+
+use rudo_gc::{collect_full, Gc, GcCell, GcWeakVec, Trace};
+
+#[derive(Trace)]
+struct Subscriber {
+    id: u32,
+}
+
+#[test]
+fn test_compacts_after_collection() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let list: GcWeakVec<Subscriber> = GcWeakVec::new();
+    let kept = Gc::new(GcCell::new(Vec::new()));
+    for id in 0..10 {
+        let subscriber = Gc::new(Subscriber { id });
+        list.push(&subscriber);
+        kept.borrow_mut().push(subscriber);
+    }
+    kept.borrow_mut()
+        .retain(|subscriber| subscriber.id % 3 == 0);
+
+    collect_full();
+    // No explicit compaction: the collection triggers it.
+    assert_eq!(list.len(), kept.borrow().len());
+
+    let ids: Vec<u32> = list.iter().map(|subscriber| subscriber.id).collect();
+    assert_eq!(ids, [0, 3, 6, 9]);
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_iter_and_compact_drop_dead_entries() {
+    let list = GcWeakVec::default();
+    let live = Gc::new(Subscriber { id: 1 });
+    list.push(&live);
+    list.push(&Gc::new(Subscriber { id: 2 }));
+    list.push(&Gc::new(Subscriber { id: 3 }));
+
+    assert_eq!(list.compact(), 2);
+    assert_eq!(list.compact(), 0);
+
+    list.push(&Gc::new(Subscriber { id: 4 }));
+    let ids: Vec<u32> = list.iter().map(|subscriber| subscriber.id).collect();
+    assert_eq!(ids, [1]);
+    assert_eq!(list.len(), 1);
+
+    drop(live);
+    assert_eq!(list.iter().count(), 0);
+    assert!(list.is_empty());
+}
+
+#[derive(Trace)]
+struct Subject {
+    observers: GcWeakVec<Subscriber>,
+    keep: GcCell<Option<Gc<Subscriber>>>,
+}
+
+#[test]
+fn test_weak_vec_inside_gc_does_not_keep_targets_alive() {
+    let subject = Gc::new(Subject {
+        observers: GcWeakVec::new(),
+        keep: GcCell::new(None),
+    });
+    let observer = Gc::new(Subscriber { id: 5 });
+    subject.observers.push(&observer);
+    *subject.keep.borrow_mut() = Some(observer.clone());
+    drop(observer);
+    collect_full();
+    assert_eq!(subject.observers.iter().next().unwrap().id, 5);
+
+    *subject.keep.borrow_mut() = None;
+    collect_full();
+    assert!(subject.observers.is_empty());
+}
@@ -0,0 +1,41 @@
+//! Tests for `GcCell::borrow_state`.
+
+use rudo_gc::{BorrowState, Gc, GcCell};
+
+#[test]
+fn test_shared_borrows_are_counted() {
+    let cell = GcCell::new(vec![1, 2, 3]);
+    assert_eq!(cell.borrow_state(), BorrowState::Unborrowed);
+
+    let a = cell.borrow_counted();
+    let b = cell.borrow_counted();
+    assert_eq!(cell.borrow_state(), BorrowState::Shared(2));
+    assert_eq!(a.len() + b.len(), 6);
+
+    drop(a);
+    assert_eq!(cell.borrow_state(), BorrowState::Shared(1));
+    drop(b);
+    assert_eq!(cell.borrow_state(), BorrowState::Unborrowed);
+}
+
+#[test]
+fn test_mutable_borrow_is_exclusive() {
+    let gc = Gc::new(GcCell::new(0));
+    {
+        let mut value = gc.borrow_mut();
+        *value += 1;
+        assert_eq!(gc.borrow_state(), BorrowState::Exclusive);
+    }
+    assert_eq!(gc.borrow_state(), BorrowState::Unborrowed);
+    assert_eq!(*gc.borrow(), 1);
+}
+
+#[test]
+fn test_plain_borrows_are_shared_but_not_counted() {
+    let cell = GcCell::new(1);
+    let plain: std::cell::Ref<'_, i32> = cell.borrow();
+    assert_eq!(cell.borrow_state(), BorrowState::Shared(0));
+    let counted = cell.borrow_counted();
+    assert_eq!(cell.borrow_state(), BorrowState::Shared(1));
+    assert_eq!(*plain + *counted, 2);
+}
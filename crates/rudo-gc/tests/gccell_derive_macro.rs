@@ -1,6 +1,8 @@
 //! Tests for the `GcCell` derive macro.
 
-use rudo_gc::{cell::GcCell, Gc, Trace};
+use rudo_gc::gc::incremental::{IncrementalMarkState, MarkPhase};
+use rudo_gc::heap::with_heap;
+use rudo_gc::{cell::GcCell, Gc, GcCapture, NoGcPointers, Trace, Weak, WeakGc};
 use rudo_gc_derive::GcCell;
 
 #[derive(Trace, GcCell)]
@@ -134,3 +136,145 @@ fn test_unnamed_struct() {
     borrow.0 = Gc::new(66);
     assert_eq!(*borrow.0, 66);
 }
+
+#[derive(Trace, GcCell)]
+enum Expr {
+    Literal(i64),
+    Neg(Gc<Self>),
+    Binary {
+        op: char,
+        lhs: Gc<Self>,
+        rhs: Gc<Self>,
+    },
+    Call(String, Vec<Gc<Self>>),
+    Empty,
+}
+
+#[derive(Trace, GcCell)]
+enum NoGcEnum {
+    Number(u32),
+    Text { text: String },
+    Nothing,
+}
+
+#[test]
+fn test_enum() {
+    let lhs = Gc::new(Expr::Literal(1));
+    let rhs = Gc::new(Expr::Neg(Gc::new(Expr::Literal(2))));
+    let arg = Gc::new(Expr::Empty);
+
+    let captured = |expr: &Expr| {
+        let mut ptrs = Vec::new();
+        expr.capture_gc_ptrs_into(&mut ptrs);
+        ptrs
+    };
+    assert!(captured(&Expr::Literal(3)).is_empty());
+    assert!(captured(&Expr::Empty).is_empty());
+    assert_eq!(captured(&Expr::Neg(lhs.clone())).len(), 1);
+    assert_eq!(
+        captured(&Expr::Binary {
+            op: '+',
+            lhs: lhs.clone(),
+            rhs: rhs.clone(),
+        })
+        .len(),
+        2
+    );
+    assert_eq!(
+        captured(&Expr::Call("f".to_string(), vec![arg, lhs.clone()])).len(),
+        2
+    );
+
+    let cell = GcCell::new(Expr::Empty);
+    *cell.borrow_mut() = Expr::Binary { op: '*', lhs, rhs };
+    assert!(matches!(*cell.borrow(), Expr::Binary { op: '*', .. }));
+}
+
+#[derive(Trace, GcCell)]
+struct RecursiveNoGcStruct {
+    value: u64,
+    next: Option<Box<Self>>,
+}
+
+#[derive(Trace, GcCell)]
+struct GenericStruct<T: Trace + 'static> {
+    value: T,
+}
+
+// `GcCell::borrow_mut` skips its barriers exactly where this is proven.
+const _: () = assert!(<NoGcStruct as GcCapture>::NO_GC_POINTERS);
+const _: () = assert!(<GcCell<i32> as GcCapture>::NO_GC_POINTERS);
+const _: () = assert!(!<BasicStruct as GcCapture>::NO_GC_POINTERS);
+const _: () = assert!(!<UnnamedStruct as GcCapture>::NO_GC_POINTERS);
+// `InnerStruct` has no `NoGcPointers` impl, so its `Gc` keeps the barriers.
+const _: () = assert!(!<NestedStruct as GcCapture>::NO_GC_POINTERS);
+const _: () = assert!(!<GenericStruct<Gc<i32>> as GcCapture>::NO_GC_POINTERS);
+const _: () = assert!(<NoGcEnum as GcCapture>::NO_GC_POINTERS);
+const _: () = assert!(!<Expr as GcCapture>::NO_GC_POINTERS);
+// Recursive structs are not proven free of `Gc` and keep the barriers.
+const _: () = assert!(!<RecursiveNoGcStruct as GcCapture>::NO_GC_POINTERS);
+
+const fn assert_no_gc_pointers<T: NoGcPointers + ?Sized>() {}
+
+#[test]
+fn test_no_gc_pointers_impls() {
+    assert_no_gc_pointers::<NoGcStruct>();
+    assert_no_gc_pointers::<NoGcEnum>();
+    assert_no_gc_pointers::<GenericStruct<String>>();
+
+    let cell = GcCell::new(RecursiveNoGcStruct {
+        value: 1,
+        next: None,
+    });
+    cell.borrow_mut().value = 2;
+    assert_eq!(cell.borrow().value, 2);
+
+    let cell = GcCell::new(NoGcEnum::Nothing);
+    *cell.borrow_mut() = NoGcEnum::Text {
+        text: "text".to_string(),
+    };
+    *cell.borrow_mut() = NoGcEnum::Number(3);
+    assert!(matches!(*cell.borrow(), NoGcEnum::Number(3)));
+}
+
+#[derive(Trace, GcCell)]
+struct WeakStruct {
+    parent: Option<Weak<i32>>,
+    sibling: WeakGc<i32>,
+    child: Gc<i32>,
+}
+
+#[derive(Trace, GcCell)]
+struct OnlyWeakStruct {
+    parent: Option<Weak<i32>>,
+}
+
+#[test]
+fn test_weak_fields_are_not_captured() {
+    let target = Gc::new(1);
+    let child = Gc::new(2);
+    let value = WeakStruct {
+        parent: Some(Gc::downgrade(&target)),
+        sibling: WeakGc::from(&target),
+        child: child.clone(),
+    };
+    let mut ptrs = Vec::new();
+    value.capture_gc_ptrs_into(&mut ptrs);
+    assert_eq!(ptrs.len(), 1);
+    assert_eq!(
+        ptrs[0].as_ptr().cast::<u8>().cast_const(),
+        Gc::internal_ptr(&child)
+    );
+
+    // Overwriting a weak field during marking records nothing.
+    let cell = Gc::new(GcCell::new(OnlyWeakStruct {
+        parent: Some(Gc::downgrade(&target)),
+    }));
+    with_heap(rudo_gc::heap::LocalHeap::clear_satb_buffer);
+    let state = IncrementalMarkState::global();
+    state.set_phase(MarkPhase::Marking);
+    cell.borrow_mut().parent = None;
+    state.set_phase(MarkPhase::Idle);
+    assert!(with_heap(rudo_gc::heap::LocalHeap::flush_satb_buffer).is_empty());
+    assert!(cell.borrow().parent.is_none());
+}
@@ -0,0 +1,79 @@
+//! Tests for `Gc::get_mut`.
+
+use std::sync::{Mutex, PoisonError};
+
+use rudo_gc::gc::incremental::{IncrementalMarkState, MarkPhase};
+use rudo_gc::{collect_full, Gc, Trace};
+
+/// One test drives the global marking phase; keep the others off it.
+static MARKING_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Trace)]
+struct Node {
+    value: u64,
+    next: Option<Gc<Self>>,
+}
+
+#[test]
+fn test_get_mut_requires_unique_gc() {
+    let _lock = MARKING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut gc = Gc::new(Node {
+        value: 1,
+        next: None,
+    });
+    Gc::get_mut(&mut gc).unwrap().value = 2;
+    assert_eq!(gc.value, 2);
+
+    let other = gc.clone();
+    assert!(Gc::get_mut(&mut gc).is_none());
+    drop(other);
+
+    let weak = Gc::downgrade(&gc);
+    assert!(Gc::get_mut(&mut gc).is_none());
+    drop(weak);
+
+    assert!(Gc::get_mut(&mut gc).is_some());
+}
+
+#[test]
+fn test_get_mut_keeps_new_children_alive() {
+    let _lock = MARKING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut head = Gc::new(Node {
+        value: 0,
+        next: None,
+    });
+    collect_full();
+
+    let node = Gc::get_mut(&mut head).unwrap();
+    node.next = Some(Gc::new(Node {
+        value: 7,
+        next: None,
+    }));
+    collect_full();
+
+    assert_eq!(head.next.as_ref().unwrap().value, 7);
+}
+
+#[test]
+fn test_get_mut_while_marking_keeps_old_children_alive() {
+    let _lock = MARKING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let child = Gc::new(Node {
+        value: 3,
+        next: None,
+    });
+    let mut head = Gc::new(Node {
+        value: 0,
+        next: Some(child.clone()),
+    });
+
+    let state = IncrementalMarkState::global();
+    state.set_phase(MarkPhase::Marking);
+    let moved = Gc::get_mut(&mut head).unwrap().next.take();
+    state.set_phase(MarkPhase::Idle);
+
+    assert!(Gc::ptr_eq(moved.as_ref().unwrap(), &child));
+    assert!(head.next.is_none());
+    drop(moved);
+    collect_full();
+    assert_eq!(child.value, 3);
+}
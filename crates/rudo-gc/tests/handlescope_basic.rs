@@ -1,6 +1,8 @@
 //! Integration tests for `HandleScope` and `Handle`.
 
-use rudo_gc::handles::{EscapeableHandleScope, HandleScope, MaybeHandle, SealedHandleScope};
+use rudo_gc::handles::{
+    EscapeableHandleScope, Handle, HandleScope, MaybeHandle, SealedHandleScope,
+};
 use rudo_gc::heap::with_heap_and_tcb;
 use rudo_gc::{Gc, Trace};
 
@@ -132,6 +134,24 @@ fn test_multiple_handles_same_scope() {
     });
 }
 
+#[test]
+fn test_try_handle_weak() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let scope = HandleScope::new(tcb);
+
+        let gc = Gc::new(TestData { value: 5 });
+        let weak = Gc::downgrade(&gc);
+        let handle = scope.try_handle_weak(&weak).unwrap();
+        assert_eq!(handle.value, 5);
+        assert_eq!(Gc::ref_count(&gc).get(), 1);
+
+        let gone = Gc::downgrade(&Gc::new(TestData { value: 6 }));
+        assert!(scope.try_handle_weak(&gone).is_none());
+    });
+}
+
 #[test]
 fn test_escapeable_handlescope_creation() {
     rudo_gc::test_util::reset();
@@ -195,6 +215,49 @@ fn test_escapeable_handlescope_double_escape_panics() {
     });
 }
 
+#[test]
+fn test_escapeable_handlescope_escape_many() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let outer = HandleScope::new(tcb);
+        // Keep the Gcs alive so the escaped handles stay valid (bug74)
+        let gcs: Vec<Gc<TestData>> = (1..=3).map(|value| Gc::new(TestData { value })).collect();
+
+        let escaped = {
+            let escape_scope = EscapeableHandleScope::with_capacity(tcb, 3);
+            let handles: Vec<_> = gcs.iter().map(|gc| escape_scope.handle(gc)).collect();
+            escape_scope.escape_many(&outer, &handles)
+        };
+
+        let values: Vec<i32> = escaped.iter().map(|handle| handle.value).collect();
+        assert_eq!(values, [1, 2, 3]);
+
+        // The escaped handles live in the outer scope, after the inner
+        // scope's slots have been released and reused.
+        let fourth = Gc::new(TestData { value: 4 });
+        let reused = outer.handle(&fourth);
+        assert_eq!(reused.value, 4);
+        assert_eq!(escaped[2].to_gc().value, 3);
+    });
+}
+
+#[test]
+#[should_panic(expected = "has capacity for 2")]
+fn test_escapeable_handlescope_escape_many_over_capacity_panics() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let outer = HandleScope::new(tcb);
+        let escape_scope = EscapeableHandleScope::with_capacity(tcb, 2);
+
+        let gcs: Vec<Gc<i32>> = (0..3).map(Gc::new).collect();
+        let handles: Vec<_> = gcs.iter().map(|gc| escape_scope.handle(gc)).collect();
+
+        let _ = escape_scope.escape_many(&outer, &handles);
+    });
+}
+
 #[test]
 fn test_maybe_handle_empty() {
     let maybe: MaybeHandle<'_, i32> = MaybeHandle::empty();
@@ -262,6 +325,48 @@ fn test_sealed_handlescope_prevents_allocation() {
     });
 }
 
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "handle used after its scope was dropped")]
+fn test_handle_used_after_scope_dropped() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let _outer = HandleScope::new(tcb);
+        let gc = Gc::new(42i32);
+
+        let leaked = {
+            let inner = HandleScope::new(tcb);
+            let handle = inner.handle(&gc);
+            // SAFETY: deliberately not; this is the bug being diagnosed.
+            unsafe { std::mem::transmute::<Handle<'_, i32>, Handle<'static, i32>>(handle) }
+        };
+        // A new scope at the same level reuses the dropped scope's slots.
+        let _reopened = HandleScope::new(tcb);
+        let _ = *leaked;
+    });
+}
+
+#[test]
+fn test_outer_handle_valid_after_inner_scope_dropped() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let outer = HandleScope::new(tcb);
+        let gc = Gc::new(TestData { value: 1 });
+        let handle = outer.handle(&gc);
+        let maybe = MaybeHandle::from_handle(handle);
+
+        for _ in 0..3 {
+            let inner = HandleScope::new(tcb);
+            let _ = inner.handle(&gc);
+        }
+
+        assert_eq!(handle.value, 1);
+        assert_eq!(maybe.to_handle().unwrap().value, 1);
+    });
+}
+
 #[test]
 fn test_handle_display() {
     rudo_gc::test_util::reset();
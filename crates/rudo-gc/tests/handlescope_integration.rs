@@ -241,3 +241,81 @@ fn empty_maybe_handle() {
     assert!(empty.is_empty());
     assert!(empty.to_handle().is_none());
 }
+
+#[inline(never)]
+fn rooted_list(len: i32) -> Gc<Vec<Gc<GcRootTestData>>> {
+    Gc::new(
+        (0..len)
+            .map(|value| Gc::new(GcRootTestData { value }))
+            .collect(),
+    )
+}
+
+#[test]
+fn gc_iter_survives_collection_in_loop() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let scope = HandleScope::new(tcb);
+        let list = rooted_list(1000);
+        let iter = scope.iter(&list);
+
+        assert_eq!(iter.len(), 1000);
+        let mut seen = 0;
+        for (expected, item) in (0..).zip(iter) {
+            if expected % 100 == 0 {
+                rudo_gc::collect_full();
+            }
+            assert_eq!(item.value, expected);
+            seen += 1;
+        }
+        assert_eq!(seen, 1000);
+    });
+}
+
+#[derive(Trace)]
+struct ChainNode {
+    value: i32,
+    next: Option<Gc<Self>>,
+}
+
+#[inline(never)]
+fn rooted_chain(len: i32) -> Gc<ChainNode> {
+    let mut head = Gc::new(ChainNode {
+        value: len - 1,
+        next: None,
+    });
+    for value in (0..len - 1).rev() {
+        head = Gc::new(ChainNode {
+            value,
+            next: Some(head),
+        });
+    }
+    head
+}
+
+#[test]
+fn gc_chain_survives_collection_in_loop() {
+    rudo_gc::test_util::reset();
+
+    with_heap_and_tcb(|_, tcb| {
+        let scope = HandleScope::new(tcb);
+        let head = rooted_chain(1000);
+        let handles: Vec<_> = (0..)
+            .zip(scope.iter_chain(&head, |node| node.next.as_ref()))
+            .map(|(expected, node)| {
+                if expected % 100 == 0 {
+                    rudo_gc::collect_full();
+                }
+                assert_eq!(node.value, expected);
+                node
+            })
+            .collect();
+        rudo_gc::collect_full();
+
+        // Every yielded handle stays valid for the rest of the scope.
+        assert_eq!(handles.len(), 1000);
+        assert_eq!(handles[999].value, 999);
+        assert_eq!(handles[0].value, 0);
+    });
+}
@@ -0,0 +1,87 @@
+//! Tests for configurable small-object size classes.
+//!
+//! The configuration is process-wide and fixed by the first heap, so every
+//! check lives in one test that configures before allocating anything.
+
+use rudo_gc::heap::ptr_to_page_header;
+use rudo_gc::{collect_full, configure_heap, size_classes, Gc, HeapConfig, HeapConfigError};
+
+#[repr(align(32))]
+struct Aligned([u8; 32]);
+
+unsafe impl rudo_gc::Trace for Aligned {
+    fn trace(&self, _visitor: &mut impl rudo_gc::Visitor) {}
+}
+
+const CLASSES: [usize; 8] = [16, 32, 48, 64, 128, 256, 512, 2048];
+
+fn block_size_of<T: rudo_gc::Trace + 'static>(gc: &Gc<T>) -> usize {
+    let ptr = rudo_gc::test_util::internal_ptr(gc);
+    // SAFETY: `gc` keeps its page allocated.
+    unsafe { (*ptr_to_page_header(ptr).as_ptr()).block_size as usize }
+}
+
+#[test]
+fn test_custom_size_classes() {
+    for invalid in [
+        &[][..],
+        &[16, 32, 64][..],
+        &[32, 16, 2048][..],
+        &[16, 40, 2048][..],
+        &[16, 16, 2048][..],
+        &[16, 32, 48, 64, 96, 128, 256, 512, 2048][..],
+    ] {
+        assert_eq!(
+            configure_heap(&HeapConfig {
+                size_classes: invalid
+            }),
+            Err(HeapConfigError::InvalidSizeClasses),
+            "{invalid:?}"
+        );
+    }
+
+    configure_heap(&HeapConfig {
+        size_classes: &CLASSES,
+    })
+    .unwrap();
+    assert_eq!(size_classes(), CLASSES);
+    assert_eq!(
+        configure_heap(&HeapConfig::default()),
+        Err(HeapConfigError::AlreadyInitialized)
+    );
+
+    // Some payload between these sizes puts its `GcBox` in the 48-byte class.
+    let objects = (
+        Gc::new([1u8; 8]),
+        Gc::new([2u8; 16]),
+        Gc::new([3u8; 24]),
+        Gc::new([4u8; 32]),
+        Gc::new([5u8; 40]),
+    );
+    let block_sizes = [
+        block_size_of(&objects.0),
+        block_size_of(&objects.1),
+        block_size_of(&objects.2),
+        block_size_of(&objects.3),
+        block_size_of(&objects.4),
+    ];
+    assert!(block_sizes.contains(&48), "{block_sizes:?}");
+    assert!(block_sizes.iter().all(|size| CLASSES.contains(size)));
+
+    // Types aligned past 16 skip the 48-byte class.
+    let aligned = Gc::new(Aligned([6; 32]));
+    assert_eq!(aligned.as_ptr() as usize % 32, 0);
+    assert_eq!(aligned.0[0], 6);
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+    let garbage: Vec<_> = (0..1000u32).map(|i| Gc::new([i; 10])).collect();
+    drop(garbage);
+    collect_full();
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+    assert_eq!(objects.4[0], 5);
+    for i in 0..1000u32 {
+        assert_eq!(Gc::new([i; 10])[9], i);
+    }
+}
@@ -0,0 +1,93 @@
+//! Tests for immediate integers stored inline in a `Gc`.
+
+#![cfg(target_pointer_width = "64")]
+
+use rudo_gc::{alloc_counters, collect_full, heap::with_heap, Gc, GcCell, Trace};
+
+#[derive(Trace)]
+struct Slot {
+    value: GcCell<Gc<String>>,
+}
+
+#[test]
+fn test_million_ints_allocate_nothing() {
+    collect_full();
+    let before = alloc_counters();
+    let pages_before = with_heap(|heap| heap.all_pages().count());
+
+    let values: Vec<Gc<u64>> = (0..1_000_000).map(Gc::new_int).collect();
+
+    assert_eq!(alloc_counters().since(&before).unwrap().created, 0);
+    assert_eq!(with_heap(|heap| heap.all_pages().count()), pages_before);
+    for (i, gc) in (0..1_000_000).zip(&values) {
+        assert_eq!(gc.as_int(), Some(i));
+    }
+}
+
+#[test]
+fn test_round_trips_extremes() {
+    for value in [0, 1, -1, i32::MIN, i32::MAX] {
+        let gc = Gc::<u64>::new_int(value);
+        assert_eq!(gc.as_int(), Some(value));
+        assert_eq!(gc.clone().as_int(), Some(value));
+    }
+    assert!(Gc::ptr_eq(&Gc::<u64>::new_int(3), &Gc::new_int(3)));
+    assert!(!Gc::ptr_eq(&Gc::<u64>::new_int(3), &Gc::new_int(4)));
+    assert_eq!(format!("{:?}", Gc::<u64>::new_int(-5)), "Gc(<int -5>)");
+    assert_eq!(Gc::new(9_u64).as_int(), None);
+}
+
+#[test]
+fn test_ints_inside_objects_survive_collection() {
+    let slot = Gc::new(Slot {
+        value: GcCell::new(Gc::new_int(42)),
+    });
+    collect_full();
+    assert_eq!(slot.value.borrow().as_int(), Some(42));
+
+    *slot.value.borrow_mut() = Gc::new("boxed".to_string());
+    collect_full();
+    assert_eq!(slot.value.borrow().as_str(), "boxed");
+
+    *slot.value.borrow_mut() = Gc::new_int(-1);
+    collect_full();
+    assert_eq!(slot.value.borrow().as_int(), Some(-1));
+}
+
+#[test]
+#[should_panic(expected = "immediate integer")]
+fn test_deref_of_int_panics() {
+    let gc = Gc::<u64>::new_int(1);
+    let _ = *gc;
+}
+
+#[test]
+fn test_ints_report_fixed_counts() {
+    let gc = Gc::<u64>::new_int(8);
+    let clone = gc.clone();
+    assert_eq!(Gc::strong_count(&gc), 1);
+    assert_eq!(Gc::weak_count(&gc), 0);
+    assert_eq!(Gc::ref_count(&clone).get(), 1);
+}
+
+#[test]
+fn test_ints_compare_hash_and_display_by_value() {
+    use std::hash::{BuildHasher, RandomState};
+
+    let object = Gc::new(5_u64);
+    assert_eq!(Gc::<u64>::new_int(5), Gc::new_int(5));
+    assert_ne!(Gc::<u64>::new_int(5), Gc::new_int(6));
+    assert_ne!(Gc::new_int(5), object);
+    assert_ne!(object, Gc::new_int(5));
+
+    let state = RandomState::new();
+    assert_eq!(
+        state.hash_one(Gc::<u64>::new_int(1)),
+        state.hash_one(Gc::<u64>::new_int(1))
+    );
+
+    let mut ordered = [object, Gc::new_int(2), Gc::new_int(-3)];
+    ordered.sort();
+    let shown: Vec<_> = ordered.iter().map(ToString::to_string).collect();
+    assert_eq!(shown, ["-3", "2", "5"]);
+}
@@ -0,0 +1,75 @@
+//! Allocation under incremental marking advances the collection before
+//! mapping new pages.
+//!
+//! Kept in its own binary because it drives the global marking state.
+
+mod common;
+
+use rudo_gc::gc::incremental::{
+    execute_snapshot, IncrementalConfig, IncrementalMarkState, MarkPhase,
+};
+use rudo_gc::heap::{with_heap, LocalHeap};
+use rudo_gc::{current_reserved_size, test_util, Gc, GcCell, Trace};
+
+const BATCH: usize = 4096;
+
+#[derive(Trace)]
+struct Node {
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+/// Allocate a batch of nodes that are garbage as soon as they exist.
+fn make_garbage(count: usize) {
+    let node = |_| Node {
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles(count, node, |node| &node.this);
+}
+
+#[test]
+fn test_allocation_during_marking_reuses_swept_memory() {
+    // The collection finished by allocation sweeps the first batch young.
+    let _young = common::YoungGarbage::expected();
+    test_util::reset();
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        enabled: true,
+        ..IncrementalConfig::default()
+    });
+
+    // Without a collection in progress every page is mapped fresh.
+    let before = current_reserved_size();
+    make_garbage(BATCH);
+    let eager_growth = current_reserved_size() - before;
+    assert!(eager_growth > 0);
+
+    with_heap(|heap: &mut LocalHeap| {
+        // Allocation marks objects black; start from clear marks like a
+        // collection does.
+        for page in heap.all_pages() {
+            // SAFETY: the heap's pages are valid and nothing else uses them.
+            unsafe { (*page.as_ptr()).clear_all_marks() };
+        }
+        let heaps: [&LocalHeap; 1] = [heap];
+        execute_snapshot(&heaps);
+    });
+    assert_eq!(state.phase(), MarkPhase::Marking);
+
+    // The first page needed finishes the collection, and the rest of the
+    // batch fits in what it swept.
+    let before = current_reserved_size();
+    make_garbage(BATCH);
+    let growth = current_reserved_size().saturating_sub(before);
+    assert_eq!(
+        state.phase(),
+        MarkPhase::Idle,
+        "allocation should have finished the collection"
+    );
+    assert!(
+        growth < eager_growth,
+        "heap grew by {growth} bytes under marking, {eager_growth} without"
+    );
+
+    state.set_config(IncrementalConfig::default());
+    test_util::reset();
+}
@@ -35,6 +35,7 @@ fn test_incremental_with_generational_config() {
         increment_size: 1000,
         max_dirty_pages: 100,
         remembered_buffer_len: 16,
+        satb_buffer_len: 32,
         slice_timeout_ms: 10,
     });
 
@@ -118,6 +119,7 @@ fn test_incremental_config_persists_across_gc() {
         increment_size: 500,
         max_dirty_pages: 200,
         remembered_buffer_len: 8,
+        satb_buffer_len: 32,
         slice_timeout_ms: 5,
     });
 
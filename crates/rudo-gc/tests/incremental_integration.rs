@@ -248,6 +248,7 @@ fn test_config_with_various_fallback_thresholds() {
         increment_size: 500,
         max_dirty_pages: 500,
         remembered_buffer_len: 16,
+        satb_buffer_len: 32,
         slice_timeout_ms: 25,
     };
 
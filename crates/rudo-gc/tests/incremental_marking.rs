@@ -44,6 +44,7 @@ fn test_incremental_config_custom() {
         increment_size: 500,
         max_dirty_pages: 500,
         remembered_buffer_len: 16,
+        satb_buffer_len: 32,
         slice_timeout_ms: 25,
     };
 
@@ -514,3 +515,115 @@ fn test_gc_capture_gc_pointer_stability_in_loop() {
         assert_eq!(item.id, idx as u32, "Item {idx} corrupted");
     }
 }
+
+#[test]
+fn test_incremental_marking_traces_mutably_borrowed_gccell() {
+    test_util::reset();
+
+    IncrementalMarkState::global().set_config(IncrementalConfig {
+        enabled: true,
+        increment_size: 1,
+        ..Default::default()
+    });
+
+    #[derive(Trace)]
+    struct Holder {
+        items: GcCell<Vec<Gc<Data>>>,
+    }
+
+    let holder = Gc::new(Holder {
+        items: GcCell::new((0..50).map(|value| Gc::new(Data { value })).collect()),
+    });
+
+    {
+        // The mutator keeps an exclusive borrow across several collections,
+        // each marked in one-object slices.
+        let mut items = holder.items.borrow_mut();
+        rudo_gc::collect_full();
+        items.retain(|item| item.value % 2 == 0);
+        items.extend((50..75).map(|value| Gc::new(Data { value })));
+        rudo_gc::collect_full();
+        rudo_gc::collect_full();
+    }
+
+    IncrementalMarkState::global().set_config(IncrementalConfig::default());
+
+    let values: Vec<usize> = holder
+        .items
+        .borrow()
+        .iter()
+        .map(|item| item.value)
+        .collect();
+    let expected: Vec<usize> = (0..50).step_by(2).chain(50..75).collect();
+    assert_eq!(values, expected);
+}
+
+/// Whether the object behind `gc` carries a mark bit.
+fn is_marked<T: Trace>(gc: &Gc<T>) -> bool {
+    let ptr = Gc::internal_ptr(gc);
+    unsafe {
+        let header = rudo_gc::heap::ptr_to_page_header(ptr);
+        let idx = rudo_gc::heap::ptr_to_object_index(ptr).unwrap();
+        (*header.as_ptr()).is_marked(idx)
+    }
+}
+
+#[derive(Trace)]
+struct NestedHolder {
+    items: GcCell<Vec<Gc<NestedData>>>,
+}
+
+/// The leaves are reachable only through the `NestedData` in the cell.
+#[inline(never)]
+fn nested_holder() -> Gc<NestedHolder> {
+    Gc::new(NestedHolder {
+        items: GcCell::new(
+            (0..8)
+                .map(|value| {
+                    Gc::new(NestedData {
+                        inner: Gc::new(Data { value }),
+                        value,
+                    })
+                })
+                .collect(),
+        ),
+    })
+}
+
+#[test]
+fn test_mark_slices_trace_children_of_barrier_marked_objects() {
+    test_util::reset();
+
+    let holder = nested_holder();
+    // Clear the marks `Gc::new` set.
+    rudo_gc::collect_full();
+
+    rudo_gc::heap::with_heap(|heap: &mut rudo_gc::heap::LocalHeap| {
+        let heaps: [&rudo_gc::heap::LocalHeap; 1] = [heap];
+        rudo_gc::gc::incremental::execute_snapshot(&heaps)
+    });
+    assert!(is_incremental_marking_active());
+
+    // The insertion barrier marks the items before any slice traces them.
+    drop(holder.items.borrow_mut());
+
+    let mut slices = 0;
+    while let rudo_gc::gc::incremental::MarkSliceResult::Pending { .. } =
+        rudo_gc::heap::with_heap(|heap| rudo_gc::gc::incremental::incremental_mark_slice(heap, 1))
+    {
+        slices += 1;
+        assert!(slices < 1000, "marking did not finish");
+    }
+
+    let unmarked = holder
+        .items
+        .borrow()
+        .iter()
+        .filter(|item| !is_marked(&item.inner))
+        .count();
+    test_util::reset();
+    assert_eq!(
+        unmarked, 0,
+        "children of barrier-marked objects were not traced"
+    );
+}
@@ -3,8 +3,9 @@
 #![allow(clippy::significant_drop_tightening, clippy::items_after_statements)]
 
 use rudo_gc::gc::incremental::{
-    is_incremental_marking_active, is_write_barrier_active, IncrementalConfig,
-    IncrementalMarkState, MarkPhase, MarkSliceResult,
+    incremental_mark_progress, incremental_worklist_len, is_incremental_marking_active,
+    is_write_barrier_active, last_fallback_reason, IncrementalConfig, IncrementalMarkState,
+    MarkPhase, MarkSliceResult,
 };
 use rudo_gc::test_util;
 
@@ -139,6 +140,26 @@ fn test_fallback_request() {
     assert!(!state.fallback_requested());
 }
 
+#[test]
+fn test_last_fallback_reason() {
+    test_util::reset();
+    assert_eq!(last_fallback_reason(), None);
+
+    let state = IncrementalMarkState::global();
+    state.set_phase(MarkPhase::Marking);
+    state.request_fallback(rudo_gc::gc::incremental::FallbackReason::SatbBufferOverflow);
+    assert_eq!(
+        last_fallback_reason(),
+        Some(rudo_gc::gc::incremental::FallbackReason::SatbBufferOverflow)
+    );
+
+    // Outlives the fallback flag until the next cycle resets the stats.
+    state.reset_fallback();
+    assert!(last_fallback_reason().is_some());
+    state.stats().reset();
+    assert_eq!(last_fallback_reason(), None);
+}
+
 #[test]
 fn test_worklist_operations() {
     test_util::reset();
@@ -157,6 +178,38 @@ fn test_worklist_operations() {
     assert!(state.worklist_is_empty());
 }
 
+#[test]
+fn test_mark_progress_estimate() {
+    test_util::reset();
+
+    let state = IncrementalMarkState::global();
+    assert!(incremental_mark_progress().abs() < f32::EPSILON);
+    assert_eq!(incremental_worklist_len(), 0);
+
+    state.set_phase(MarkPhase::Marking);
+    assert!(incremental_mark_progress().abs() < f32::EPSILON);
+
+    use std::ptr::NonNull;
+    use std::sync::atomic::Ordering;
+    for _ in 0..3 {
+        state.push_work(NonNull::dangling());
+    }
+    state.stats().objects_marked.store(1, Ordering::Relaxed);
+    assert_eq!(incremental_worklist_len(), 3);
+    assert!((incremental_mark_progress() - 0.25).abs() < f32::EPSILON);
+
+    while state.pop_work().is_some() {}
+    state.stats().objects_marked.store(4, Ordering::Relaxed);
+    assert_eq!(incremental_worklist_len(), 0);
+    assert!((incremental_mark_progress() - 1.0).abs() < f32::EPSILON);
+
+    state.set_phase(MarkPhase::Sweeping);
+    assert!((incremental_mark_progress() - 1.0).abs() < f32::EPSILON);
+
+    test_util::reset();
+    assert!(incremental_mark_progress().abs() < f32::EPSILON);
+}
+
 #[test]
 fn test_config_update() {
     test_util::reset();
@@ -173,6 +226,7 @@ fn test_config_update() {
         increment_size: 500,
         max_dirty_pages: 500,
         remembered_buffer_len: 16,
+        satb_buffer_len: 32,
         slice_timeout_ms: 25,
     };
     state.set_config(new_config);
@@ -0,0 +1,84 @@
+//! The incremental write barrier must not skip young objects.
+//!
+//! Young objects need no generational barrier, but while incremental major
+//! marking runs a mutation to any object, young or old, has to reach SATB.
+//! Kept in its own binary because it drives the global marking state.
+
+use rudo_gc::gc::incremental::{
+    count_dirty_pages, execute_final_mark, execute_snapshot, mark_slice, IncrementalConfig,
+    IncrementalMarkState, MarkPhase, MarkSliceResult,
+};
+use rudo_gc::heap::{ptr_to_object_index, ptr_to_page_header, with_heap, LocalHeap};
+use rudo_gc::{test_util, Gc, GcCell, Trace};
+
+#[derive(Trace)]
+struct Leaf {
+    value: usize,
+}
+
+#[derive(Trace)]
+struct Holder {
+    slot: GcCell<Option<Gc<Leaf>>>,
+}
+
+fn is_marked(addr: *const u8) -> bool {
+    // SAFETY: `addr` is an allocated object on this thread's heap.
+    unsafe {
+        let idx = ptr_to_object_index(addr).unwrap();
+        (*ptr_to_page_header(addr).as_ptr()).is_marked(idx)
+    }
+}
+
+#[test]
+fn test_young_object_mutation_during_incremental_marking() {
+    test_util::reset();
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        enabled: true,
+        ..IncrementalConfig::default()
+    });
+
+    let holder = Gc::new(Holder {
+        slot: GcCell::new(Some(Gc::new(Leaf { value: 1 }))),
+    });
+    let old_leaf = test_util::internal_ptr(holder.slot.borrow().as_ref().unwrap());
+    let holder_addr = test_util::internal_ptr(&holder);
+    // SAFETY: `holder_addr` is allocated on this thread's heap.
+    let holder_page = unsafe { ptr_to_page_header(holder_addr) };
+    assert_eq!(
+        // SAFETY: as above.
+        unsafe {
+            (*holder_page.as_ptr())
+                .generation
+                .load(std::sync::atomic::Ordering::Acquire)
+        },
+        0,
+        "holder should still be young"
+    );
+
+    with_heap(|heap: &mut LocalHeap| {
+        let heaps: [&LocalHeap; 1] = [heap];
+        execute_snapshot(&heaps);
+    });
+    assert_eq!(state.phase(), MarkPhase::Marking);
+
+    let dirty_before = with_heap(|heap: &mut LocalHeap| count_dirty_pages(heap));
+    *holder.slot.borrow_mut() = Some(Gc::new(Leaf { value: 2 }));
+    let dirty_after = with_heap(|heap: &mut LocalHeap| count_dirty_pages(heap));
+    assert!(
+        dirty_after > dirty_before,
+        "mutating a young object during marking must dirty its page"
+    );
+
+    with_heap(|heap: &mut LocalHeap| {
+        while let MarkSliceResult::Pending { .. } = mark_slice(heap, 100) {}
+        let heaps: &mut [&mut LocalHeap; 1] = &mut [heap];
+        execute_final_mark(heaps);
+    });
+    assert!(is_marked(old_leaf), "SATB must keep the overwritten leaf");
+    assert!(is_marked(holder_addr));
+    assert_eq!(holder.slot.borrow().as_ref().unwrap().value, 2);
+
+    state.set_phase(MarkPhase::Idle);
+    test_util::reset();
+}
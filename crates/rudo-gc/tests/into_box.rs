@@ -0,0 +1,47 @@
+//! Tests for `Gc::into_box`.
+
+use rudo_gc::heap::{find_gc_box_from_ptr, with_heap};
+use rudo_gc::{Gc, GcCell, Trace};
+
+#[derive(Trace)]
+struct Node {
+    name: String,
+    children: GcCell<Vec<u64>>,
+}
+
+fn is_allocated(addr: *const u8) -> bool {
+    // SAFETY: `addr` came from a `Gc` on this thread's heap.
+    with_heap(|heap| unsafe { find_gc_box_from_ptr(heap, addr) }.is_some())
+}
+
+#[test]
+fn test_into_box_moves_value_and_frees_slot() {
+    let gc = Gc::new(Node {
+        name: "root".to_string(),
+        children: GcCell::new(vec![1, 2, 3]),
+    });
+    let addr = Gc::internal_ptr(&gc);
+    assert!(gc.is_unique());
+    assert!(is_allocated(addr));
+
+    let boxed = gc.into_box().expect("unique Gc");
+    assert_eq!(boxed.name, "root");
+    assert_eq!(*boxed.children.borrow(), vec![1, 2, 3]);
+    assert!(!is_allocated(addr));
+}
+
+#[test]
+fn test_into_box_requires_unique() {
+    let gc = Gc::new(7u64);
+    let other = gc.clone();
+    assert!(!gc.is_unique());
+    assert!(gc.into_box().is_none());
+    assert_eq!(*other, 7);
+
+    let weak = Gc::downgrade(&other);
+    assert!(!other.is_unique());
+    assert!(other.into_box().is_none());
+    drop(weak);
+
+    assert!(!Gc::new(()).is_unique());
+}
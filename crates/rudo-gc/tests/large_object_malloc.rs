@@ -0,0 +1,81 @@
+//! Tests for the `large-object-malloc` backend.
+
+#![cfg(feature = "large-object-malloc")]
+#![allow(clippy::large_stack_arrays)]
+
+use rudo_gc::heap::{page_mask, PageHeader, PAGE_FLAG_MALLOC};
+use rudo_gc::test_util::internal_ptr;
+use rudo_gc::{collect_full, Gc, GcCell, Trace};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn page_flags<T: Trace>(gc: &Gc<T>) -> u8 {
+    let header = (internal_ptr(gc) as usize & page_mask()) as *const PageHeader;
+    unsafe { (*header).flags.load(Ordering::Acquire) }
+}
+
+#[derive(Trace)]
+struct Block {
+    data: [u64; 1024],
+}
+
+#[test]
+fn test_medium_large_objects_use_global_allocator() {
+    let block = Gc::new(Block { data: [7; 1024] });
+    assert_ne!(page_flags(&block) & PAGE_FLAG_MALLOC, 0);
+    assert!(block.data.iter().all(|&x| x == 7));
+
+    // Past the threshold, objects keep a dedicated mapping.
+    let huge = Gc::new([0u64; 32 * 1024]);
+    assert_eq!(page_flags(&huge) & PAGE_FLAG_MALLOC, 0);
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Node {
+    data: [u64; 1024],
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[inline(never)]
+fn make_cycles(count: usize) {
+    for i in 0..count {
+        let node = Gc::new(Node {
+            data: [i as u64; 1024],
+            next: GcCell::new(None),
+        });
+        *node.next.borrow_mut() = Some(node.clone());
+    }
+}
+
+/// Overwrite dead stack slots that might still hold the garbage's addresses.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_collector_frees_allocator_backed_objects() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    for round in 0..10 {
+        let before = DROPS.load(Ordering::SeqCst);
+        make_cycles(50);
+        scrub_stack();
+        unsafe { rudo_gc::test_util::clear_registers() };
+        collect_full();
+        let dropped = DROPS.load(Ordering::SeqCst) - before;
+        assert!(dropped >= 45, "round {round}: only {dropped} of 50 freed");
+    }
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
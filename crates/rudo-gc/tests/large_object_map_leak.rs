@@ -1,4 +1,4 @@
-use rudo_gc::heap::{page_size, segment_manager};
+use rudo_gc::heap::{large_object_entry, page_size, segment_manager};
 use rudo_gc::Gc;
 use std::sync::PoisonError;
 
@@ -20,11 +20,7 @@ fn test_large_object_map_cleanup() {
     .join()
     .unwrap();
 
-    let contains = segment_manager()
-        .lock()
-        .unwrap_or_else(PoisonError::into_inner)
-        .large_object_map
-        .contains_key(&addr);
+    let contains = large_object_entry(addr).is_some();
     assert!(contains, "Large object should be in global map before GC");
 
     let mut allocations = Vec::new();
@@ -36,11 +32,7 @@ fn test_large_object_map_cleanup() {
 
     rudo_gc::collect_full();
 
-    let contains = segment_manager()
-        .lock()
-        .unwrap_or_else(PoisonError::into_inner)
-        .large_object_map
-        .contains_key(&addr);
+    let contains = large_object_entry(addr).is_some();
     assert!(
         !contains,
         "Large object should be removed from global map after GC"
@@ -79,7 +71,7 @@ fn test_large_object_global_map_cleanup_on_thread_exit() {
         .unwrap_or_else(PoisonError::into_inner);
 
     for addr in &page_addrs {
-        let in_map = manager.large_object_map.contains_key(addr);
+        let in_map = large_object_entry(*addr).is_some();
         let in_orphan = manager.orphan_by_addr.contains_key(addr);
         assert!(
             !in_map && !in_orphan,
@@ -88,3 +80,54 @@ fn test_large_object_global_map_cleanup_on_thread_exit() {
     }
     drop(manager);
 }
+
+#[test]
+fn test_large_object_churn_keeps_single_map_consistent() {
+    // (page address, head page address) for every page of every object.
+    let pages = std::thread::spawn(|| {
+        let mut pages = Vec::new();
+        for round in 0..8 {
+            let objects: Vec<_> = (0..16)
+                .map(|_| Gc::new(LargeObject { data: [0; 5000] }))
+                .collect();
+            for g in &objects {
+                let head = Gc::as_ptr(g) as usize & !(page_size() - 1);
+                for p in 0..2 {
+                    let page_addr = head + p * page_size();
+                    let (head_addr, _, _) = large_object_entry(page_addr)
+                        .expect("every page of a live large object is in the global map");
+                    assert_eq!(head_addr, head);
+                    if round == 7 {
+                        pages.push((page_addr, head));
+                    }
+                }
+            }
+            drop(objects);
+            rudo_gc::collect_full();
+        }
+        pages
+    })
+    .join()
+    .unwrap();
+
+    let mut allocations = Vec::new();
+    for _ in 0..2500 {
+        allocations.push(Gc::new([0u8; 4096]));
+    }
+
+    drop(allocations);
+
+    rudo_gc::collect_full();
+
+    let remaining: Vec<_> = pages
+        .iter()
+        .filter(|(page_addr, head)| {
+            large_object_entry(*page_addr).map(|entry| entry.0) == Some(*head)
+        })
+        .map(|(page_addr, _)| *page_addr)
+        .collect();
+    assert!(
+        remaining.is_empty(),
+        "Pages {remaining:x?} of freed large objects are still in the global map"
+    );
+}
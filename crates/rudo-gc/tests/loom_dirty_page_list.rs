@@ -43,6 +43,12 @@ fn create_test_page() -> NonNull<PageHeader> {
             free_list_head: AtomicU16::new(u16::MAX),
             #[cfg(not(feature = "lazy-sweep"))]
             free_list_head: u16::MAX,
+            #[cfg(feature = "thin-headers")]
+            drop_fn: |_| {},
+            #[cfg(feature = "thin-headers")]
+            trace_fn: |_, _| {},
+            #[cfg(all(feature = "thin-headers", feature = "type-tracking"))]
+            type_info: rudo_gc::heap::TypeInfo::of::<()>,
         });
         NonNull::new_unchecked(ptr)
     }
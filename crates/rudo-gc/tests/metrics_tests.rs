@@ -239,6 +239,7 @@ fn test_fallback_reason_variants() {
     assert_eq!(FallbackReason::SliceTimeout.to_u32(), 2);
     assert_eq!(FallbackReason::WorklistUnbounded.to_u32(), 3);
     assert_eq!(FallbackReason::SatbBufferOverflow.to_u32(), 4);
+    assert_eq!(FallbackReason::NoGcGuard.to_u32(), 5);
 
     // Test from_u32
     assert_eq!(FallbackReason::from_u32(0), FallbackReason::None);
@@ -255,6 +256,7 @@ fn test_fallback_reason_variants() {
         FallbackReason::from_u32(4),
         FallbackReason::SatbBufferOverflow
     );
+    assert_eq!(FallbackReason::from_u32(5), FallbackReason::NoGcGuard);
     assert_eq!(FallbackReason::from_u32(99), FallbackReason::None); // Invalid value defaults to None
 }
 
@@ -589,3 +591,70 @@ fn test_history_empty() {
         assert!(max >= Duration::ZERO, "Max should be non-negative");
     }
 }
+
+/// Test that the Prometheus export is well-formed and tracks collections.
+#[test]
+fn test_gc_metrics_prometheus_format() {
+    let _objects: Gc<Vec<Gc<u64>>> = Gc::new((0..100).map(Gc::new).collect());
+    rudo_gc::collect_full();
+
+    let text = rudo_gc::gc_metrics_prometheus();
+
+    let value_of = |series: &str| -> f64 {
+        text.lines()
+            .find_map(|line| line.strip_prefix(series)?.strip_prefix(' ')?.parse().ok())
+            .unwrap_or_else(|| panic!("missing series {series} in:\n{text}"))
+    };
+
+    assert!(value_of("rudo_gc_collections_total{type=\"major\"}") >= 1.0);
+    assert!(value_of("rudo_gc_collections_total{type=\"minor\"}") >= 0.0);
+    assert!(value_of("rudo_gc_collections_total{type=\"incremental\"}") >= 0.0);
+    assert!(value_of("rudo_gc_heap_reserved_bytes") >= 1.0);
+    // Reserved memory covers every thread, this one included.
+    #[allow(clippy::cast_precision_loss)]
+    let own_reserved = rudo_gc::current_reserved_size() as f64;
+    assert!(value_of("rudo_gc_heap_reserved_bytes") >= own_reserved);
+    assert!(
+        value_of("rudo_gc_heap_reserved_bytes")
+            >= value_of("rudo_gc_heap_live_bytes{generation=\"old\"}")
+    );
+    value_of("rudo_gc_last_pause_seconds");
+    value_of("rudo_gc_reclaimed_bytes_total");
+
+    // Every sample belongs to a metric declared with HELP and TYPE lines.
+    for line in text.lines().filter(|line| !line.starts_with('#')) {
+        let name = line.split(['{', ' ']).next().unwrap();
+        assert!(
+            text.contains(&format!("# HELP {name} ")),
+            "no HELP for {name}"
+        );
+        assert!(
+            text.contains(&format!("# TYPE {name} ")),
+            "no TYPE for {name}"
+        );
+    }
+}
+
+/// Test that cumulative stats accumulate across collections.
+#[test]
+fn test_cumulative_gc_stats_accumulate() {
+    use rudo_gc::{cumulative_gc_stats, global_metrics};
+
+    let before = cumulative_gc_stats();
+    let _objects: Gc<Vec<Gc<u64>>> = Gc::new((0..100).map(Gc::new).collect());
+    rudo_gc::collect_full();
+    rudo_gc::collect_full();
+    let after = cumulative_gc_stats();
+
+    // Other tests may collect concurrently, so only lower bounds hold.
+    assert!(after.total_collections >= before.total_collections + 2);
+    assert!(after.major_collections >= before.major_collections + 2);
+    assert!(after.total_pause_time > before.total_pause_time);
+    assert!(after.max_pause_time >= rudo_gc::last_gc_metrics().duration);
+    assert!(after.max_pause_time <= after.total_pause_time);
+    assert!(
+        after.total_collections
+            >= after.minor_collections + after.major_collections + after.incremental_collections
+    );
+    assert!(global_metrics().snapshot().total_collections >= after.total_collections);
+}
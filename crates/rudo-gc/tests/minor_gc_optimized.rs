@@ -194,6 +194,27 @@ fn test_large_object_old_to_young_reference() {
     }
 }
 
+/// Test that a child reachable only through a freshly promoted parent survives
+/// the following minor collections.
+///
+/// Promotion re-traces promoted objects and records any reference that still
+/// points into the young generation, so no write barrier is needed here.
+#[test]
+fn test_promoted_parent_keeps_child_alive_across_minor_gc() {
+    let parent: Gc<Node<i32>> = Gc::new(Node::new(1));
+    parent.set_next(Gc::new(Node::new(2)));
+
+    // First minor GC promotes the parent (and its surviving child).
+    collect();
+    // Subsequent minor GCs must still see the child through the parent.
+    collect();
+    collect();
+
+    let next = parent.next.borrow();
+    let child = next.as_ref().expect("child reference was lost");
+    assert_eq!(*child.value.borrow(), 2);
+}
+
 /// Test that objects without old-to-young references are collected.
 #[test]
 fn test_young_objects_without_old_refs_are_collected() {
@@ -0,0 +1,90 @@
+//! Tests for the `gc-mutation-log` edge mutation log.
+
+#![cfg(feature = "gc-mutation-log")]
+
+use rudo_gc::{clear_mutation_log, mutation_log, Gc, GcCell, MutationRecord, Trace};
+
+#[derive(Trace)]
+struct Leaf {
+    id: u32,
+}
+
+#[derive(Trace)]
+struct Node {
+    left: GcCell<Option<Gc<Leaf>>>,
+    right: GcCell<Option<Gc<Leaf>>>,
+    count: GcCell<u32>,
+}
+
+fn addr<T: Trace>(gc: &Gc<T>) -> usize {
+    Gc::downgrade(gc).raw_addr()
+}
+
+fn new_node() -> Gc<Node> {
+    Gc::new(Node {
+        left: GcCell::new(None),
+        right: GcCell::new(None),
+        count: GcCell::new(0),
+    })
+}
+
+#[test]
+fn test_log_records_transitions_in_order() {
+    clear_mutation_log();
+    let node = new_node();
+    let a = Gc::new(Leaf { id: 1 });
+    let b = Gc::new(Leaf { id: 2 });
+
+    *node.left.borrow_mut() = Some(a.clone());
+    *node.right.borrow_mut() = Some(b.clone());
+    *node.left.borrow_mut() = Some(b.clone());
+    // Neither of these changes an edge.
+    *node.count.borrow_mut() += 1;
+    *node.left.borrow_mut() = Some(b.clone());
+    *node.right.borrow_mut() = None;
+
+    let base = addr(&node);
+    let left = std::ptr::from_ref(&node.left) as usize - base;
+    let right = std::ptr::from_ref(&node.right) as usize - base;
+    let record = |field_offset, old: &[&Gc<Leaf>], new: &[&Gc<Leaf>]| MutationRecord {
+        container: base,
+        field_offset,
+        old: old.iter().map(|gc| addr(gc)).collect(),
+        new: new.iter().map(|gc| addr(gc)).collect(),
+    };
+
+    assert_eq!(
+        mutation_log(),
+        [
+            record(left, &[], &[&a]),
+            record(right, &[], &[&b]),
+            record(left, &[&a], &[&b]),
+            record(right, &[&b], &[]),
+        ]
+    );
+    assert_eq!(a.id + b.id, 3);
+}
+
+#[test]
+fn test_pending_mutation_waits_for_guard() {
+    clear_mutation_log();
+    let node = new_node();
+    let leaf = Gc::new(Leaf { id: 3 });
+
+    let mut guard = node.left.borrow_mut();
+    *guard = Some(leaf.clone());
+    assert!(mutation_log().is_empty());
+    drop(guard);
+
+    assert_eq!(mutation_log().len(), 1);
+    assert_eq!(mutation_log()[0].new, [addr(&leaf)]);
+}
+
+#[test]
+fn test_cells_outside_the_heap_are_not_logged() {
+    clear_mutation_log();
+    let cell = GcCell::new(None);
+    *cell.borrow_mut() = Some(Gc::new(Leaf { id: 4 }));
+
+    assert!(mutation_log().is_empty());
+}
@@ -0,0 +1,107 @@
+//! Tests for `Gc::on_last_strong_drop`.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rudo_gc::{collect_full, Gc, GcCell, Trace};
+
+#[derive(Trace)]
+struct Node {
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+/// Counts how many times it is dropped.
+struct Witness(Arc<AtomicUsize>);
+
+impl Drop for Witness {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_callback_runs_on_last_strong_drop_with_weak_alive() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let gc = Gc::new(7u64);
+    let weak = Gc::downgrade(&gc);
+
+    let seen = events.clone();
+    let observer = weak.clone();
+    gc.on_last_strong_drop(Box::new(move || {
+        // The object is dying: weak references no longer upgrade.
+        let upgraded = observer.upgrade().is_some();
+        seen.lock()
+            .unwrap()
+            .push(format!("first upgraded={upgraded}"));
+    }));
+    let seen = events.clone();
+    gc.on_last_strong_drop(Box::new(move || seen.lock().unwrap().push("second".into())));
+
+    let clone = gc.clone();
+    drop(gc);
+    assert!(events.lock().unwrap().is_empty());
+
+    // No collection needed.
+    drop(clone);
+    assert_eq!(*events.lock().unwrap(), ["first upgraded=false", "second"]);
+    assert!(weak.upgrade().is_none());
+}
+
+const CYCLES: usize = 16;
+
+/// Unreachable nodes, each with a callback that should never run.
+fn make_cycles(dropped: &Arc<AtomicUsize>, ran: &Arc<AtomicUsize>) {
+    let node = |_| Node {
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles_with(
+        CYCLES,
+        node,
+        |node| &node.this,
+        |node| {
+            let witness = Witness(dropped.clone());
+            let ran = ran.clone();
+            node.on_last_strong_drop(Box::new(move || {
+                let _witness = witness;
+                ran.fetch_add(1, Ordering::SeqCst);
+            }));
+        },
+    );
+}
+
+/// Conservative stack scanning may keep a cycle or two alive, so the
+/// assertions only require that most of them are collected.
+#[test]
+fn test_collected_object_drops_callback_unrun() {
+    // The collection sweeps cycles made just before it.
+    let _young = common::YoungGarbage::expected();
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let ran = Arc::new(AtomicUsize::new(0));
+    make_cycles(&dropped, &ran);
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+
+    let dropped = dropped.load(Ordering::SeqCst);
+    assert!(
+        dropped >= CYCLES - 2,
+        "dropped {dropped} of {CYCLES} callbacks"
+    );
+    assert_eq!(ran.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_into_box_drops_callback_unrun() {
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let gc = Gc::new(String::from("moved"));
+    let witness = Witness(dropped.clone());
+    gc.on_last_strong_drop(Box::new(move || {
+        let _witness = witness;
+        panic!("the value moved out; nothing died");
+    }));
+
+    let boxed = gc.into_box().expect("unique Gc");
+    assert_eq!(*boxed, "moved");
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+}
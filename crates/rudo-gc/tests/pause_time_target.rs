@@ -0,0 +1,55 @@
+//! Tests for `set_pause_time_target`.
+//!
+//! The thresholds are global, so everything runs in one test.
+
+use std::time::Duration;
+
+use rudo_gc::{
+    collect_full, current_gc_policy_config, pause_time_target, set_gc_policy,
+    set_pause_time_target, Gc, GcPolicy,
+};
+
+fn major_threshold() -> usize {
+    current_gc_policy_config().major_threshold
+}
+
+#[test]
+fn test_major_threshold_follows_pause_time_target() {
+    set_gc_policy(GcPolicy::Balanced);
+    let live: Gc<Vec<Gc<[u64; 8]>>> = Gc::new((0..50_000).map(|i| Gc::new([i; 8])).collect());
+
+    // Off by default: collections leave the threshold alone.
+    assert_eq!(pause_time_target(), Duration::ZERO);
+    let start = major_threshold();
+    collect_full();
+    assert_eq!(major_threshold(), start);
+
+    // No real pause fits in a nanosecond, so every collection lowers it.
+    set_pause_time_target(Duration::from_nanos(1));
+    let mut previous = start;
+    for _ in 0..4 {
+        collect_full();
+        let threshold = major_threshold();
+        assert!(threshold < previous, "{threshold} >= {previous}");
+        previous = threshold;
+    }
+    // It bottoms out instead of reaching zero.
+    for _ in 0..16 {
+        collect_full();
+    }
+    let floor = major_threshold();
+    assert!(floor > 0);
+    collect_full();
+    assert_eq!(major_threshold(), floor);
+
+    // Cheap collections against a generous target let the heap grow again.
+    set_pause_time_target(Duration::from_secs(3600));
+    for _ in 0..4 {
+        collect_full();
+    }
+    assert!(major_threshold() > floor);
+
+    assert_eq!(live.len(), 50_000);
+    set_pause_time_target(Duration::ZERO);
+    set_gc_policy(GcPolicy::Balanced);
+}
@@ -0,0 +1,45 @@
+//! Tests for `Gc::pointer_offsets`.
+
+use rudo_gc::{Gc, Trace};
+use std::mem::offset_of;
+
+#[derive(Trace)]
+#[repr(C)]
+struct Leaf {
+    weight: u32,
+    tag: Gc<u8>,
+}
+
+#[derive(Trace)]
+#[repr(C)]
+struct Node {
+    id: u64,
+    left: Gc<Self>,
+    value: f64,
+    leaf: Leaf,
+    right: Gc<Self>,
+}
+
+#[test]
+fn test_pointer_offsets_match_field_offsets() {
+    // SAFETY: zeroed `Gc`s are null handles and zero is valid for the
+    // numeric fields.
+    let offsets = unsafe { Gc::<Leaf>::pointer_offsets() };
+    assert_eq!(offsets, [offset_of!(Leaf, tag)]);
+
+    let mut expected = vec![
+        offset_of!(Node, left),
+        offset_of!(Node, leaf) + offset_of!(Leaf, tag),
+        offset_of!(Node, right),
+    ];
+    expected.sort_unstable();
+    // SAFETY: as above.
+    assert_eq!(unsafe { Gc::<Node>::pointer_offsets() }, expected);
+}
+
+#[test]
+fn test_pointer_offsets_empty_without_gc_fields() {
+    // SAFETY: zero is a valid `u64` and `(u32, f32)`.
+    assert!(unsafe { Gc::<u64>::pointer_offsets() }.is_empty());
+    assert!(unsafe { Gc::<(u32, f32)>::pointer_offsets() }.is_empty());
+}
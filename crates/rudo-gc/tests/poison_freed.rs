@@ -0,0 +1,62 @@
+//! Tests for the `poison-freed` debug feature.
+
+#![cfg(feature = "poison-freed")]
+
+use rudo_gc::heap::POISON_BYTE;
+use rudo_gc::test_util::internal_ptr;
+use rudo_gc::{collect_full, Gc, GcBox};
+
+const COUNT: usize = 64;
+
+/// Allocate and drop objects, returning where their slots were.
+#[inline(never)]
+fn allocate_and_drop() -> Vec<usize> {
+    (0..COUNT as u64)
+        .map(|i| {
+            let gc = Gc::new([i; 6]);
+            internal_ptr(&gc) as usize
+        })
+        .collect()
+}
+
+/// Overwrite dead stack slots that might still hold the objects' addresses.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_freed_slots_hold_poison() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let slots = allocate_and_drop();
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+    #[cfg(feature = "lazy-sweep")]
+    rudo_gc::heap::with_heap(|heap| rudo_gc::gc::sweep_pending(heap, usize::MAX));
+
+    // The value sits right after the header, and the free-list link stays
+    // inside the header.
+    let value_offset = std::mem::size_of::<GcBox<()>>();
+    let poisoned = slots
+        .iter()
+        .filter(|&&slot| {
+            let value = (slot + value_offset) as *const [u8; 48];
+            unsafe { value.read() }.iter().all(|&b| b == POISON_BYTE)
+        })
+        .count();
+    assert!(
+        poisoned >= COUNT - 4,
+        "only {poisoned} of {COUNT} freed slots were poisoned"
+    );
+
+    // The free list survives poisoning: the slots can be handed out again.
+    let reused: Vec<Gc<[u64; 6]>> = (0..COUNT as u64).map(|i| Gc::new([i; 6])).collect();
+    assert!(reused.iter().zip(0..).all(|(gc, i)| **gc == [i; 6]));
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
@@ -0,0 +1,101 @@
+//! Tests for the `rayon` thread pool integration.
+
+#![cfg(feature = "rayon")]
+#![allow(clippy::use_self)]
+
+use rudo_gc::heap::{thread_registry, THREAD_STATE_INACTIVE};
+use rudo_gc::rayon::GcThreadPoolBuilderExt;
+use rudo_gc::{collect_full, Gc, GcCell, Trace};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Node {
+    next: GcCell<Option<Gc<Node>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Build self-referencing objects that only the collector can reclaim.
+#[inline(never)]
+fn make_cycles(count: usize) -> usize {
+    for _ in 0..count {
+        let node = Gc::new(Node {
+            next: GcCell::new(None),
+        });
+        *node.next.borrow_mut() = Some(node.clone());
+    }
+    (0..count).map(|i| *Gc::new(i)).sum()
+}
+
+fn inactive_threads() -> usize {
+    thread_registry()
+        .lock()
+        .unwrap()
+        .threads
+        .iter()
+        .filter(|tcb| tcb.state.load(Ordering::Acquire) == THREAD_STATE_INACTIVE)
+        .count()
+}
+
+#[test]
+fn test_join_allocating_while_another_thread_collects() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    // This thread owns a heap too and stays executing while it waits on the
+    // pool, so the other thread's collections only cover its own heap.
+    let owned = Gc::new(7_u32);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .gc_handlers()
+        .build()
+        .unwrap();
+
+    let done = Arc::new(AtomicBool::new(false));
+    let collector = {
+        let done = Arc::clone(&done);
+        std::thread::spawn(move || {
+            // Register this thread's heap before the first handshake.
+            drop(Gc::new(0_u32));
+            while !done.load(Ordering::Acquire) {
+                collect_full();
+                std::thread::yield_now();
+            }
+        })
+    };
+
+    for _ in 0..20 {
+        let (a, b) =
+            pool.install(|| rudo_gc::rayon::join(|| make_cycles(100), || make_cycles(100)));
+        assert_eq!(a + b, 2 * (0..100).sum::<usize>());
+    }
+
+    done.store(true, Ordering::Release);
+    collector.join().unwrap();
+    assert_eq!(*owned, 7);
+
+    // Parked workers are inactive, so collections from this thread include
+    // their heaps. Lazily swept slots are finalized as the workers allocate.
+    assert!(inactive_threads() >= 2);
+    let before = DROPS.load(Ordering::SeqCst);
+    for _ in 0..10 {
+        pool.install(|| rudo_gc::rayon::join(|| make_cycles(100), || make_cycles(100)));
+        collect_full();
+    }
+    let reclaimed = DROPS.load(Ordering::SeqCst) - before;
+    assert!(
+        reclaimed >= 1_000,
+        "only {reclaimed} of 2000 worker cycles reclaimed"
+    );
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
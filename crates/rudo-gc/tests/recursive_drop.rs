@@ -0,0 +1,136 @@
+//! Tests that dropping long `Gc`-linked lists does not recurse per link.
+
+use rudo_gc::{collect_full, is_gc_paused, Gc, GcCell, Trace};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const LEN: usize = 1_000_000;
+
+/// Long enough to overflow the test thread's stack if the sweep recursed.
+const RING_LEN: usize = 100_000;
+
+static NODE_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Node {
+    value: usize,
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        NODE_DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+static PANICKY_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// Panics when dropped if `panics` is set.
+#[derive(Trace)]
+struct PanickyNode {
+    panics: bool,
+    next: Option<Gc<Self>>,
+}
+
+impl Drop for PanickyNode {
+    fn drop(&mut self) {
+        PANICKY_DROPS.fetch_add(1, Ordering::SeqCst);
+        assert!(!self.panics, "drop panicked");
+    }
+}
+
+/// Build a list of `len` nodes and return its head and tail.
+#[inline(never)]
+fn build_list(len: usize) -> (Gc<Node>, Gc<Node>) {
+    let tail = Gc::new(Node {
+        value: 0,
+        next: GcCell::new(None),
+    });
+    let mut head = tail.clone();
+    for value in 1..len {
+        head = Gc::new(Node {
+            value,
+            next: GcCell::new(Some(head)),
+        });
+    }
+    (head, tail)
+}
+
+/// Close the list into a ring so that only the collector can reclaim it.
+#[inline(never)]
+fn make_ring_garbage() {
+    let (head, tail) = build_list(RING_LEN);
+    *tail.next.borrow_mut() = Some(head);
+}
+
+/// Overwrite dead stack slots that might still hold the ring's addresses.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_dropping_long_list() {
+    let (head, tail) = build_list(LEN);
+    assert_eq!(head.value, LEN - 1);
+    drop(tail);
+
+    let before = NODE_DROPS.load(Ordering::SeqCst);
+    drop(head);
+    assert_eq!(NODE_DROPS.load(Ordering::SeqCst) - before, LEN);
+
+    // The dead nodes' slots are reclaimed without dropping them again.
+    collect_full();
+    assert_eq!(NODE_DROPS.load(Ordering::SeqCst) - before, LEN);
+}
+
+#[test]
+fn test_collecting_long_ring() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let before = NODE_DROPS.load(Ordering::SeqCst);
+    make_ring_garbage();
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+    #[cfg(feature = "lazy-sweep")]
+    rudo_gc::heap::with_heap(|heap| rudo_gc::gc::sweep_pending(heap, usize::MAX));
+    assert_eq!(NODE_DROPS.load(Ordering::SeqCst) - before, RING_LEN);
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_panicking_drop_leaves_queue_usable() {
+    let tail = Gc::new(PanickyNode {
+        panics: false,
+        next: None,
+    });
+    let middle = Gc::new(PanickyNode {
+        panics: false,
+        next: Some(tail),
+    });
+    let head = Gc::new(PanickyNode {
+        panics: true,
+        next: Some(middle),
+    });
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(head)));
+    assert!(result.is_err());
+    // The nodes queued behind the panicking one were still dropped, and the
+    // collector is not held off.
+    assert_eq!(PANICKY_DROPS.load(Ordering::SeqCst), 3);
+    assert!(!is_gc_paused());
+
+    // Later drops run as usual.
+    drop(Gc::new(PanickyNode {
+        panics: false,
+        next: Some(Gc::new(PanickyNode {
+            panics: false,
+            next: None,
+        })),
+    }));
+    assert_eq!(PANICKY_DROPS.load(Ordering::SeqCst), 5);
+}
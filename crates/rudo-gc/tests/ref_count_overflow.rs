@@ -0,0 +1,81 @@
+//! Reference counts abort the process instead of wrapping past
+//! `MAX_REF_COUNT`.
+//!
+//! Counts are set near the limit with `test_util::set_ref_counts` rather
+//! than by creating that many references. Tests expected to abort re-run
+//! themselves in a child process.
+
+use std::process::Command;
+
+use rudo_gc::{test_util, Gc, MAX_REF_COUNT};
+
+/// Set in the child process to the name of the test to run for real.
+const CHILD_ENV: &str = "RUDO_GC_REF_COUNT_OVERFLOW_CHILD";
+
+/// Runs `name` in a child process and asserts that it aborted.
+fn assert_child_aborts(name: &str) {
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args([name, "--exact", "--test-threads=1"])
+        .env(CHILD_ENV, name)
+        .status()
+        .unwrap();
+    assert!(!status.success(), "{name} did not abort");
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(status.signal(), Some(6), "{name} did not abort: {status}");
+    }
+}
+
+fn is_child(name: &str) -> bool {
+    std::env::var(CHILD_ENV).is_ok_and(|child| child == name)
+}
+
+#[test]
+fn test_counts_up_to_max_are_allowed() {
+    let gc = Gc::new(7u64);
+    // SAFETY: restored below before `gc` is dropped.
+    unsafe { test_util::set_ref_counts(&gc, MAX_REF_COUNT - 1, MAX_REF_COUNT - 1) };
+
+    let clone = gc.clone();
+    let weak = Gc::downgrade(&gc);
+    assert_eq!(Gc::ref_count(&gc).get(), MAX_REF_COUNT);
+    assert_eq!(Gc::weak_count(&gc), MAX_REF_COUNT);
+    assert_eq!(*clone, 7);
+
+    // SAFETY: back to the references that exist: `gc`, `clone` and `weak`.
+    unsafe { test_util::set_ref_counts(&gc, 2, 1) };
+    drop(weak);
+    drop(clone);
+    assert_eq!(Gc::ref_count(&gc).get(), 1);
+}
+
+#[test]
+fn test_clone_past_max_aborts() {
+    let name = "test_clone_past_max_aborts";
+    if !is_child(name) {
+        assert_child_aborts(name);
+        return;
+    }
+    let gc = Gc::new(7u64);
+    // SAFETY: the clone below aborts before anything is dropped.
+    unsafe { test_util::set_ref_counts(&gc, MAX_REF_COUNT, 0) };
+    let clone = gc.clone();
+    std::mem::forget([gc, clone]);
+    // Only reached if the count wrapped; exit cleanly so the parent fails.
+    std::process::exit(0);
+}
+
+#[test]
+fn test_downgrade_past_max_aborts() {
+    let name = "test_downgrade_past_max_aborts";
+    if !is_child(name) {
+        assert_child_aborts(name);
+        return;
+    }
+    let gc = Gc::new(7u64);
+    // SAFETY: the downgrade below aborts before anything is dropped.
+    unsafe { test_util::set_ref_counts(&gc, 1, MAX_REF_COUNT) };
+    std::mem::forget(Gc::downgrade(&gc));
+    std::process::exit(0);
+}
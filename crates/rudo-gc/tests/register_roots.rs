@@ -0,0 +1,91 @@
+//! A `Gc` whose only reference lives in a callee-saved register must survive
+//! a collection: `spill_registers_and_scan` has to find it there.
+
+#![cfg(all(any(target_arch = "x86_64", target_arch = "aarch64"), not(miri)))]
+
+use rudo_gc::{collect_full, Gc, Trace};
+
+/// Keeps the address out of stack slots between allocation and the asm block.
+const MASK: usize = 0x5a5a_5a5a_5a5a_5a5a;
+const MAGIC: u64 = 0x00c0_ffee_d00d_f00d;
+
+#[derive(Trace)]
+struct Payload {
+    magic: u64,
+    pad: [u64; 7],
+}
+
+/// Allocate a `Payload` and leak its handle, returning the masked address.
+#[inline(never)]
+fn allocate_hidden() -> usize {
+    let gc = Gc::new(Payload {
+        magic: MAGIC,
+        pad: [0; 7],
+    });
+    let hidden = rudo_gc::test_util::internal_ptr(&gc) as usize ^ MASK;
+    std::mem::forget(gc);
+    hidden
+}
+
+/// Overwrite dead stack slots that might still hold the unmasked address.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+extern "C" fn collect_trampoline() {
+    collect_full();
+}
+
+#[test]
+fn test_root_held_only_in_callee_saved_register_survives() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let hidden = allocate_hidden();
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+
+    // Unmask into a callee-saved register and collect while it is live there.
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        std::arch::asm!(
+            "mov r12, {hidden}",
+            "xor r12, {mask}",
+            "call {collect}",
+            hidden = in(reg) hidden,
+            mask = in(reg) MASK,
+            collect = in(reg) collect_trampoline as extern "C" fn(),
+            out("r12") _,
+            clobber_abi("C"),
+        );
+    }
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        std::arch::asm!(
+            "eor x20, {hidden}, {mask}",
+            "blr {collect}",
+            hidden = in(reg) hidden,
+            mask = in(reg) MASK,
+            collect = in(reg) collect_trampoline as extern "C" fn(),
+            out("x20") _,
+            clobber_abi("C"),
+        );
+    }
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+
+    let raw = (hidden ^ MASK) as *const u8;
+    unsafe {
+        let header = rudo_gc::heap::ptr_to_page_header(raw);
+        let index = rudo_gc::heap::ptr_to_object_index(raw).unwrap();
+        assert!(
+            (*header.as_ptr()).is_allocated(index),
+            "object referenced only from a register was swept"
+        );
+        let gc: Gc<Payload> = rudo_gc::test_util::from_raw(raw);
+        assert_eq!(gc.magic, MAGIC);
+    }
+}
@@ -0,0 +1,42 @@
+//! Tests for `test_util::reset_preserving_metrics`.
+
+use rudo_gc::{collect_full, current_heap_size, gc_history, global_metrics, test_util, Gc};
+
+const COLLECTIONS_PER_PHASE: usize = 3;
+
+#[inline(never)]
+fn run_phase() {
+    for _ in 0..COLLECTIONS_PER_PHASE {
+        let gc = Gc::new(vec![0u8; 256]);
+        drop(gc);
+        collect_full();
+    }
+}
+
+#[test]
+fn test_metrics_accumulate_across_phases() {
+    test_util::reset();
+    test_util::reset_metrics();
+    assert_eq!(global_metrics().total_collections(), 0);
+    assert_eq!(gc_history().total_recorded(), 0);
+
+    run_phase();
+    let first = global_metrics().total_collections();
+    assert!(first >= COLLECTIONS_PER_PHASE);
+
+    test_util::reset_preserving_metrics();
+    assert_eq!(current_heap_size(), 0);
+    assert_eq!(global_metrics().total_collections(), first);
+
+    run_phase();
+    let total = global_metrics().total_collections();
+    assert!(total >= first + COLLECTIONS_PER_PHASE);
+    assert_eq!(gc_history().total_recorded(), total);
+
+    // A plain reset keeps the metrics too; only reset_metrics clears them.
+    test_util::reset();
+    assert_eq!(global_metrics().total_collections(), total);
+    test_util::reset_metrics();
+    assert_eq!(global_metrics().total_collections(), 0);
+    assert_eq!(gc_history().total_recorded(), 0);
+}
@@ -0,0 +1,58 @@
+//! Tests for sizing the per-thread SATB buffer.
+
+use rudo_gc::gc::incremental::{IncrementalConfig, IncrementalMarkState};
+use rudo_gc::heap::with_heap;
+use rudo_gc::{satb_overflow_count, set_satb_buffer_capacity, test_util, Gc, GcBox};
+use std::ptr::NonNull;
+
+fn record(gc_box: NonNull<GcBox<()>>, count: usize) -> bool {
+    with_heap(|heap| (0..count).all(|_| heap.record_satb_old_value(gc_box)))
+}
+
+#[test]
+fn test_resize_keeps_buffered_values() {
+    test_util::reset();
+    let value = Gc::new(0_u64);
+    #[allow(clippy::cast_ptr_alignment)]
+    let gc_box = NonNull::new(Gc::internal_ptr(&value).cast::<GcBox<()>>().cast_mut()).unwrap();
+
+    assert!(record(gc_box, 10));
+    let overflows = satb_overflow_count();
+
+    // Growing keeps the ten values and makes room for thousands more.
+    set_satb_buffer_capacity(4096);
+    assert_eq!(with_heap(|heap| heap.satb_buffer_capacity()), 4096);
+    assert!(record(gc_box, 4000));
+    assert_eq!(satb_overflow_count(), overflows);
+    assert_eq!(with_heap(|heap| heap.flush_satb_buffer().len()), 4010);
+
+    // A smaller buffer overflows sooner and spills into the overflow buffer.
+    set_satb_buffer_capacity(8);
+    assert!(record(gc_box, 7));
+    assert!(!record(gc_box, 1));
+    assert_eq!(satb_overflow_count(), overflows + 1);
+    assert_eq!(with_heap(|heap| heap.flush_satb_overflow_buffer().len()), 8);
+
+    set_satb_buffer_capacity(0);
+    assert_eq!(with_heap(|heap| heap.satb_buffer_capacity()), 1);
+
+    assert_eq!(*value, 0);
+    set_satb_buffer_capacity(rudo_gc::gc::incremental::DEFAULT_SATB_BUFFER_LEN);
+    test_util::reset();
+}
+
+#[test]
+fn test_new_threads_use_configured_capacity() {
+    let state = IncrementalMarkState::global();
+    state.set_config(IncrementalConfig {
+        satb_buffer_len: 100,
+        ..IncrementalConfig::default()
+    });
+
+    let capacity = std::thread::spawn(|| with_heap(|heap| heap.satb_buffer_capacity()))
+        .join()
+        .unwrap();
+    assert_eq!(capacity, 100);
+
+    state.set_config(IncrementalConfig::default());
+}
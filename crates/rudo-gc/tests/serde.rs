@@ -0,0 +1,49 @@
+//! Serde support for `GcCell` (requires the `serde` feature).
+
+#![cfg(feature = "serde")]
+
+use rudo_gc::{Gc, GcCell, Trace};
+use serde::{Deserialize, Serialize};
+
+#[derive(Trace, Serialize, Deserialize)]
+struct Document {
+    title: String,
+    revision: GcCell<i32>,
+}
+
+#[test]
+fn test_gc_cell_round_trips_through_serde() {
+    let doc = Document {
+        title: "notes".to_string(),
+        revision: GcCell::new(3),
+    };
+    *doc.revision.borrow_mut() += 1;
+
+    let json = serde_json::to_string(&doc).unwrap();
+    assert_eq!(json, r#"{"title":"notes","revision":4}"#);
+
+    let back: Document = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.title, "notes");
+    assert_eq!(*back.revision.borrow(), 4);
+}
+
+#[test]
+fn test_gc_cell_inside_gc_serializes_current_value() {
+    let doc = Gc::new(Document {
+        title: "shared".to_string(),
+        revision: GcCell::new(0),
+    });
+    *doc.revision.borrow_mut() = 7;
+
+    let json = serde_json::to_string(&*doc).unwrap();
+    let back: Document = serde_json::from_str(&json).unwrap();
+    assert_eq!(*back.revision.borrow(), 7);
+}
+
+#[test]
+fn test_gc_cell_serialize_while_mutably_borrowed_is_an_error() {
+    let cell = GcCell::new(1);
+    let _guard = cell.borrow_mut();
+    let err = serde_json::to_string(&cell).unwrap_err();
+    assert!(err.to_string().contains("mutably borrowed"), "{err}");
+}
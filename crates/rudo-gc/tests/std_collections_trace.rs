@@ -0,0 +1,136 @@
+//! Tests for the `Trace` impls of std collections holding `Gc` pointers.
+
+mod common;
+
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+
+use rudo_gc::{collect_full, Gc, GcCell, Trace};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Trace, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Leaf(u32);
+
+impl Drop for Leaf {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+fn drops() -> usize {
+    DROPS.with(Cell::get)
+}
+
+#[derive(Trace)]
+struct Node {
+    id: u32,
+    edges: GcCell<HashMap<u32, Gc<Self>>>,
+    leaf: Leaf,
+}
+
+fn node(id: u32) -> Gc<Node> {
+    Gc::new(Node {
+        id,
+        edges: GcCell::new(HashMap::new()),
+        leaf: Leaf(id),
+    })
+}
+
+const CYCLES: usize = 16;
+
+/// Pairs of nodes that point at each other, and at themselves, only through
+/// their maps, so only the collector can reclaim them.
+#[inline(never)]
+fn make_cycles() {
+    for i in 0..CYCLES {
+        let id = u32::try_from(i).unwrap() * 2;
+        let a = node(id);
+        let b = node(id + 1);
+        a.edges.borrow_mut().insert(b.id, b.clone());
+        a.edges.borrow_mut().insert(a.id, a.clone());
+        b.edges.borrow_mut().insert(a.id, a.clone());
+    }
+}
+
+/// Conservative stack scanning may keep a cycle or two alive, so the
+/// assertions only require that most of them are collected.
+#[test]
+fn test_cyclic_hash_maps_are_collected() {
+    // The collection sweeps cycles made just before it.
+    let _young = common::YoungGarbage::expected();
+    DROPS.with(|drops| drops.set(0));
+    make_cycles();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+    assert!(
+        drops() >= 2 * (CYCLES - 2),
+        "dropped {} of {} nodes",
+        drops(),
+        2 * CYCLES
+    );
+}
+
+#[test]
+fn test_hash_map_keeps_values_alive() {
+    let a = node(100);
+    let b = node(101);
+    a.edges.borrow_mut().insert(b.id, b.clone());
+    b.edges.borrow_mut().insert(a.id, a.clone());
+    let weak_b = Gc::downgrade(&b);
+    drop(b);
+
+    collect_full();
+    let b = weak_b.upgrade().expect("b is reachable through a's map");
+    assert!(Gc::ptr_eq(&b.edges.borrow()[&100], &a));
+
+    // Break the cycle so no garbage is left for the other tests' counts.
+    b.edges.borrow_mut().clear();
+}
+
+#[derive(Trace)]
+struct Containers {
+    btree_map: BTreeMap<Gc<Leaf>, Gc<Leaf>>,
+    btree_set: BTreeSet<Gc<Leaf>>,
+    hash_set: HashSet<Gc<Leaf>>,
+    deque: VecDeque<Gc<Leaf>>,
+    heap: BinaryHeap<Gc<Leaf>>,
+}
+
+#[test]
+fn test_collections_keep_their_elements_alive() {
+    DROPS.with(|drops| drops.set(0));
+    let mut next = 0;
+    let mut leaf = || {
+        next += 1;
+        Gc::new(Leaf(next))
+    };
+
+    let containers = Gc::new(Containers {
+        btree_map: (0..20).map(|_| (leaf(), leaf())).collect(),
+        btree_set: (0..20).map(|_| leaf()).collect(),
+        hash_set: (0..20).map(|_| leaf()).collect(),
+        deque: (0..20).map(|_| leaf()).collect(),
+        heap: (0..20).map(|_| leaf()).collect(),
+    });
+
+    collect_full();
+    assert_eq!(drops(), 0);
+    let sum: u32 = containers
+        .btree_map
+        .iter()
+        .flat_map(<[_; 2]>::from)
+        .chain(&containers.btree_set)
+        .chain(&containers.hash_set)
+        .chain(&containers.deque)
+        .chain(containers.heap.iter())
+        .map(|leaf| leaf.0)
+        .sum();
+    assert_eq!(sum, (1..=120).sum());
+
+    drop(containers);
+    collect_full();
+    assert_eq!(drops(), 120);
+}
@@ -0,0 +1,73 @@
+//! Concurrent marking stress run for the `strict-ordering` feature.
+//!
+//! Meant to be run under a thread sanitizer as well, see the README.
+
+#![cfg(feature = "strict-ordering")]
+
+use std::thread;
+
+use rudo_gc::{collect, set_incremental_config, Gc, GcCell, IncrementalConfig, Trace};
+
+#[derive(Trace)]
+struct Node {
+    value: usize,
+    next: GcCell<Option<Gc<Self>>>,
+}
+
+fn node(value: usize) -> Gc<Node> {
+    Gc::new(Node {
+        value,
+        next: GcCell::new(None),
+    })
+}
+
+/// Build a list, repeatedly relinking its tail through barriered stores and
+/// collecting, and check every node survived.
+fn mutate_and_collect(seed: usize) {
+    let head = node(seed);
+    let mut tail = head.clone();
+    for i in 1..200 {
+        let next = node(seed + i);
+        *tail.next.borrow_mut() = Some(next.clone());
+        // Swap in a replacement so the old edge has to be preserved by SATB.
+        let replacement = node(seed + i);
+        *tail.next.borrow_mut() = Some(replacement.clone());
+        tail = replacement;
+        if i % 50 == 0 {
+            collect();
+        }
+    }
+    collect();
+
+    let mut count = 0;
+    let mut current = Some(head);
+    while let Some(n) = current {
+        assert_eq!(n.value, seed + count);
+        count += 1;
+        let next = n.next.borrow().clone();
+        current = next;
+    }
+    assert_eq!(count, 200);
+}
+
+#[test]
+fn test_concurrent_marking_stress() {
+    set_incremental_config(IncrementalConfig {
+        enabled: true,
+        increment_size: 100,
+        max_dirty_pages: 1000,
+        remembered_buffer_len: 32,
+        satb_buffer_len: 32,
+        slice_timeout_ms: 50,
+    });
+
+    let workers: Vec<_> = (0..4)
+        .map(|t| thread::spawn(move || mutate_and_collect(t * 1000)))
+        .collect();
+    mutate_and_collect(10_000);
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    set_incremental_config(IncrementalConfig::default());
+}
@@ -8,6 +8,7 @@ use std::sync::{Arc, Barrier};
 use std::thread;
 
 static ORPHAN_DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+static COLLECTING_DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
 
 thread_local! {
     static DROP_COUNT: Cell<usize> = const { Cell::new(0) };
@@ -146,3 +147,28 @@ fn test_orphan_cross_page_reference() {
     // Triggering GC will call sweep_orphan_pages.
     collect_full();
 }
+
+struct CollectsOnDrop {
+    payload: [u64; 6],
+}
+
+unsafe impl Trace for CollectsOnDrop {
+    fn trace(&self, _: &mut impl Visitor) {}
+}
+
+impl Drop for CollectsOnDrop {
+    fn drop(&mut self) {
+        COLLECTING_DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+        collect_full();
+        assert_eq!(self.payload, [7; 6], "slot reclaimed mid-drop");
+    }
+}
+
+/// Test that a collection run from a destructor leaves the object being
+/// dropped alone: it is dead and unmarked, but its slot is still in use.
+#[test]
+fn test_collect_inside_drop() {
+    drop(Gc::new(CollectsOnDrop { payload: [7; 6] }));
+    collect_full();
+    assert_eq!(COLLECTING_DROP_COUNT.load(Ordering::SeqCst), 1);
+}
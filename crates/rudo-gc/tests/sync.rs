@@ -572,6 +572,7 @@ fn test_gc_rwlock_write_guard_drop_triggers_generational_barrier() {
         increment_size: 1024,
         max_dirty_pages: 64,
         remembered_buffer_len: 32,
+        satb_buffer_len: 32,
         slice_timeout_ms: 10,
     });
 
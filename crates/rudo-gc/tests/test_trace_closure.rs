@@ -1,5 +1,6 @@
-use rudo_gc::{Gc, Trace, TraceClosure, Visitor};
+use rudo_gc::{Gc, GcRoots, Trace, TraceClosure, Visitor};
 use std::cell::Cell;
+use std::rc::Rc;
 
 #[derive(Trace)]
 struct Data {
@@ -64,3 +65,68 @@ fn test_trace_closure_preserves_captured_gc() {
 
     drop(effect);
 }
+
+thread_local! {
+    static LEAF_DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Trace)]
+struct Leaf(u32);
+
+impl Drop for Leaf {
+    fn drop(&mut self) {
+        LEAF_DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+const LEAVES: u32 = 16;
+
+type Callback = Gc<TraceClosure<Box<dyn Fn()>, GcRoots>>;
+
+/// The closure only holds raw pointers, as an FFI callback would, so the
+/// builder's roots are all that keeps the leaves alive.
+#[inline(never)]
+fn ffi_style_callback(total: Rc<Cell<u32>>) -> Callback {
+    let mut builder = TraceClosure::builder();
+    let mut ptrs = Vec::new();
+    for i in 0..LEAVES {
+        let leaf = Gc::new(Leaf(i));
+        ptrs.push(Gc::as_ptr(&leaf));
+        builder = builder.root(&leaf);
+    }
+    let closure: Box<dyn Fn()> = Box::new(move || {
+        // SAFETY: the `TraceClosure` roots every leaf.
+        total.set(ptrs.iter().map(|&leaf| unsafe { (*leaf).0 }).sum());
+    });
+    Gc::new(builder.build(closure))
+}
+
+#[test]
+fn test_builder_roots_survive_minor_and_major_collection() {
+    LEAF_DROPS.with(|drops| drops.set(0));
+    let total = Rc::new(Cell::new(0));
+    let callback = ffi_style_callback(total.clone());
+    unsafe { rudo_gc::test_util::clear_registers() };
+
+    rudo_gc::collect();
+    rudo_gc::collect_full();
+    assert_eq!(LEAF_DROPS.with(Cell::get), 0);
+    callback.call();
+    assert_eq!(total.get(), (0..LEAVES).sum());
+
+    drop(callback);
+    assert_eq!(LEAF_DROPS.with(Cell::get), LEAVES as usize);
+}
+
+#[test]
+fn test_gc_roots_len() {
+    let mut roots = GcRoots::new();
+    assert!(roots.is_empty());
+    let a = Gc::new(1u8);
+    roots.push(&a);
+    roots.push(&Gc::new("two"));
+    assert_eq!(roots.len(), 2);
+    assert_eq!(Gc::strong_count(&a), 2);
+    drop(roots);
+    assert_eq!(Gc::strong_count(&a), 1);
+}
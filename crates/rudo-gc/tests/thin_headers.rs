@@ -0,0 +1,83 @@
+//! Tests for the `thin-headers` object layout.
+
+#![cfg(feature = "thin-headers")]
+
+use rudo_gc::heap::{page_mask, page_size};
+use rudo_gc::test_util::internal_ptr;
+use rudo_gc::{collect_full, Gc, GcBox, Trace};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn page_of<T: Trace>(gc: &Gc<T>) -> usize {
+    internal_ptr(gc) as usize & page_mask()
+}
+
+#[test]
+fn test_small_objects_pack_tighter() {
+    // Only the counts, dropping state and generation remain in the header.
+    assert_eq!(std::mem::size_of::<GcBox<u32>>(), 32);
+
+    let objects: Gc<Vec<Gc<u32>>> = Gc::new((0..10_000).map(Gc::new).collect());
+    let pages: HashSet<usize> = objects.iter().map(page_of).collect();
+    let per_object = pages.len() * page_size() / objects.len();
+
+    // With per-object drop/trace pointers a `GcBox<u32>` needs a 64-byte
+    // slot, before even counting page headers.
+    assert!(
+        per_object < 64,
+        "expected 32-byte slots plus page headers, got {per_object} bytes per object"
+    );
+    assert!(objects.iter().zip(0..).all(|(gc, i)| **gc == i));
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Trace)]
+struct Left {
+    id: u64,
+    right: Gc<Right>,
+}
+
+#[derive(Trace)]
+struct Right {
+    id: u64,
+    pad: u64,
+}
+
+impl Drop for Right {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_interleaved_types_keep_pages_homogeneous() {
+    assert_eq!(
+        std::mem::size_of::<GcBox<Left>>(),
+        std::mem::size_of::<GcBox<Right>>()
+    );
+
+    let lefts: Gc<Vec<Gc<Left>>> = Gc::new(
+        (0..1_000)
+            .map(|id| {
+                let right = Gc::new(Right { id, pad: 0 });
+                Gc::new(Left { id, right })
+            })
+            .collect(),
+    );
+
+    let left_pages: HashSet<usize> = lefts.iter().map(page_of).collect();
+    let right_pages: HashSet<usize> = lefts.iter().map(|left| page_of(&left.right)).collect();
+    assert!(left_pages.is_disjoint(&right_pages));
+
+    // Each `Right` is only reachable through its `Left`, so it survives only
+    // if the page-level trace function ran.
+    collect_full();
+    assert!(lefts
+        .iter()
+        .all(|left| left.right.id == left.id && left.right.pad == 0));
+
+    let before = DROPS.load(Ordering::SeqCst);
+    drop(lefts);
+    assert_eq!(DROPS.load(Ordering::SeqCst) - before, 1_000);
+}
@@ -0,0 +1,70 @@
+//! Tests for `GcThreadLocalRoot`, which keeps thread-local `Gc` caches alive.
+
+use rudo_gc::{collect_full, Gc, GcThreadLocalRoot, Trace, Visitor};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CACHED_DROPS: AtomicUsize = AtomicUsize::new(0);
+static EXITED_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Node {
+    value: u64,
+    drops: &'static AtomicUsize,
+}
+
+unsafe impl Trace for Node {
+    fn trace(&self, _: &mut impl Visitor) {}
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+thread_local! {
+    static CACHE: GcThreadLocalRoot<Node> = const { GcThreadLocalRoot::new() };
+}
+
+#[inline(never)]
+fn fill_cache(drops: &'static AtomicUsize) {
+    CACHE.with(|cache| cache.set(Gc::new(Node { value: 42, drops })));
+}
+
+/// Overwrite dead stack slots that might still hold the node's address.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_thread_local_root_survives_collection() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    fill_cache(&CACHED_DROPS);
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+    collect_full();
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+
+    assert_eq!(CACHED_DROPS.load(Ordering::SeqCst), 0);
+    assert_eq!(CACHE.with(|cache| cache.get().unwrap().value), 42);
+
+    // Taking the value stops rooting it.
+    drop(CACHE.with(GcThreadLocalRoot::take));
+    assert_eq!(CACHED_DROPS.load(Ordering::SeqCst), 1);
+    assert!(!CACHE.with(GcThreadLocalRoot::is_set));
+}
+
+#[test]
+fn test_thread_local_root_released_on_thread_exit() {
+    std::thread::spawn(|| fill_cache(&EXITED_DROPS))
+        .join()
+        .unwrap();
+    collect_full();
+    assert_eq!(EXITED_DROPS.load(Ordering::SeqCst), 1);
+}
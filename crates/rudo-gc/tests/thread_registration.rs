@@ -0,0 +1,26 @@
+//! Threads that allocate GC pages must be registered with the collector.
+
+use rudo_gc::heap::debug_registered_thread_count;
+use rudo_gc::{collect_full, Gc};
+
+#[test]
+fn test_allocating_thread_is_registered() {
+    let root = Gc::new(1_u64);
+    let before = debug_registered_thread_count();
+
+    let during = std::thread::spawn(|| {
+        let value = Gc::new(2_u64);
+        let count = debug_registered_thread_count();
+        assert_eq!(*value, 2);
+        count
+    })
+    .join()
+    .unwrap();
+    assert_eq!(during, before + 1);
+
+    // The exited thread unregisters; collecting still sees every owning heap
+    // registered.
+    assert_eq!(debug_registered_thread_count(), before);
+    collect_full();
+    assert_eq!(*root, 1);
+}
@@ -2,6 +2,20 @@ use rudo_gc::heap::{page_size, with_heap};
 use rudo_gc::{set_suspicious_sweep_detection, Gc};
 use std::thread;
 
+/// The size class serving `GcBox<i32>`.
+fn gc_box_i32_size_class() -> usize {
+    let expected = if cfg!(feature = "thin-headers") {
+        32
+    } else {
+        64
+    };
+    assert_eq!(
+        std::mem::size_of::<rudo_gc::GcBox<i32>>().next_power_of_two(),
+        expected
+    );
+    expected
+}
+
 #[test]
 fn test_tlab_thread_isolation() {
     use std::sync::{Arc, Barrier};
@@ -45,10 +59,10 @@ fn test_tlab_thread_isolation() {
 #[test]
 fn test_tlab_exhaustion_allocates_new_page() {
     // We want to exhaust a TLAB for a specific size class.
-    // GcBox<i32> fits in the 64-byte size class.
+    // GcBox<i32> fits in the 64-byte size class (32-byte with `thin-headers`).
     // We calculate the number of objects dynamically based on the page size.
 
-    let size_class = 64;
+    let size_class = gc_box_i32_size_class();
     let header_size = rudo_gc::heap::PageHeader::header_size(size_class);
     let objects_per_page = (page_size() - header_size) / size_class;
     let count = objects_per_page + 10; // Ensure we need more than one page
@@ -105,8 +119,12 @@ fn test_tlab_bump_pointer_contiguity() {
             diff1, diff2,
             "Bump pointer should allocate with constant stride"
         );
-        // For i32, it's 64 bytes as calculated before.
-        assert_eq!(diff1, 64, "Expected 64-byte size class for GcBox<i32>");
+        // For i32, it's 64 bytes as calculated before (32 with `thin-headers`).
+        assert_eq!(
+            diff1,
+            gc_box_i32_size_class(),
+            "Expected GcBox<i32> size class stride"
+        );
     })
     .join()
     .unwrap();
@@ -1,6 +1,8 @@
 use rudo_gc::tokio::{GcRootGuard, GcRootSet, GcTokioExt};
 use rudo_gc::{Gc, Trace};
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
 
 #[derive(Trace)]
 struct TestData {
@@ -101,7 +103,101 @@ fn test_yield_now() {
     });
 }
 
+#[test]
+fn test_yield_now_runs_mark_slice() {
+    use rudo_gc::gc::incremental::{incremental_worklist_len, IncrementalMarkState, MarkPhase};
+
+    rudo_gc::test_util::reset();
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let gc = Gc::new(TestData { value: 1 });
+        let state = IncrementalMarkState::global();
+        state.set_phase(MarkPhase::Marking);
+        state.push_work(
+            NonNull::new(Gc::internal_ptr(&gc).cast_mut())
+                .unwrap()
+                .cast(),
+        );
+
+        gc.yield_scheduler_only().await;
+        assert_eq!(incremental_worklist_len(), 1);
+
+        gc.yield_now().await;
+        assert_eq!(incremental_worklist_len(), 0);
+    });
+    rudo_gc::test_util::reset();
+}
+
 fn gc_internal_ptr<T: Trace + 'static>(gc: &Gc<T>) -> NonNull<u8> {
     let ptr = Gc::<T>::as_ptr(gc);
     NonNull::new(ptr as *mut u8).unwrap()
 }
+
+static BLOCKING_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted(i32);
+
+unsafe impl Trace for Counted {
+    fn trace(&self, _: &mut impl rudo_gc::Visitor) {}
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        BLOCKING_DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Hand two fresh `Gc`s to a blocking task that waits for `resume` before
+/// reading them, so no reference stays on this thread.
+#[inline(never)]
+fn spawn_waiting_task(
+    started: mpsc::Sender<()>,
+    resume: mpsc::Receiver<()>,
+) -> tokio::task::JoinHandle<i32> {
+    let roots = (Gc::new(Counted(7)), Gc::new(Counted(35)));
+    rudo_gc::tokio::spawn_blocking(roots, move |(a, b)| {
+        started.send(()).unwrap();
+        resume.recv().unwrap();
+        a.0 + b.0
+    })
+}
+
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_spawn_blocking_roots_gc() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let (started_tx, started_rx) = mpsc::channel();
+    let (resume_tx, resume_rx) = mpsc::channel();
+    let task = {
+        let _runtime = rt.enter();
+        spawn_waiting_task(started_tx, resume_rx)
+    };
+    started_rx.recv().unwrap();
+
+    // The blocking thread is not a GC thread, so only the cross-thread roots
+    // keep the values alive.
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+    rudo_gc::collect_full();
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+    assert_eq!(BLOCKING_DROPS.load(Ordering::SeqCst), 0);
+
+    resume_tx.send(()).unwrap();
+    assert_eq!(rt.block_on(task).unwrap(), 42);
+}
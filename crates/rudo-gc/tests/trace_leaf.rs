@@ -0,0 +1,83 @@
+//! Tests for `TraceLeaf` and `impl_trace_leaf!`.
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+
+use rudo_gc::{collect_full, impl_trace_leaf, Gc, Trace, TraceLeaf, Weak};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+struct Sample {
+    values: Vec<u8>,
+}
+
+impl Drop for Sample {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+// SAFETY: `Sample` holds no `Gc` pointers.
+impl_trace_leaf!(unsafe Sample);
+
+/// Holds leaves, and itself, so only the collector can reclaim it.
+#[derive(Trace)]
+struct Graph {
+    bytes: Gc<Vec<u8>>,
+    sample: Gc<Sample>,
+    this: RefCell<Option<Gc<Self>>>,
+}
+
+const GRAPHS: usize = 64;
+
+const fn assert_leaf<T: TraceLeaf + ?Sized>() {}
+
+/// Returns weak handles to the byte buffers. They live on the Rust heap,
+/// where conservative stack scanning can't mistake them for roots.
+#[inline(never)]
+fn make_garbage() -> Vec<Weak<Vec<u8>>> {
+    (0..GRAPHS)
+        .map(|_| {
+            let bytes = Gc::new(vec![1, 2, 3]);
+            let graph = Gc::new(Graph {
+                bytes: bytes.clone(),
+                sample: Gc::new(Sample {
+                    values: vec![4, 5, 6],
+                }),
+                this: RefCell::new(None),
+            });
+            *graph.this.borrow_mut() = Some(graph.clone());
+            assert_eq!(*graph.bytes, [1, 2, 3]);
+            assert_eq!(graph.sample.values, [4, 5, 6]);
+            Gc::downgrade(&bytes)
+        })
+        .collect()
+}
+
+#[test]
+fn test_std_types_are_leaves() {
+    assert_leaf::<u8>();
+    assert_leaf::<String>();
+    assert_leaf::<str>();
+    assert_leaf::<std::path::PathBuf>();
+    assert_leaf::<Sample>();
+}
+
+/// Conservative stack scanning may keep a graph or two alive, so the
+/// assertions only require that most of them are collected.
+#[test]
+fn test_leaves_and_derived_types_are_collected() {
+    // The collection sweeps graphs made just before it.
+    let _young = common::YoungGarbage::expected();
+    let bytes = make_garbage();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+
+    let freed_bytes = bytes.iter().filter(|weak| !weak.is_alive()).count();
+    let freed_samples = DROPS.with(Cell::get);
+    assert!(freed_bytes >= GRAPHS - 2, "freed {freed_bytes} buffers");
+    assert!(freed_samples >= GRAPHS - 2, "freed {freed_samples} samples");
+}
@@ -0,0 +1,93 @@
+//! `Trace::trace_order` decides which unreachable objects finalize first.
+
+mod common;
+
+use std::cell::RefCell;
+
+use rudo_gc::{collect_full, Gc, GcCell, Trace, TraceOrder, Visitor};
+
+thread_local! {
+    static DROPS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Must be finalized before the caches that flush through it.
+struct Connection {
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+unsafe impl Trace for Connection {
+    fn trace(&self, visitor: &mut impl Visitor) {
+        self.this.trace(visitor);
+    }
+
+    fn trace_order(&self) -> TraceOrder {
+        TraceOrder(10)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.borrow_mut().push("connection"));
+    }
+}
+
+struct QueryCache {
+    this: GcCell<Option<Gc<Self>>>,
+}
+
+unsafe impl Trace for QueryCache {
+    fn trace(&self, visitor: &mut impl Visitor) {
+        self.this.trace(visitor);
+    }
+}
+
+impl Drop for QueryCache {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.borrow_mut().push("cache"));
+    }
+}
+
+fn make_caches(count: usize) {
+    let cache = |_| QueryCache {
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles(count, cache, |cache| &cache.this);
+}
+
+/// Unreachable caches and a connection, with the connection between the
+/// caches in heap order.
+fn make_garbage() {
+    make_caches(3);
+    let connection = |_| Connection {
+        this: GcCell::new(None),
+    };
+    common::make_self_cycles(1, connection, |connection| &connection.this);
+    make_caches(3);
+}
+
+/// Overwrite dead stack slots that might still point at the garbage.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+#[test]
+fn test_higher_trace_order_finalizes_first() {
+    // The collection finalizes garbage made just before it.
+    let _young = common::YoungGarbage::expected();
+    make_garbage();
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+
+    let drops = DROPS.with(|d| d.borrow().clone());
+    // Conservative stack scanning may keep a cycle alive, so only the
+    // relative order of what was finalized is checked.
+    if let Some(connection) = drops.iter().position(|&d| d == "connection") {
+        assert_eq!(connection, 0, "drop order: {drops:?}");
+        assert!(drops.len() > 1, "no cache was finalized: {drops:?}");
+    } else {
+        panic!("connection was not finalized: {drops:?}");
+    }
+}
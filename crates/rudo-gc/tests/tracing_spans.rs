@@ -0,0 +1,91 @@
+//! Tests that GC spans nest the way flamegraph tools expect.
+
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use rudo_gc::{collect_full, Gc};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+/// A span's path from the root span, such as `gc_collect/gc_phase(Mark)`.
+struct SpanPath(String);
+
+/// Records the path of every span created.
+struct PathRecorder(Arc<Mutex<Vec<String>>>);
+
+/// Extracts the `phase` field of `gc_phase` spans.
+struct PhaseField(Option<String>);
+
+impl Visit for PhaseField {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "phase" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for PathRecorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut phase = PhaseField(None);
+        attrs.record(&mut phase);
+        let name = attrs.metadata().name();
+        let name = phase
+            .0
+            .map_or_else(|| name.to_string(), |phase| format!("{name}({phase})"));
+
+        let span = ctx.span(id).expect("span was just created");
+        let path = span
+            .parent()
+            .and_then(|parent| {
+                let extensions = parent.extensions();
+                extensions
+                    .get::<SpanPath>()
+                    .map(|parent| format!("{}/{name}", parent.0))
+            })
+            .unwrap_or(name);
+        span.extensions_mut().insert(SpanPath(path.clone()));
+        self.0.lock().unwrap().push(path);
+    }
+}
+
+#[test]
+fn test_collect_full_span_hierarchy() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = Registry::default().with(PathRecorder(Arc::clone(&paths)));
+    let live = Gc::new(7);
+    tracing::subscriber::with_default(subscriber, collect_full);
+    assert_eq!(*live, 7);
+
+    let paths = std::mem::take(&mut *paths.lock().unwrap());
+    for expected in [
+        "gc_collect/gc_phase(Clear)",
+        "gc_collect/gc_phase(Mark)",
+        "gc_collect/gc_phase(Mark)/gc_mark_heap/gc_scan_roots",
+        "gc_collect/gc_phase(Mark)/gc_mark_heap/gc_trace_graph",
+        "gc_collect/gc_phase(Sweep)",
+        "gc_collect/gc_phase(Sweep)/gc_sweep_orphans",
+    ] {
+        assert!(
+            paths.iter().any(|path| path == expected),
+            "missing span {expected} in {paths:#?}"
+        );
+    }
+    // Phases follow each other rather than nesting.
+    assert!(
+        paths
+            .iter()
+            .all(|path| path.matches("gc_phase").count() <= 1),
+        "nested phases in {paths:#?}"
+    );
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
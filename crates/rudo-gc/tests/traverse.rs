@@ -0,0 +1,166 @@
+//! Tests for `Gc::traverse`, the cycle-safe breadth-first graph iterator.
+
+#![allow(clippy::use_self)]
+
+use rudo_gc::{Gc, GcCell, Trace};
+use std::collections::HashMap;
+
+#[derive(Trace)]
+struct Node {
+    id: u32,
+    edges: GcCell<Vec<Gc<Node>>>,
+}
+
+fn node(id: u32) -> Gc<Node> {
+    Gc::new(Node {
+        id,
+        edges: GcCell::new(Vec::new()),
+    })
+}
+
+fn link(from: &Gc<Node>, to: &Gc<Node>) {
+    from.edges.borrow_mut().push(to.clone());
+}
+
+#[test]
+fn test_traverse_cyclic_graph_visits_each_node_once() {
+    // 0 -> 1 -> 3 -> 0 and 0 -> 2 -> 3, plus a self-loop on 2.
+    let nodes: Vec<_> = (0..4).map(node).collect();
+    link(&nodes[0], &nodes[1]);
+    link(&nodes[0], &nodes[2]);
+    link(&nodes[1], &nodes[3]);
+    link(&nodes[2], &nodes[3]);
+    link(&nodes[2], &nodes[2]);
+    link(&nodes[3], &nodes[0]);
+
+    let order: Vec<u32> = nodes[0].traverse().map(|n| n.id).collect();
+    assert_eq!(order, [0, 1, 2, 3]);
+
+    let mut seen = HashMap::new();
+    for n in nodes[3].traverse() {
+        *seen.entry(n.id).or_insert(0) += 1;
+    }
+    assert_eq!(seen.len(), 4);
+    assert!(seen.values().all(|&count| count == 1));
+}
+
+#[test]
+fn test_traverse_follows_edges_through_other_types() {
+    #[derive(Trace)]
+    struct Wrapper {
+        inner: Gc<Node>,
+    }
+
+    #[derive(Trace)]
+    struct Branch {
+        id: u32,
+        via: GcCell<Option<Gc<Wrapper>>>,
+    }
+
+    let root = Gc::new(Branch {
+        id: 1,
+        via: GcCell::new(None),
+    });
+    // Branch(1) -> Wrapper -> Node, and the Node is not a Branch: only the
+    // root is yielded, but reaching the Node must not panic or loop.
+    *root.via.borrow_mut() = Some(Gc::new(Wrapper { inner: node(9) }));
+    assert_eq!(root.traverse().map(|b| b.id).collect::<Vec<_>>(), [1]);
+}
+
+#[test]
+fn test_traverse_unaffected_by_mutation_after_yield() {
+    let a = node(0);
+    let b = node(1);
+    link(&a, &b);
+
+    let mut walk = a.traverse();
+    let first = walk.next().unwrap();
+    assert_eq!(first.id, 0);
+    // Dropping every external handle keeps queued nodes alive.
+    first.edges.borrow_mut().clear();
+    drop(b);
+    assert_eq!(walk.next().unwrap().id, 1);
+    assert!(walk.next().is_none());
+}
+
+fn addr(gc: &Gc<impl Trace + 'static>) -> usize {
+    Gc::downgrade(gc).raw_addr()
+}
+
+#[test]
+fn test_reachability_follows_edges_and_terminates_on_cycles() {
+    // 0 -> 1 -> 2 -> 0, and 3 -> 1 with nothing pointing back at 3.
+    let nodes: Vec<_> = (0..4).map(node).collect();
+    link(&nodes[0], &nodes[1]);
+    link(&nodes[1], &nodes[2]);
+    link(&nodes[2], &nodes[0]);
+    link(&nodes[3], &nodes[1]);
+
+    assert!(rudo_gc::is_reachable(&nodes[0], &nodes[2]));
+    assert!(rudo_gc::is_reachable(&nodes[2], &nodes[1]));
+    assert!(rudo_gc::is_reachable(&nodes[3], &nodes[0]));
+    assert!(rudo_gc::is_reachable(&nodes[1], &nodes[1]));
+    assert!(!rudo_gc::is_reachable(&nodes[0], &nodes[3]));
+}
+
+#[test]
+fn test_retaining_path_is_shortest_and_crosses_types() {
+    #[derive(Trace)]
+    struct Holder {
+        held: Gc<Node>,
+    }
+
+    // 0 -> 1 -> 2 -> 3, plus a shorter route to 3 through a `Holder`.
+    let nodes: Vec<_> = (0..4).map(node).collect();
+    link(&nodes[0], &nodes[1]);
+    link(&nodes[1], &nodes[2]);
+    link(&nodes[2], &nodes[3]);
+
+    assert_eq!(
+        rudo_gc::retaining_path(&nodes[0], &nodes[3]),
+        Some(nodes.iter().map(addr).collect())
+    );
+
+    let holder = Gc::new(Holder {
+        held: nodes[3].clone(),
+    });
+    let root = Gc::new(Node {
+        id: 9,
+        edges: GcCell::new(vec![nodes[0].clone()]),
+    });
+    let shortcut = Gc::new(GcCell::new(Some(holder.clone())));
+    assert_eq!(
+        rudo_gc::retaining_path(&shortcut, &nodes[3]),
+        Some(vec![addr(&shortcut), addr(&holder), addr(&nodes[3])])
+    );
+    assert_eq!(
+        rudo_gc::retaining_path(&root, &nodes[3]).map(|path| path.len()),
+        Some(5)
+    );
+    assert_eq!(rudo_gc::retaining_path(&nodes[3], &root), None);
+}
+
+#[test]
+fn test_subgraph_stats_counts_each_object_once() {
+    // A chain of 10 nodes whose tail links back to the head.
+    let nodes: Vec<_> = (0..10).map(node).collect();
+    for pair in nodes.windows(2) {
+        link(&pair[0], &pair[1]);
+    }
+    link(&nodes[9], &nodes[0]);
+
+    let stats = nodes[0].subgraph_stats();
+    assert_eq!(stats.object_count, 10);
+    assert_eq!(stats.max_depth, 9);
+    let slot = std::mem::size_of::<Node>();
+    assert!(stats.total_bytes >= 10 * slot);
+    assert!(stats.total_bytes <= 10 * 4 * slot.next_power_of_two());
+
+    let tail = nodes[5].subgraph_stats();
+    assert_eq!(tail.object_count, 10);
+    assert_eq!(tail.total_bytes, stats.total_bytes);
+
+    let leaf = node(99).subgraph_stats();
+    assert_eq!(leaf.object_count, 1);
+    assert_eq!(leaf.max_depth, 0);
+}
@@ -0,0 +1,125 @@
+//! Tests for returning empty pages to the OS with `trim_heap`.
+
+use rudo_gc::{
+    collect_full, current_reserved_size, last_gc_metrics, set_heap_trim_threshold, trim_heap, Gc,
+    Trace,
+};
+
+#[derive(Trace)]
+struct Node {
+    value: u64,
+    payload: [u64; 7],
+}
+
+const SPIKE: u64 = 20_000;
+
+#[inline(never)]
+fn allocate_spike() {
+    let spike: Vec<_> = (0..SPIKE)
+        .map(|value| {
+            Gc::new(Node {
+                value,
+                payload: [value; 7],
+            })
+        })
+        .collect();
+    assert_eq!(spike.last().unwrap().value, SPIKE - 1);
+}
+
+/// Collect garbage the tests dropped on purpose.
+fn collect_garbage() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+    collect_full();
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+fn collect_spike() {
+    allocate_spike();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_garbage();
+}
+
+#[test]
+fn test_trim_heap_returns_empty_pages() {
+    let survivor = Gc::new(Node {
+        value: 7,
+        payload: [7; 7],
+    });
+
+    collect_spike();
+    let before = current_reserved_size();
+    let returned = trim_heap();
+    assert!(returned > 0, "nothing returned");
+    assert_eq!(current_reserved_size(), before - returned);
+
+    assert_eq!(survivor.value, 7);
+    assert_eq!(survivor.payload, [7; 7]);
+
+    // Allocation maps fresh pages again.
+    let fresh: Vec<_> = (0..1000u64)
+        .map(|value| {
+            Gc::new(Node {
+                value,
+                payload: [0; 7],
+            })
+        })
+        .collect();
+    assert!(fresh
+        .iter()
+        .enumerate()
+        .all(|(i, node)| node.value == i as u64));
+    drop(fresh);
+
+    collect_garbage();
+    assert_eq!(last_gc_metrics().bytes_returned, returned);
+    collect_garbage();
+    assert_eq!(last_gc_metrics().bytes_returned, 0);
+    assert_eq!(survivor.value, 7);
+}
+
+#[test]
+fn test_major_collection_trims_past_threshold() {
+    let survivor = Gc::new(Node {
+        value: 9,
+        payload: [9; 7],
+    });
+
+    set_heap_trim_threshold(Some(2));
+    assert_eq!(rudo_gc::heap_trim_threshold(), Some(2));
+
+    collect_spike();
+    // A lazy sweep leaves pages to empty on the next collection.
+    let mut returned = last_gc_metrics().bytes_returned;
+    if returned == 0 {
+        collect_garbage();
+        returned = last_gc_metrics().bytes_returned;
+    }
+    set_heap_trim_threshold(None);
+    assert!(returned > 0, "no pages trimmed");
+    assert_eq!(survivor.payload, [9; 7]);
+
+    // With the threshold off, collections leave empty pages mapped.
+    collect_spike();
+    collect_garbage();
+    assert_eq!(last_gc_metrics().bytes_returned, 0);
+    assert!(trim_heap() > 0);
+}
+
+#[test]
+fn test_bytes_returned_counts_only_this_thread() {
+    collect_garbage();
+
+    // The other thread trims but does not collect again.
+    let returned = std::thread::spawn(|| {
+        collect_spike();
+        trim_heap()
+    })
+    .join()
+    .unwrap();
+    assert!(returned > 0, "nothing returned");
+
+    collect_garbage();
+    assert_eq!(last_gc_metrics().bytes_returned, 0);
+}
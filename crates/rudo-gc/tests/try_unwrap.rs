@@ -0,0 +1,73 @@
+//! Tests for `Gc::try_unwrap`.
+
+use std::sync::{Mutex, PoisonError};
+
+use rudo_gc::gc::incremental::{IncrementalMarkState, MarkPhase};
+use rudo_gc::heap::{find_gc_box_from_ptr, with_heap};
+use rudo_gc::{Gc, GcCell, Trace};
+
+/// One test drives the global marking phase; keep the others off it.
+static MARKING_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Trace)]
+struct Node {
+    name: String,
+    children: GcCell<Vec<Gc<u64>>>,
+}
+
+fn is_allocated(addr: *const u8) -> bool {
+    // SAFETY: `addr` came from a `Gc` on this thread's heap.
+    with_heap(|heap| unsafe { find_gc_box_from_ptr(heap, addr) }.is_some())
+}
+
+#[test]
+fn test_try_unwrap_moves_value_and_frees_slot() {
+    let _lock = MARKING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let child = Gc::new(5u64);
+    let gc = Gc::new(Node {
+        name: "root".to_string(),
+        children: GcCell::new(vec![child.clone()]),
+    });
+    let addr = Gc::internal_ptr(&gc);
+    assert!(is_allocated(addr));
+
+    let node = Gc::try_unwrap(gc).ok().expect("unique Gc");
+    assert_eq!(node.name, "root");
+    assert!(Gc::ptr_eq(&node.children.borrow()[0], &child));
+    assert!(!is_allocated(addr));
+}
+
+#[test]
+fn test_try_unwrap_returns_shared_gc_unchanged() {
+    let _lock = MARKING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let gc = Gc::new(7u64);
+    let other = gc.clone();
+    let gc = Gc::try_unwrap(gc).unwrap_err();
+    assert!(Gc::ptr_eq(&gc, &other));
+    assert_eq!(Gc::strong_count(&gc), 2);
+    drop(other);
+
+    let weak = Gc::downgrade(&gc);
+    let gc = Gc::try_unwrap(gc).unwrap_err();
+    assert_eq!(*gc, 7);
+    assert!(weak.is_alive());
+    drop(weak);
+
+    assert_eq!(Gc::try_unwrap(gc), Ok(7));
+}
+
+#[test]
+fn test_try_unwrap_fails_while_marking() {
+    let _lock = MARKING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let gc = Gc::new(String::from("marked"));
+    let addr = Gc::internal_ptr(&gc);
+
+    let state = IncrementalMarkState::global();
+    state.set_phase(MarkPhase::Marking);
+    let result = Gc::try_unwrap(gc);
+    state.set_phase(MarkPhase::Idle);
+
+    let gc = result.unwrap_err();
+    assert!(is_allocated(addr));
+    assert_eq!(Gc::try_unwrap(gc).as_deref(), Ok("marked"));
+}
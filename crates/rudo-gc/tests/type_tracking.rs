@@ -0,0 +1,151 @@
+//! Tests for `type-tracking`: the reclaimed-object histogram and finding
+//! live objects by type.
+
+#![cfg(feature = "type-tracking")]
+#![allow(clippy::use_self)]
+
+use rudo_gc::{
+    collect_full, find_objects_of_type, last_collection_reclaimed_by_type, Gc, GcCell, Trace,
+};
+use std::any::type_name;
+
+#[derive(Trace)]
+struct Apple {
+    next: GcCell<Option<Gc<Apple>>>,
+}
+
+#[derive(Trace)]
+struct Pear {
+    next: GcCell<Option<Gc<Pear>>>,
+}
+
+/// Build self-referencing objects that only the collector can reclaim.
+#[inline(never)]
+fn make_garbage(apples: usize, pears: usize) {
+    for _ in 0..apples {
+        let apple = Gc::new(Apple {
+            next: GcCell::new(None),
+        });
+        *apple.next.borrow_mut() = Some(apple.clone());
+    }
+    for _ in 0..pears {
+        let pear = Gc::new(Pear {
+            next: GcCell::new(None),
+        });
+        *pear.next.borrow_mut() = Some(pear.clone());
+    }
+}
+
+/// Overwrite dead stack slots that might still hold the garbage's addresses.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+fn count_of(histogram: &[(&'static str, usize)], name: &str) -> usize {
+    histogram
+        .iter()
+        .find(|(type_name, _)| *type_name == name)
+        .map_or(0, |(_, count)| *count)
+}
+
+#[test]
+fn test_reclaimed_objects_grouped_by_type() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    collect_full();
+    make_garbage(30, 20);
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    collect_full();
+
+    let histogram = last_collection_reclaimed_by_type();
+    assert_eq!(count_of(&histogram, type_name::<Apple>()), 30);
+    assert_eq!(count_of(&histogram, type_name::<Pear>()), 20);
+
+    let apples = histogram
+        .iter()
+        .position(|(name, _)| *name == type_name::<Apple>());
+    let pears = histogram
+        .iter()
+        .position(|(name, _)| *name == type_name::<Pear>());
+    assert!(apples < pears, "entries are sorted by count: {histogram:?}");
+
+    // The next collection starts a fresh histogram.
+    collect_full();
+    assert_eq!(
+        count_of(&last_collection_reclaimed_by_type(), type_name::<Apple>()),
+        0
+    );
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_find_objects_of_type_returns_live_instances() {
+    let apples: Vec<Gc<Apple>> = (0..3)
+        .map(|_| {
+            Gc::new(Apple {
+                next: GcCell::new(None),
+            })
+        })
+        .collect();
+    let _pears: Vec<Gc<Pear>> = (0..2)
+        .map(|_| {
+            Gc::new(Pear {
+                next: GcCell::new(None),
+            })
+        })
+        .collect();
+
+    let found = find_objects_of_type::<Apple>();
+    assert_eq!(found.len(), apples.len());
+    for apple in &apples {
+        assert!(found.iter().any(|gc| Gc::ptr_eq(gc, apple)));
+        // The walk hands out new references.
+        assert_eq!(Gc::ref_count(apple).get(), 2);
+    }
+    assert_eq!(find_objects_of_type::<Pear>().len(), 2);
+    assert!(find_objects_of_type::<String>().is_empty());
+
+    drop(found);
+    assert_eq!(Gc::ref_count(&apples[0]).get(), 1);
+}
+
+#[derive(Trace)]
+struct Quince {
+    next: GcCell<Option<Gc<Quince>>>,
+}
+
+#[test]
+fn test_find_objects_of_type_sees_only_this_thread() {
+    let (allocated_tx, allocated_rx) = std::sync::mpsc::channel();
+    let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+    let other = std::thread::spawn(move || {
+        let _quinces: Vec<Gc<Quince>> = (0..3)
+            .map(|_| {
+                Gc::new(Quince {
+                    next: GcCell::new(None),
+                })
+            })
+            .collect();
+        allocated_tx
+            .send(find_objects_of_type::<Quince>().len())
+            .unwrap();
+        done_rx.recv().unwrap();
+    });
+
+    assert_eq!(allocated_rx.recv().unwrap(), 3);
+    let mine = Gc::new(Quince {
+        next: GcCell::new(None),
+    });
+    let found = find_objects_of_type::<Quince>();
+    assert_eq!(found.len(), 1);
+    assert!(Gc::ptr_eq(&found[0], &mine));
+
+    done_tx.send(()).unwrap();
+    other.join().unwrap();
+}
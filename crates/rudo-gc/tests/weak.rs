@@ -1,6 +1,6 @@
 //! Tests for the Weak<T> implementation.
 
-use rudo_gc::{collect_full, Gc, Trace, Weak};
+use rudo_gc::{collect_full, Gc, GcCell, Trace, Weak};
 use std::cell::Cell;
 
 #[cfg(feature = "test-util")]
@@ -429,3 +429,43 @@ fn test_may_be_valid_fast_path() {
         assert!(weak.may_be_valid());
     }
 }
+
+#[test]
+fn test_upgrade_all_skips_dead() {
+    let gcs: Vec<Gc<usize>> = (0..10).map(Gc::new).collect();
+    let mut weaks: Vec<Weak<usize>> = gcs.iter().map(Gc::downgrade).collect();
+    weaks.push(Weak::default());
+
+    // Drop the odd values; their weak references die with them.
+    let live: Vec<Gc<usize>> = gcs.into_iter().filter(|gc| **gc % 2 == 0).collect();
+
+    let upgraded = Weak::upgrade_all(&weaks);
+    assert_eq!(upgraded.len(), live.len());
+    for (upgraded, live) in upgraded.iter().zip(&live) {
+        assert!(Gc::ptr_eq(upgraded, live));
+    }
+    assert!(weaks
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .all(|weak| weak.upgrade().is_none()));
+}
+
+#[test]
+fn test_weak_new_is_dangling() {
+    let weak: Weak<i32> = Weak::new();
+    assert!(weak.upgrade().is_none());
+    assert!(!weak.may_be_valid());
+    assert_eq!(weak.strong_count(), 0);
+    assert!(Weak::ptr_eq(&weak, &Weak::default()));
+
+    // Tracing and collecting with a dangling weak in the graph is a no-op.
+    let holder = Gc::new(GcCell::new(weak));
+    collect_full();
+    assert!(holder.borrow().upgrade().is_none());
+
+    // The placeholder can be filled in once the target exists.
+    let target = Gc::new(7);
+    *holder.borrow_mut() = Gc::downgrade(&target);
+    assert_eq!(*holder.borrow().upgrade().unwrap(), 7);
+}
@@ -360,6 +360,7 @@ fn enable_incremental() {
         increment_size: 100,
         max_dirty_pages: 1000,
         remembered_buffer_len: 32,
+        satb_buffer_len: 32,
         slice_timeout_ms: 50,
     };
     set_incremental_config(config);
@@ -371,6 +372,7 @@ fn disable_incremental() {
         increment_size: 100,
         max_dirty_pages: 1000,
         remembered_buffer_len: 32,
+        satb_buffer_len: 32,
         slice_timeout_ms: 50,
     };
     set_incremental_config(config);
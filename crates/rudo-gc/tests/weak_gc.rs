@@ -0,0 +1,93 @@
+//! Tests for `WeakGc`, the field type that stores assigned `Gc`s weakly.
+
+use std::cell::Cell;
+
+use rudo_gc::{collect_full, Gc, GcCell, Trace, WeakGc};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Trace)]
+struct Node {
+    parent: GcCell<WeakGc<Self>>,
+    children: GcCell<Vec<Gc<Self>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+fn add_child(parent: &Gc<Node>) -> Gc<Node> {
+    let child = Gc::new(Node {
+        parent: GcCell::new(parent.clone().into()),
+        children: GcCell::new(Vec::new()),
+    });
+    parent.children.borrow_mut().push(child.clone());
+    child
+}
+
+/// A root with `width` children, each with `width` children of its own.
+#[inline(never)]
+fn tree(width: usize) -> Gc<Node> {
+    let root = Gc::new(Node {
+        parent: GcCell::new(WeakGc::new()),
+        children: GcCell::new(Vec::new()),
+    });
+    for _ in 0..width {
+        let child = add_child(&root);
+        for _ in 0..width {
+            add_child(&child);
+        }
+    }
+    root
+}
+
+#[test]
+fn test_parent_links_do_not_keep_tree_alive() {
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+
+    DROPS.with(|drops| drops.set(0));
+    let root = tree(4);
+
+    let leaf = root.children.borrow()[2].children.borrow()[1].clone();
+    let parent = leaf.parent.borrow().upgrade().unwrap();
+    assert!(Gc::ptr_eq(&parent, &root.children.borrow()[2]));
+    assert!(Gc::ptr_eq(
+        &parent.parent.borrow().upgrade().unwrap(),
+        &root
+    ));
+    drop((leaf, parent));
+
+    // No cycle: dropping the last `Gc` frees the whole tree at once.
+    drop(root);
+    assert_eq!(DROPS.with(Cell::get), 1 + 4 + 4 * 4);
+    collect_full();
+    assert_eq!(DROPS.with(Cell::get), 1 + 4 + 4 * 4);
+
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_conversions() {
+    let value = Gc::new(7u32);
+
+    let weak = WeakGc::from(&value);
+    assert_eq!(weak.strong_count(), 1);
+    assert_eq!(Option::<Gc<u32>>::from(&weak).as_deref(), Some(&7));
+
+    let from_option: WeakGc<u32> = Some(value.clone()).into();
+    assert!(from_option.is_alive());
+    let empty: WeakGc<u32> = None.into();
+    assert!(empty.upgrade().is_none());
+    assert!(WeakGc::<u32>::default().upgrade().is_none());
+
+    let copy = weak.clone().into_weak();
+    drop(value);
+    assert!(weak.upgrade().is_none());
+    assert!(copy.upgrade().is_none());
+}
@@ -0,0 +1,106 @@
+//! Tests for `WeakHandle`, a weak entry in the cross-thread root table.
+
+use rudo_gc::{collect_full, Gc, Trace, Visitor, WeakHandle};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static UNROOTED_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Node {
+    drops: &'static AtomicUsize,
+}
+
+unsafe impl Trace for Node {
+    fn trace(&self, _: &mut impl Visitor) {}
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Create a node that only the returned weak handle refers to.
+#[inline(never)]
+fn unrooted_handle(drops: &'static AtomicUsize) -> WeakHandle<Node> {
+    WeakHandle::new(&Gc::new(Node { drops }))
+}
+
+/// Overwrite dead stack slots that might still hold the node's address.
+#[inline(never)]
+fn scrub_stack() {
+    let scratch = [0usize; 1024];
+    std::hint::black_box(&scratch);
+}
+
+fn collect_unrooted() {
+    scrub_stack();
+    unsafe { rudo_gc::test_util::clear_registers() };
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(false);
+    collect_full();
+    collect_full();
+    #[cfg(feature = "debug-suspicious-sweep")]
+    rudo_gc::set_suspicious_sweep_detection(true);
+}
+
+#[test]
+fn test_upgrade_while_strongly_held() {
+    let gc = Gc::new(5_u64);
+    let weak = WeakHandle::new(&gc);
+    assert!(weak.is_alive());
+    assert_eq!(*weak.upgrade().unwrap(), 5);
+    assert_eq!(Gc::weak_count(&gc), 1);
+
+    let clone = weak.clone();
+    assert_eq!(Gc::weak_count(&gc), 2);
+    drop(weak);
+    assert_eq!(Gc::weak_count(&gc), 1);
+    assert_eq!(*clone.upgrade().unwrap(), 5);
+    drop(clone);
+    assert_eq!(Gc::weak_count(&gc), 0);
+}
+
+#[test]
+fn test_handle_does_not_keep_target_alive() {
+    let weak = unrooted_handle(&UNROOTED_DROPS);
+    collect_unrooted();
+    assert_eq!(UNROOTED_DROPS.load(Ordering::SeqCst), 1);
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_none());
+
+    // A clone of a reaped handle never upgrades either.
+    let clone = weak.clone();
+    drop(weak);
+    assert!(!clone.is_alive());
+    assert!(clone.upgrade().is_none());
+}
+
+#[test]
+fn test_is_alive_from_other_thread() {
+    let gc = Gc::new(3_u64);
+    let weak = WeakHandle::new(&gc);
+    let origin = std::thread::current().id();
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            assert!(weak.is_alive());
+            assert_eq!(weak.origin_thread(), origin);
+            let upgraded = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let _ = weak.upgrade();
+            }));
+            assert!(upgraded.is_err());
+        });
+    });
+    drop(gc);
+}
+
+#[test]
+fn test_handle_is_gone_after_origin_thread_exits() {
+    let weak = std::thread::spawn(|| {
+        let gc = Gc::new(11_u64);
+        WeakHandle::new(&gc)
+    })
+    .join()
+    .unwrap();
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_none());
+}
@@ -0,0 +1,50 @@
+//! Tests for `Gc::with_field` functional updates.
+
+use rudo_gc::{Gc, Trace};
+
+#[derive(Trace, Clone)]
+struct Leaf {
+    value: u32,
+}
+
+#[derive(Trace, Clone)]
+struct Branch {
+    name: String,
+    left: Gc<Leaf>,
+    right: Gc<Leaf>,
+}
+
+#[test]
+fn test_with_field_copies_and_shares_children() {
+    let original = Gc::new(Branch {
+        name: "root".to_string(),
+        left: Gc::new(Leaf { value: 1 }),
+        right: Gc::new(Leaf { value: 2 }),
+    });
+
+    let patched = original.with_field(|branch| branch.right = Gc::new(Leaf { value: 3 }));
+
+    assert!(!Gc::ptr_eq(&original, &patched));
+    assert_eq!(original.right.value, 2);
+    assert_eq!(patched.right.value, 3);
+    assert!(Gc::ptr_eq(&original.left, &patched.left));
+    assert_eq!(original.name, patched.name);
+}
+
+#[test]
+fn test_with_field_never_mutates_in_place() {
+    let original = Gc::new(Branch {
+        name: "only".to_string(),
+        left: Gc::new(Leaf { value: 1 }),
+        right: Gc::new(Leaf { value: 2 }),
+    });
+    let left = original.left.clone();
+
+    // Even the sole reference is copied rather than updated.
+    let renamed = original.with_field(|branch| branch.name.push_str("-renamed"));
+    drop(original);
+
+    assert_eq!(renamed.name, "only-renamed");
+    assert!(Gc::ptr_eq(&renamed.left, &left));
+    assert_eq!(Gc::ref_count(&left).get(), 2);
+}
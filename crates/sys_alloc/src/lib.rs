@@ -1,3 +1,4 @@
+use std::fs::File;
 use std::io;
 
 #[cfg(unix)]
@@ -54,21 +55,70 @@ impl Mmap {
         self.len() == 0
     }
 
-    /// Flushes the memory mapped region to disk (if file backed) or ensures
-    /// visibility. For anonymous mappings, this is generally a no-op or ensures
-    /// cache coherence.
+    /// Flushes the whole mapping to its backing file, waiting until the
+    /// write completes.
+    ///
+    /// Uses `msync(MS_SYNC)` on Unix, and `FlushViewOfFile` followed by
+    /// `FlushFileBuffers` on Windows. Anonymous mappings have no backing
+    /// file, so this does nothing for them.
     ///
     /// # Errors
     ///
     /// Returns an error if the underlying system call fails.
-    /// Currently for anonymous memory this always succeeds.
-    /// Requires `sys_alloc` to expose `from_raw` parts or similar.
-    /// Since `Mmap` wraps `os::MmapInner`, we need `os::MmapInner` to support this too or just handle it here.
+    pub fn flush(&self) -> io::Result<()> {
+        self.flush_range(0, self.len())
+    }
+
+    /// Flushes `len` bytes starting `offset` bytes into the mapping, like
+    /// [`flush`](Self::flush). The range is widened to whole pages.
+    ///
+    /// # Errors
     ///
+    /// Returns an [`io::ErrorKind::InvalidInput`] error if the range does
+    /// not lie within the mapping, or the error from the system call.
+    pub fn flush_range(&self, offset: usize, len: usize) -> io::Result<()> {
+        if offset.checked_add(len).is_none_or(|end| end > self.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "flush range {offset}+{len} exceeds the mapping's {} bytes",
+                    self.len()
+                ),
+            ));
+        }
+        if len == 0 {
+            return Ok(());
+        }
+        self.inner.flush(offset, len)
+    }
+
+    /// Returns true if the mapping was created by [`MmapOptions::map_file`].
+    #[must_use]
+    pub const fn is_file_backed(&self) -> bool {
+        self.inner.is_file_backed()
+    }
+
+    /// Returns the granularity of this mapping: the huge page size if
+    /// [`MmapOptions::huge_pages`] obtained real huge pages, otherwise the
+    /// crate-level [`allocation_granularity`].
+    ///
+    /// Addresses and lengths passed back to the OS for this mapping, such
+    /// as a hint for a mapping that should follow it, must be aligned to
+    /// this.
+    #[must_use]
+    pub fn allocation_granularity(&self) -> usize {
+        self.inner
+            .huge_page_size()
+            .unwrap_or_else(allocation_granularity)
+    }
+
     /// Consumes the `Mmap` and returns the raw pointer and length.
     /// The memory will won't be unmapped when this struct is dropped.
     /// The caller is responsible for cleaning up the memory, e.g. by
     /// creating a new `Mmap` with `from_raw` and dropping it.
+    ///
+    /// Only anonymous mappings without guard pages round-trip: `from_raw`
+    /// always gives one.
     #[must_use]
     pub const fn into_raw(self) -> (*mut u8, usize) {
         let ptr = self.inner.ptr();
@@ -95,11 +145,14 @@ unsafe impl Sync for Mmap {}
 
 /// Configuration for creating a memory mapping.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // independent builder flags
 pub struct MmapOptions {
     len: usize,
     hint_addr: usize,
     populate: bool,
     no_reserve: bool,
+    huge_pages: bool,
+    guard_pages: bool,
     strict: bool,
 }
 
@@ -113,6 +166,8 @@ impl MmapOptions {
             hint_addr: 0,
             populate: false,
             no_reserve: false,
+            huge_pages: false,
+            guard_pages: false,
             strict: false,
         }
     }
@@ -157,6 +212,45 @@ impl MmapOptions {
         self
     }
 
+    /// Sets whether to back an anonymous mapping with huge pages.
+    ///
+    /// On Linux, this first tries `MAP_HUGETLB`, which needs huge pages
+    /// reserved by the administrator and a length that is a multiple of
+    /// their size. If the kernel rejects it, the mapping falls back to
+    /// normal pages advised with `madvise(MADV_HUGEPAGE)` for transparent
+    /// huge pages. On Windows, this tries `MEM_LARGE_PAGES`, which needs
+    /// `SeLockMemoryPrivilege`, and falls back to normal pages. Elsewhere
+    /// it has no effect. Use [`Mmap::allocation_granularity`] to find out
+    /// what was obtained.
+    ///
+    /// The hint address is honored as without huge pages, so callers that
+    /// color addresses (like rudo-gc's `HEAP_HINT_ADDRESS`) keep working;
+    /// align the hint to the huge page size for `MAP_HUGETLB` to succeed
+    /// there. Ignored by [`map_file`](Self::map_file).
+    #[must_use]
+    pub const fn huge_pages(mut self, huge_pages: bool) -> Self {
+        self.huge_pages = huge_pages;
+        self
+    }
+
+    /// Sets whether to bracket an anonymous mapping with guard pages.
+    ///
+    /// The OS mapping grows by one [`allocation_granularity`] at each end,
+    /// and both ends are made inaccessible with `mprotect(PROT_NONE)` on
+    /// Unix or `VirtualProtect(PAGE_NOACCESS)` on Windows. The resulting
+    /// [`Mmap`] describes only the accessible middle, at the hint address
+    /// if one was given, so a stray access just past either end faults
+    /// instead of landing in a neighbouring mapping.
+    ///
+    /// Meant for debugging allocators; it costs address space and a
+    /// mapping per end. Huge pages are not used with guard pages, and
+    /// [`map_file`](Self::map_file) ignores this.
+    #[must_use]
+    pub const fn guard_pages(mut self, guard_pages: bool) -> Self {
+        self.guard_pages = guard_pages;
+        self
+    }
+
     /// Sets whether the hint address is strict.
     ///
     /// If true, `map_anon` will return an error if the OS cannot map the memory
@@ -185,6 +279,76 @@ impl MmapOptions {
     /// Returns an error if the length is 0, or if the system call fails (e.g. out of memory),
     /// or if strict hint compliance is requested but cannot be satisfied.
     pub unsafe fn map_anon(&self) -> io::Result<Mmap> {
+        self.map_with(|| unsafe {
+            let guard = if self.guard_pages {
+                allocation_granularity()
+            } else {
+                0
+            };
+            let len = self
+                .len
+                .checked_add(2 * guard)
+                .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+            // Place the leading guard just below the hint.
+            let hint = self.hint_addr.saturating_sub(guard);
+            let mut inner = os::MmapInner::map_anon(
+                hint,
+                len,
+                self.populate,
+                self.no_reserve,
+                self.huge_pages && guard == 0,
+            )?;
+            if guard > 0 {
+                inner.protect_guards(guard)?;
+            }
+            Ok(inner)
+        })
+    }
+
+    /// Maps the first `len` bytes of `file`, shared with the file: writes
+    /// through the mapping reach the file, and [`Mmap::flush`] waits for
+    /// them to land on disk.
+    ///
+    /// The hint, populate and strict settings apply as for [`map_anon`].
+    /// Uses `mmap` with `MAP_SHARED` on Unix and `CreateFileMappingW` with
+    /// `MapViewOfFileEx` on Windows. `file` must be open for reading and
+    /// writing; the mapping stays valid after it is closed.
+    ///
+    /// [`map_anon`]: Self::map_anon
+    ///
+    /// # Safety
+    ///
+    /// As for [`map_anon`](Self::map_anon). In addition, the file must not
+    /// be truncated below `len` bytes while mapped, and anything else
+    /// writing to it races with writes through the mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length is 0 or the file is shorter than
+    /// it, if the system call fails, or if strict hint compliance is
+    /// requested but cannot be satisfied.
+    pub unsafe fn map_file(&self, file: &File) -> io::Result<Mmap> {
+        let file_len = file.metadata()?.len();
+        if u64::try_from(self.len).is_ok_and(|len| len > file_len) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot map {} bytes of a {file_len}-byte file", self.len),
+            ));
+        }
+        self.map_with(|| unsafe {
+            os::MmapInner::map_file(
+                file,
+                self.hint_addr,
+                self.len,
+                self.populate,
+                self.no_reserve,
+            )
+        })
+    }
+
+    /// Shared by [`Self::map_anon`] and [`Self::map_file`]: validate the
+    /// length, run `map`, and enforce a strict hint.
+    fn map_with(&self, map: impl FnOnce() -> io::Result<os::MmapInner>) -> io::Result<Mmap> {
         if self.len == 0 {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -192,31 +356,44 @@ impl MmapOptions {
             ));
         }
 
-        let inner = unsafe {
-            let inner =
-                os::MmapInner::map_anon(self.hint_addr, self.len, self.populate, self.no_reserve)?;
-
-            if self.strict && self.hint_addr != 0 {
-                let ptr = inner.ptr() as usize;
-                if ptr != self.hint_addr {
-                    // MmapInner drop will unmap the wrong memory
-                    return Err(io::Error::new(
-                        io::ErrorKind::AddrNotAvailable,
-                        format!(
-                            "Strict hint failed: requested {:#x}, got {:#x}",
-                            self.hint_addr, ptr
-                        ),
-                    ));
-                }
+        let inner = map().map_err(|err| self.map_error(&err))?;
+
+        if self.strict && self.hint_addr != 0 {
+            let ptr = inner.ptr() as usize;
+            if ptr != self.hint_addr {
+                // MmapInner drop will unmap the wrong memory
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrNotAvailable,
+                    format!(
+                        "Strict hint failed: requested {:#x}, got {:#x} ({} bytes)",
+                        self.hint_addr, ptr, self.len
+                    ),
+                ));
             }
-
-            inner
-        };
+        }
 
         Ok(Mmap { inner })
     }
 }
 
+impl MmapOptions {
+    /// Wraps an OS mapping error with what was requested, keeping its kind.
+    fn map_error(&self, err: &io::Error) -> io::Error {
+        let at = if self.hint_addr == 0 {
+            "at any address".to_owned()
+        } else {
+            format!("at {:#x}", self.hint_addr)
+        };
+        io::Error::new(
+            err.kind(),
+            format!(
+                "failed to map {} bytes {at} (populate={}, no_reserve={}, huge_pages={}): {err}",
+                self.len, self.populate, self.no_reserve, self.huge_pages
+            ),
+        )
+    }
+}
+
 impl Default for MmapOptions {
     fn default() -> Self {
         Self::new()
@@ -371,4 +548,165 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_flush() {
+        let len = 2 * page_size();
+        let mmap = unsafe {
+            MmapOptions::new()
+                .len(len)
+                .map_anon()
+                .expect("failed to map")
+        };
+        unsafe { ptr::write_volatile(mmap.ptr().add(page_size() + 1), 7) };
+
+        mmap.flush().expect("flush");
+        // Unaligned ranges are widened to whole pages.
+        mmap.flush_range(page_size() + 1, 10).expect("flush_range");
+        mmap.flush_range(len, 0).expect("empty range at the end");
+
+        for (offset, range_len) in [(len, 1), (1, len), (usize::MAX, 2)] {
+            let err = mmap.flush_range(offset, range_len).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+        assert_eq!(
+            unsafe { ptr::read_volatile(mmap.ptr().add(page_size() + 1)) },
+            7
+        );
+    }
+
+    #[test]
+    fn test_map_file() {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let len = page_size();
+        let mut file = tempfile::tempfile().expect("tempfile");
+        file.set_len(len as u64).expect("set_len");
+
+        let mmap = unsafe {
+            MmapOptions::new()
+                .len(len)
+                .map_file(&file)
+                .expect("failed to map file")
+        };
+        assert!(mmap.is_file_backed());
+        unsafe { ptr::copy_nonoverlapping(b"arena".as_ptr(), mmap.ptr().add(8), 5) };
+        mmap.flush_range(8, 5).expect("flush_range");
+        drop(mmap);
+
+        let mut contents = Vec::new();
+        file.seek(SeekFrom::Start(0)).expect("seek");
+        file.read_to_end(&mut contents).expect("read");
+        assert_eq!(contents.len(), len);
+        assert_eq!(&contents[8..13], b"arena");
+
+        // A mapping past the end of the file is refused up front.
+        let err = unsafe { MmapOptions::new().len(2 * len).map_file(&file) }
+            .err()
+            .expect("mapping past the end of the file should fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let anon = unsafe { MmapOptions::new().len(len).map_anon() }.expect("failed to map");
+        assert!(!anon.is_file_backed());
+    }
+
+    #[test]
+    fn test_huge_pages() {
+        // A multiple of the common 2 MiB huge page, so MAP_HUGETLB can work
+        // where pages are reserved; elsewhere this falls back to small pages.
+        let len = 4 * 1024 * 1024;
+        let mmap = unsafe {
+            MmapOptions::new()
+                .len(len)
+                .huge_pages(true)
+                .map_anon()
+                .expect("huge page mapping should fall back, not fail")
+        };
+        unsafe {
+            ptr::write_volatile(mmap.ptr(), 42);
+            ptr::write_volatile(mmap.ptr().add(len - 1), 43);
+            assert_eq!(ptr::read_volatile(mmap.ptr()), 42);
+            assert_eq!(ptr::read_volatile(mmap.ptr().add(len - 1)), 43);
+        }
+
+        let granularity = mmap.allocation_granularity();
+        assert!(granularity.is_power_of_two());
+        assert!(granularity >= allocation_granularity());
+        assert_eq!(mmap.ptr() as usize % granularity, 0);
+
+        let small = unsafe { MmapOptions::new().len(page_size()).map_anon() }.expect("map");
+        assert_eq!(small.allocation_granularity(), allocation_granularity());
+    }
+
+    #[test]
+    fn test_guard_pages() {
+        let len = 4 * page_size();
+        let guard = allocation_granularity();
+        let mmap = unsafe {
+            MmapOptions::new()
+                .len(len)
+                .guard_pages(true)
+                .map_anon()
+                .expect("failed to map with guard pages")
+        };
+        assert_eq!(mmap.len(), len);
+        assert_eq!(mmap.ptr() as usize % guard, 0);
+        unsafe {
+            ptr::write_volatile(mmap.ptr(), 1);
+            ptr::write_volatile(mmap.ptr().add(len - 1), 2);
+            assert_eq!(ptr::read_volatile(mmap.ptr()), 1);
+            assert_eq!(ptr::read_volatile(mmap.ptr().add(len - 1)), 2);
+        }
+
+        // Both neighbours of the accessible region are mapped with no access.
+        #[cfg(target_os = "linux")]
+        {
+            let maps = std::fs::read_to_string("/proc/self/maps").expect("maps");
+            let start = mmap.ptr() as usize;
+            for guard_start in [start - guard, start + len] {
+                let line = maps
+                    .lines()
+                    .find(|line| {
+                        let range = line.split_whitespace().next().unwrap();
+                        let (lo, hi) = range.split_once('-').unwrap();
+                        let lo = usize::from_str_radix(lo, 16).unwrap();
+                        let hi = usize::from_str_radix(hi, 16).unwrap();
+                        (lo..hi).contains(&guard_start)
+                    })
+                    .expect("guard page should be mapped");
+                assert!(line.contains(" ---p "), "{line}");
+            }
+        }
+
+        // A strict hint places the accessible region, not the guard.
+        let hint = mmap.ptr() as usize;
+        drop(mmap);
+        if let Ok(mmap) = unsafe {
+            MmapOptions::new()
+                .len(len)
+                .with_hint(hint)
+                .strict(true)
+                .guard_pages(true)
+                .map_anon()
+        } {
+            assert_eq!(mmap.ptr() as usize, hint);
+            assert_eq!(mmap.len(), len);
+        }
+    }
+
+    #[test]
+    fn test_map_failure_reports_request() {
+        // No address space can hold this, so the OS call itself fails.
+        let len = usize::MAX - allocation_granularity() + 1;
+        let err = unsafe { MmapOptions::new().len(len).populate(true).map_anon() }
+            .err()
+            .expect("mapping an absurd length should fail");
+
+        let msg = err.to_string();
+        assert!(msg.contains(&format!("failed to map {len} bytes")), "{msg}");
+        assert!(msg.contains("at any address"), "{msg}");
+        assert!(msg.contains("populate=true, no_reserve=false"), "{msg}");
+        // The OS error and its kind are preserved.
+        assert!(err.kind() != io::ErrorKind::Other, "{msg}");
+    }
 }
@@ -1,4 +1,6 @@
+use std::fs::File;
 use std::io::{self, Error};
+use std::os::fd::AsRawFd;
 use std::ptr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
@@ -28,6 +30,34 @@ const MAP_NORESERVE: libc::c_int = libc::MAP_NORESERVE;
 )))]
 const MAP_NORESERVE: libc::c_int = 0;
 
+#[cfg(target_os = "linux")]
+const MAP_HUGETLB: libc::c_int = libc::MAP_HUGETLB;
+
+#[cfg(not(target_os = "linux"))]
+const MAP_HUGETLB: libc::c_int = 0;
+
+/// Size of the pages `MAP_HUGETLB` hands out, from `Hugepagesize` in
+/// `/proc/meminfo`, cached atomically. Defaults to 2 MiB.
+fn huge_page_size() -> usize {
+    static HUGE_PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+    match HUGE_PAGE_SIZE.load(Ordering::Relaxed) {
+        0 => {
+            let size = std::fs::read_to_string("/proc/meminfo")
+                .ok()
+                .and_then(|meminfo| {
+                    let line = meminfo.lines().find(|l| l.starts_with("Hugepagesize:"))?;
+                    let kib: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+                    Some(kib * 1024)
+                })
+                .unwrap_or(2 * 1024 * 1024);
+            HUGE_PAGE_SIZE.store(size, Ordering::Relaxed);
+            size
+        }
+        size => size,
+    }
+}
+
 /// Returns the system page size, cached atomically.
 pub fn page_size() -> usize {
     static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
@@ -46,6 +76,12 @@ pub fn page_size() -> usize {
 pub struct MmapInner {
     ptr: *mut libc::c_void,
     len: usize,
+    /// Whether this maps a file rather than anonymous memory.
+    file_backed: bool,
+    /// Whether this is backed by `MAP_HUGETLB` pages.
+    huge: bool,
+    /// Bytes of `PROT_NONE` guard at each end of `ptr..ptr + len`.
+    guard: usize,
 }
 
 impl MmapInner {
@@ -59,6 +95,7 @@ impl MmapInner {
         len: usize,
         populate: bool,
         no_reserve: bool,
+        huge_pages: bool,
     ) -> io::Result<Self> {
         let populate = if populate { MAP_POPULATE } else { 0 };
         let no_reserve = if no_reserve { MAP_NORESERVE } else { 0 };
@@ -73,26 +110,131 @@ impl MmapInner {
         let flags = libc::MAP_PRIVATE | libc::MAP_ANON | populate | no_reserve;
         let prot = libc::PROT_READ | libc::PROT_WRITE;
 
+        if huge_pages && MAP_HUGETLB != 0 {
+            let ptr = unsafe { libc::mmap(addr, len, prot, flags | MAP_HUGETLB, -1, 0) };
+            if ptr != libc::MAP_FAILED {
+                return Ok(Self {
+                    ptr,
+                    len,
+                    file_backed: false,
+                    huge: true,
+                    guard: 0,
+                });
+            }
+            // No huge pages reserved, or `len` is not a multiple of their
+            // size: fall back to transparent huge pages below.
+        }
+
         let ptr = unsafe { libc::mmap(addr, len, prot, flags, -1, 0) };
 
         if ptr == libc::MAP_FAILED {
             return Err(Error::last_os_error());
         }
 
+        // Only advice: the kernel may still back the range with small pages.
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if huge_pages {
+            unsafe { libc::madvise(ptr, len, libc::MADV_HUGEPAGE) };
+        }
+
         // Basic verification: if we gave a hint, did we get it?
         // Note: we don't enforce strictness here (returning error if mismatch),
         // that is up to the higher level policy.
         // But for Address Space Coloring, the caller needs to check `ptr`.
 
-        Ok(Self { ptr, len })
+        Ok(Self {
+            ptr,
+            len,
+            file_backed: false,
+            huge: false,
+            guard: 0,
+        })
+    }
+
+    /// Creates a shared mapping of the first `len` bytes of `file`.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because it calls `mmap`.
+    pub unsafe fn map_file(
+        file: &File,
+        hint_addr: usize,
+        len: usize,
+        populate: bool,
+        no_reserve: bool,
+    ) -> io::Result<Self> {
+        let populate = if populate { MAP_POPULATE } else { 0 };
+        let no_reserve = if no_reserve { MAP_NORESERVE } else { 0 };
+
+        let addr = if hint_addr == 0 {
+            ptr::null_mut()
+        } else {
+            hint_addr as *mut libc::c_void
+        };
+
+        let flags = libc::MAP_SHARED | populate | no_reserve;
+        let prot = libc::PROT_READ | libc::PROT_WRITE;
+
+        let ptr = unsafe { libc::mmap(addr, len, prot, flags, file.as_raw_fd(), 0) };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(Self {
+            ptr,
+            len,
+            file_backed: true,
+            huge: false,
+            guard: 0,
+        })
     }
 
     pub const fn ptr(&self) -> *mut u8 {
-        self.ptr.cast::<u8>()
+        self.ptr.cast::<u8>().wrapping_add(self.guard)
     }
 
     pub const fn len(&self) -> usize {
-        self.len
+        self.len - 2 * self.guard
+    }
+
+    pub const fn is_file_backed(&self) -> bool {
+        self.file_backed
+    }
+
+    /// The page size backing this mapping, if it is not the system's.
+    pub fn huge_page_size(&self) -> Option<usize> {
+        self.huge.then(huge_page_size)
+    }
+
+    /// Makes the first and last `guard` bytes inaccessible with
+    /// `mprotect(PROT_NONE)`, leaving the middle as the mapping.
+    pub fn protect_guards(&mut self, guard: usize) -> io::Result<()> {
+        for start in [self.ptr, self.ptr.wrapping_byte_add(self.len - guard)] {
+            if unsafe { libc::mprotect(start, guard, libc::PROT_NONE) } != 0 {
+                return Err(Error::last_os_error());
+            }
+        }
+        self.guard = guard;
+        Ok(())
+    }
+
+    /// Synchronously writes `len` bytes at `offset` back to the backing
+    /// file with `msync(MS_SYNC)`. `msync` needs a page-aligned start, so
+    /// the range begins at the page holding `offset`. Anonymous mappings
+    /// have nothing to write back.
+    pub fn flush(&self, offset: usize, len: usize) -> io::Result<()> {
+        if !self.file_backed {
+            return Ok(());
+        }
+        let aligned = offset - offset % page_size();
+        let len = len + (offset - aligned);
+        let ret = unsafe { libc::msync(self.ptr().add(aligned).cast(), len, libc::MS_SYNC) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Error::last_os_error())
+        }
     }
 
     /// Creates a `MmapInner` from a raw pointer and length.
@@ -100,6 +242,9 @@ impl MmapInner {
         Self {
             ptr: ptr.cast::<libc::c_void>(),
             len,
+            file_backed: false,
+            huge: false,
+            guard: 0,
         }
     }
 }
@@ -1,10 +1,20 @@
+use std::fs::File;
 use std::io::{self, Error};
 use std::mem;
+#[cfg(not(miri))]
+use std::os::windows::io::AsRawHandle;
 use std::ptr;
 
+#[cfg(not(miri))]
+use windows_sys::Win32::Foundation::CloseHandle;
+#[cfg(not(miri))]
+use windows_sys::Win32::Storage::FileSystem::FlushFileBuffers;
 #[cfg(not(miri))]
 use windows_sys::Win32::System::Memory::{
-    VirtualAlloc, VirtualFree, MEM_COMMIT, MEM_RELEASE, MEM_RESERVE, PAGE_READWRITE,
+    CreateFileMappingW, FlushViewOfFile, GetLargePageMinimum, MapViewOfFileEx, UnmapViewOfFile,
+    VirtualAlloc, VirtualFree, VirtualProtect, FILE_MAP_READ, FILE_MAP_WRITE,
+    MEMORY_MAPPED_VIEW_ADDRESS, MEM_COMMIT, MEM_LARGE_PAGES, MEM_RELEASE, MEM_RESERVE,
+    PAGE_NOACCESS, PAGE_PROTECTION_FLAGS, PAGE_READWRITE,
 };
 #[cfg(not(miri))]
 use windows_sys::Win32::System::SystemInformation::{GetSystemInfo, SYSTEM_INFO};
@@ -52,6 +62,13 @@ pub fn page_size() -> usize {
 pub struct MmapInner {
     ptr: *mut std::ffi::c_void,
     len: usize,
+    /// The mapped file, kept to flush its buffers. `None` for `VirtualAlloc`
+    /// memory.
+    file: Option<File>,
+    /// Whether this is backed by `MEM_LARGE_PAGES`.
+    large_pages: bool,
+    /// Bytes of `PAGE_NOACCESS` guard at each end of `ptr..ptr + len`.
+    guard: usize,
 }
 
 impl MmapInner {
@@ -61,10 +78,12 @@ impl MmapInner {
         len: usize,
         _populate: bool,
         _no_reserve: bool,
+        huge_pages: bool,
     ) -> io::Result<MmapInner> {
         #[cfg(miri)]
         {
             use std::alloc::{alloc_zeroed, Layout};
+            let _ = huge_pages;
             // Miri doesn't support VirtualAlloc, use std::alloc
             // We align to allocation_granularity() to mimic Windows behavior
             let align = allocation_granularity();
@@ -80,6 +99,9 @@ impl MmapInner {
             Ok(MmapInner {
                 ptr: ptr as *mut std::ffi::c_void,
                 len,
+                file: None,
+                large_pages: false,
+                guard: 0,
             })
         }
         #[cfg(not(miri))]
@@ -90,6 +112,27 @@ impl MmapInner {
                 hint_addr as *const std::ffi::c_void
             };
 
+            // Large pages need SeLockMemoryPrivilege and a length that is a
+            // multiple of their size; without either, use normal pages.
+            let large = GetLargePageMinimum();
+            if huge_pages && large != 0 && len % large == 0 {
+                let ptr = VirtualAlloc(
+                    addr,
+                    len,
+                    MEM_COMMIT | MEM_RESERVE | MEM_LARGE_PAGES,
+                    PAGE_READWRITE,
+                );
+                if !ptr.is_null() {
+                    return Ok(MmapInner {
+                        ptr,
+                        len,
+                        file: None,
+                        large_pages: true,
+                        guard: 0,
+                    });
+                }
+            }
+
             // Windows requires MEM_RESERVE | MEM_COMMIT to actually get usable memory
             let mut ptr = VirtualAlloc(addr, len, MEM_COMMIT | MEM_RESERVE, PAGE_READWRITE);
 
@@ -102,16 +145,133 @@ impl MmapInner {
                 return Err(Error::last_os_error());
             }
 
-            Ok(MmapInner { ptr, len })
+            Ok(MmapInner {
+                ptr,
+                len,
+                file: None,
+                large_pages: false,
+                guard: 0,
+            })
+        }
+    }
+
+    /// Creates a view of the first `len` bytes of `file`, shared with it.
+    pub unsafe fn map_file(
+        file: &File,
+        hint_addr: usize,
+        len: usize,
+        _populate: bool,
+        _no_reserve: bool,
+    ) -> io::Result<MmapInner> {
+        #[cfg(miri)]
+        {
+            // Miri doesn't support file mappings.
+            let _ = (file, hint_addr, len);
+            Err(Error::from(io::ErrorKind::Unsupported))
+        }
+        #[cfg(not(miri))]
+        {
+            // Kept to flush the file's buffers; cloned first so nothing needs
+            // undoing if it fails.
+            let file = file.try_clone()?;
+            let size = len as u64;
+            let mapping = CreateFileMappingW(
+                file.as_raw_handle(),
+                ptr::null(),
+                PAGE_READWRITE,
+                (size >> 32) as u32,
+                size as u32,
+                ptr::null(),
+            );
+            if mapping.is_null() {
+                return Err(Error::last_os_error());
+            }
+
+            let addr = hint_addr as *const std::ffi::c_void;
+            let access = FILE_MAP_READ | FILE_MAP_WRITE;
+            let mut view = MapViewOfFileEx(mapping, access, 0, 0, len, addr);
+
+            // As in `map_anon`, fall back to letting the OS pick the address.
+            if view.Value.is_null() && !addr.is_null() {
+                view = MapViewOfFileEx(mapping, access, 0, 0, len, ptr::null());
+            }
+            let err = Error::last_os_error();
+
+            // The view keeps the mapping object alive.
+            CloseHandle(mapping);
+
+            if view.Value.is_null() {
+                return Err(err);
+            }
+
+            Ok(MmapInner {
+                ptr: view.Value,
+                len,
+                file: Some(file),
+                large_pages: false,
+                guard: 0,
+            })
         }
     }
 
     pub const fn ptr(&self) -> *mut u8 {
-        self.ptr.cast::<u8>()
+        self.ptr.cast::<u8>().wrapping_add(self.guard)
     }
 
     pub const fn len(&self) -> usize {
-        self.len
+        self.len - 2 * self.guard
+    }
+
+    pub const fn is_file_backed(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// The page size backing this mapping, if it is not the system's.
+    pub fn huge_page_size(&self) -> Option<usize> {
+        #[cfg(miri)]
+        {
+            None
+        }
+        #[cfg(not(miri))]
+        {
+            self.large_pages.then(|| unsafe { GetLargePageMinimum() })
+        }
+    }
+
+    /// Makes the first and last `guard` bytes inaccessible with
+    /// `VirtualProtect(PAGE_NOACCESS)`, leaving the middle as the mapping.
+    pub fn protect_guards(&mut self, guard: usize) -> io::Result<()> {
+        #[cfg(not(miri))]
+        for start in [self.ptr, self.ptr.wrapping_byte_add(self.len - guard)] {
+            let mut old: PAGE_PROTECTION_FLAGS = 0;
+            if unsafe { VirtualProtect(start, guard, PAGE_NOACCESS, &mut old) } == 0 {
+                return Err(Error::last_os_error());
+            }
+        }
+        self.guard = guard;
+        Ok(())
+    }
+
+    /// Flushes `len` bytes at `offset` to the backing file:
+    /// `FlushViewOfFile` writes the dirty pages, and `FlushFileBuffers`
+    /// waits for them to reach the disk. `VirtualAlloc` memory is not a
+    /// view of a file and has nothing to flush.
+    pub fn flush(&self, offset: usize, len: usize) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        #[cfg(miri)]
+        let _ = (file, offset, len);
+        #[cfg(not(miri))]
+        unsafe {
+            if FlushViewOfFile(self.ptr().add(offset).cast(), len) == 0 {
+                return Err(Error::last_os_error());
+            }
+            if FlushFileBuffers(file.as_raw_handle()) == 0 {
+                return Err(Error::last_os_error());
+            }
+        }
+        Ok(())
     }
 
     /// Creates a `MmapInner` from a raw pointer and length.
@@ -119,6 +279,9 @@ impl MmapInner {
         Self {
             ptr: ptr.cast::<std::ffi::c_void>(),
             len,
+            file: None,
+            large_pages: false,
+            guard: 0,
         }
     }
 }
@@ -135,7 +298,9 @@ impl Drop for MmapInner {
                     dealloc(self.ptr.cast::<u8>(), layout);
                 }
                 #[cfg(not(miri))]
-                {
+                if self.file.is_some() {
+                    UnmapViewOfFile(MEMORY_MAPPED_VIEW_ADDRESS { Value: self.ptr });
+                } else {
                     // MEM_RELEASE requires dwSize to be 0
                     VirtualFree(self.ptr, 0, MEM_RELEASE);
                 }